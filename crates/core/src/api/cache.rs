@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use http::{
+	header::{ACCEPT, AUTHORIZATION, CACHE_CONTROL, COOKIE, ETAG, IF_NONE_MATCH},
+	HeaderMap, HeaderValue, StatusCode,
+};
+use trice::Instant;
+
+use crate::{
+	ctx::Context,
+	dbs::Options,
+	err::Error,
+	kvs::cache::ds::{Entry, Lookup},
+	sql::{Duration, Value},
+};
+
+use super::{method::Method, response::ApiResponse};
+
+/// A response stored by the `api::res::cache` middleware
+struct CachedResponse {
+	expires: Instant,
+	response: ApiResponse,
+}
+
+/// Identifies the cached response for a request. Responses are cached per
+/// authenticated identity and per request credentials, so they are never
+/// shared between clients which authenticate differently. The identifying
+/// values are hashed, so no credentials are kept in the cache.
+pub fn key(opt: &Options, path: &str, request: &Value) -> String {
+	let mut hasher = blake3::Hasher::new();
+	let mut update = |v: &str| {
+		hasher.update(v.as_bytes());
+		hasher.update(&[0]);
+	};
+	update(&opt.auth.level().to_string());
+	update(opt.auth.id());
+	update(&request.pick(&["params".into()]).to_string());
+	update(&request.pick(&["query".into()]).to_string());
+	for name in [ACCEPT, AUTHORIZATION, COOKIE] {
+		update(&request.pick(&["headers".into(), name.as_str().into()]).to_string());
+	}
+	format!("cache:{path}:{}", hasher.finalize().to_hex())
+}
+
+/// Fetches a cached response which has not yet expired
+pub fn get(ctx: &Context, ns: &str, db: &str, key: &str) -> Result<Option<ApiResponse>, Error> {
+	let Some(cache) = ctx.get_cache() else {
+		return Ok(None);
+	};
+
+	match cache.get(&Lookup::Api(ns, db, key)) {
+		Some(v) => {
+			let v = v.try_into_type::<CachedResponse>()?;
+			Ok((v.expires > Instant::now()).then(|| v.response.clone()))
+		}
+		None => Ok(None),
+	}
+}
+
+/// Stores a successful response in the datastore cache for the given duration
+pub fn set(
+	ctx: &Context,
+	ns: &str,
+	db: &str,
+	key: &str,
+	ttl: &Duration,
+	res: &mut ApiResponse,
+) -> Result<(), Error> {
+	if !res.status.is_success() {
+		return Ok(());
+	}
+
+	if !res.headers.contains_key(CACHE_CONTROL) {
+		res.headers.insert(CACHE_CONTROL, format!("max-age={}", ttl.as_secs()).parse()?);
+	}
+
+	if let Some(cache) = ctx.get_cache() {
+		let entry = CachedResponse {
+			expires: Instant::now() + **ttl,
+			response: res.clone(),
+		};
+		cache.insert(Lookup::Api(ns, db, key), Entry::Any(Arc::new(entry)));
+	}
+
+	Ok(())
+}
+
+/// Adds an `ETag` header to a successful response, and replaces the
+/// response with `304 Not Modified` when the tag matches one of the
+/// tags in the `If-None-Match` header of a `GET` request
+pub fn etag(method: Method, request: &HeaderMap, res: &mut ApiResponse) -> Result<(), Error> {
+	if !res.status.is_success() {
+		return Ok(());
+	}
+
+	let tag = match res.headers.get(ETAG) {
+		Some(v) => v.clone(),
+		None => {
+			let body = res.body.as_ref().map(|v| v.to_string()).unwrap_or_default();
+			let hash = blake3::hash(body.as_bytes()).to_hex();
+			let tag: HeaderValue = format!("W/\"{}\"", &hash[..32]).parse()?;
+			res.headers.insert(ETAG, tag.clone());
+			tag
+		}
+	};
+
+	if method == Method::Get {
+		let tag = tag.to_str()?.trim_start_matches("W/");
+		let matches = request
+			.get_all(IF_NONE_MATCH)
+			.iter()
+			.filter_map(|v| v.to_str().ok())
+			.flat_map(|v| v.split(','))
+			.map(|v| v.trim().trim_start_matches("W/"))
+			.any(|v| v == "*" || v == tag);
+
+		if matches {
+			res.status = StatusCode::NOT_MODIFIED;
+			res.body = None;
+		}
+	}
+
+	Ok(())
+}
@@ -5,7 +5,11 @@ use crate::{
 	sql::{Bytesize, Duration},
 };
 
-use super::middleware::{invoke::InvokeMiddleware, CollectedMiddleware};
+use super::{
+	cors::CorsPolicy,
+	limit::RateLimit,
+	middleware::{invoke::InvokeMiddleware, CollectedMiddleware},
+};
 
 #[derive(Default, Debug)]
 pub struct InvocationContext {
@@ -13,7 +17,12 @@ pub struct InvocationContext {
 	pub request_body_raw: bool,
 	pub response_body_raw: bool,
 	pub response_headers: Option<HeaderMap>,
+	pub response_etag: bool,
+	pub response_cache: Option<Duration>,
+	pub response_compress: bool,
 	pub timeout: Option<Duration>,
+	pub cors: Option<CorsPolicy>,
+	pub rate_limit: Option<RateLimit>,
 }
 
 impl InvocationContext {
@@ -0,0 +1,150 @@
+use http::{
+	header::{
+		ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_HEADERS,
+		ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS,
+		ACCESS_CONTROL_MAX_AGE, ACCESS_CONTROL_REQUEST_HEADERS, ORIGIN, VARY,
+	},
+	HeaderMap, HeaderValue, StatusCode,
+};
+
+use crate::{
+	err::Error,
+	sql::{Duration, Object, Value},
+};
+
+use super::response::ApiResponse;
+
+/// A CORS policy, as configured by the `api::cors` middleware
+#[derive(Clone, Debug, Default)]
+pub struct CorsPolicy {
+	/// The allowed origins, where `None` allows any origin
+	pub origins: Option<Vec<String>>,
+	/// The allowed request methods, defaulting to those of the API
+	pub methods: Option<Vec<String>>,
+	/// The allowed request headers, defaulting to those requested
+	pub headers: Option<Vec<String>>,
+	/// The response headers which are exposed to the client
+	pub expose: Vec<String>,
+	/// Whether credentials are allowed
+	pub credentials: bool,
+	/// How long a preflight response may be cached for
+	pub max_age: Option<Duration>,
+}
+
+impl CorsPolicy {
+	pub fn new(origins: Value, options: Option<Object>) -> Result<Self, Error> {
+		let mut policy = CorsPolicy {
+			origins: match origins {
+				Value::Strand(v) if v.as_str() == "*" => None,
+				v => Some(Self::strings("origins", v)?),
+			},
+			..Default::default()
+		};
+
+		for (k, v) in options.unwrap_or_default() {
+			match k.as_str() {
+				"methods" => policy.methods = Some(Self::strings("methods", v)?),
+				"headers" => policy.headers = Some(Self::strings("headers", v)?),
+				"expose" => policy.expose = Self::strings("expose", v)?,
+				"credentials" => policy.credentials = v.coerce_to_bool()?,
+				"max_age" => policy.max_age = Some(v.coerce_to_duration()?),
+				_ => {
+					return Err(Error::InvalidArguments {
+						name: "api::cors".to_string(),
+						message: format!("Unexpected option '{k}'"),
+					})
+				}
+			}
+		}
+
+		Ok(policy)
+	}
+
+	/// Converts a string, or an array of strings, into a list of strings
+	fn strings(option: &str, value: Value) -> Result<Vec<String>, Error> {
+		match value {
+			Value::Strand(v) => Ok(vec![v.0]),
+			Value::Array(v) => v.into_iter().map(Value::coerce_to_string).collect(),
+			v => Err(Error::InvalidArguments {
+				name: "api::cors".to_string(),
+				message: format!(
+					"Expected a string or an array for '{option}', found {}",
+					v.kindof()
+				),
+			}),
+		}
+	}
+
+	/// Returns the value for the `Access-Control-Allow-Origin` header, if
+	/// the origin of the request is allowed by this policy
+	fn allow_origin(&self, request: &HeaderMap) -> Option<HeaderValue> {
+		let origin = request.get(ORIGIN)?;
+		match &self.origins {
+			// Credentialed requests can not use a wildcard
+			None if self.credentials => Some(origin.clone()),
+			None => Some(HeaderValue::from_static("*")),
+			Some(origins) => {
+				let value = origin.to_str().ok()?;
+				origins.iter().any(|v| v == value).then(|| origin.clone())
+			}
+		}
+	}
+
+	/// Adds the CORS headers for a request to the response headers
+	pub fn apply(&self, request: &HeaderMap, response: &mut HeaderMap) -> Result<(), Error> {
+		if let Some(origin) = self.allow_origin(request) {
+			if origin != "*" {
+				let vary = match response.get(VARY) {
+					Some(v) => format!("{}, origin", v.to_str()?).parse()?,
+					None => HeaderValue::from_static("origin"),
+				};
+				response.insert(VARY, vary);
+			}
+			response.insert(ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+			if self.credentials {
+				response.insert(ACCESS_CONTROL_ALLOW_CREDENTIALS, HeaderValue::from_static("true"));
+			}
+			if !self.expose.is_empty() {
+				response.insert(ACCESS_CONTROL_EXPOSE_HEADERS, self.expose.join(", ").parse()?);
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Computes the response to a preflight request, where `methods`
+	/// are the methods which are defined on the requested API
+	pub fn preflight(&self, request: &HeaderMap, methods: &[String]) -> Result<ApiResponse, Error> {
+		let mut headers = HeaderMap::new();
+		self.apply(request, &mut headers)?;
+
+		if headers.contains_key(ACCESS_CONTROL_ALLOW_ORIGIN) {
+			let methods = self.methods.as_deref().unwrap_or(methods);
+			headers
+				.insert(ACCESS_CONTROL_ALLOW_METHODS, methods.join(", ").to_uppercase().parse()?);
+
+			match &self.headers {
+				Some(v) => {
+					headers.insert(ACCESS_CONTROL_ALLOW_HEADERS, v.join(", ").parse()?);
+				}
+				None => {
+					if let Some(v) = request.get(ACCESS_CONTROL_REQUEST_HEADERS) {
+						headers.insert(ACCESS_CONTROL_ALLOW_HEADERS, v.clone());
+					}
+				}
+			}
+
+			if let Some(max_age) = &self.max_age {
+				headers.insert(ACCESS_CONTROL_MAX_AGE, max_age.as_secs().into());
+			}
+		}
+
+		Ok(ApiResponse {
+			raw: None,
+			status: StatusCode::NO_CONTENT,
+			body: None,
+			headers,
+			compress: false,
+		})
+	}
+}
@@ -5,9 +5,11 @@ use reblessive::{tree::Stk, TreeStack};
 
 use super::{
 	body::ApiBody,
+	cache,
 	context::InvocationContext,
+	limit::RateLimit,
 	method::Method,
-	middleware::CollectMiddleware,
+	middleware::{CollectMiddleware, CollectedMiddleware},
 	response::{ApiResponse, ResponseInstruction},
 };
 use crate::{
@@ -18,10 +20,16 @@ use crate::{
 	kvs::{Datastore, Transaction},
 	sql::{
 		statements::{define::config::api::ApiConfig, define::ApiDefinition},
-		Object, Value,
+		Closure, Function, Ident, Kind, Object, Param, Value,
 	},
 };
 
+/// The internal function which computes the action of an API, once the
+/// request has passed through any custom middleware. The rate limit and
+/// the response cache are applied within it, so that neither of them can
+/// bypass the checks performed by the custom middleware.
+pub(crate) const ACTION_FUNCTION: &str = "api::invoke::action";
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ApiInvocation {
@@ -57,12 +65,63 @@ impl ApiInvocation {
 
 		let mut ctx = ds.setup_ctx()?;
 		ctx.set_transaction(tx);
+		sess.context(&mut ctx);
 		let ctx = &ctx.freeze();
 
 		let mut stack = TreeStack::new();
 		stack.enter(|stk| self.invoke_with_context(stk, ctx, &opt, api, body)).finish().await
 	}
 
+	/// Computes the response to a CORS preflight request, where the method
+	/// of the invocation is the method requested by the preflight request.
+	/// Returns `None` when no CORS policy is configured for that method.
+	pub async fn preflight_with_transaction(
+		self,
+		tx: Arc<Transaction>,
+		ds: Arc<Datastore>,
+		sess: &Session,
+		api: &ApiDefinition,
+	) -> Result<Option<ApiResponse>, Error> {
+		let opt = ds.setup_options(sess);
+
+		let mut ctx = ds.setup_ctx()?;
+		ctx.set_transaction(tx);
+		let ctx = &ctx.freeze();
+
+		let action_config = match api.actions.iter().find(|x| x.methods.contains(&self.method)) {
+			Some(v) => &v.config,
+			None if api.fallback.is_some() => &None,
+			None => return Ok(None),
+		};
+
+		let mut configs: Vec<&ApiConfig> = Vec::new();
+		let global = ctx.tx().get_db_optional_config(opt.ns()?, opt.db()?, "api").await?;
+		configs.extend(global.as_ref().map(|v| v.inner.try_into_api()).transpose()?);
+		configs.extend(api.config.as_ref());
+		configs.extend(action_config);
+
+		let middleware: Vec<&RequestMiddleware> =
+			configs.into_iter().filter_map(|v| v.middleware.as_ref()).collect();
+		let builtin: CollectedMiddleware =
+			middleware.collect()?.into_iter().filter(|(k, _)| k.starts_with("api::")).collect();
+
+		let mut inv_ctx = InvocationContext::default();
+		inv_ctx.apply_middleware(builtin)?;
+
+		let Some(cors) = inv_ctx.cors else {
+			return Ok(None);
+		};
+
+		// Any method is allowed when the API has a fallback action
+		let methods: Vec<String> = if api.fallback.is_some() {
+			["delete", "get", "patch", "post", "put", "trace"].map(String::from).to_vec()
+		} else {
+			api.actions.iter().flat_map(|x| x.methods.iter()).map(Method::to_string).collect()
+		};
+
+		cors.preflight(&self.headers, &methods).map(Some)
+	}
+
 	// The `invoke` method accepting a parameter like `Option<&mut Stk>`
 	// causes issues with axum, hence the separation
 	pub async fn invoke_with_context(
@@ -90,7 +149,10 @@ impl ApiInvocation {
 
 		let middleware: Vec<&RequestMiddleware> =
 			configs.into_iter().filter_map(|v| v.middleware.as_ref()).collect();
-		let builtin = middleware.collect()?;
+		// Builtin middleware configure the invocation, while
+		// custom middleware are called around the action
+		let (builtin, custom): (CollectedMiddleware, CollectedMiddleware) =
+			middleware.collect()?.into_iter().partition(|(k, _)| k.starts_with("api::"));
 
 		let mut inv_ctx = InvocationContext::default();
 		inv_ctx.apply_middleware(builtin)?;
@@ -104,6 +166,18 @@ impl ApiInvocation {
 			ResponseInstruction::for_format(&self)?
 		};
 
+		let (method, headers) = (self.method, self.headers.clone());
+
+		// Configure the rate limit and cache applied to the action
+		let config = Value::Object(
+			map! {
+				"path" => Value::from(api.path.to_string()),
+				"limit", if let Some(v) = &inv_ctx.rate_limit => Value::from(v),
+				"cache", if let (Some(v), Method::Get) = (inv_ctx.response_cache, method) => Value::from(v),
+			}
+			.into(),
+		);
+
 		let body = body.process(&inv_ctx, &self).await?;
 
 		// Edit the options
@@ -126,15 +200,430 @@ impl ApiInvocation {
 
 		// Compute the action
 
+		let action = wrap_middleware(custom, action, config);
 		let res = action.compute(stk, &ctx, &opt, None).await?;
 
 		let mut res = ApiResponse::try_from(res)?;
-		if let Some(headers) = inv_ctx.response_headers {
-			let mut headers = headers;
+		if let Some(mut headers) = inv_ctx.response_headers.take() {
 			headers.extend(res.headers);
 			res.headers = headers;
 		}
 
+		finish(&inv_ctx, method, &headers, &mut res)?;
+
 		Ok(Some((res, res_instruction)))
 	}
 }
+
+/// Computes the action of an API for a request which has passed through any
+/// custom middleware, counting it against the rate limit, and serving or
+/// storing the response in the cache, as configured by the builtin middleware
+pub(crate) async fn invoke_action(
+	stk: &mut Stk,
+	ctx: &Context,
+	opt: &Options,
+	request: Value,
+	action: Closure,
+	config: Object,
+) -> Result<Value, Error> {
+	let (ns, db) = (opt.ns()?, opt.db()?);
+	let Some(Value::Strand(path)) = config.get("path") else {
+		return Err(fail!("Expected the path of the API to be configured"));
+	};
+
+	// Count the request against the rate limit
+	if let Some(v) = config.get("limit") {
+		if let Some(res) = RateLimit::try_from(v)?.check(ctx, ns, db, path.as_str())? {
+			return res.try_into();
+		}
+	}
+
+	// Serve the response from the cache if possible
+	let cached = match config.get("cache") {
+		Some(Value::Duration(ttl)) => Some((ttl, cache::key(opt, path.as_str(), &request))),
+		_ => None,
+	};
+	if let Some((_, key)) = &cached {
+		if let Some(res) = cache::get(ctx, ns, db, key)? {
+			return res.try_into();
+		}
+	}
+
+	let res = action.compute(stk, ctx, opt, None, vec![request]).await?;
+
+	// Possibly store the response in the cache
+	match &cached {
+		Some((ttl, key)) => {
+			let mut res = ApiResponse::try_from(res)?;
+			cache::set(ctx, ns, db, key, ttl, &mut res)?;
+			res.try_into()
+		}
+		None => Ok(res),
+	}
+}
+
+/// Applies the builtin middleware which operate on the final response,
+/// whether it was computed, served from the cache, or rate limited
+fn finish(
+	inv_ctx: &InvocationContext,
+	method: Method,
+	headers: &HeaderMap,
+	res: &mut ApiResponse,
+) -> Result<(), Error> {
+	if inv_ctx.response_etag {
+		cache::etag(method, headers, res)?;
+	}
+	if let Some(cors) = &inv_ctx.cors {
+		cors.apply(headers, &mut res.headers)?;
+	}
+	res.compress = inv_ctx.response_compress;
+	Ok(())
+}
+
+/// Wraps the action in the custom middleware functions, outermost first.
+///
+/// Each function is called with the request object and a `$next` closure,
+/// which accepts a request and runs the rest of the chain, returning its
+/// response. A middleware can therefore modify the request, return its own
+/// response without calling `$next`, or modify the response it receives.
+/// The innermost `$next` closure computes the action through [`invoke_action`].
+fn wrap_middleware(custom: CollectedMiddleware, action: &Value, config: Value) -> Value {
+	let action = Closure {
+		args: vec![(Ident::from("request"), Kind::Object)],
+		returns: None,
+		body: action.to_owned(),
+	};
+	let mut next: Value = Function::Normal(
+		ACTION_FUNCTION.to_owned(),
+		vec![Value::from(Param::from("request")), Value::from(action), config],
+	)
+	.into();
+	for (name, args) in custom.into_iter().rev() {
+		let name = name.strip_prefix("fn::").unwrap_or(name);
+		let closure = Closure {
+			args: vec![(Ident::from("request"), Kind::Object)],
+			returns: None,
+			body: next,
+		};
+		let mut fargs = vec![Value::from(Param::from("request")), Value::from(closure)];
+		fargs.extend(args.iter().cloned());
+		next = Function::Custom(name.to_owned(), fargs).into();
+	}
+	next
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use http::{HeaderMap, StatusCode};
+
+	use super::ApiInvocation;
+	use crate::api::{body::ApiBody, method::Method, response::ApiResponse};
+	use crate::dbs::capabilities::{Capabilities, ExperimentalTarget};
+	use crate::dbs::Session;
+	use crate::kvs::{Datastore, LockType::Optimistic, TransactionType::Read};
+	use crate::sql::{statements::FindApi, Value};
+
+	/// Creates a datastore with the APIs defined by the given statements
+	async fn datastore(sql: &str) -> (Arc<Datastore>, Session) {
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(
+			Capabilities::default().with_experimental(ExperimentalTarget::DefineApi.into()),
+		);
+		let ses = Session::owner().with_ns("test").with_db("test");
+		for res in ds.execute(sql, &ses, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		(Arc::new(ds), ses)
+	}
+
+	/// Invokes an API through `api::invoke`, returning the response object
+	async fn invoke(ds: &Datastore, ses: &Session, sql: &str) -> Value {
+		ds.execute(sql, ses, None).await.unwrap().remove(0).result.unwrap()
+	}
+
+	/// Invokes an API, or its CORS preflight, as an HTTP request would
+	async fn request(
+		ds: &Arc<Datastore>,
+		ses: &Session,
+		path: &str,
+		method: Method,
+		headers: &[(&'static str, &'static str)],
+		preflight: bool,
+	) -> Option<ApiResponse> {
+		let tx = Arc::new(ds.transaction(Read, Optimistic).await.unwrap());
+		let apis = tx.all_db_apis("test", "test").await.unwrap();
+		let apis = apis.as_ref();
+		let segments: Vec<&str> = path.split('/').filter(|x| !x.is_empty()).collect();
+		let (api, params) = apis.find_api(segments, method)?;
+		let invocation = ApiInvocation {
+			params,
+			method,
+			query: Default::default(),
+			headers: headers
+				.iter()
+				.map(|(k, v)| (k.parse().unwrap(), v.parse().unwrap()))
+				.collect(),
+		};
+		let res = match preflight {
+			true => invocation.preflight_with_transaction(tx.clone(), ds.clone(), ses, api).await,
+			false => invocation
+				.invoke_with_transaction(
+					tx.clone(),
+					ds.clone(),
+					ses,
+					api,
+					ApiBody::from_value(Value::None),
+				)
+				.await
+				.map(|v| v.map(|v| v.0)),
+		};
+		tx.cancel().await.unwrap();
+		res.unwrap()
+	}
+
+	/// Returns a response header as a string
+	fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+		headers.get(name).map(|v| v.to_str().unwrap())
+	}
+
+	#[tokio::test]
+	async fn custom_middleware_before_cache() {
+		let (ds, ses) = datastore(
+			r#"
+			DEFINE FUNCTION fn::auth($req: object, $next: function) {
+				RETURN IF $req.headers.authorization == "secret" {
+					$next($req)
+				} ELSE {
+					{ status: 401 }
+				};
+			};
+			DEFINE API "/cached" FOR get MIDDLEWARE api::rate_limit(3, 1m), api::res::cache(1m), fn::auth() THEN {
+				RETURN { status: 200, body: rand::uuid() };
+			};
+		"#,
+		)
+		.await;
+		let invoke = |headers: &'static str| {
+			let ds = &ds;
+			let ses = &ses;
+			async move {
+				let sql = format!("api::invoke('/cached', {{ headers: {headers} }})");
+				ds.execute(&sql, ses, None).await.unwrap().remove(0).result.unwrap()
+			}
+		};
+		// An authorized response is cached
+		let first = invoke("{ authorization: 'secret' }").await;
+		assert_eq!(first.pick(&["status".into()]), Value::from(200));
+		let second = invoke("{ authorization: 'secret' }").await;
+		assert_eq!(first.pick(&["body".into()]), second.pick(&["body".into()]));
+		// The cached response is not served without authorization
+		let res = invoke("{}").await;
+		assert_eq!(res.pick(&["status".into()]), Value::from(401));
+		// Unauthorized requests are not counted against the rate limit
+		let res = invoke("{ authorization: 'secret', cookie: 'session=other' }").await;
+		assert_eq!(res.pick(&["status".into()]), Value::from(200));
+		// Requests with other credentials are not served from the cache
+		assert_ne!(first.pick(&["body".into()]), res.pick(&["body".into()]));
+		let res = invoke("{ authorization: 'secret' }").await;
+		assert_eq!(res.pick(&["status".into()]), Value::from(429));
+	}
+
+	#[tokio::test]
+	async fn cors() {
+		let (ds, ses) = datastore(
+			r#"
+			DEFINE API "/cors" FOR get, post MIDDLEWARE api::cors(["https://example.com"], { credentials: true, expose: "x-id", max_age: 10m }) THEN {
+				RETURN { status: 200, body: "ok" };
+			};
+			DEFINE API "/open" FOR get MIDDLEWARE api::cors("*") THEN {
+				RETURN { status: 200, body: "ok" };
+			};
+			DEFINE API "/closed" FOR get THEN {
+				RETURN { status: 200, body: "ok" };
+			};
+		"#,
+		)
+		.await;
+		let origin = [("origin", "https://example.com")];
+		// Allowed origins receive the CORS headers
+		let res = request(&ds, &ses, "/cors", Method::Get, &origin, false).await.unwrap();
+		assert_eq!(
+			header(&res.headers, "access-control-allow-origin"),
+			Some("https://example.com")
+		);
+		assert_eq!(header(&res.headers, "access-control-allow-credentials"), Some("true"));
+		assert_eq!(header(&res.headers, "access-control-expose-headers"), Some("x-id"));
+		assert_eq!(header(&res.headers, "vary"), Some("origin"));
+		// Other origins do not
+		let other = [("origin", "https://other.com")];
+		let res = request(&ds, &ses, "/cors", Method::Get, &other, false).await.unwrap();
+		assert_eq!(header(&res.headers, "access-control-allow-origin"), None);
+		assert_eq!(header(&res.headers, "access-control-allow-credentials"), None);
+		// Any origin is allowed by a wildcard, without varying the response
+		let res = request(&ds, &ses, "/open", Method::Get, &other, false).await.unwrap();
+		assert_eq!(header(&res.headers, "access-control-allow-origin"), Some("*"));
+		assert_eq!(header(&res.headers, "vary"), None);
+		// Preflight requests are answered with the allowed methods and headers
+		let headers =
+			[("origin", "https://example.com"), ("access-control-request-headers", "x-id")];
+		let res = request(&ds, &ses, "/cors", Method::Post, &headers, true).await.unwrap();
+		assert_eq!(res.status, StatusCode::NO_CONTENT);
+		assert_eq!(header(&res.headers, "access-control-allow-methods"), Some("GET, POST"));
+		assert_eq!(header(&res.headers, "access-control-allow-headers"), Some("x-id"));
+		assert_eq!(header(&res.headers, "access-control-max-age"), Some("600"));
+		let res = request(&ds, &ses, "/cors", Method::Post, &other, true).await.unwrap();
+		assert_eq!(header(&res.headers, "access-control-allow-methods"), None);
+		// There is no preflight response without a CORS policy
+		assert!(request(&ds, &ses, "/closed", Method::Get, &origin, true).await.is_none());
+	}
+
+	#[tokio::test]
+	async fn rate_limit() {
+		let (ds, ses) = datastore(
+			r#"
+			DEFINE API "/limited" FOR get MIDDLEWARE api::rate_limit(2, 1m) THEN {
+				RETURN { status: 200, body: "ok" };
+			};
+			DEFINE API "/other" FOR get MIDDLEWARE api::rate_limit(2, 1m) THEN {
+				RETURN { status: 200, body: "ok" };
+			};
+		"#,
+		)
+		.await;
+		// Requests over the limit are rejected
+		for status in [200, 200, 429] {
+			let res = invoke(&ds, &ses, "api::invoke('/limited')").await;
+			assert_eq!(res.pick(&["status".into()]), Value::from(status));
+		}
+		let res = request(&ds, &ses, "/limited", Method::Get, &[], false).await.unwrap();
+		assert_eq!(res.status, StatusCode::TOO_MANY_REQUESTS);
+		assert_eq!(res.body, None);
+		let retry: u64 = header(&res.headers, "retry-after").unwrap().parse().unwrap();
+		assert!((1..=60).contains(&retry));
+		assert_eq!(header(&res.headers, "x-ratelimit-limit"), Some("2"));
+		assert_eq!(header(&res.headers, "x-ratelimit-remaining"), Some("0"));
+		// Requests are counted separately for each API
+		let res = invoke(&ds, &ses, "api::invoke('/other')").await;
+		assert_eq!(res.pick(&["status".into()]), Value::from(200));
+		// Requests can only be grouped by ip address or token
+		let sql = r#"
+			DEFINE API "/invalid" FOR get MIDDLEWARE api::rate_limit(2, 1m, "user") THEN {};
+			api::invoke('/invalid');
+		"#;
+		let mut res = ds.execute(sql, &ses, None).await.unwrap();
+		let err = res.remove(1).result.unwrap_err().to_string();
+		assert!(err.ends_with("Expected 'ip' or 'token', found 'user'"), "{err}");
+	}
+
+	#[tokio::test]
+	async fn etag() {
+		let (ds, ses) = datastore(
+			r#"
+			DEFINE API "/tagged" FOR get, post MIDDLEWARE api::res::etag() THEN {
+				RETURN { status: 200, body: "hello" };
+			};
+			DEFINE API "/missing" FOR get MIDDLEWARE api::res::etag() THEN {
+				RETURN { status: 404, body: "missing" };
+			};
+		"#,
+		)
+		.await;
+		// Successful responses are tagged with a weak tag of their body
+		let res = invoke(&ds, &ses, "api::invoke('/tagged')").await;
+		let Value::Strand(etag) = res.pick(&["headers".into(), "etag".into()]) else {
+			panic!("expected an etag header")
+		};
+		assert!(etag.starts_with("W/\""));
+		let tag = etag.trim_start_matches("W/");
+		// Conditional requests with a matching tag are not modified
+		for condition in
+			[etag.as_str().to_owned(), tag.to_owned(), format!("\"other\", {tag}"), "*".to_owned()]
+		{
+			let sql = format!(
+				"api::invoke('/tagged', {{ headers: {{ 'if-none-match': {} }} }})",
+				Value::from(condition)
+			);
+			let res = invoke(&ds, &ses, &sql).await;
+			assert_eq!(res.pick(&["status".into()]), Value::from(304));
+			assert_eq!(res.pick(&["body".into()]), Value::None);
+		}
+		// Other conditional requests receive the full response
+		let sql = "api::invoke('/tagged', { headers: { 'if-none-match': '\"other\"' } })";
+		let res = invoke(&ds, &ses, sql).await;
+		assert_eq!(res.pick(&["status".into()]), Value::from(200));
+		assert_eq!(res.pick(&["body".into()]), Value::from("hello"));
+		// Only GET requests are answered as not modified
+		let sql = format!(
+			"api::invoke('/tagged', {{ method: 'post', headers: {{ 'if-none-match': {} }} }})",
+			Value::from(etag.as_str())
+		);
+		let res = invoke(&ds, &ses, &sql).await;
+		assert_eq!(res.pick(&["status".into()]), Value::from(200));
+		// Unsuccessful responses are not tagged
+		let res = invoke(&ds, &ses, "api::invoke('/missing')").await;
+		assert_eq!(res.pick(&["headers".into(), "etag".into()]), Value::None);
+	}
+
+	#[tokio::test]
+	async fn cache() {
+		let (ds, ses) = datastore(
+			r#"
+			DEFINE API "/cached" FOR get, post MIDDLEWARE api::res::cache(1m) THEN {
+				RETURN { status: 200, body: rand::uuid() };
+			};
+			DEFINE API "/failing" FOR get MIDDLEWARE api::res::cache(1m) THEN {
+				RETURN { status: 500, body: rand::uuid() };
+			};
+		"#,
+		)
+		.await;
+		let body = |res: &Value| res.pick(&["body".into()]);
+		// Responses are served from the cache
+		let first = invoke(&ds, &ses, "api::invoke('/cached')").await;
+		let second = invoke(&ds, &ses, "api::invoke('/cached')").await;
+		assert_eq!(body(&first), body(&second));
+		assert_eq!(
+			first.pick(&["headers".into(), "cache-control".into()]),
+			Value::from("max-age=60")
+		);
+		// Requests with other parameters are cached separately
+		let res = invoke(&ds, &ses, "api::invoke('/cached', { query: { page: '2' } })").await;
+		assert_ne!(body(&first), body(&res));
+		// Only GET requests are cached
+		let sql = "api::invoke('/cached', { method: 'post' })";
+		let (first, second) = (invoke(&ds, &ses, sql).await, invoke(&ds, &ses, sql).await);
+		assert_ne!(body(&first), body(&second));
+		assert_eq!(first.pick(&["headers".into(), "cache-control".into()]), Value::None);
+		// Unsuccessful responses are not cached
+		let sql = "api::invoke('/failing')";
+		let (first, second) = (invoke(&ds, &ses, sql).await, invoke(&ds, &ses, sql).await);
+		assert_eq!(first.pick(&["status".into()]), Value::from(500));
+		assert_ne!(body(&first), body(&second));
+	}
+
+	#[tokio::test]
+	async fn compression() {
+		let (ds, ses) = datastore(
+			r#"
+			DEFINE API "/compressed" FOR get MIDDLEWARE api::res::compress() THEN {
+				RETURN { status: 200, body: "hello" };
+			};
+			DEFINE API "/uncompressed" FOR get MIDDLEWARE api::res::compress(false) THEN {
+				RETURN { status: 200, body: "hello" };
+			};
+			DEFINE API "/plain" FOR get THEN {
+				RETURN { status: 200, body: "hello" };
+			};
+		"#,
+		)
+		.await;
+		// Only responses with compression enabled are marked for compression
+		for (path, compress) in [("/compressed", true), ("/uncompressed", false), ("/plain", false)]
+		{
+			let res = request(&ds, &ses, path, Method::Get, &[], false).await.unwrap();
+			assert_eq!(res.compress, compress, "{path}");
+			assert_eq!(res.body, Some(Value::from("hello")));
+		}
+	}
+}
@@ -0,0 +1,185 @@
+use dashmap::DashMap;
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use trice::Instant;
+
+use crate::{
+	ctx::Context,
+	err::Error,
+	sql::{Duration, Value},
+};
+
+use super::response::ApiResponse;
+
+static RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("x-ratelimit-limit");
+static RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
+
+/// What requests are grouped by when they are counted
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RateLimitKey {
+	/// Requests are counted per client ip address
+	Ip,
+	/// Requests are counted per authentication token, or
+	/// per client ip address for unauthenticated requests
+	Token,
+}
+
+/// A rate limit, as configured by the `api::rate_limit` middleware
+#[derive(Clone, Debug)]
+pub struct RateLimit {
+	/// The number of requests allowed within a window
+	pub limit: u64,
+	/// The length of a window
+	pub window: Duration,
+	/// What requests are grouped by
+	pub key: RateLimitKey,
+}
+
+/// The number of counted clients above which passed windows are removed
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// The requests counted within the current window
+struct RateWindow {
+	start: Instant,
+	expires: Instant,
+	count: u64,
+}
+
+/// The request counts of every rate limited client on this node. Unlike
+/// the entries of the datastore cache, these are never evicted while
+/// their window is still running.
+#[derive(Default)]
+pub(crate) struct RateLimits {
+	windows: DashMap<(String, String, String), RateWindow>,
+}
+
+impl RateLimits {
+	/// Counts a request in the current window of a client, starting a
+	/// new window if the previous one has passed. Returns the start of
+	/// the window, and the number of requests counted within it.
+	fn count(
+		&self,
+		key: (String, String, String),
+		now: Instant,
+		window: Duration,
+	) -> (Instant, u64) {
+		let res = {
+			// The entry is locked while the window is updated
+			let mut v = self.windows.entry(key).or_insert_with(|| RateWindow {
+				start: now,
+				expires: now,
+				count: 0,
+			});
+			if now >= v.expires {
+				v.start = now;
+				v.expires = now + *window;
+				v.count = 0;
+			}
+			v.count += 1;
+			(v.start, v.count)
+		};
+		// Remove any windows which have passed
+		if self.windows.len() > PRUNE_THRESHOLD {
+			self.windows.retain(|_, v| v.expires > now);
+		}
+		res
+	}
+}
+
+impl From<&RateLimit> for Value {
+	fn from(v: &RateLimit) -> Self {
+		let key = match v.key {
+			RateLimitKey::Ip => "ip",
+			RateLimitKey::Token => "token",
+		};
+		Value::Object(
+			map! {
+				"limit" => Value::from(v.limit as i64),
+				"window" => Value::from(v.window),
+				"key" => Value::from(key),
+			}
+			.into(),
+		)
+	}
+}
+
+impl TryFrom<&Value> for RateLimit {
+	type Error = Error;
+	fn try_from(v: &Value) -> Result<Self, Self::Error> {
+		let limit = v.pick(&["limit".into()]).coerce_to_u64()?;
+		let window = v.pick(&["window".into()]).coerce_to_duration()?;
+		let key = v.pick(&["key".into()]).coerce_to_string()?;
+		RateLimit::new(limit, window, Some(key))
+	}
+}
+
+impl RateLimit {
+	pub fn new(limit: u64, window: Duration, key: Option<String>) -> Result<Self, Error> {
+		let key = match key.as_deref() {
+			None | Some("ip") => RateLimitKey::Ip,
+			Some("token") => RateLimitKey::Token,
+			Some(v) => {
+				return Err(Error::InvalidArguments {
+					name: "api::rate_limit".to_string(),
+					message: format!("Expected 'ip' or 'token', found '{v}'"),
+				})
+			}
+		};
+
+		Ok(Self {
+			limit,
+			window,
+			key,
+		})
+	}
+
+	/// Identifies the client which made the request
+	fn client(&self, ctx: &Context) -> String {
+		if self.key == RateLimitKey::Token {
+			if let Some(tk) = ctx.value("token").filter(|v| v.is_some()) {
+				return format!("tk:{}", blake3::hash(tk.to_string().as_bytes()).to_hex());
+			}
+		}
+
+		match ctx.value("session").map(|v| v.pick(&["ip".into()])) {
+			Some(Value::Strand(ip)) => format!("ip:{ip}"),
+			_ => "anonymous".to_string(),
+		}
+	}
+
+	/// Counts the request against the limit of the client, returning a
+	/// response to send instead if the client has exceeded the limit.
+	/// The request count is kept in memory, so it is local to this node.
+	pub fn check(
+		&self,
+		ctx: &Context,
+		ns: &str,
+		db: &str,
+		path: &str,
+	) -> Result<Option<ApiResponse>, Error> {
+		let Some(cache) = ctx.get_cache() else {
+			return Ok(None);
+		};
+
+		let key = (ns.to_owned(), db.to_owned(), format!("{path}:{}", self.client(ctx)));
+		let now = Instant::now();
+
+		let (start, count) = cache.rate_limits().count(key, now, self.window);
+		if count <= self.limit {
+			return Ok(None);
+		}
+
+		let retry = self.window.saturating_sub(now.duration_since(start));
+		let mut headers = HeaderMap::new();
+		headers.insert(http::header::RETRY_AFTER, retry.as_secs().max(1).into());
+		headers.insert(RATELIMIT_LIMIT.clone(), self.limit.into());
+		headers.insert(RATELIMIT_REMAINING.clone(), HeaderValue::from_static("0"));
+
+		Ok(Some(ApiResponse {
+			raw: None,
+			status: StatusCode::TOO_MANY_REQUESTS,
+			body: None,
+			headers,
+			compress: false,
+		}))
+	}
+}
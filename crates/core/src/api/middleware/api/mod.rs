@@ -1,9 +1,29 @@
 pub mod req;
 pub mod res;
 
-use crate::{api::context::InvocationContext, err::Error, sql::Duration};
+use crate::{
+	api::{context::InvocationContext, cors::CorsPolicy, limit::RateLimit},
+	err::Error,
+	sql::{Duration, Object, Value},
+};
 
 pub fn timeout(context: &mut InvocationContext, (timeout,): (Duration,)) -> Result<(), Error> {
 	context.timeout = Some(timeout);
 	Ok(())
 }
+
+pub fn cors(
+	context: &mut InvocationContext,
+	(origins, options): (Value, Option<Object>),
+) -> Result<(), Error> {
+	context.cors = Some(CorsPolicy::new(origins, options)?);
+	Ok(())
+}
+
+pub fn rate_limit(
+	context: &mut InvocationContext,
+	(limit, window, key): (u64, Duration, Option<String>),
+) -> Result<(), Error> {
+	context.rate_limit = Some(RateLimit::new(limit, window, key)?);
+	Ok(())
+}
@@ -3,7 +3,7 @@ use http::{HeaderMap, HeaderName, HeaderValue};
 use crate::{
 	api::context::InvocationContext,
 	err::Error,
-	sql::{Object, Value},
+	sql::{Duration, Object, Value},
 };
 
 pub fn raw_body(context: &mut InvocationContext, (raw,): (Option<bool>,)) -> Result<(), Error> {
@@ -11,6 +11,24 @@ pub fn raw_body(context: &mut InvocationContext, (raw,): (Option<bool>,)) -> Res
 	Ok(())
 }
 
+pub fn etag(context: &mut InvocationContext, (etag,): (Option<bool>,)) -> Result<(), Error> {
+	context.response_etag = etag.unwrap_or(true);
+	Ok(())
+}
+
+pub fn cache(context: &mut InvocationContext, (ttl,): (Duration,)) -> Result<(), Error> {
+	context.response_cache = Some(ttl);
+	Ok(())
+}
+
+pub fn compress(
+	context: &mut InvocationContext,
+	(compress,): (Option<bool>,),
+) -> Result<(), Error> {
+	context.response_compress = compress.unwrap_or(true);
+	Ok(())
+}
+
 pub fn header(
 	context: &mut InvocationContext,
 	(name, value): (String, Value),
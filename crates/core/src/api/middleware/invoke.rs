@@ -41,8 +41,13 @@ impl<'a> InvokeMiddleware<'a> for (&'a String, &'a Vec<Value>) {
 			"api::res::raw_body" => api::res::raw_body,
 			"api::res::headers" => api::res::headers,
 			"api::res::header" => api::res::header,
+			"api::res::etag" => api::res::etag,
+			"api::res::cache" => api::res::cache,
+			"api::res::compress" => api::res::compress,
 			//
 			"api::timeout" => api::timeout,
+			"api::cors" => api::cors,
+			"api::rate_limit" => api::rate_limit,
 		)
 	}
 }
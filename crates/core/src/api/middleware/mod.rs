@@ -43,12 +43,7 @@ impl<'a> CollectMiddleware<'a> for Vec<&'a RequestMiddleware> {
 		for map in self.iter() {
 			for (k, v) in map.iter() {
 				match k.split_once("::") {
-					Some(("api", _)) => middleware.push((k, v)),
-					Some(("fn", _)) => {
-						return Err(Error::Unreachable(
-							"Custom middleware are not yet supported".into(),
-						))
-					}
+					Some(("api" | "fn", _)) => middleware.push((k, v)),
 					_ => {
 						return Err(Error::Unreachable(
 							"Found a middleware which is unparsable".into(),
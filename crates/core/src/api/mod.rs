@@ -1,7 +1,10 @@
 pub mod body;
+pub mod cache;
 pub mod context;
+pub mod cors;
 pub mod err;
 pub mod invocation;
+pub mod limit;
 pub mod method;
 pub mod middleware;
 pub mod path;
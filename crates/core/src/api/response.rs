@@ -7,12 +7,14 @@ use crate::{err::Error, rpc::format::Format, sql::Value};
 
 use super::{err::ApiError, invocation::ApiInvocation};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ApiResponse {
 	pub raw: Option<bool>,
 	pub status: StatusCode,
 	pub body: Option<Value>,
 	pub headers: HeaderMap,
+	/// Whether the encoded response body should be compressed
+	pub compress: bool,
 }
 
 impl TryFrom<Value> for ApiResponse {
@@ -56,6 +58,7 @@ impl TryFrom<Value> for ApiResponse {
 					status,
 					body,
 					headers,
+					compress: false,
 				})
 			}
 		} else {
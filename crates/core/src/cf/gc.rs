@@ -83,6 +83,15 @@ pub async fn gc_range(tx: &Transaction, ns: &str, db: &str, vt: VersionStamp) ->
 	);
 	// Delete the entire range in grouped batches
 	tx.delr(beg..end).await?;
+	// Record the watermark, so that readers can detect removed changes
+	let key = crate::key::database::cw::new(ns, db);
+	let prev = match tx.get(key.clone(), None).await? {
+		Some(v) => VersionStamp::from_slice(&v)?,
+		None => VersionStamp::ZERO,
+	};
+	if vt > prev {
+		tx.set(key, vt.as_bytes().to_vec(), None).await?;
+	}
 	// Ok all good
 	Ok(())
 }
@@ -12,7 +12,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::fmt::{self, Display, Formatter};
 
 // Mutation is a single mutation to a table.
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[non_exhaustive]
 pub enum TableMutation {
@@ -20,7 +20,7 @@ pub enum TableMutation {
 	// we do include it in the first field for convenience.
 	Set(Thing, Value),
 	Del(Thing),
-	Def(DefineTableStatement),
+	Def(Box<DefineTableStatement>),
 	#[revision(start = 2)]
 	/// Includes the ID, current value (after change), changes that can be applied to get the original
 	/// value
@@ -30,6 +30,9 @@ pub enum TableMutation {
 	#[revision(start = 2)]
 	/// Delete a record where the ID is stored, and the now-deleted value
 	DelWithOriginal(Thing, Value),
+	#[revision(start = 3)]
+	/// Delete every record in the table, which is stored by name
+	Truncate(String),
 }
 
 impl From<DefineTableStatement> for Value {
@@ -116,7 +119,16 @@ impl TableMutation {
 				h
 			}
 			TableMutation::Def(t) => {
-				h.insert("define_table".to_string(), Value::from(t));
+				h.insert("define_table".to_string(), Value::from(*t));
+				h
+			}
+			TableMutation::Truncate(tb) => {
+				h.insert(
+					"truncate_table".to_string(),
+					Value::Object(Object::from(map! {
+						"name".to_string() => Value::from(tb),
+					})),
+				);
 				h
 			}
 			TableMutation::DelWithOriginal(id, _val) => {
@@ -164,6 +176,7 @@ impl Display for TableMutation {
 			TableMutation::Del(id) => write!(f, "DEL {}", id),
 			TableMutation::DelWithOriginal(id, _) => write!(f, "DEL {}", id),
 			TableMutation::Def(t) => write!(f, "{}", t),
+			TableMutation::Truncate(tb) => write!(f, "TRUNCATE {}", tb),
 		}
 	}
 }
@@ -233,10 +246,10 @@ mod tests {
 						]))),
 					),
 					TableMutation::Del(Thing::from(("mytb".to_string(), "tobie".to_string()))),
-					TableMutation::Def(DefineTableStatement {
+					TableMutation::Def(Box::new(DefineTableStatement {
 						name: "mytb".into(),
 						..DefineTableStatement::default()
-					}),
+					})),
 				],
 			)]),
 		);
@@ -295,10 +308,10 @@ mod tests {
 								"note" => Value::from("surreal"),
 						})),
 					),
-					TableMutation::Def(DefineTableStatement {
+					TableMutation::Def(Box::new(DefineTableStatement {
 						name: "mytb".into(),
 						..DefineTableStatement::default()
-					}),
+					})),
 				],
 			)]),
 		);
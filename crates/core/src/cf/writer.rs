@@ -20,6 +20,8 @@ type PreparedWrite = (Vec<u8>, Vec<u8>, Vec<u8>, crate::kvs::Val);
 #[non_exhaustive]
 pub struct Writer {
 	buf: Buffer,
+	/// The number of mutations buffered for each table when each savepoint was created
+	save_points: Vec<HashMap<ChangeKey, usize>>,
 }
 
 #[non_exhaustive]
@@ -27,7 +29,7 @@ pub struct Buffer {
 	pub b: HashMap<ChangeKey, TableMutations>,
 }
 
-#[derive(Hash, Eq, PartialEq, Debug)]
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
 #[non_exhaustive]
 pub struct ChangeKey {
 	pub ns: String,
@@ -61,6 +63,37 @@ impl Writer {
 	pub(crate) fn new() -> Self {
 		Self {
 			buf: Buffer::new(),
+			save_points: Vec::new(),
+		}
+	}
+
+	/// Records the buffered mutations, so that any mutations
+	/// buffered after this point can later be discarded
+	pub(crate) fn new_save_point(&mut self) {
+		let sp = self.buf.b.iter().map(|(k, v)| (k.clone(), v.1.len())).collect();
+		self.save_points.push(sp);
+	}
+
+	/// Discards the mutations buffered since the last savepoint
+	pub(crate) fn rollback_to_save_point(&mut self) -> Result<(), Error> {
+		let Some(sp) = self.save_points.pop() else {
+			return Err(fail!("No current SavePoint"));
+		};
+		self.buf.b.retain(|k, v| match sp.get(k) {
+			Some(len) => {
+				v.1.truncate(*len);
+				true
+			}
+			None => false,
+		});
+		Ok(())
+	}
+
+	/// Releases the last savepoint, keeping the buffered mutations
+	pub(crate) fn release_last_save_point(&mut self) -> Result<(), Error> {
+		match self.save_points.pop() {
+			Some(_) => Ok(()),
+			None => Err(fail!("No current SavePoint")),
 		}
 	}
 
@@ -117,7 +150,16 @@ impl Writer {
 			ns.to_string(),
 			db.to_string(),
 			tb.to_string(),
-			TableMutation::Def(dt.to_owned()),
+			TableMutation::Def(Box::new(dt.to_owned())),
+		)
+	}
+
+	pub(crate) fn truncate_table(&mut self, ns: &str, db: &str, tb: &str) {
+		self.buf.push(
+			ns.to_string(),
+			db.to_string(),
+			tb.to_string(),
+			TableMutation::Truncate(tb.to_string()),
 		)
 	}
 
@@ -23,6 +23,10 @@ pub static MAX_CONCURRENT_TASKS: LazyLock<usize> =
 pub static MAX_COMPUTATION_DEPTH: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_MAX_COMPUTATION_DEPTH", u32, 120);
 
+/// Specifies how many times a WHILE loop will iterate before an error is returned.
+pub static MAX_LOOP_ITERATIONS: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_MAX_LOOP_ITERATIONS", u64, 1_000_000);
+
 /// Specifies how deep the parser will parse nested objects and arrays in a query.
 pub static MAX_OBJECT_PARSING_DEPTH: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_MAX_OBJECT_PARSING_DEPTH", u32, 100);
@@ -51,6 +55,21 @@ pub static NORMAL_FETCH_SIZE: LazyLock<u32> =
 pub static EXPORT_BATCH_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_EXPORT_BATCH_SIZE", u32, 1000);
 
+/// The maximum number of change sets that should be read at once for replication.
+pub static SYNC_BATCH_SIZE: LazyLock<u32> = lazy_env_parse!("SURREAL_SYNC_BATCH_SIZE", u32, 1000);
+
+/// The maximum number of statements that should be applied at once when applying a replicated snapshot or changes.
+pub static SYNC_APPLY_BATCH_SIZE: LazyLock<u32> =
+	lazy_env_parse!("SURREAL_SYNC_APPLY_BATCH_SIZE", u32, 1000);
+
+/// The maximum size in bytes of a replicated snapshot or set of changes, which is applied in a single transaction.
+pub static SYNC_APPLY_MAX_SIZE: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_SYNC_APPLY_MAX_SIZE", u64, 256 * 1024 * 1024);
+
+/// The maximum number of expired records that should be deleted at once in each transaction.
+pub static EXPIRY_BATCH_SIZE: LazyLock<u32> =
+	lazy_env_parse!("SURREAL_EXPIRY_BATCH_SIZE", u32, 1000);
+
 /// The maximum number of keys that should be scanned at once for count queries.
 pub static COUNT_BATCH_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_COUNT_BATCH_SIZE", u32, 10_000);
@@ -79,6 +98,34 @@ pub static SCRIPTING_MAX_TIME_LIMIT: LazyLock<usize> =
 pub static INSECURE_FORWARD_ACCESS_ERRORS: LazyLock<bool> =
 	lazy_env_parse!("SURREAL_INSECURE_FORWARD_ACCESS_ERRORS", bool, false);
 
+/// The number of failed authentication attempts after which an account is locked (0 disables lockout)
+pub static AUTH_LOCKOUT_ATTEMPTS: LazyLock<u32> =
+	lazy_env_parse!("SURREAL_AUTH_LOCKOUT_ATTEMPTS", u32, 0);
+
+/// The window in seconds within which failed authentication attempts are counted
+pub static AUTH_LOCKOUT_WINDOW: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_AUTH_LOCKOUT_WINDOW", u64, 900);
+
+/// The duration in seconds for which an account remains locked
+pub static AUTH_LOCKOUT_DURATION: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_AUTH_LOCKOUT_DURATION", u64, 900);
+
+/// The signin variable which identifies the subject of record access attempts (record access is not locked out if unset)
+pub static AUTH_LOCKOUT_RECORD_IDENTIFIER: LazyLock<String> =
+	lazy_env_parse!("SURREAL_AUTH_LOCKOUT_RECORD_IDENTIFIER", String);
+
+/// The minimum length of passwords set with DEFINE USER (0 disables the check)
+pub static PASSWORD_MIN_LENGTH: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_PASSWORD_MIN_LENGTH", usize, 0);
+
+/// The minimum number of character classes (lowercase, uppercase, digits, symbols) in passwords set with DEFINE USER
+pub static PASSWORD_MIN_CHARACTER_CLASSES: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_PASSWORD_MIN_CHARACTER_CLASSES", usize, 0);
+
+/// The number of previous passwords which can not be reused when redefining a user
+pub static PASSWORD_HISTORY: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_PASSWORD_HISTORY", usize, 0);
+
 #[cfg(storage)]
 /// Specifies the buffer limit for external sorting.
 pub static EXTERNAL_SORTING_BUFFER_LIMIT: LazyLock<usize> =
@@ -11,6 +11,7 @@ use crate::idx::trees::store::IndexStores;
 use crate::kvs::cache::ds::DatastoreCache;
 #[cfg(not(target_family = "wasm"))]
 use crate::kvs::IndexBuilder;
+use crate::kvs::Sequences;
 use crate::kvs::Transaction;
 use crate::sql::value::Value;
 use async_channel::Sender;
@@ -53,6 +54,8 @@ pub struct MutableContext {
 	// The index concurrent builders
 	#[cfg(not(target_family = "wasm"))]
 	index_builder: Option<IndexBuilder>,
+	// The sequence value allocator
+	sequences: Option<Sequences>,
 	// Capabilities
 	capabilities: Arc<Capabilities>,
 	#[cfg(storage)]
@@ -106,6 +109,7 @@ impl MutableContext {
 			cache: None,
 			#[cfg(not(target_family = "wasm"))]
 			index_builder: None,
+			sequences: None,
 			#[cfg(storage)]
 			temporary_directory: None,
 			transaction: None,
@@ -128,6 +132,7 @@ impl MutableContext {
 			cache: parent.cache.clone(),
 			#[cfg(not(target_family = "wasm"))]
 			index_builder: parent.index_builder.clone(),
+			sequences: parent.sequences.clone(),
 			#[cfg(storage)]
 			temporary_directory: parent.temporary_directory.clone(),
 			transaction: parent.transaction.clone(),
@@ -153,6 +158,7 @@ impl MutableContext {
 			cache: parent.cache.clone(),
 			#[cfg(not(target_family = "wasm"))]
 			index_builder: parent.index_builder.clone(),
+			sequences: parent.sequences.clone(),
 			#[cfg(storage)]
 			temporary_directory: parent.temporary_directory.clone(),
 			transaction: parent.transaction.clone(),
@@ -178,6 +184,7 @@ impl MutableContext {
 			index_stores: from.index_stores.clone(),
 			cache: from.cache.clone(),
 			index_builder: from.index_builder.clone(),
+			sequences: from.sequences.clone(),
 			#[cfg(storage)]
 			temporary_directory: from.temporary_directory.clone(),
 			transaction: None,
@@ -193,6 +200,7 @@ impl MutableContext {
 		index_stores: IndexStores,
 		cache: Arc<DatastoreCache>,
 		#[cfg(not(target_family = "wasm"))] index_builder: IndexBuilder,
+		sequences: Sequences,
 		#[cfg(storage)] temporary_directory: Option<Arc<PathBuf>>,
 	) -> Result<MutableContext, Error> {
 		let mut ctx = Self {
@@ -209,6 +217,7 @@ impl MutableContext {
 			cache: Some(cache),
 			#[cfg(not(target_family = "wasm"))]
 			index_builder: Some(index_builder),
+			sequences: Some(sequences),
 			#[cfg(storage)]
 			temporary_directory,
 			transaction: None,
@@ -339,6 +348,11 @@ impl MutableContext {
 		&self.index_stores
 	}
 
+	/// Get the sequence value allocator for this context/ds
+	pub(crate) fn get_sequences(&self) -> Option<&Sequences> {
+		self.sequences.as_ref()
+	}
+
 	/// Get the index_builder for this context/ds
 	#[cfg(not(target_family = "wasm"))]
 	pub(crate) fn get_index_builder(&self) -> Option<&IndexBuilder> {
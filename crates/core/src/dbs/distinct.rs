@@ -1,7 +1,15 @@
 use crate::ctx::Context;
-use crate::dbs::Processed;
+#[cfg(storage)]
+use crate::dbs::file::FileCollector;
+use crate::dbs::result::Results;
+use crate::dbs::{Options, Processed, Statement};
+use crate::err::Error;
+use crate::idx::planner::RecordStrategy;
 use crate::kvs::Key;
+use crate::sql::statements::SetOperator;
+use crate::sql::{Fields, Value};
 use radix_trie::Trie;
+use reblessive::tree::Stk;
 use std::default::Default;
 
 // TODO: This is currently processed in memory. In the future is should be on disk (mmap?)
@@ -36,3 +44,156 @@ impl SyncDistinct {
 		}
 	}
 }
+
+/// Removes duplicate output values, for SELECT DISTINCT statements
+#[derive(Default)]
+pub(super) struct ValueDistinct {
+	processed: Distinct,
+}
+
+impl ValueDistinct {
+	pub(super) fn new(stm: &Statement<'_>, results: &Results) -> Option<Self> {
+		match results {
+			// Grouped values are deduplicated once they are grouped
+			Results::Groups(_) => None,
+			// Values in temporary files are deduplicated on disk
+			#[cfg(storage)]
+			Results::File(_) => None,
+			// Windowed values are deduplicated once the windows are computed
+			_ if stm.expr().is_some_and(Fields::has_windows) => None,
+			_ if stm.distinct() => Some(Self::default()),
+			_ => None,
+		}
+	}
+
+	pub(super) fn check_already_processed(&mut self, val: &Value) -> bool {
+		// If the serialization failed we couldn't have processed it.
+		if let Ok(key) = revision::to_vec(val) {
+			if self.processed.get(&key).is_some() {
+				true
+			} else {
+				self.processed.insert(key, true);
+				false
+			}
+		} else {
+			false
+		}
+	}
+
+	fn contains(&self, val: &Value) -> bool {
+		revision::to_vec(val).is_ok_and(|key| self.processed.get(&key).is_some())
+	}
+}
+
+/// The results of a SELECT statement, which are combined with the results of
+/// other statements by a set operator. If temporary files are enabled, the
+/// values are read lazily from disk, and are deduplicated and ordered on disk.
+pub(crate) struct SetResults(Results);
+
+impl SetResults {
+	pub(super) fn new(results: Results) -> Self {
+		Self(results)
+	}
+
+	/// Outputs the values, without any set operator
+	pub(crate) async fn take(self) -> Result<Vec<Value>, Error> {
+		let mut results = self.0;
+		results.take().await
+	}
+
+	/// Combines these results with the results of another statement.
+	///
+	/// Duplicate values are removed, unless the operator is UNION ALL.
+	pub(crate) async fn combine(
+		self,
+		#[cfg(storage)] ctx: &Context,
+		#[cfg(storage)] tempfiles: bool,
+		op: SetOperator,
+		other: SetResults,
+	) -> Result<Self, Error> {
+		#[cfg(storage)]
+		if tempfiles {
+			if let Some(temp_dir) = ctx.temporary_directory() {
+				let left = self.0.into_values().await?;
+				let (_, right) = other.0.into_values().await?;
+				let f = FileCollector::combine(temp_dir, op, left, right).await?;
+				return Ok(Self(Results::File(Box::new(f))));
+			}
+		}
+		let mut left = self.0;
+		let mut right = other.0;
+		let mut left = left.take().await?;
+		let right = right.take().await?;
+		// All values are kept, so there is nothing to deduplicate
+		if op == SetOperator::UnionAll {
+			left.extend(right);
+			return Ok(Self(left.into()));
+		}
+		let mut output = ValueDistinct::default();
+		if op == SetOperator::Union {
+			let values: Vec<Value> = left
+				.into_iter()
+				.chain(right)
+				.filter(|v| !output.check_already_processed(v))
+				.collect();
+			return Ok(Self(values.into()));
+		}
+		let mut other = ValueDistinct::default();
+		for v in right.iter() {
+			other.check_already_processed(v);
+		}
+		let keep = op == SetOperator::Intersect;
+		let values: Vec<Value> = left
+			.into_iter()
+			.filter(|v| other.contains(v) == keep && !output.check_already_processed(v))
+			.collect();
+		Ok(Self(values.into()))
+	}
+
+	/// Orders, pages and fetches the combined results, with the collectors of
+	/// a SELECT statement, and outputs the values.
+	pub(crate) async fn output(
+		self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+		start: Option<u32>,
+		limit: Option<u32>,
+	) -> Result<Vec<Value>, Error> {
+		let mut results = match self.0 {
+			// Values in temporary files are ordered on disk
+			#[cfg(storage)]
+			Results::File(f) => Results::File(f),
+			// Otherwise collect the values for any ORDER BY clause
+			mut res => {
+				let mut ordered = Results::ordered(stm, start, limit);
+				for v in res.take().await? {
+					ordered.push(stk, ctx, opt, stm, RecordStrategy::KeysAndValues, v).await?;
+				}
+				ordered
+			}
+		};
+		if let Some(orders) = stm.order() {
+			#[cfg(not(target_family = "wasm"))]
+			results.sort(orders).await?;
+			#[cfg(target_family = "wasm")]
+			results.sort(orders);
+		}
+		results.start_limit(None, start, limit).await?;
+		let mut values = results.take().await?;
+		// Process any FETCH clause
+		if let Some(fetchs) = stm.fetch() {
+			let mut idioms = Vec::with_capacity(fetchs.0.len());
+			for fetch in fetchs.iter() {
+				fetch.compute(stk, ctx, opt, &mut idioms).await?;
+			}
+			for i in &idioms {
+				for obj in &mut values {
+					stk.run(|stk| obj.fetch(stk, ctx, opt, i)).await?;
+				}
+			}
+		}
+		Ok(values)
+	}
+}
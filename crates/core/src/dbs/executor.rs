@@ -2,9 +2,11 @@ use crate::ctx::reason::Reason;
 use crate::ctx::Context;
 use crate::dbs::response::Response;
 use crate::dbs::Force;
+use crate::dbs::NotificationSavePoint;
 use crate::dbs::Options;
 use crate::dbs::QueryType;
 use crate::err::Error;
+use crate::iam::check::role_policies;
 use crate::iam::Action;
 use crate::iam::ResourceKind;
 use crate::kvs::Datastore;
@@ -16,7 +18,7 @@ use crate::sql::query::Query;
 use crate::sql::statement::Statement;
 use crate::sql::statements::{OptionStatement, UseStatement};
 use crate::sql::value::Value;
-use crate::sql::Base;
+use crate::sql::{Base, Ident};
 use futures::{Stream, StreamExt};
 use reblessive::TreeStack;
 use std::pin::{pin, Pin};
@@ -38,6 +40,16 @@ pub struct Executor {
 	ctx: Context,
 }
 
+/// A savepoint created within a transaction block
+struct Savepoint {
+	/// The name of the savepoint, until it is released
+	name: Option<Ident>,
+	/// The index of the first result which follows the savepoint
+	pos: usize,
+	/// The notifications sent since the savepoint was created
+	buffer: Option<NotificationSavePoint>,
+}
+
 impl Executor {
 	pub fn new(ctx: Context, opt: Options) -> Self {
 		Executor {
@@ -98,6 +110,14 @@ impl Executor {
 		Ok(())
 	}
 
+	/// Resolves the policies of the custom roles which are assigned to the
+	/// authenticated actor, so that the current role definitions are used
+	/// for all permission checks within the transaction.
+	async fn set_roles(&mut self, txn: &Transaction) -> Result<(), Error> {
+		self.opt.roles = role_policies(txn, &self.opt.auth).await?;
+		Ok(())
+	}
+
 	/// Executes a statement which needs a transaction with the supplied transaction.
 	#[instrument(level = "debug", name = "executor", target = "surrealdb::core::dbs", skip_all)]
 	async fn execute_transaction_statement(
@@ -105,6 +125,10 @@ impl Executor {
 		txn: Arc<Transaction>,
 		stmt: Statement,
 	) -> Result<Value, Error> {
+		// Ensure that no data is written to a read only datastore
+		if self.opt.read_only && stmt.writeable() {
+			return Err(Error::ReadOnly);
+		}
 		let res = match stmt {
 			Statement::Set(stm) => {
 				// Avoid moving in and out of the context via Arc::get_mut
@@ -158,6 +182,89 @@ impl Executor {
 		return res;
 	}
 
+	/// Executes a savepoint statement within a transaction block.
+	///
+	/// Each savepoint is stored along with the index of the first result
+	/// which follows it, so that the results of any statements which are
+	/// rolled back can be updated. Released savepoints lose their name,
+	/// but remain in the transaction until it finishes, so that rolling
+	/// back to an enclosing savepoint also undoes any changes made since.
+	async fn execute_savepoint_statement(
+		&mut self,
+		txn: &Transaction,
+		savepoints: &mut Vec<Savepoint>,
+		stmt: Statement,
+	) -> Result<Value, Error> {
+		// Find the index of a named savepoint
+		let find = |savepoints: &[Savepoint], name: &Ident| {
+			savepoints.iter().rposition(|v| v.name.as_ref() == Some(name)).ok_or_else(|| {
+				Error::SpNotFound {
+					name: name.to_raw(),
+				}
+			})
+		};
+		match stmt {
+			Statement::Savepoint(stmt) => {
+				txn.lock().await.new_save_point().await;
+				let sp = self.new_savepoint(stmt.name);
+				savepoints.push(sp);
+				Ok(Value::None)
+			}
+			Statement::Rollback(stmt) => {
+				let i = find(savepoints, &stmt.name)?;
+				let pos = savepoints[i].pos;
+				// Roll back this savepoint and any created after it
+				let mut lock = txn.lock().await;
+				for _ in i..savepoints.len() {
+					lock.rollback_to_save_point().await?;
+				}
+				// The savepoint remains after rolling back to it
+				lock.new_save_point().await;
+				drop(lock);
+				// Clear the cache, as cached entries may have been rolled back
+				txn.clear();
+				// Discard the notifications sent since the savepoint
+				for sp in savepoints.drain(i..).rev() {
+					if let Some(buffer) = sp.buffer {
+						self.opt.sender = Some(buffer.rollback());
+					}
+				}
+				let sp = self.new_savepoint(stmt.name.clone());
+				savepoints.push(sp);
+				// Update the results of the statements which were rolled back
+				for res in self.results.iter_mut().skip(pos) {
+					if res.result.is_ok() {
+						res.query_type = QueryType::Other;
+						res.result = Err(Error::QueryRolledBack {
+							name: stmt.name.to_raw(),
+						});
+					}
+				}
+				Ok(Value::None)
+			}
+			Statement::Release(stmt) => {
+				let i = find(savepoints, &stmt.name)?;
+				// Release this savepoint and any created after it
+				for sp in &mut savepoints[i..] {
+					sp.name = None;
+				}
+				Ok(Value::None)
+			}
+			_ => Err(fail!("Unexpected statement type encountered: {stmt:?}")),
+		}
+	}
+
+	/// Creates a savepoint which buffers any notifications sent after it
+	fn new_savepoint(&mut self, name: Ident) -> Savepoint {
+		let buffer = self.opt.sender.take().map(NotificationSavePoint::new);
+		self.opt.sender = buffer.as_ref().map(NotificationSavePoint::sender);
+		Savepoint {
+			name: Some(name),
+			pos: self.results.len() + 1,
+			buffer,
+		}
+	}
+
 	/// Execute a query not wrapped in a transaction block.
 	async fn execute_bare_statement(
 		&mut self,
@@ -178,9 +285,20 @@ impl Executor {
 		match stmt {
 			// These statements don't need a transaction.
 			Statement::Use(stmt) => self.execute_use_statement(stmt).map(|_| Value::None),
+			// Savepoints can only be used within a transaction block.
+			Statement::Savepoint(_) | Statement::Rollback(_) | Statement::Release(_) => {
+				Err(Error::QueryNotExecutedDetail {
+					message: "Tried to use a savepoint outside of a transaction".to_string(),
+				})
+			}
 			stmt => {
 				let writeable = stmt.writeable();
 				let txn = Arc::new(kvs.transaction(writeable.into(), LockType::Optimistic).await?);
+				// Resolve the custom role policies for the transaction
+				if let Err(e) = self.set_roles(&txn).await {
+					let _ = txn.cancel().await;
+					return Err(e);
+				}
 				let receiver = self.ctx.has_notifications().then(|| {
 					let (send, recv) = async_channel::unbounded();
 					self.opt.sender = Some(send);
@@ -272,6 +390,12 @@ impl Executor {
 			return Ok(());
 		};
 
+		// Resolve the custom role policies once for the whole transaction
+		if let Err(e) = self.set_roles(&txn).await {
+			let _ = txn.cancel().await;
+			return Err(e);
+		}
+
 		// Create a sender for this transaction only if the context allows for notifications.
 		let receiver = self.ctx.has_notifications().then(|| {
 			let (send, recv) = async_channel::unbounded();
@@ -282,6 +406,10 @@ impl Executor {
 		let txn = Arc::new(txn);
 		let start_results = self.results.len();
 		let mut skip_remaining = false;
+		// The savepoints created within this transaction
+		let mut savepoints: Vec<Savepoint> = Vec::new();
+		// Whether a statement failed after a savepoint was created
+		let mut failed = false;
 
 		// loop over the statements until we hit a cancel or a commit statement.
 		while let Some(stmt) = stream.next().await {
@@ -329,6 +457,21 @@ impl Executor {
 				continue;
 			}
 
+			// After a failed statement, nothing is executed until the
+			// transaction is rolled back to a savepoint, or finished.
+			if failed
+				&& !matches!(
+					stmt,
+					Statement::Cancel(_) | Statement::Commit(_) | Statement::Rollback(_)
+				) {
+				self.results.push(Response {
+					time: Duration::ZERO,
+					result: Err(Error::QueryNotExecuted),
+					query_type: QueryType::Other,
+				});
+				continue;
+			}
+
 			trace!(target: TARGET, statement = %stmt, "Executing statement");
 
 			let query_type = match stmt {
@@ -389,7 +532,29 @@ impl Executor {
 
 					return Ok(());
 				}
+				Statement::Commit(_) if failed => {
+					// A statement failed and was not rolled back, so cancel the transaction
+					let _ = txn.cancel().await;
+
+					for res in &mut self.results[start_results..] {
+						if res.result.is_ok() {
+							res.query_type = QueryType::Other;
+							res.result = Err(Error::QueryNotExecuted);
+						}
+					}
+
+					self.opt.sender = None;
+
+					return Ok(());
+				}
 				Statement::Commit(_) => {
+					// Pass on the notifications sent since each savepoint
+					while let Some(sp) = savepoints.pop() {
+						if let Some(buffer) = sp.buffer {
+							self.opt.sender = Some(buffer.release());
+						}
+					}
+
 					let mut lock = txn.lock().await;
 
 					// complete_changes and then commit.
@@ -443,14 +608,32 @@ impl Executor {
 				stmt => {
 					skip_remaining = matches!(stmt, Statement::Output(_));
 
-					let r = match self.execute_transaction_statement(txn.clone(), stmt).await {
-						Ok(x) => Ok(x),
+					let res = match stmt {
+						Statement::Savepoint(_)
+						| Statement::Rollback(_)
+						| Statement::Release(_) => {
+							self.execute_savepoint_statement(&txn, &mut savepoints, stmt).await
+						}
+						stmt => self.execute_transaction_statement(txn.clone(), stmt).await,
+					};
+
+					let r = match res {
+						Ok(x) => {
+							// Only a rollback can succeed after a failed statement
+							failed = false;
+							Ok(x)
+						}
 						Err(Error::Return {
 							value,
 						}) => {
 							skip_remaining = true;
 							Ok(value)
 						}
+						// The transaction can be rolled back to a savepoint, so keep it open.
+						Err(e) if !savepoints.is_empty() && e.is_catchable() => {
+							failed = true;
+							Err(e)
+						}
 						Err(e) => {
 							for res in &mut self.results[start_results..] {
 								res.query_type = QueryType::Other;
@@ -591,6 +774,78 @@ impl Executor {
 		}
 		Ok(this.results)
 	}
+
+	/// Executes a query within a transaction which is managed by the caller.
+	///
+	/// The transaction is neither committed nor cancelled here, and any
+	/// statements following a failed statement are not executed.
+	#[instrument(level = "debug", name = "executor", target = "surrealdb::core::dbs", skip_all)]
+	pub async fn execute_with_transaction(
+		ctx: Context,
+		opt: Options,
+		qry: Query,
+		txn: Arc<Transaction>,
+	) -> Result<Vec<Response>, Error> {
+		let mut this = Executor::new(ctx, opt);
+		let mut failed = false;
+		// Resolve the custom role policies for the transaction
+		this.set_roles(&txn).await?;
+
+		for stmt in qry.into_iter() {
+			if failed {
+				this.results.push(Response {
+					time: Duration::ZERO,
+					result: Err(Error::QueryNotExecuted),
+					query_type: QueryType::Other,
+				});
+				continue;
+			}
+
+			let query_type = match stmt {
+				Statement::Live(_) => QueryType::Live,
+				Statement::Kill(_) => QueryType::Kill,
+				_ => QueryType::Other,
+			};
+
+			let now = Instant::now();
+			let result = match stmt {
+				// handle option here because it doesn't produce a result.
+				Statement::Option(stmt) => {
+					this.execute_option_statement(stmt)?;
+					continue;
+				}
+				Statement::Use(stmt) => this.execute_use_statement(stmt).map(|_| Value::None),
+				Statement::Begin(_) => Err(Error::QueryNotExecutedDetail {
+					message: "Tried to start a transaction while another transaction was open"
+						.to_string(),
+				}),
+				Statement::Cancel(_) | Statement::Commit(_) => Err(Error::QueryNotExecutedDetail {
+					message: "Tried to finish a transaction which was started over RPC".to_string(),
+				}),
+				Statement::Savepoint(_) | Statement::Rollback(_) | Statement::Release(_) => {
+					Err(Error::QueryNotExecutedDetail {
+						message:
+							"Tried to use a savepoint in a transaction which was started over RPC"
+								.to_string(),
+					})
+				}
+				stmt => match this.execute_transaction_statement(txn.clone(), stmt).await {
+					Err(Error::Return {
+						value,
+					}) => Ok(value),
+					res => res,
+				},
+			};
+
+			failed = result.is_err();
+			this.results.push(Response {
+				time: now.elapsed(),
+				result,
+				query_type,
+			});
+		}
+		Ok(this.results)
+	}
 }
 
 #[cfg(test)]
@@ -1,12 +1,18 @@
 use crate::cnf::EXTERNAL_SORTING_BUFFER_LIMIT;
+use crate::ctx::Context;
 use crate::dbs::plan::Explanation;
+use crate::dbs::window::Windows;
+use crate::dbs::Options;
 use crate::err::Error;
 use crate::sql::order::Ordering;
+use crate::sql::statements::SetOperator;
 use crate::sql::Value;
 use ext_sort::{ExternalChunk, ExternalSorter, ExternalSorterBuilder, LimitedBufferBuilder};
 use rand::seq::SliceRandom as _;
 use rand::Rng as _;
+use reblessive::tree::Stk;
 use revision::Revisioned;
+use std::cmp::Ordering as CmpOrdering;
 use std::fs::{File, OpenOptions};
 use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Take, Write};
 use std::path::{Path, PathBuf};
@@ -15,6 +21,9 @@ use tempfile::{Builder, TempDir};
 #[cfg(not(target_family = "wasm"))]
 use tokio::task::spawn_blocking;
 
+/// The values of a collector, which are read lazily from its temporary files
+pub(super) type FileValues = Box<dyn Iterator<Item = Result<Value, Error>> + Send>;
+
 pub(super) struct FileCollector {
 	dir: TempDir,
 	len: usize,
@@ -22,6 +31,7 @@ pub(super) struct FileCollector {
 	reader: Option<FileReader>,
 	orders: Option<Ordering>,
 	paging: FilePaging,
+	distinct: bool,
 }
 
 impl FileCollector {
@@ -30,16 +40,21 @@ impl FileCollector {
 
 	const SORT_DIRECTORY_NAME: &'static str = "so";
 
+	const DISTINCT_DIRECTORY_NAME: &'static str = "di";
+
+	const WINDOW_DIRECTORY_NAME: &'static str = "wi";
+
 	const USIZE_SIZE: usize = mem::size_of::<usize>();
 
 	pub(super) fn new(temp_dir: &Path) -> Result<Self, Error> {
 		let dir = Builder::new().prefix("SURREAL").tempdir_in(temp_dir)?;
 		Ok(Self {
 			len: 0,
-			writer: Some(FileWriter::new(&dir)?),
+			writer: Some(FileWriter::new(dir.path())?),
 			reader: None,
 			orders: None,
 			paging: Default::default(),
+			distinct: false,
 			dir,
 		})
 	}
@@ -66,7 +81,7 @@ impl FileCollector {
 		if self.reader.is_none() {
 			if let Some(writer) = self.writer.take() {
 				writer.flush()?;
-				self.reader = Some(FileReader::new(self.len, &self.dir)?);
+				self.reader = Some(FileReader::new(self.len, self.dir.path())?);
 			}
 		}
 		Ok(())
@@ -75,6 +90,10 @@ impl FileCollector {
 		self.orders = Some(orders.clone());
 	}
 
+	pub(super) fn distinct(&mut self) {
+		self.distinct = true;
+	}
+
 	pub(super) fn len(&self) -> usize {
 		self.len
 	}
@@ -87,6 +106,9 @@ impl FileCollector {
 	pub(super) async fn take_vec(&mut self) -> Result<Vec<Value>, Error> {
 		self.check_reader()?;
 		if let Some(mut reader) = self.reader.take() {
+			if self.distinct {
+				reader = self.distinct_reader(reader).await?;
+			}
 			if let Some((start, num)) = self.paging.get_start_num(reader.len as u32) {
 				if let Some(orders) = self.orders.take() {
 					return self.sort_and_take_vec(reader, orders, start, num).await;
@@ -97,6 +119,304 @@ impl FileCollector {
 		Ok(vec![])
 	}
 
+	/// Removes any duplicate values, before the values are sorted and paged
+	async fn distinct_reader(&self, reader: FileReader) -> Result<FileReader, Error> {
+		let dir = self.dir.path().join(Self::DISTINCT_DIRECTORY_NAME);
+		let left = reader.len;
+		let f = move || Self::dedup(reader, left, SetOperator::Union, &dir);
+		#[cfg(target_family = "wasm")]
+		let res = f();
+		#[cfg(not(target_family = "wasm"))]
+		let res = spawn_blocking(f).await.map_err(|e| Error::Internal(format!("{e}")))?;
+		//
+		res
+	}
+
+	/// Consumes the collector, reading its values lazily from the temporary files.
+	/// Any duplicate values are removed, but the values are neither sorted nor
+	/// paged, as the collectors of set operator operands have no such clauses.
+	pub(super) async fn into_values(mut self) -> Result<(usize, FileValues), Error> {
+		self.check_reader()?;
+		let Some(mut reader) = self.reader.take() else {
+			return Ok((0, Box::new(std::iter::empty())));
+		};
+		if self.distinct {
+			reader = self.distinct_reader(reader).await?;
+		}
+		let len = reader.len;
+		let values = FileValuesIterator {
+			records: reader.into_iter(),
+			_dir: self.dir,
+		};
+		Ok((len, Box::new(values)))
+	}
+
+	/// Combines the values of two statements with a set operator on disk,
+	/// removing any duplicate values. The left values are given with their number.
+	pub(super) async fn combine(
+		temp_dir: &Path,
+		op: SetOperator,
+		left: (usize, FileValues),
+		right: FileValues,
+	) -> Result<Self, Error> {
+		let dir = Builder::new().prefix("SURREAL").tempdir_in(temp_dir)?;
+		let path = dir.path().to_path_buf();
+		let f = move || {
+			let (len, left) = left;
+			let values = left.chain(right);
+			// All values are kept, so there is nothing to deduplicate
+			if op == SetOperator::UnionAll {
+				let mut writer = FileWriter::new(&path)?;
+				let mut len = 0;
+				for val in values {
+					writer.push(val?)?;
+					len += 1;
+				}
+				writer.flush()?;
+				return FileReader::new(len, &path);
+			}
+			Self::dedup(values, len, op, &path)
+		};
+		#[cfg(target_family = "wasm")]
+		let res = f();
+		#[cfg(not(target_family = "wasm"))]
+		let res = spawn_blocking(f).await.map_err(|e| Error::Internal(format!("{e}")))?;
+		//
+		let reader = res?;
+		Ok(Self {
+			len: reader.len,
+			writer: None,
+			reader: Some(reader),
+			orders: None,
+			paging: Default::default(),
+			distinct: false,
+			dir,
+		})
+	}
+
+	/// Removes any duplicate values, keeping the first occurrence of each value in
+	/// its original position. As when deduplicating in memory, values are equal if
+	/// their serialized bytes are equal. The values from position `left` onwards are
+	/// the right side of the set operator: they are only kept by a UNION, and
+	/// otherwise decide which values on the left are kept.
+	///
+	/// The values are sorted on disk by their serialized bytes and position, so the
+	/// first value of each run of equal values is its first occurrence. The kept
+	/// values are then sorted back to their original position.
+	fn dedup<I>(values: I, left: usize, op: SetOperator, dir: &Path) -> Result<FileReader, Error>
+	where
+		I: IntoIterator<Item = Result<Value, Error>>,
+	{
+		let keyed = values.into_iter().enumerate().map(|(n, v)| {
+			let v = v?;
+			let key = revision::to_vec(&v)?;
+			Ok(Value::from(vec![Value::Bytes(key.into()), Value::from(n), v]))
+		});
+		let cmp = |a: &Value, b: &Value| {
+			let (a_key, a_pos, _) = Self::keyed(a);
+			let (b_key, b_pos, _) = Self::keyed(b);
+			a_key.cmp(b_key).then_with(|| a_pos.cmp(&b_pos))
+		};
+		let sorted = Self::sort_into(keyed, &dir.join("k"), cmp, |v| v)?;
+		// Write the first occurrence of each value, if it is kept
+		let kept = dir.join("d");
+		fs::create_dir_all(&kept)?;
+		let mut writer = FileWriter::new(&kept)?;
+		let mut len = 0;
+		let mut push = |first: Value, right: bool| {
+			let (_, pos, _) = Self::keyed(&first);
+			let keep = match op {
+				SetOperator::Union | SetOperator::UnionAll => true,
+				SetOperator::Intersect => pos < left && right,
+				SetOperator::Except => pos < left && !right,
+			};
+			if keep {
+				let Value::Array(mut v) = first else {
+					return Ok(());
+				};
+				let val = v.0.swap_remove(2);
+				writer.push(Value::from(vec![Value::from(pos), val]))?;
+				len += 1;
+			}
+			Ok::<(), Error>(())
+		};
+		// The first value of the current run, and whether the run has values on the right
+		let mut run: Option<(Value, bool)> = None;
+		for val in sorted {
+			let val = val?;
+			let (key, pos, _) = Self::keyed(&val);
+			let right = pos >= left;
+			match &mut run {
+				Some((first, r)) if Self::keyed(first).0 == key => *r |= right,
+				_ => {
+					if let Some((first, right)) = run.replace((val, right)) {
+						push(first, right)?;
+					}
+				}
+			}
+		}
+		if let Some((first, right)) = run {
+			push(first, right)?;
+		}
+		writer.flush()?;
+		let kept = FileReader::new(len, &kept)?;
+		// Sort the kept values back to their original position
+		let cmp = |a: &Value, b: &Value| Self::positioned(a).0.cmp(Self::positioned(b).0);
+		let unwrap = |v: Value| match v {
+			Value::Array(mut v) if v.len() == 2 => v.0.swap_remove(1),
+			v => v,
+		};
+		Self::sort_into(kept, &dir.join("r"), cmp, unwrap)
+	}
+
+	/// Splits a keyed value into its serialized bytes, its original position and the value itself
+	fn keyed(val: &Value) -> (&Value, usize, &Value) {
+		match val {
+			Value::Array(v) if v.len() == 3 => match &v[1] {
+				Value::Number(n) => (&v[0], n.as_usize(), &v[2]),
+				_ => (&v[0], 0, &v[2]),
+			},
+			v => (&Value::None, 0, v),
+		}
+	}
+
+	/// Computes the window functions of the values. For each window function,
+	/// the values are sorted on disk by partition and order, each partition is
+	/// then computed in memory, and the values are finally sorted back to their
+	/// original position.
+	pub(super) async fn windows(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		windows: &Windows,
+	) -> Result<(), Error> {
+		self.check_reader()?;
+		let Some(mut reader) = self.reader.take() else {
+			return Ok(());
+		};
+		for i in 0..windows.len() {
+			let dir = self.dir.path().join(Self::WINDOW_DIRECTORY_NAME).join(i.to_string());
+			// Sort the values by partition and order, keeping the position of each value
+			let sorted = {
+				let dir = dir.join("p");
+				let windows = windows.clone();
+				let f = move || {
+					let values = reader
+						.into_iter()
+						.enumerate()
+						.map(|(n, v)| v.map(|v| Value::from(vec![Value::from(n), v])));
+					let cmp = |a: &Value, b: &Value| {
+						let (a_pos, a) = Self::positioned(a);
+						let (b_pos, b) = Self::positioned(b);
+						windows
+							.compare(i, &windows.row(i, a), &windows.row(i, b))
+							.then_with(|| a_pos.cmp(b_pos))
+					};
+					Self::sort_into(values, &dir, cmp, |v| v)
+				};
+				#[cfg(target_family = "wasm")]
+				let res = f();
+				#[cfg(not(target_family = "wasm"))]
+				let res = spawn_blocking(f).await.map_err(|e| Error::Internal(format!("{e}")))?;
+				//
+				res?
+			};
+			// Compute the window function over each partition
+			let computed = dir.join("c");
+			fs::create_dir_all(&computed)?;
+			let mut writer = FileWriter::new(&computed)?;
+			let mut values = Vec::new();
+			let mut rows = Vec::new();
+			for val in sorted {
+				let val = val?;
+				let row = windows.row(i, Self::positioned(&val).1);
+				if rows.last().is_some_and(|r| !windows.same_partition(r, &row)) {
+					let res = windows.partition(stk, ctx, opt, i, mem::take(&mut rows)).await?;
+					Self::write_windowed(&mut writer, windows, i, mem::take(&mut values), res)?;
+				}
+				values.push(val);
+				rows.push(row);
+			}
+			let res = windows.partition(stk, ctx, opt, i, rows).await?;
+			Self::write_windowed(&mut writer, windows, i, values, res)?;
+			writer.flush()?;
+			let computed = FileReader::new(self.len, &computed)?;
+			// Sort the values back to their original position
+			let f = move || {
+				let cmp = |a: &Value, b: &Value| Self::positioned(a).0.cmp(Self::positioned(b).0);
+				let unwrap = |v: Value| match v {
+					Value::Array(mut v) if v.len() == 2 => v.0.swap_remove(1),
+					v => v,
+				};
+				Self::sort_into(computed, &dir.join("r"), cmp, unwrap)
+			};
+			#[cfg(target_family = "wasm")]
+			let res = f();
+			#[cfg(not(target_family = "wasm"))]
+			let res = spawn_blocking(f).await.map_err(|e| Error::Internal(format!("{e}")))?;
+			//
+			reader = res?;
+		}
+		self.reader = Some(reader);
+		Ok(())
+	}
+
+	/// Splits a value into its original position and the value itself
+	fn positioned(val: &Value) -> (&Value, &Value) {
+		match val {
+			Value::Array(v) if v.len() == 2 => (&v[0], &v[1]),
+			v => (&Value::None, v),
+		}
+	}
+
+	/// Writes the values of a partition, with the computed result of a window function
+	fn write_windowed(
+		writer: &mut FileWriter,
+		windows: &Windows,
+		i: usize,
+		values: Vec<Value>,
+		res: Vec<Value>,
+	) -> Result<(), Error> {
+		for (val, res) in values.into_iter().zip(res) {
+			let mut val = val;
+			if let Value::Array(v) = &mut val {
+				if let Some(row) = v.get_mut(1) {
+					windows.set(i, row, res);
+				}
+			}
+			writer.push(val)?;
+		}
+		Ok(())
+	}
+
+	/// Sorts the values on disk, and writes the sorted values to a new file in the directory
+	fn sort_into<I, C, M>(values: I, dir: &Path, cmp: C, map: M) -> Result<FileReader, Error>
+	where
+		I: IntoIterator<Item = Result<Value, Error>>,
+		C: Fn(&Value, &Value) -> CmpOrdering + Send + Sync + Copy,
+		M: Fn(Value) -> Value,
+	{
+		let sort_dir = dir.join(Self::SORT_DIRECTORY_NAME);
+		fs::create_dir_all(&sort_dir)?;
+
+		let sorter: ExternalSorter<Value, Error, LimitedBufferBuilder, ValueExternalChunk> =
+			ExternalSorterBuilder::new()
+				.with_tmp_dir(&sort_dir)
+				.with_buffer(LimitedBufferBuilder::new(*EXTERNAL_SORTING_BUFFER_LIMIT, true))
+				.build()?;
+
+		let sorted = sorter.sort_by(values, cmp)?;
+		let mut writer = FileWriter::new(dir)?;
+		let mut len = 0;
+		for val in sorted {
+			writer.push(map(val?))?;
+			len += 1;
+		}
+		writer.flush()?;
+		FileReader::new(len, dir)
+	}
+
 	async fn sort_and_take_vec(
 		&mut self,
 		reader: FileReader,
@@ -190,15 +510,15 @@ struct FileWriter {
 }
 
 impl FileWriter {
-	fn new(dir: &TempDir) -> Result<Self, Error> {
+	fn new(dir: &Path) -> Result<Self, Error> {
 		let index = OpenOptions::new()
 			.create_new(true)
 			.append(true)
-			.open(dir.path().join(FileCollector::INDEX_FILE_NAME))?;
+			.open(dir.join(FileCollector::INDEX_FILE_NAME))?;
 		let records = OpenOptions::new()
 			.create_new(true)
 			.append(true)
-			.open(dir.path().join(FileCollector::RECORDS_FILE_NAME))?;
+			.open(dir.join(FileCollector::RECORDS_FILE_NAME))?;
 		Ok(Self {
 			index: BufWriter::new(index),
 			records: BufWriter::new(records),
@@ -246,9 +566,9 @@ struct FileReader {
 }
 
 impl FileReader {
-	fn new(len: usize, dir: &TempDir) -> Result<Self, Error> {
-		let index = dir.path().join(FileCollector::INDEX_FILE_NAME);
-		let records = dir.path().join(FileCollector::RECORDS_FILE_NAME);
+	fn new(len: usize, dir: &Path) -> Result<Self, Error> {
+		let index = dir.join(FileCollector::INDEX_FILE_NAME);
+		let records = dir.join(FileCollector::RECORDS_FILE_NAME);
 		Ok(Self {
 			len,
 			index,
@@ -378,6 +698,24 @@ impl ExactSizeIterator for FileRecordsIterator {
 	}
 }
 
+/// Reads the records of a collector, keeping its temporary files until the records are read
+struct FileValuesIterator {
+	records: FileRecordsIterator,
+	_dir: TempDir,
+}
+
+impl Iterator for FileValuesIterator {
+	type Item = Result<Value, Error>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.records.next()
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.records.size_hint()
+	}
+}
+
 #[derive(Default)]
 struct FilePaging {
 	start: Option<u32>,
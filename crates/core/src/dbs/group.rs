@@ -18,7 +18,7 @@ pub(super) struct GroupsCollector {
 }
 
 #[derive(Default)]
-struct Aggregator {
+pub(super) struct Aggregator {
 	array: Option<Array>,
 	first_val: Option<Value>,
 	count: Option<usize>,
@@ -189,6 +189,14 @@ impl GroupsCollector {
 }
 
 impl Aggregator {
+	/// Creates an aggregator which computes an optimised aggregate function
+	/// incrementally, one value at a time
+	pub(super) fn new(func: &Function) -> Self {
+		let mut agr = Self::default();
+		agr.prepare(&Value::Function(Box::new(func.clone())));
+		agr
+	}
+
 	fn prepare(&mut self, expr: &Value) {
 		let (a, f) = match expr {
 			Value::Function(f) => (f.get_optimised_aggregate(), Some(f)),
@@ -267,7 +275,7 @@ impl Aggregator {
 		}
 	}
 
-	async fn push(
+	pub(super) async fn push(
 		&mut self,
 		stk: &mut Stk,
 		ctx: &Context,
@@ -338,25 +346,26 @@ impl Aggregator {
 		Ok(())
 	}
 
-	fn compute(&mut self, a: OptimisedAggregate) -> Result<Value, Error> {
+	/// Computes the value of an optimised aggregate, from the values pushed so far
+	pub(super) fn compute(&self, a: OptimisedAggregate) -> Result<Value, Error> {
 		Ok(match a {
 			OptimisedAggregate::None => Value::None,
-			OptimisedAggregate::Count => self.count.take().map(|v| v.into()).unwrap_or(Value::None),
+			OptimisedAggregate::Count => self.count.map(|v| v.into()).unwrap_or(Value::None),
 			OptimisedAggregate::CountFunction => {
-				self.count_function.take().map(|(_, v)| v.into()).unwrap_or(Value::None)
+				self.count_function.as_ref().map(|(_, v)| (*v).into()).unwrap_or(Value::None)
 			}
-			OptimisedAggregate::MathMax => self.math_max.take().unwrap_or(Value::None),
-			OptimisedAggregate::MathMin => self.math_min.take().unwrap_or(Value::None),
-			OptimisedAggregate::MathSum => self.math_sum.take().unwrap_or(Value::None),
+			OptimisedAggregate::MathMax => self.math_max.clone().unwrap_or(Value::None),
+			OptimisedAggregate::MathMin => self.math_min.clone().unwrap_or(Value::None),
+			OptimisedAggregate::MathSum => self.math_sum.clone().unwrap_or(Value::None),
 			OptimisedAggregate::MathMean => {
-				if let Some((v, i)) = self.math_mean.take() {
-					v.try_float_div(i.into()).unwrap_or(f64::NAN.into())
+				if let Some((v, i)) = &self.math_mean {
+					v.clone().try_float_div((*i).into()).unwrap_or(f64::NAN.into())
 				} else {
 					Value::None
 				}
 			}
-			OptimisedAggregate::TimeMax => self.time_max.take().unwrap_or(Value::None),
-			OptimisedAggregate::TimeMin => self.time_min.take().unwrap_or(Value::None),
+			OptimisedAggregate::TimeMax => self.time_max.clone().unwrap_or(Value::None),
+			OptimisedAggregate::TimeMin => self.time_min.clone().unwrap_or(Value::None),
 		})
 	}
 
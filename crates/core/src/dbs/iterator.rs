@@ -1,8 +1,9 @@
 use crate::ctx::Context;
 use crate::ctx::{Canceller, MutableContext};
-use crate::dbs::distinct::SyncDistinct;
+use crate::dbs::distinct::{SetResults, SyncDistinct, ValueDistinct};
 use crate::dbs::plan::{Explanation, Plan};
 use crate::dbs::result::Results;
+use crate::dbs::window::Windows;
 use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::doc::Document;
@@ -123,6 +124,8 @@ pub(crate) struct Iterator {
 	guaranteed: Option<Iterable>,
 	/// Set if the iterator can be cancelled once it reaches start/limit
 	cancel_on_limit: Option<u32>,
+	/// Set if duplicate output values are removed
+	distinct: Option<ValueDistinct>,
 }
 
 impl Clone for Iterator {
@@ -138,6 +141,7 @@ impl Clone for Iterator {
 			entries: self.entries.clone(),
 			guaranteed: None,
 			cancel_on_limit: None,
+			distinct: None,
 		}
 	}
 }
@@ -353,6 +357,34 @@ impl Iterator {
 		stm: &Statement<'_>,
 		rs: RecordStrategy,
 	) -> Result<Value, Error> {
+		// Extract the output from the result
+		let mut results = self.collect_results(stk, ctx, opt, stm, rs).await?;
+		// Output the results
+		Ok(results.take().await?.into())
+	}
+
+	/// Process the records, keeping the output for a set operator
+	pub(crate) async fn output_set(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+		rs: RecordStrategy,
+	) -> Result<SetResults, Error> {
+		let results = self.collect_results(stk, ctx, opt, stm, rs).await?;
+		Ok(SetResults::new(results))
+	}
+
+	/// Process the records, returning the collected results
+	async fn collect_results(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+		rs: RecordStrategy,
+	) -> Result<Results, Error> {
 		// Log the statement
 		trace!(target: TARGET, statement = %stm, "Iterating statement");
 		// Enable context override
@@ -371,6 +403,8 @@ impl Iterator {
 			self.start,
 			self.limit,
 		)?;
+		// Prepare the removal of any duplicate values
+		self.distinct = ValueDistinct::new(stm, &self.results);
 		// Extract the expected behaviour depending on the presence of EXPLAIN with or without FULL
 		let mut plan = Plan::new(ctx, stm, &self.entries, &self.results);
 		// Check if we actually need to process and iterate over the results
@@ -414,6 +448,8 @@ impl Iterator {
 			self.output_split(stk, ctx, opt, stm, rs).await?;
 			// Process any GROUP BY clause
 			self.output_group(stk, ctx, opt, stm).await?;
+			// Process any window functions
+			self.output_windows(stk, ctx, opt, stm, rs).await?;
 			// Process any ORDER BY clause
 			if let Some(orders) = stm.order() {
 				#[cfg(not(target_family = "wasm"))]
@@ -431,19 +467,12 @@ impl Iterator {
 			}
 		}
 
-		// Extract the output from the result
-		let mut results = self.results.take().await?;
-
 		// Output the explanation if any
 		if let Some(e) = plan.explanation {
-			results.clear();
-			for v in e.output() {
-				results.push(v)
-			}
+			return Ok(e.output().into());
 		}
-
 		// Output the results
-		Ok(results.into())
+		Ok(mem::take(&mut self.results))
 	}
 
 	#[inline]
@@ -488,6 +517,16 @@ impl Iterator {
 			return false;
 		}
 
+		// If duplicate values are removed, we can't
+		if stm.distinct() {
+			return false;
+		}
+
+		// If window functions are computed over the values, we can't
+		if stm.expr().is_some_and(Fields::has_windows) {
+			return false;
+		}
+
 		// If there is no specified order, we can
 		if stm.order().is_none() {
 			return true;
@@ -554,6 +593,10 @@ impl Iterator {
 			for split in splits.iter() {
 				// Get the query result
 				let res = self.results.take().await?;
+				// Remove any duplicates of the split values
+				if let Some(d) = &mut self.distinct {
+					*d = ValueDistinct::default();
+				}
 				// Loop over each value
 				for obj in &res {
 					// Get the value at the path
@@ -567,7 +610,7 @@ impl Iterator {
 								// Set the value at the path
 								obj.set(stk, ctx, opt, split, val).await?;
 								// Add the object to the results
-								self.results.push(stk, ctx, opt, stm, rs, obj).await?;
+								self.push(stk, ctx, opt, stm, rs, obj).await?;
 							}
 						}
 						_ => {
@@ -576,7 +619,7 @@ impl Iterator {
 							// Set the value at the path
 							obj.set(stk, ctx, opt, split, val).await?;
 							// Add the object to the results
-							self.results.push(stk, ctx, opt, stm, rs, obj).await?;
+							self.push(stk, ctx, opt, stm, rs, obj).await?;
 						}
 					}
 				}
@@ -594,7 +637,45 @@ impl Iterator {
 	) -> Result<(), Error> {
 		// Process any GROUP clause
 		if let Results::Groups(g) = &mut self.results {
-			self.results = Results::Memory(g.output(stk, ctx, opt, stm).await?);
+			let mut values = g.output(stk, ctx, opt, stm).await?;
+			// Remove any duplicate grouped values
+			if stm.distinct() {
+				let mut distinct = ValueDistinct::default();
+				let mut v = values.take_vec();
+				v.retain(|v| !distinct.check_already_processed(v));
+				values = v.into();
+			}
+			self.results = Results::Memory(values);
+		}
+		// Everything ok
+		Ok(())
+	}
+
+	async fn output_windows(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+		rs: RecordStrategy,
+	) -> Result<(), Error> {
+		if let Some(windows) = Windows::new(stm) {
+			// Values in temporary files are computed on disk
+			#[cfg(storage)]
+			if let Results::File(f) = &mut self.results {
+				return f.windows(stk, ctx, opt, &windows).await;
+			}
+			let values = self.results.take().await?;
+			let values = windows.compute(stk, ctx, opt, values).await?;
+			// Collect the computed values for any ORDER BY clause
+			self.results = Results::ordered(stm, self.start, self.limit);
+			// Remove any duplicates of the computed values
+			if stm.distinct() {
+				self.distinct = Some(ValueDistinct::default());
+			}
+			for v in values {
+				self.push(stk, ctx, opt, stm, rs, v).await?;
+			}
 		}
 		// Everything ok
 		Ok(())
@@ -692,6 +773,24 @@ impl Iterator {
 		stk.run(|stk| Document::process(stk, ctx, opt, stm, pro)).await
 	}
 
+	/// Add a value to the results, unless it is a duplicate
+	async fn push(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		stm: &Statement<'_>,
+		rs: RecordStrategy,
+		val: Value,
+	) -> Result<(), Error> {
+		if let Some(d) = &mut self.distinct {
+			if d.check_already_processed(&val) {
+				return Ok(());
+			}
+		}
+		self.results.push(stk, ctx, opt, stm, rs, val).await
+	}
+
 	/// Accept a processed record result
 	async fn result(
 		&mut self,
@@ -719,7 +818,7 @@ impl Iterator {
 				return;
 			}
 			Ok(v) => {
-				if let Err(e) = self.results.push(stk, ctx, opt, stm, rs, v).await {
+				if let Err(e) = self.push(stk, ctx, opt, stm, rs, v).await {
 					self.error = Some(e);
 					self.run.cancel();
 					return;
@@ -16,11 +16,13 @@ mod session;
 mod statement;
 mod store;
 mod variables;
+mod window;
 
 pub mod capabilities;
 pub mod node;
 
 pub use self::capabilities::Capabilities;
+pub(crate) use self::distinct::SetResults;
 pub(crate) use self::executor::*;
 pub(crate) use self::iterator::*;
 pub use self::notification::*;
@@ -1,6 +1,7 @@
 #[cfg(test)]
 use crate::dbs::fuzzy_eq::FuzzyEq;
 use crate::sql::{Object, Uuid, Value};
+use async_channel::{Receiver, Sender};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Debug, Display};
@@ -64,6 +65,47 @@ impl Notification {
 	}
 }
 
+/// Buffers the notifications sent after a savepoint is created,
+/// so that they can be discarded if the savepoint is rolled back
+pub(crate) struct NotificationSavePoint {
+	/// The sender which was in use when the savepoint was created
+	parent: Sender<Notification>,
+	/// The sender for notifications sent after the savepoint
+	send: Sender<Notification>,
+	/// The notifications sent after the savepoint
+	recv: Receiver<Notification>,
+}
+
+impl NotificationSavePoint {
+	/// Starts buffering notifications which would be sent to the parent
+	pub(crate) fn new(parent: Sender<Notification>) -> Self {
+		let (send, recv) = async_channel::unbounded();
+		Self {
+			parent,
+			send,
+			recv,
+		}
+	}
+
+	/// Get the sender for notifications sent after the savepoint
+	pub(crate) fn sender(&self) -> Sender<Notification> {
+		self.send.clone()
+	}
+
+	/// Passes the buffered notifications on to the parent, returning its sender
+	pub(crate) fn release(self) -> Sender<Notification> {
+		while let Ok(v) = self.recv.try_recv() {
+			let _ = self.parent.try_send(v);
+		}
+		self.parent
+	}
+
+	/// Discards the buffered notifications, returning the sender of the parent
+	pub(crate) fn rollback(self) -> Sender<Notification> {
+		self.parent
+	}
+}
+
 #[cfg(test)]
 impl FuzzyEq for Notification {
 	fn fuzzy_eq(&self, other: &Self) -> bool {
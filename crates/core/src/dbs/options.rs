@@ -1,10 +1,11 @@
 use crate::cnf::MAX_COMPUTATION_DEPTH;
 use crate::dbs::Notification;
 use crate::err::Error;
-use crate::iam::{Action, Auth, ResourceKind};
+use crate::iam::{Action, Auth, Resource, ResourceKind};
 use crate::sql::statements::define::{DefineIndexStatement, DefineTableStatement};
 use crate::sql::Base;
 use async_channel::Sender;
+use cedar_policy::PolicySet;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -28,6 +29,9 @@ pub struct Options {
 	dive: u32,
 	/// Connection authentication data
 	pub(crate) auth: Arc<Auth>,
+	/// The compiled policies of the custom roles of the
+	/// authenticated actor, resolved for each transaction
+	pub(crate) roles: Option<Arc<PolicySet>>,
 	/// Is authentication enabled on this datastore?
 	pub(crate) auth_enabled: bool,
 	/// Whether live queries can be used?
@@ -40,6 +44,8 @@ pub struct Options {
 	pub(crate) strict: bool,
 	/// Should we process field queries?
 	pub(crate) import: bool,
+	/// Should we reject statements which write data?
+	pub(crate) read_only: bool,
 	/// Should we process function futures?
 	pub(crate) futures: Futures,
 	/// The data version as nanosecond timestamp
@@ -83,10 +89,12 @@ impl Options {
 			force: Force::None,
 			strict: false,
 			import: false,
+			read_only: false,
 			futures: Futures::Disabled,
 			auth_enabled: true,
 			sender: None,
 			auth: Arc::new(Auth::default()),
+			roles: None,
 			version: None,
 		}
 	}
@@ -138,6 +146,14 @@ impl Options {
 	/// code which uses this `Options`, with chaining.
 	pub fn with_auth(mut self, auth: Arc<Auth>) -> Self {
 		self.auth = auth;
+		self.roles = None;
+		self
+	}
+
+	/// Specify the compiled custom role policies of the
+	/// authenticated actor for the current transaction.
+	pub fn with_roles(mut self, roles: Option<Arc<PolicySet>>) -> Self {
+		self.roles = roles;
 		self
 	}
 
@@ -167,6 +183,12 @@ impl Options {
 		self
 	}
 
+	/// Specify if statements which write data should be rejected
+	pub fn with_read_only(mut self, read_only: bool) -> Self {
+		self.read_only = read_only;
+		self
+	}
+
 	/// Specify if we are currently importing data
 	pub fn with_import(mut self, import: bool) -> Self {
 		self.set_import(import);
@@ -224,6 +246,7 @@ impl Options {
 		Self {
 			sender: self.sender.clone(),
 			auth,
+			roles: None,
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force: self.force.clone(),
@@ -237,6 +260,7 @@ impl Options {
 		Self {
 			sender: self.sender.clone(),
 			auth: self.auth.clone(),
+			roles: self.roles.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force: self.force.clone(),
@@ -250,6 +274,7 @@ impl Options {
 		Self {
 			sender: self.sender.clone(),
 			auth: self.auth.clone(),
+			roles: self.roles.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force,
@@ -262,6 +287,7 @@ impl Options {
 		Self {
 			sender: self.sender.clone(),
 			auth: self.auth.clone(),
+			roles: self.roles.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force: self.force.clone(),
@@ -275,6 +301,7 @@ impl Options {
 		Self {
 			sender: self.sender.clone(),
 			auth: self.auth.clone(),
+			roles: self.roles.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force: self.force.clone(),
@@ -288,6 +315,7 @@ impl Options {
 		Self {
 			sender: self.sender.clone(),
 			auth: self.auth.clone(),
+			roles: self.roles.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force: self.force.clone(),
@@ -306,6 +334,7 @@ impl Options {
 	pub fn new_with_sender(&self, sender: Sender<Notification>) -> Self {
 		Self {
 			auth: self.auth.clone(),
+			roles: self.roles.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force: self.force.clone(),
@@ -335,6 +364,7 @@ impl Options {
 		Ok(Self {
 			sender: self.sender.clone(),
 			auth: self.auth.clone(),
+			roles: self.roles.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force: self.force.clone(),
@@ -421,13 +451,18 @@ impl Options {
 			return Ok(());
 		}
 
-		self.auth.is_allowed(action, &res).map_err(Error::IamError)
+		match &self.roles {
+			Some(roles) => self.auth.is_allowed_with(action, &res, roles),
+			None => self.auth.is_allowed(action, &res),
+		}
+		.map_err(Error::IamError)
 	}
 
 	/// Checks the current server configuration, and
 	/// user authentication information to determine
-	/// whether we need to process table permissions
-	/// on each document.
+	/// whether we need to process the permissions of
+	/// the resource of the given kind and name, such
+	/// as the table which a document belongs to.
 	///
 	/// This method is repeatedly called during the
 	/// document processing operations, and so the
@@ -435,7 +470,12 @@ impl Options {
 	/// We decided to bypass the system cedar auth
 	/// system as a temporary solution until the
 	/// new authorization system is optimised.
-	pub fn check_perms(&self, action: Action) -> Result<bool, Error> {
+	pub fn check_perms(
+		&self,
+		action: Action,
+		kind: ResourceKind,
+		name: &str,
+	) -> Result<bool, Error> {
 		// Check if permissions are enabled for this sub-process
 		if !self.perms {
 			return Ok(false);
@@ -448,13 +488,14 @@ impl Options {
 		match action {
 			// This is a request to edit a resource
 			Action::Edit => {
+				let (ns, db) = self.ns_db()?;
 				// Check if the actor is allowed to edit
-				let allowed = self.auth.has_editor_role();
+				let allowed = self.auth.has_editor_role()
+					|| self.is_allowed_by_custom_roles(action, kind, name, ns, db);
 				// Today all users have at least View
 				// permissions, so if the target database
 				// belongs to the user's level, we don't
 				// need to check any table permissions.
-				let (ns, db) = self.ns_db()?;
 				let db_in_actor_level = self.auth.is_root()
 					|| self.auth.is_ns_check(ns)
 					|| self.auth.is_db_check(ns, db);
@@ -464,13 +505,14 @@ impl Options {
 			}
 			// This is a request to view a resource
 			Action::View => {
+				let (ns, db) = self.ns_db()?;
 				// Check if the actor is allowed to view
-				let allowed = self.auth.has_viewer_role();
+				let allowed = self.auth.has_viewer_role()
+					|| self.is_allowed_by_custom_roles(action, kind, name, ns, db);
 				// Today, Owner and Editor roles have
 				// Edit permissions, so if the target
 				// database belongs to the user's level
 				// we don't need to check table permissions.
-				let (ns, db) = self.ns_db()?;
 				let db_in_actor_level = self.auth.is_root()
 					|| self.auth.is_ns_check(ns)
 					|| self.auth.is_db_check(ns, db);
@@ -480,6 +522,32 @@ impl Options {
 			}
 		}
 	}
+
+	/// Checks if one of the custom roles of the actor
+	/// allows an action on a resource in a database.
+	/// The resource is only created when the actor
+	/// has any custom roles, to keep the permission
+	/// checks fast for the built-in roles.
+	fn is_allowed_by_custom_roles(
+		&self,
+		action: Action,
+		kind: ResourceKind,
+		name: &str,
+		ns: &str,
+		db: &str,
+	) -> bool {
+		match &self.roles {
+			Some(roles) if self.auth.has_custom_roles() => self
+				.auth
+				.is_allowed_with(
+					action,
+					&Resource::new(name.to_owned(), kind, (ns, db).into()),
+					roles,
+				)
+				.is_ok(),
+			_ => false,
+		}
+	}
 }
 
 #[cfg(test)]
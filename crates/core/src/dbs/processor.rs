@@ -478,7 +478,7 @@ pub(super) trait Collector {
 					RecordStrategy::KeysOnly => {
 						self.collect_range_keys(ctx, opt, &tb, v, sc).await?
 					}
-					RecordStrategy::KeysAndValues => {
+					RecordStrategy::KeysAndValues | RecordStrategy::IndexOnly => {
 						self.collect_range(ctx, opt, &tb, v, sc).await?
 					}
 				},
@@ -489,7 +489,7 @@ pub(super) trait Collector {
 						RecordStrategy::KeysOnly => {
 							self.collect_table_keys(&ctx, opt, &v, sc).await?
 						}
-						RecordStrategy::KeysAndValues => {
+						RecordStrategy::KeysAndValues | RecordStrategy::IndexOnly => {
 							self.collect_table(&ctx, opt, &v, sc).await?
 						}
 					}
@@ -904,7 +904,7 @@ pub(super) trait Collector {
 		let (ns, db) = opt.ns_db()?;
 		ctx.tx().check_ns_db_tb(ns, db, &table.0, opt.strict).await?;
 		if let Some(exe) = ctx.get_query_executor() {
-			if let Some(iterator) = exe.new_iterator(opt, irf).await? {
+			if let Some(iterator) = exe.new_iterator(opt, irf, rs).await? {
 				let txn = ctx.tx();
 				match rs {
 					RecordStrategy::Count => {
@@ -913,7 +913,8 @@ pub(super) trait Collector {
 					RecordStrategy::KeysOnly => {
 						self.collect_index_item_key(ctx, &txn, iterator).await?
 					}
-					RecordStrategy::KeysAndValues => {
+					// The index only iterators return the values rebuilt from the index entries
+					RecordStrategy::KeysAndValues | RecordStrategy::IndexOnly => {
 						self.collect_index_item_key_value(ctx, &txn, iterator).await?
 					}
 				}
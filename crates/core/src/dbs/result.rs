@@ -1,7 +1,7 @@
 use crate::cnf::MAX_ORDER_LIMIT_PRIORITY_QUEUE_SIZE;
 use crate::ctx::Context;
 #[cfg(storage)]
-use crate::dbs::file::FileCollector;
+use crate::dbs::file::{FileCollector, FileValues};
 use crate::dbs::group::GroupsCollector;
 use crate::dbs::plan::Explanation;
 use crate::dbs::store::{MemoryCollector, MemoryOrdered, MemoryOrderedLimit, MemoryRandom};
@@ -9,7 +9,7 @@ use crate::dbs::{Options, Statement};
 use crate::err::Error;
 use crate::idx::planner::RecordStrategy;
 use crate::sql::order::Ordering;
-use crate::sql::Value;
+use crate::sql::{Fields, Value};
 use reblessive::tree::Stk;
 
 pub(super) enum Results {
@@ -37,27 +37,40 @@ impl Results {
 		#[cfg(storage)]
 		if stm.tempfiles() {
 			if let Some(temp_dir) = ctx.temporary_directory() {
-				return Ok(Self::File(Box::new(FileCollector::new(temp_dir)?)));
+				let mut f = FileCollector::new(temp_dir)?;
+				if stm.distinct() {
+					f.distinct();
+				}
+				return Ok(Self::File(Box::new(f)));
 			}
 		}
+		// Window functions are computed over all of the values, before these are ordered
+		if stm.expr().is_some_and(Fields::has_windows) {
+			return Ok(Self::Memory(Default::default()));
+		}
+		Ok(Self::ordered(stm, start, limit))
+	}
+
+	/// Prepares the in-memory results, depending on any ORDER BY clause
+	pub(super) fn ordered(stm: &Statement<'_>, start: Option<u32>, limit: Option<u32>) -> Self {
 		if let Some(ordering) = stm.order() {
 			return match ordering {
-				Ordering::Random => Ok(Self::MemoryRandom(MemoryRandom::new(None))),
+				Ordering::Random => Self::MemoryRandom(MemoryRandom::new(None)),
 				Ordering::Order(orders) => {
 					if let Some(limit) = limit {
 						let limit = start.unwrap_or(0) + limit;
 						if limit <= *MAX_ORDER_LIMIT_PRIORITY_QUEUE_SIZE {
-							return Ok(Self::MemoryOrderedLimit(MemoryOrderedLimit::new(
+							return Self::MemoryOrderedLimit(MemoryOrderedLimit::new(
 								limit as usize,
 								orders.clone(),
-							)));
+							));
 						}
 					}
-					Ok(Self::MemoryOrdered(MemoryOrdered::new(orders.clone(), None)))
+					Self::MemoryOrdered(MemoryOrdered::new(orders.clone(), None))
 				}
 			};
 		}
-		Ok(Self::Memory(Default::default()))
+		Self::Memory(Default::default())
 	}
 
 	pub(super) async fn push(
@@ -173,6 +186,17 @@ impl Results {
 		})
 	}
 
+	/// Consumes the results, reading the values in any temporary files lazily.
+	/// Returns the number of values alongside the values.
+	#[cfg(storage)]
+	pub(super) async fn into_values(mut self) -> Result<(usize, FileValues), Error> {
+		if let Self::File(f) = self {
+			return f.into_values().await;
+		}
+		let values = self.take().await?;
+		Ok((values.len(), Box::new(values.into_iter().map(Ok))))
+	}
+
 	pub(super) fn explain(&self, exp: &mut Explanation) {
 		match self {
 			Self::None => exp.add_collector("None", vec![]),
@@ -396,6 +396,14 @@ impl Statement<'_> {
 		}
 	}
 
+	/// Returns any DISTINCT clause if specified
+	pub(crate) fn distinct(&self) -> bool {
+		match self {
+			Statement::Select(v) => v.distinct,
+			_ => false,
+		}
+	}
+
 	/// Returns any EXPLAIN clause if specified
 	pub(crate) fn explain(&self) -> Option<&Explain> {
 		match self {
@@ -0,0 +1,253 @@
+use crate::ctx::Context;
+use crate::dbs::group::Aggregator;
+use crate::dbs::{Options, Statement};
+use crate::err::Error;
+use crate::idx::planner::RecordStrategy;
+use crate::sql::function::OptimisedAggregate;
+use crate::sql::order::OrderList;
+use crate::sql::{Array, Function, Idiom, Value, Window};
+use reblessive::tree::Stk;
+use std::cmp::Ordering;
+
+/// Computes the window functions of a statement, once all of the rows are
+/// known. Each row holds the values computed by [`Window::compute`] in place
+/// of each window function, which are then replaced by the function result.
+#[derive(Clone)]
+pub(super) struct Windows {
+	windows: Vec<(Idiom, Window)>,
+	single: bool,
+}
+
+/// The values of a row which a window function is computed with
+#[derive(Default)]
+pub(super) struct WindowRow {
+	partition: Value,
+	order: Array,
+	args: Array,
+}
+
+impl Windows {
+	pub(super) fn new(stm: &Statement<'_>) -> Option<Self> {
+		let fields = stm.expr()?;
+		let windows: Vec<_> =
+			fields.windows().map(|(name, window)| (name.into_owned(), window.clone())).collect();
+		if windows.is_empty() {
+			return None;
+		}
+		Some(Self {
+			windows,
+			single: fields.single().is_some(),
+		})
+	}
+
+	/// The number of window functions
+	pub(super) fn len(&self) -> usize {
+		self.windows.len()
+	}
+
+	/// Get the values of a row for a window function
+	pub(super) fn row(&self, i: usize, row: &Value) -> WindowRow {
+		let val = match self.single {
+			true => row.clone(),
+			false => row.pick(&self.windows[i].0),
+		};
+		match val {
+			Value::Array(Array(v)) => {
+				let mut v = v.into_iter();
+				match (v.next(), v.next(), v.next()) {
+					(Some(partition), Some(Value::Array(order)), Some(Value::Array(args))) => {
+						WindowRow {
+							partition,
+							order,
+							args,
+						}
+					}
+					_ => WindowRow::default(),
+				}
+			}
+			_ => WindowRow::default(),
+		}
+	}
+
+	/// Set the result of a window function on a row
+	pub(super) fn set(&self, i: usize, row: &mut Value, val: Value) {
+		match self.single {
+			true => *row = val,
+			false => row.put(&self.windows[i].0, val),
+		}
+	}
+
+	/// Compare two rows by the partition, and then the order of a window function
+	pub(super) fn compare(&self, i: usize, a: &WindowRow, b: &WindowRow) -> Ordering {
+		a.partition.cmp(&b.partition).then_with(|| self.compare_order(i, a, b))
+	}
+
+	/// Compare two rows by the order of a window function
+	fn compare_order(&self, i: usize, a: &WindowRow, b: &WindowRow) -> Ordering {
+		let Some(orders) = &self.windows[i].1.order else {
+			return Ordering::Equal;
+		};
+		Self::compare_values(orders, &a.order, &b.order)
+	}
+
+	fn compare_values(orders: &OrderList, a: &Array, b: &Array) -> Ordering {
+		for (i, order) in orders.iter().enumerate() {
+			let (x, y) = match (a.get(i), b.get(i)) {
+				(Some(x), Some(y)) => (x, y),
+				_ => continue,
+			};
+			// Reverse the ordering if DESC
+			let o = match order.direction {
+				true => x.compare(y, &[], order.collate, order.numeric),
+				false => y.compare(x, &[], order.collate, order.numeric),
+			};
+			match o {
+				Some(Ordering::Equal) | None => continue,
+				Some(o) => return o,
+			}
+		}
+		Ordering::Equal
+	}
+
+	/// Check if two rows are in the same partition of a window function
+	pub(super) fn same_partition(&self, a: &WindowRow, b: &WindowRow) -> bool {
+		a.partition == b.partition
+	}
+
+	/// Computes all of the window functions on rows held in memory
+	pub(super) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		mut rows: Vec<Value>,
+	) -> Result<Vec<Value>, Error> {
+		for i in 0..self.len() {
+			let mut vals: Vec<WindowRow> = rows.iter().map(|r| self.row(i, r)).collect();
+			// Sort the rows by partition and order, keeping equal rows in place
+			let mut ordered: Vec<usize> = (0..rows.len()).collect();
+			ordered.sort_by(|a, b| self.compare(i, &vals[*a], &vals[*b]));
+			// Compute the window function for each partition
+			let mut start = 0;
+			while start < ordered.len() {
+				let first = &vals[ordered[start]];
+				let end = ordered[start..]
+					.iter()
+					.position(|x| !self.same_partition(first, &vals[*x]))
+					.map_or(ordered.len(), |n| start + n);
+				let partition: Vec<WindowRow> =
+					ordered[start..end].iter().map(|x| std::mem::take(&mut vals[*x])).collect();
+				let res = self.partition(stk, ctx, opt, i, partition).await?;
+				for (x, val) in ordered[start..end].iter().zip(res) {
+					self.set(i, &mut rows[*x], val);
+				}
+				start = end;
+			}
+		}
+		Ok(rows)
+	}
+
+	/// Computes a window function over the ordered rows of a single partition
+	pub(super) async fn partition(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		i: usize,
+		rows: Vec<WindowRow>,
+	) -> Result<Vec<Value>, Error> {
+		let window = &self.windows[i].1;
+		let mut res = Vec::with_capacity(rows.len());
+		match &window.func {
+			Function::Normal(name, _) if name == "window::row_number" => {
+				res.extend((1..=rows.len()).map(Value::from));
+			}
+			Function::Normal(name, _) if name == "window::rank" => {
+				let mut rank = 0;
+				for (n, row) in rows.iter().enumerate() {
+					// Equal rows have the same rank, leaving a gap afterwards
+					if n == 0 || self.compare_order(i, &rows[n - 1], row).is_ne() {
+						rank = n + 1;
+					}
+					res.push(Value::from(rank));
+				}
+			}
+			Function::Normal(name, _) if name == "window::lag" || name == "window::lead" => {
+				for (n, row) in rows.iter().enumerate() {
+					let offset = match row.args.get(1) {
+						Some(Value::Number(v)) => v.as_int(),
+						Some(Value::None) | None => 1,
+						Some(v) => {
+							return Err(Error::InvalidArguments {
+								name: name.to_owned(),
+								message: format!("The offset must be a number, but found {v}"),
+							})
+						}
+					};
+					let pos = match name.as_str() {
+						"window::lag" => (n as i64).checked_sub(offset),
+						_ => (n as i64).checked_add(offset),
+					};
+					let val = pos
+						.and_then(|p| usize::try_from(p).ok())
+						.and_then(|p| rows.get(p))
+						.and_then(|r| r.args.first())
+						.or_else(|| row.args.get(2))
+						.cloned()
+						.unwrap_or_default();
+					res.push(val);
+				}
+			}
+			Function::Normal(name, _) => {
+				// Without an order, each row is computed over the whole partition.
+				// Otherwise each row is computed over every row up to the last row
+				// which is ordered equally to it, and the aggregate is updated with
+				// each group of equal rows. Aggregates which can not be updated are
+				// only computed once, over the whole partition.
+				let a = window.func.get_optimised_aggregate();
+				if let OptimisedAggregate::None = a {
+					if window.order.is_some() {
+						return Err(Error::InvalidArguments {
+							name: name.to_owned(),
+							message:
+								"This function can not be used in a window with an ORDER BY clause"
+									.to_owned(),
+						});
+					}
+					let vals: Vec<Value> =
+						rows.iter().map(|r| r.args.first().cloned().unwrap_or_default()).collect();
+					// The other arguments of the function are those of the first row
+					let mut args = vec![Value::from(vals)];
+					args.extend(
+						rows.first().into_iter().flat_map(|r| r.args.iter().skip(1)).cloned(),
+					);
+					let val = Function::Normal(name.to_owned(), args)
+						.compute(stk, ctx, opt, None)
+						.await?;
+					res.extend(std::iter::repeat_n(val, rows.len()));
+					return Ok(res);
+				}
+				let mut agr = Aggregator::new(&window.func);
+				let mut start = 0;
+				while start < rows.len() {
+					let end = match window.order {
+						Some(_) => rows[start..]
+							.iter()
+							.position(|r| self.compare_order(i, &rows[start], r).is_ne())
+							.map_or(rows.len(), |n| start + n),
+						None => rows.len(),
+					};
+					for r in &rows[start..end] {
+						let v = r.args.first().cloned().unwrap_or_default();
+						agr.push(stk, ctx, opt, RecordStrategy::KeysAndValues, v).await?;
+					}
+					let val = agr.compute(a)?;
+					res.extend(std::iter::repeat_n(val, end - start));
+					start = end;
+				}
+			}
+			f => return Err(fail!("Unexpected window function: {f}")),
+		}
+		Ok(res)
+	}
+}
@@ -5,7 +5,7 @@ use crate::dbs::Workable;
 use crate::doc::Document;
 use crate::doc::Permitted::*;
 use crate::err::Error;
-use crate::iam::Action;
+use crate::iam::{Action, ResourceKind};
 use crate::sql::paths::ID;
 use crate::sql::paths::IN;
 use crate::sql::paths::OUT;
@@ -100,6 +100,35 @@ impl Document {
 		// Carry on
 		Ok(())
 	}
+	/// Checks that a record has not expired on a
+	/// table with an `EXPIRE` clause. Expired
+	/// records are hidden until they are removed
+	/// by the background expiry task. Record link
+	/// fetches and graph traversals also read the
+	/// records through this check, and counts on
+	/// these tables process each record.
+	pub(super) async fn check_record_expired(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		_stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Get the record id
+		let Some(rid) = &self.id else {
+			return Ok(());
+		};
+		// Check if the table has an expiry
+		if self.tb(ctx, opt).await?.expire.is_none() {
+			return Ok(());
+		}
+		// Check if the record has expired
+		let (ns, db) = opt.ns_db()?;
+		if ctx.tx().is_record_expired(ns, db, &rid.tb, &rid.id).await? {
+			return Err(Error::Ignore);
+		}
+		// Carry on
+		Ok(())
+	}
 	/// Checks that the fields of a document are
 	/// correct. If an `id` field is specified then
 	/// it will check that the `id` field does not
@@ -340,7 +369,7 @@ impl Document {
 		// Check if this record exists
 		if self.id.is_some() {
 			// Should we run permissions checks?
-			if opt.check_perms(Action::View)? {
+			if opt.check_perms(Action::View, ResourceKind::Table, &self.id()?.tb)? {
 				// Get the table for this document
 				let table = self.tb(ctx, opt).await?;
 				// Get the correct document to check
@@ -381,7 +410,7 @@ impl Document {
 		// Check if this record exists
 		if self.id.is_some() {
 			// Should we run permissions checks?
-			if opt.check_perms(stm.into())? {
+			if opt.check_perms(stm.into(), ResourceKind::Table, &self.id()?.tb)? {
 				// Get the table for this document
 				let table = self.tb(ctx, opt).await?;
 				// Get the permissions for this table
@@ -410,7 +439,7 @@ impl Document {
 		// Check if this record exists
 		if self.id.is_some() {
 			// Should we run permissions checks?
-			if opt.check_perms(stm.into())? {
+			if opt.check_perms(stm.into(), ResourceKind::Table, &self.id()?.tb)? {
 				// Check that record authentication matches session
 				if opt.auth.is_record() {
 					let ns = opt.ns()?;
@@ -290,11 +290,11 @@ impl Document {
 		permitted: Permitted,
 	) -> Result<bool, Error> {
 		// Check if this record exists
-		if self.id.is_none() {
+		let Some(rid) = &self.id else {
 			return Ok(false);
-		}
+		};
 		// Are permissions being skipped?
-		if !opt.check_perms(Action::View)? {
+		if !opt.check_perms(Action::View, ResourceKind::Table, &rid.tb)? {
 			return Ok(false);
 		}
 		// Fetch the fields for the table
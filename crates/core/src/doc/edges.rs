@@ -33,9 +33,7 @@ impl Document {
 			let rid = self.id()?;
 			// Get the transaction
 			let txn = ctx.tx();
-			// Lock the transaction
-			let mut txn = txn.lock().await;
-			// For enforced relations, ensure that the edges exist
+			// For enforced relations, ensure that the edges exist and have not expired
 			if matches!(
 				tb.kind,
 				TableType::Relation(Relation {
@@ -45,19 +43,25 @@ impl Document {
 			) {
 				// Check that the `in` record exists
 				let key = crate::key::thing::new(ns, db, &l.tb, &l.id);
-				if !txn.exists(key, None).await? {
+				if !txn.exists(key, None).await?
+					|| txn.is_record_expired(ns, db, &l.tb, &l.id).await?
+				{
 					return Err(Error::IdNotFound {
 						rid: l.to_string(),
 					});
 				}
 				// Check that the `out` record exists
 				let key = crate::key::thing::new(ns, db, &r.tb, &r.id);
-				if !txn.exists(key, None).await? {
+				if !txn.exists(key, None).await?
+					|| txn.is_record_expired(ns, db, &r.tb, &r.id).await?
+				{
 					return Err(Error::IdNotFound {
 						rid: r.to_string(),
 					});
 				}
 			}
+			// Lock the transaction
+			let mut txn = txn.lock().await;
 			// Get temporary edge references
 			let (ref o, ref i) = (Dir::Out, Dir::In);
 			// Store the left pointer edge
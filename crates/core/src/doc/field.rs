@@ -4,7 +4,7 @@ use crate::dbs::Options;
 use crate::dbs::Statement;
 use crate::doc::Document;
 use crate::err::Error;
-use crate::iam::Action;
+use crate::iam::{Action, ResourceKind};
 use crate::kvs::KeyEncode as _;
 use crate::sql::data::Data;
 use crate::sql::idiom::Idiom;
@@ -528,7 +528,7 @@ impl FieldEditContext<'_> {
 	/// Process any PERMISSIONS clause for the field definition
 	async fn process_permissions_clause(&mut self, val: Value) -> Result<Value, Error> {
 		// Check for a PERMISSIONS clause
-		if self.opt.check_perms(Action::Edit)? {
+		if self.opt.check_perms(Action::Edit, ResourceKind::Table, &self.rid.tb)? {
 			// Get the permission clause
 			let perms = if self.doc.is_new() {
 				&self.def.permissions.create
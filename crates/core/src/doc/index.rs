@@ -3,6 +3,7 @@ use crate::dbs::Options;
 use crate::dbs::{Force, Statement};
 use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
+use crate::idx::entry::IndexEntry;
 use crate::idx::ft::FtIndex;
 use crate::idx::trees::mtree::MTreeIndex;
 use crate::idx::IndexKeyBase;
@@ -102,6 +103,8 @@ impl Document {
 	/// Eg. IF the index is composed of the columns `name` and `instrument`
 	/// Given this doc: { "id": 1, "instrument":"piano", "name":"Tobie" }
 	/// It will return: ["Tobie", "piano"]
+	/// The values of any included fields are appended after the indexed values.
+	/// Returns `None` if there is no document, or if the document does not match the index condition.
 	pub(crate) async fn build_opt_values(
 		stk: &mut Stk,
 		ctx: &Context,
@@ -112,8 +115,15 @@ impl Document {
 		if !doc.doc.as_ref().is_some() {
 			return Ok(None);
 		}
-		let mut o = Vec::with_capacity(ix.cols.len());
-		for i in ix.cols.iter() {
+		// Records which don't match a partial index are not indexed
+		if let Some(cond) = &ix.cond {
+			if !cond.compute(stk, ctx, opt, Some(doc)).await?.is_truthy() {
+				return Ok(None);
+			}
+		}
+		// The included values follow the indexed values
+		let mut o = Vec::with_capacity(ix.cols.len() + ix.include.len());
+		for i in ix.cols.iter().chain(ix.include.iter()) {
 			let v = i.compute(stk, ctx, opt, Some(doc)).await?;
 			o.push(v);
 		}
@@ -304,10 +314,11 @@ impl<'a> IndexOperation<'a> {
 		let mut txn = txn.lock().await;
 		// Delete the old index data
 		if let Some(o) = self.o.take() {
+			let entry = IndexEntry::encode(self.ix, self.rid, &o)?;
 			let i = Indexable::new(o, self.ix);
 			for o in i {
 				let key = self.get_unique_index_key(&o)?;
-				match txn.delc(key, Some(revision::to_vec(self.rid)?)).await {
+				match txn.delc(key, Some(entry.clone())).await {
 					Err(Error::TxConditionNotMet) => Ok(()),
 					Err(e) => Err(e),
 					Ok(v) => Ok(v),
@@ -316,14 +327,15 @@ impl<'a> IndexOperation<'a> {
 		}
 		// Create the new index data
 		if let Some(n) = self.n.take() {
+			let entry = IndexEntry::encode(self.ix, self.rid, &n)?;
 			let i = Indexable::new(n, self.ix);
 			for n in i {
 				if !n.is_all_none_or_null() {
 					let key = self.get_unique_index_key(&n)?;
-					if txn.putc(key, revision::to_vec(self.rid)?, None).await.is_err() {
+					if txn.putc(key, entry.clone(), None).await.is_err() {
 						let key = self.get_unique_index_key(&n)?;
 						let val = txn.get(key, None).await?.unwrap();
-						let rid: Thing = revision::from_slice(&val)?;
+						let rid = IndexEntry::decode(self.ix, &val)?.rid;
 						return self.err_index_exists(rid, n);
 					}
 				}
@@ -339,10 +351,11 @@ impl<'a> IndexOperation<'a> {
 		let mut txn = txn.lock().await;
 		// Delete the old index data
 		if let Some(o) = self.o.take() {
+			let entry = IndexEntry::encode(self.ix, self.rid, &o)?;
 			let i = Indexable::new(o, self.ix);
 			for o in i {
 				let key = self.get_non_unique_index_key(&o)?;
-				match txn.delc(key, Some(revision::to_vec(self.rid)?)).await {
+				match txn.delc(key, Some(entry.clone())).await {
 					Err(Error::TxConditionNotMet) => Ok(()),
 					Err(e) => Err(e),
 					Ok(v) => Ok(v),
@@ -351,13 +364,14 @@ impl<'a> IndexOperation<'a> {
 		}
 		// Create the new index data
 		if let Some(n) = self.n.take() {
+			let entry = IndexEntry::encode(self.ix, self.rid, &n)?;
 			let i = Indexable::new(n, self.ix);
 			for n in i {
 				let key = self.get_non_unique_index_key(&n)?;
-				if txn.putc(key, revision::to_vec(self.rid)?, None).await.is_err() {
+				if txn.putc(key, entry.clone(), None).await.is_err() {
 					let key = self.get_non_unique_index_key(&n)?;
 					let val = txn.get(key, None).await?.unwrap();
-					let rid: Thing = revision::from_slice(&val)?;
+					let rid = IndexEntry::decode(self.ix, &val)?.rid;
 					return self.err_index_exists(rid, n);
 				}
 			}
@@ -6,6 +6,8 @@ use crate::dbs::Statement;
 use crate::doc::CursorDoc;
 use crate::doc::Document;
 use crate::err::Error;
+use crate::iam::check::role_policies;
+use crate::iam::ResourceKind;
 use crate::sql::paths::AC;
 use crate::sql::paths::META;
 use crate::sql::paths::RD;
@@ -107,7 +109,10 @@ impl Document {
 			// use for processing this LIVE query statement.
 			// This ensures that we are using the auth data
 			// of the user who created the LIVE query.
-			let lqopt = opt.new_with_perms(true).with_auth(Arc::from(auth));
+			// The custom roles of the user are resolved
+			// against the current role definitions.
+			let roles = role_policies(&ctx.tx(), &auth).await?;
+			let lqopt = opt.new_with_perms(true).with_auth(Arc::from(auth)).with_roles(roles);
 			// First of all, let's check to see if the WHERE
 			// clause of the LIVE query is matched by this
 			// document. If it is then we can continue.
@@ -224,7 +229,7 @@ impl Document {
 		doc: &CursorDoc,
 	) -> Result<(), Error> {
 		// Should we run permissions checks?
-		if opt.check_perms(stm.into())? {
+		if opt.check_perms(stm.into(), ResourceKind::Table, &self.id()?.tb)? {
 			// Get the table
 			let tb = self.tb(ctx, opt).await?;
 			// Process the table permissions
@@ -4,7 +4,7 @@ use crate::dbs::Statement;
 use crate::doc::Document;
 use crate::doc::Permitted::*;
 use crate::err::Error;
-use crate::iam::Action;
+use crate::iam::{Action, ResourceKind};
 use crate::sql::idiom::Idiom;
 use crate::sql::output::Output;
 use crate::sql::paths::META;
@@ -198,7 +198,7 @@ impl Document {
 		// Check if this record exists
 		if self.id.is_some() {
 			// Should we run permissions checks?
-			if opt.check_perms(Action::View)? {
+			if opt.check_perms(Action::View, ResourceKind::Table, &self.id()?.tb)? {
 				// Loop through all field statements
 				for fd in self.fd(ctx, opt).await?.iter() {
 					// Loop over each field in document
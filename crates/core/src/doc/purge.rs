@@ -47,6 +47,10 @@ impl Document {
 			let (ns, db) = opt.ns_db()?;
 			// Purge the record data
 			txn.del_record(ns, db, &rid.tb, &rid.id).await?;
+			// Purge the record expiry
+			if self.tb(ctx, opt).await?.expire.is_some() {
+				txn.set_record_expiry(ns, db, &rid.tb, &rid.id, None).await?;
+			}
 			// Purge the record edges
 			match (
 				self.initial.doc.as_ref().pick(&*EDGE),
@@ -223,4 +227,38 @@ impl Document {
 		// Carry on
 		Ok(())
 	}
+
+	/// Deletes the record if it has expired on a
+	/// table with an `EXPIRE` clause, but has not yet
+	/// been deleted by the background expiry task, so
+	/// that a new record can be created with this id.
+	pub(super) async fn purge_expired(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		_stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Get the record id
+		let Some(rid) = &self.id else {
+			return Ok(());
+		};
+		// Check if the table has an expiry
+		if self.tb(ctx, opt).await?.expire.is_none() {
+			return Ok(());
+		}
+		// Check if the record has expired
+		let (ns, db) = opt.ns_db()?;
+		if !ctx.tx().is_record_expired(ns, db, &rid.tb, &rid.id).await? {
+			return Ok(());
+		}
+		// Delete the expired record
+		let stm = DeleteStatement {
+			what: Values(vec![Value::from(rid.as_ref().clone())]),
+			..DeleteStatement::default()
+		};
+		stm.compute(stk, ctx, &opt.new_with_perms(false), None).await?;
+		// Carry on
+		Ok(())
+	}
 }
@@ -15,8 +15,10 @@ impl Document {
 		if !self.changed() {
 			return Ok(());
 		}
+		// Get the table for this document
+		let tb = self.tb(ctx, opt).await?;
 		// Check if the table is a view
-		if self.tb(ctx, opt).await?.drop {
+		if tb.drop {
 			return Ok(());
 		}
 		// Get the record id
@@ -101,6 +103,11 @@ impl Document {
 		}?;
 		// Update the cache
 		ctx.tx().set_record_cache(ns, db, &rid.tb, &rid.id, self.current.doc.as_arc())?;
+		// Update the record expiry
+		if let Some(expire) = &tb.expire {
+			let at = expire.expires_at(self.current.doc.as_ref());
+			ctx.tx().set_record_expiry(ns, db, &rid.tb, &rid.id, at.as_ref()).await?;
+		}
 		// Carry on
 		Ok(())
 	}
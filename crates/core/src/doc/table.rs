@@ -3,6 +3,8 @@ use crate::dbs::Options;
 use crate::dbs::{Force, Statement};
 use crate::doc::{CursorDoc, Document};
 use crate::err::Error;
+use crate::fnc::count;
+use crate::fnc::util::math::sketch::{self, Sketch};
 use crate::sql::data::Data;
 use crate::sql::expression::Expression;
 use crate::sql::field::{Field, Fields};
@@ -491,6 +493,85 @@ impl Document {
 							};
 							self.mean(&mut set_ops, &mut del_ops, &fdc.act, idiom, val)?;
 						}
+						Some(name) if name == "math::variance" || name == "math::stddev" => {
+							let val = f.args()[0].compute(stk, ctx, opt, Some(fdc.doc)).await?;
+							let val = match val {
+								Value::Number(val) => val.to_float(),
+								val => {
+									return Err(Error::InvalidAggregation {
+										name: name.to_string(),
+										table: fdc.ft.name.to_raw(),
+										message: format!(
+											"This function expects a number but found {val}"
+										),
+									})
+								}
+							};
+							let stddev = name == "math::stddev";
+							self.variance(
+								&mut set_ops,
+								&mut del_ops,
+								&fdc.act,
+								idiom,
+								val,
+								stddev,
+							)?;
+						}
+						Some(name) if name == "math::percentile" => {
+							let val = f.args()[0].compute(stk, ctx, opt, Some(fdc.doc)).await?;
+							let val = match val {
+								Value::Number(val) => val.to_float(),
+								val => {
+									return Err(Error::InvalidAggregation {
+										name: name.to_string(),
+										table: fdc.ft.name.to_raw(),
+										message: format!(
+											"This function expects a number but found {val}"
+										),
+									})
+								}
+							};
+							let perc = match f.args().get(1) {
+								Some(v) => v.compute(stk, ctx, opt, Some(fdc.doc)).await?,
+								None => Value::None,
+							};
+							let perc = match perc {
+								Value::Number(perc) => perc,
+								perc => {
+									return Err(Error::InvalidAggregation {
+										name: name.to_string(),
+										table: fdc.ft.name.to_raw(),
+										message: format!(
+											"This function expects a number percentile but found {perc}"
+										),
+									})
+								}
+							};
+							self.percentile(
+								&mut set_ops,
+								&mut del_ops,
+								&fdc.act,
+								idiom,
+								val,
+								perc,
+							)?;
+						}
+						Some("array::distinct") => {
+							let val = f.args()[0].compute(stk, ctx, opt, Some(fdc.doc)).await?;
+							self.distinct(&mut set_ops, &mut del_ops, &fdc.act, idiom, vec![val])?;
+						}
+						Some("array::group") => {
+							let val = f.args()[0].compute(stk, ctx, opt, Some(fdc.doc)).await?;
+							let vals = match val {
+								Value::Array(v) => v.0,
+								v => vec![v],
+							};
+							self.distinct(&mut set_ops, &mut del_ops, &fdc.act, idiom, vals)?;
+						}
+						Some(count::DISTINCT_FUNCTION) => {
+							let val = f.args()[0].compute(stk, ctx, opt, Some(fdc.doc)).await?;
+							self.count_distinct(&mut set_ops, &mut del_ops, &fdc.act, idiom, val)?;
+						}
 						f => return Err(fail!("Unexpected function {f:?} encountered")),
 					},
 					_ => {
@@ -543,9 +624,7 @@ impl Document {
 		val: Value,
 	) -> Result<(), Error> {
 		// Key for the value count
-		let mut key_c = Idiom::from(vec![Part::from("__")]);
-		key_c.0.push(Part::from(key.to_hash()));
-		key_c.0.push(Part::from("c"));
+		let key_c = Self::hidden(&key, "c");
 		match fdc.act {
 			FieldAction::Add => {
 				set_ops.push((
@@ -599,9 +678,7 @@ impl Document {
 		val: Value,
 	) -> Result<(), Error> {
 		// Key for the value count
-		let mut key_c = Idiom::from(vec![Part::from("__")]);
-		key_c.0.push(Part::from(key.to_hash()));
-		key_c.0.push(Part::from("c"));
+		let key_c = Self::hidden(&key, "c");
 		//
 		match fdc.act {
 			FieldAction::Add => {
@@ -656,9 +733,7 @@ impl Document {
 		val: Value,
 	) -> Result<(), Error> {
 		// Key for the value count
-		let mut key_c = Idiom::from(vec![Part::from("__")]);
-		key_c.0.push(Part::from(key.to_hash()));
-		key_c.0.push(Part::from("c"));
+		let key_c = Self::hidden(&key, "c");
 		//
 		set_ops.push((
 			key.clone(),
@@ -725,6 +800,279 @@ impl Document {
 		Ok(())
 	}
 
+	/// Set the new variance or standard deviation for the field in the foreign table
+	fn variance(
+		&self,
+		set_ops: &mut Ops,
+		del_ops: &mut Ops,
+		act: &FieldAction,
+		key: Idiom,
+		val: f64,
+		stddev: bool,
+	) -> Result<(), Error> {
+		// Keys for the running sum and sum of squares
+		let key_s = Self::hidden(&key, "s");
+		let key_q = Self::hidden(&key, "q");
+		let op = match act {
+			FieldAction::Add => Operator::Inc,
+			FieldAction::Sub => Operator::Dec,
+		};
+		set_ops.push((key_s.clone(), op.clone(), Value::from(val)));
+		set_ops.push((key_q.clone(), op, Value::from(val * val)));
+		// Update the number of values
+		let key_c = Self::hidden(&key, "c");
+		self.count(set_ops, del_ops, act, key_c.clone())?;
+		// The sample variance is (q - s * s / c) / (c - 1)
+		let c = Value::Idiom(key_c);
+		let s = Value::Idiom(key_s);
+		let var = Self::binary(
+			Self::binary(
+				Value::Idiom(key_q),
+				Operator::Sub,
+				Self::binary(Self::binary(s.clone(), Operator::Mul, s), Operator::Div, c.clone()),
+			),
+			Operator::Div,
+			Self::binary(c.clone(), Operator::Sub, Value::from(1)),
+		);
+		// Rounding errors can leave the variance slightly below zero
+		let var = Value::Function(Box::new(Function::Normal(
+			"math::max".to_string(),
+			vec![Value::from(vec![var, Value::from(0.0)])],
+		)));
+		let var = if stddev {
+			Value::Function(Box::new(Function::Normal("math::sqrt".to_string(), vec![var])))
+		} else {
+			var
+		};
+		set_ops.push((
+			key,
+			Operator::Equal,
+			Value::Subquery(Box::new(Subquery::Ifelse(IfelseStatement {
+				exprs: vec![(Self::binary(c, Operator::MoreThan, Value::from(1)), var)],
+				close: Some(Value::from(0.0)),
+			}))),
+		));
+		// Everything ok
+		Ok(())
+	}
+
+	/// Set the new approximate percentile for the field in the foreign table
+	fn percentile(
+		&self,
+		set_ops: &mut Ops,
+		del_ops: &mut Ops,
+		act: &FieldAction,
+		key: Idiom,
+		val: f64,
+		perc: Number,
+	) -> Result<(), Error> {
+		// Key for the sketch of all values
+		let key_s = Self::hidden(&key, "s");
+		// Key for the sketch bucket which counts this value
+		let mut key_b = key_s.clone();
+		key_b.0.extend(Sketch::path(val));
+		match act {
+			FieldAction::Add => set_ops.push((key_b, Operator::Inc, Value::from(1))),
+			FieldAction::Sub => {
+				set_ops.push((key_b.clone(), Operator::Dec, Value::from(1)));
+				set_ops.push(Self::unset_unused(key_b));
+			}
+		}
+		// Compute the percentile from the updated sketch
+		set_ops.push((
+			key.clone(),
+			Operator::Equal,
+			Value::Function(Box::new(Function::Normal(
+				sketch::PERCENTILE_FUNCTION.to_string(),
+				vec![Value::Idiom(key_s), Value::from(perc)],
+			))),
+		));
+		// Update the number of values
+		self.count(set_ops, del_ops, act, Self::hidden(&key, "c"))
+	}
+
+	/// Add or remove values from the distinct values for the field in the foreign table
+	fn distinct(
+		&self,
+		set_ops: &mut Ops,
+		del_ops: &mut Ops,
+		act: &FieldAction,
+		key: Idiom,
+		vals: Vec<Value>,
+	) -> Result<(), Error> {
+		// Ensure the field is set, even if there are no values
+		set_ops.push((
+			key.clone(),
+			Operator::Equal,
+			Self::binary(
+				Value::Idiom(key.clone()),
+				Operator::Nco,
+				Value::from(Vec::<Value>::new()),
+			),
+		));
+		for val in vals {
+			// Key for the number of times this value occurs
+			let key_v = Self::hidden_value(&key, &val)?;
+			match act {
+				FieldAction::Add => {
+					set_ops.push((
+						key.clone(),
+						Operator::Equal,
+						Value::Function(Box::new(Function::Normal(
+							"array::union".to_string(),
+							vec![Value::Idiom(key.clone()), Value::from(vec![val])],
+						))),
+					));
+					set_ops.push((key_v, Operator::Inc, Value::from(1)));
+				}
+				FieldAction::Sub => {
+					set_ops.push((key_v.clone(), Operator::Dec, Value::from(1)));
+					// Remove the value once it no longer occurs in the group
+					set_ops.push((
+						key.clone(),
+						Operator::Equal,
+						Value::Subquery(Box::new(Subquery::Ifelse(IfelseStatement {
+							exprs: vec![(
+								Self::binary(
+									Value::Idiom(key_v.clone()),
+									Operator::MoreThan,
+									Value::from(0),
+								),
+								Value::Idiom(key.clone()),
+							)],
+							close: Some(Value::Function(Box::new(Function::Normal(
+								"array::complement".to_string(),
+								vec![Value::Idiom(key.clone()), Value::from(vec![val])],
+							)))),
+						}))),
+					));
+					set_ops.push(Self::unset_unused(key_v));
+				}
+			}
+		}
+		// Update the number of values
+		self.count(set_ops, del_ops, act, Self::hidden(&key, "c"))
+	}
+
+	/// Set the new number of distinct values for the field in the foreign table
+	fn count_distinct(
+		&self,
+		set_ops: &mut Ops,
+		del_ops: &mut Ops,
+		act: &FieldAction,
+		key: Idiom,
+		val: Value,
+	) -> Result<(), Error> {
+		match act {
+			// Empty values are not counted, but the field is always set
+			_ if val.is_none_or_null() => {
+				set_ops.push((key.clone(), Operator::Inc, Value::from(0)));
+			}
+			FieldAction::Add => {
+				// Key for the number of times this value occurs
+				let key_v = Self::hidden_value(&key, &val)?;
+				// Count the value if it does not yet occur in the group
+				set_ops.push((
+					key.clone(),
+					Operator::Inc,
+					Value::Subquery(Box::new(Subquery::Ifelse(IfelseStatement {
+						exprs: vec![(Value::Idiom(key_v.clone()), Value::from(0))],
+						close: Some(Value::from(1)),
+					}))),
+				));
+				set_ops.push((key_v, Operator::Inc, Value::from(1)));
+			}
+			FieldAction::Sub => {
+				// Key for the number of times this value occurs
+				let key_v = Self::hidden_value(&key, &val)?;
+				set_ops.push((key_v.clone(), Operator::Dec, Value::from(1)));
+				// Stop counting the value once it no longer occurs in the group
+				set_ops.push((
+					key.clone(),
+					Operator::Dec,
+					Value::Subquery(Box::new(Subquery::Ifelse(IfelseStatement {
+						exprs: vec![(
+							Self::binary(
+								Value::Idiom(key_v.clone()),
+								Operator::MoreThan,
+								Value::from(0),
+							),
+							Value::from(0),
+						)],
+						close: Some(Value::from(1)),
+					}))),
+				));
+				set_ops.push(Self::unset_unused(key_v));
+			}
+		}
+		// Update the number of values
+		self.count(set_ops, del_ops, act, Self::hidden(&key, "c"))
+	}
+
+	/// Increment or decrement the number of values for the field in the foreign table
+	fn count(
+		&self,
+		set_ops: &mut Ops,
+		del_ops: &mut Ops,
+		act: &FieldAction,
+		key_c: Idiom,
+	) -> Result<(), Error> {
+		match act {
+			//  Increment the number of values
+			FieldAction::Add => set_ops.push((key_c, Operator::Inc, Value::from(1))),
+			FieldAction::Sub => {
+				//  Decrement the number of values
+				set_ops.push((key_c.clone(), Operator::Dec, Value::from(1)));
+				// Add a purge condition (delete record if the number of values is 0)
+				del_ops.push((key_c, Operator::Equal, Value::from(0)));
+			}
+		}
+		// Everything ok
+		Ok(())
+	}
+
+	/// Removes a value occurrence count once the value no longer occurs
+	fn unset_unused(key_v: Idiom) -> (Idiom, Operator, Value) {
+		(
+			key_v.clone(),
+			Operator::Equal,
+			Value::Subquery(Box::new(Subquery::Ifelse(IfelseStatement {
+				exprs: vec![(
+					Self::binary(Value::Idiom(key_v.clone()), Operator::MoreThan, Value::from(0)),
+					Value::Idiom(key_v),
+				)],
+				close: None,
+			}))),
+		)
+	}
+
+	/// Get the key of a hidden field used to maintain the field in the foreign table
+	fn hidden(key: &Idiom, name: &str) -> Idiom {
+		let mut idiom = Idiom::from(vec![Part::from("__")]);
+		idiom.0.push(Part::from(key.to_hash()));
+		idiom.0.push(Part::from(name));
+		idiom
+	}
+
+	/// Get the key of the hidden field which counts the occurrences of a value
+	fn hidden_value(key: &Idiom, val: &Value) -> Result<Idiom, Error> {
+		let mut idiom = Self::hidden(key, "v");
+		let hash = blake3::hash(&revision::to_vec(val)?);
+		idiom.0.push(Part::from(hash.to_hex().to_string()));
+		Ok(idiom)
+	}
+
+	/// Build a parenthesised binary expression
+	fn binary(l: Value, o: Operator, r: Value) -> Value {
+		Value::Subquery(Box::new(Subquery::Value(Value::Expression(Box::new(
+			Expression::Binary {
+				l,
+				o,
+				r,
+			},
+		)))))
+	}
+
 	/// Recomputes the value for one group
 	fn one_group_query(
 		fdc: &FieldDataContext,
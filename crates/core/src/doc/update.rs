@@ -15,6 +15,7 @@ impl Document {
 		stm: &Statement<'_>,
 	) -> Result<Value, Error> {
 		self.check_record_exists(ctx, opt, stm).await?;
+		self.check_record_expired(ctx, opt, stm).await?;
 		self.check_permissions_quick(stk, ctx, opt, stm).await?;
 		self.check_data_fields(stk, ctx, opt, stm).await?;
 		self.check_where_condition(stk, ctx, opt, stm).await?;
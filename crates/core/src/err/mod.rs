@@ -80,6 +80,28 @@ pub enum Error {
 	#[error("Couldn't write to a read only transaction")]
 	TxReadonly,
 
+	/// The datastore is a read only replica of another datastore
+	#[error("Couldn't write to a read only replica")]
+	ReadOnly,
+
+	/// The table's changes are not recorded in a change feed
+	#[error("The table '{name}' does not have a change feed, so its changes can not be read")]
+	ChangefeedMissing {
+		name: String,
+	},
+
+	/// The requested changes have been removed from the change feed
+	#[error("The changes since versionstamp {since} are no longer retained in the change feed")]
+	ChangefeedExpired {
+		since: u64,
+	},
+
+	/// The replicated data is too large to be applied in a single transaction
+	#[error("The replicated data exceeds the maximum size of {limit} bytes which can be applied in a single transaction")]
+	SyncTooLarge {
+		limit: u64,
+	},
+
 	/// The conditional value in the request was not equal
 	#[error("Value being checked was not correct")]
 	TxConditionNotMet,
@@ -104,6 +126,20 @@ pub enum Error {
 	#[error("Transaction is too large")]
 	TxTooLarge,
 
+	/// The requested interactive transaction does not exist
+	#[error("The transaction '{0}' does not exist, or has already finished")]
+	TxNotFound(String),
+
+	/// The interactive transaction was not finished before the timeout
+	#[error("The transaction '{0}' was cancelled because it exceeded the timeout")]
+	TxTimedout(String),
+
+	/// A stored value was not encrypted with any of the known encryption keys
+	#[error(
+		"Unable to decrypt a stored value, as it was not encrypted with a known encryption key"
+	)]
+	TxDecryptionFailed,
+
 	/// No namespace has been selected
 	#[error("Specify a namespace to use")]
 	NsEmpty,
@@ -299,6 +335,18 @@ pub enum Error {
 		message: String,
 	},
 
+	/// The query was executed, but its changes were rolled back to a savepoint
+	#[error("The query was rolled back to the savepoint '{name}'")]
+	QueryRolledBack {
+		name: String,
+	},
+
+	/// The requested savepoint does not exist in the current transaction
+	#[error("The savepoint '{name}' does not exist")]
+	SpNotFound {
+		name: String,
+	},
+
 	/// The permissions do not allow for changing to the specified namespace
 	#[error("You don't have permission to change to the {ns} namespace")]
 	NsNotAllowed {
@@ -371,6 +419,18 @@ pub enum Error {
 		name: String,
 	},
 
+	/// The requested sequence does not exist
+	#[error("The sequence '{name}' does not exist")]
+	SqNotFound {
+		name: String,
+	},
+
+	/// The sequence has no more values to hand out
+	#[error("The sequence '{name}' has reached its maximum value")]
+	SqExhausted {
+		name: String,
+	},
+
 	/// The requested config does not exist
 	#[error("The config for {name} does not exist")]
 	CgNotFound {
@@ -413,6 +473,26 @@ pub enum Error {
 		name: String,
 	},
 
+	/// The requested cache setting does not apply to the index type
+	#[error("The index '{name}' does not have a {cache} setting")]
+	IxCacheNotSupported {
+		name: String,
+		cache: String,
+	},
+
+	/// The index expression does not always compute to the same result
+	#[error("The index '{name}' can not be defined on the non-deterministic expression '{value}'")]
+	IxNotDeterministic {
+		name: String,
+		value: String,
+	},
+
+	/// The index type does not support storing additional fields
+	#[error("The index '{name}' can not include additional fields, as only standard and unique indexes support INCLUDE")]
+	IxIncludeNotSupported {
+		name: String,
+	},
+
 	/// The requested record does not exist
 	#[error("The record '{rid}' does not exist")]
 	IdNotFound {
@@ -443,6 +523,21 @@ pub enum Error {
 		db: String,
 	},
 
+	/// The requested namespace role does not exist
+	#[error("The role '{name}' does not exist in the namespace '{ns}'")]
+	RoleNsNotFound {
+		name: String,
+		ns: String,
+	},
+
+	/// The requested database role does not exist
+	#[error("The role '{name}' does not exist in the database '{db}'")]
+	RoleDbNotFound {
+		name: String,
+		ns: String,
+		db: String,
+	},
+
 	/// Unable to perform the realtime query
 	#[error("Unable to perform the realtime query")]
 	RealtimeDisabled,
@@ -451,6 +546,12 @@ pub enum Error {
 	#[error("Reached excessive computation depth due to functions, subqueries, or futures")]
 	ComputationDepthExceeded,
 
+	/// Reached the maximum number of iterations of a WHILE loop
+	#[error("Reached the maximum of {limit} iterations in a WHILE loop")]
+	LoopIterationsExceeded {
+		limit: u64,
+	},
+
 	/// Can not execute statement using the specified value
 	#[error("Can not execute statement using value: {value}")]
 	InvalidStatementTarget {
@@ -872,6 +973,10 @@ pub enum Error {
 	#[error("The password did not verify")]
 	InvalidPass,
 
+	/// Authentication was attempted for a subject which has been locked out
+	#[error("Too many failed authentication attempts, the account is temporarily locked")]
+	AccountLocked,
+
 	/// There was an error with authentication
 	///
 	/// This error hides different kinds of errors directly related to authentication
@@ -970,6 +1075,12 @@ pub enum Error {
 		name: String,
 	},
 
+	/// The requested sequence already exists
+	#[error("The sequence '{name}' already exists")]
+	SqAlreadyExists {
+		name: String,
+	},
+
 	/// The requested config already exists
 	#[error("The config for {name} already exists")]
 	CgAlreadyExists {
@@ -982,6 +1093,19 @@ pub enum Error {
 		name: String,
 	},
 
+	/// The table can not be renamed, as its changes are recorded in a change feed
+	#[error("The table '{name}' can not be renamed, as its changes are recorded in a change feed")]
+	TbRenameChangefeed {
+		name: String,
+	},
+
+	/// The table can not be truncated, as its records are used elsewhere
+	#[error("The table '{name}' can not be truncated, as it has {reason}. Use DELETE to remove its records instead")]
+	TbNotTruncatable {
+		name: String,
+		reason: String,
+	},
+
 	/// The requested namespace token already exists
 	#[error("The namespace token '{name}' already exists")]
 	NtAlreadyExists {
@@ -1015,6 +1139,45 @@ pub enum Error {
 		db: String,
 	},
 
+	/// The password does not satisfy the configured password policy
+	#[error("The password does not satisfy the password policy: {0}")]
+	PasswordPolicy(String),
+
+	/// The requested namespace role already exists
+	#[error("The role '{name}' already exists in the namespace '{ns}'")]
+	RoleNsAlreadyExists {
+		name: String,
+		ns: String,
+	},
+
+	/// The requested database role already exists
+	#[error("The role '{name}' already exists in the database '{db}'")]
+	RoleDbAlreadyExists {
+		name: String,
+		ns: String,
+		db: String,
+	},
+
+	/// The role name is reserved for one of the built-in roles
+	#[error("The role '{name}' can not be defined, as it is a built-in role")]
+	RoleBuiltin {
+		name: String,
+	},
+
+	/// The role can not be removed while it is assigned to a user
+	#[error("The role '{name}' can not be removed, as it is assigned to the user '{user}'")]
+	RoleInUse {
+		name: String,
+		user: String,
+	},
+
+	/// The role condition can not be compiled into a policy
+	#[error("The role '{name}' has an invalid WHEN clause: {message}")]
+	RoleInvalidCondition {
+		name: String,
+		message: String,
+	},
+
 	/// A database index entry for the specified table is already building
 	#[error("Database index `{name}` is currently building")]
 	IndexAlreadyBuilding {
@@ -1217,6 +1380,36 @@ pub enum Error {
 	#[error("Encountered an issue while processed export config: found {0}, but expected {1}.")]
 	InvalidExportConfig(Value, String),
 
+	/// The requested import or export format is not supported
+	#[error("The format '{0}' is not supported, expected 'surrealql', 'csv' or 'ndjson'")]
+	InvalidFormat(String),
+
+	/// The column mapping for an import or export could not be parsed
+	#[error("The column mapping '{0}' is invalid, expected 'column' or 'column=field'")]
+	InvalidColumn(String),
+
+	/// The CSV or NDJSON data could not be imported
+	#[error("The import data is invalid: {0}")]
+	InvalidImport(String),
+
+	/// The backup archive could not be read
+	#[error("The backup archive is invalid: {0}")]
+	InvalidBackup(String),
+
+	/// The definitions have changed since the previous backup
+	#[error(
+		"The definitions have changed since the previous backup, so a full backup is required"
+	)]
+	BackupDefinitionsChanged,
+
+	/// A full backup can only be restored into an empty datastore
+	#[error("The datastore is not empty, so a full backup can not be restored into it")]
+	RestoreNotEmpty,
+
+	/// A previous restore into the datastore did not complete
+	#[error("A previous restore into the datastore did not complete, so the datastore may be partially restored")]
+	RestoreIncomplete,
+
 	/// Found an unexpected value in a range
 	#[error("Found {found} for bound but expected {expected}.")]
 	InvalidBound {
@@ -1460,6 +1653,152 @@ impl Serialize for Error {
 	}
 }
 impl Error {
+	/// Check if this error can be caught in a TRY statement
+	pub fn is_catchable(&self) -> bool {
+		!matches!(
+			self,
+			Error::Ignore
+				| Error::Break
+				| Error::Continue
+				| Error::RetryWithId(_)
+				| Error::Return { .. }
+				| Error::QueryTimedout
+				| Error::QueryCancelled
+				| Error::TxFailure
+				| Error::TxFinished
+				| Error::TxRetryable
+		)
+	}
+
+	/// Get the kind of error which is exposed to a CATCH block
+	pub fn kind(&self) -> &'static str {
+		match self {
+			Error::Thrown(_) => "Thrown",
+			Error::RecordExists {
+				..
+			} => "RecordExists",
+			Error::IndexExists {
+				..
+			} => "IndexExists",
+			Error::TableCheck {
+				..
+			}
+			| Error::FieldCheck {
+				..
+			}
+			| Error::FieldValue {
+				..
+			}
+			| Error::SetCheck {
+				..
+			}
+			| Error::FieldReadonly {
+				..
+			}
+			| Error::FieldUndefined {
+				..
+			}
+			| Error::TableIsView {
+				..
+			}
+			| Error::IdInvalid {
+				..
+			}
+			| Error::IdMismatch {
+				..
+			}
+			| Error::InInvalid {
+				..
+			}
+			| Error::InMismatch {
+				..
+			}
+			| Error::InOverride {
+				..
+			}
+			| Error::OutInvalid {
+				..
+			}
+			| Error::OutMismatch {
+				..
+			}
+			| Error::OutOverride {
+				..
+			} => "SchemaCheck",
+			Error::CoerceTo {
+				..
+			}
+			| Error::ConvertTo {
+				..
+			}
+			| Error::LengthInvalid {
+				..
+			}
+			| Error::TryFrom(..) => "Conversion",
+			Error::TryAdd(..)
+			| Error::TrySub(..)
+			| Error::TryMul(..)
+			| Error::TryDiv(..)
+			| Error::TryRem(..)
+			| Error::TryPow(..)
+			| Error::TryNeg(..)
+			| Error::ArithmeticOverflow(..)
+			| Error::ArithmeticNegativeOverflow(..) => "Arithmetic",
+			Error::InvalidArguments {
+				..
+			}
+			| Error::InvalidFunction {
+				..
+			}
+			| Error::InvalidParam {
+				..
+			}
+			| Error::InvalidAggregation {
+				..
+			}
+			| Error::FunctionCheck {
+				..
+			} => "InvalidArguments",
+			Error::TablePermissions {
+				..
+			}
+			| Error::ParamPermissions {
+				..
+			}
+			| Error::FunctionPermissions {
+				..
+			}
+			| Error::FunctionNotAllowed(..)
+			| Error::NetTargetNotAllowed(..)
+			| Error::ScriptingNotAllowed
+			| Error::IamError(..) => "PermissionDenied",
+			Error::DeleteRejectedByReference(..) => "ReferenceRejected",
+			Error::NsNotFound {
+				..
+			}
+			| Error::DbNotFound {
+				..
+			}
+			| Error::TbNotFound {
+				..
+			}
+			| Error::FcNotFound {
+				..
+			}
+			| Error::PaNotFound {
+				..
+			}
+			| Error::SqNotFound {
+				..
+			}
+			| Error::IdNotFound {
+				..
+			}
+			| Error::NoRecordFound => "NotFound",
+			_ => "Internal",
+		}
+	}
+
 	/// Check if this error is related to schema checks
 	pub fn is_schema_related(&self) -> bool {
 		matches!(
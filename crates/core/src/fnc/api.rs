@@ -3,11 +3,15 @@ use reblessive::tree::Stk;
 use std::collections::BTreeMap;
 
 use crate::{
-	api::{body::ApiBody, invocation::ApiInvocation, method::Method},
+	api::{
+		body::ApiBody,
+		invocation::{self, ApiInvocation},
+		method::Method,
+	},
 	ctx::Context,
 	dbs::{capabilities::ExperimentalTarget, Options},
 	err::Error,
-	sql::{statements::FindApi, Object, Value},
+	sql::{statements::FindApi, Closure, Object, Value},
 };
 
 pub async fn invoke(
@@ -72,3 +76,11 @@ pub async fn invoke(
 		Ok(Value::None)
 	}
 }
+
+/// Computes the action of an API once the custom middleware have been called
+pub async fn action(
+	(stk, ctx, opt): (&mut Stk, &Context, &Options),
+	(request, action, config): (Value, Closure, Object),
+) -> Result<Value, Error> {
+	invocation::invoke_action(stk, ctx, opt, request, action, config).await
+}
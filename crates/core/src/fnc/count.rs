@@ -1,6 +1,10 @@
 use crate::err::Error;
+use crate::sql::array::Uniq;
 use crate::sql::value::Value;
 
+/// The internal name of `count(DISTINCT value)`, which can not be called by name
+pub(crate) const DISTINCT_FUNCTION: &str = "count::distinct";
+
 pub fn count((arg,): (Option<Value>,)) -> Result<Value, Error> {
 	Ok(arg
 		.map(|val| match val {
@@ -9,3 +13,10 @@ pub fn count((arg,): (Option<Value>,)) -> Result<Value, Error> {
 		})
 		.unwrap_or_else(|| 1.into()))
 }
+
+pub fn distinct((arg,): (Value,)) -> Result<Value, Error> {
+	Ok(match arg {
+		Value::Array(v) => v.uniq().iter().filter(|v| !v.is_none_or_null()).count().into(),
+		v => (!v.is_none_or_null() as i64).into(),
+	})
+}
@@ -8,6 +8,7 @@ use crate::fnc::util::math::midhinge::Midhinge;
 use crate::fnc::util::math::mode::Mode;
 use crate::fnc::util::math::nearestrank::Nearestrank;
 use crate::fnc::util::math::percentile::Percentile;
+use crate::fnc::util::math::sketch::Sketch;
 use crate::fnc::util::math::spread::Spread;
 use crate::fnc::util::math::top::Top;
 use crate::fnc::util::math::trimean::Trimean;
@@ -158,6 +159,11 @@ pub fn percentile((mut array, n): (Vec<Number>, Number)) -> Result<Value, Error>
 	Ok(array.sorted().percentile(n).into())
 }
 
+/// Computes an approximate percentile from a sketch maintained in a table view
+pub fn sketch_percentile((sketch, n): (Value, Number)) -> Result<Value, Error> {
+	Ok(Sketch::from(&sketch).percentile(n).into())
+}
+
 pub fn pow((arg, pow): (Number, Number)) -> Result<Value, Error> {
 	Ok(arg.try_pow(pow)?.into())
 }
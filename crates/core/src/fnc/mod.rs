@@ -27,6 +27,7 @@ pub mod rand;
 pub mod record;
 pub mod script;
 pub mod search;
+pub mod sequence;
 pub mod session;
 pub mod sleep;
 pub mod string;
@@ -35,6 +36,7 @@ pub mod r#type;
 pub mod util;
 pub mod value;
 pub mod vector;
+pub mod window;
 
 /// Attempts to run any function
 pub async fn run(
@@ -45,6 +47,19 @@ pub async fn run(
 	name: &str,
 	args: Vec<Value>,
 ) -> Result<Value, Error> {
+	// Computes the action of an API endpoint, after any middleware
+	if name.eq(crate::api::invocation::ACTION_FUNCTION) {
+		let args = args::FromArgs::from_args(name, args)?;
+		return stk.run(|stk| api::action((stk, ctx, opt), args)).await;
+	}
+	// Computes `count(DISTINCT value)`
+	if name.eq(count::DISTINCT_FUNCTION) {
+		return count::distinct(args::FromArgs::from_args(name, args)?);
+	}
+	// Computes a percentile maintained incrementally in a table view
+	if name.eq(util::math::sketch::PERCENTILE_FUNCTION) {
+		return math::sketch_percentile(args::FromArgs::from_args(name, args)?);
+	}
 	if name.eq("sleep")
 		|| name.eq("api::invoke")
 		|| name.eq("array::all")
@@ -68,6 +83,7 @@ pub async fn run(
 		|| name.eq("value::patch")
 		|| name.starts_with("http")
 		|| name.starts_with("search")
+		|| name.starts_with("sequence")
 		|| name.starts_with("crypto::argon2")
 		|| name.starts_with("crypto::bcrypt")
 		|| name.starts_with("crypto::pbkdf2")
@@ -453,6 +469,11 @@ pub fn synchronous(
 		"vector::similarity::jaccard" => vector::similarity::jaccard,
 		"vector::similarity::pearson" => vector::similarity::pearson,
 		"vector::similarity::spearman" => vector::similarity::spearman,
+		//
+		"window::lag" => window::lag,
+		"window::lead" => window::lead,
+		"window::rank" => window::rank,
+		"window::row_number" => window::row_number,
 	)
 }
 
@@ -526,6 +547,9 @@ pub async fn asynchronous(
 		"search::highlight" => search::highlight((ctx, doc)).await,
 		"search::offsets" => search::offsets((ctx, doc)).await,
 		//
+		"sequence::currval" => sequence::currval((ctx, opt)).await,
+		"sequence::nextval" => sequence::nextval((ctx, opt)).await,
+		//
 		"sleep" => sleep::sleep(ctx).await,
 		//
 		"type::field" => r#type::field((stk, ctx, Some(opt), doc)).await,
@@ -0,0 +1,34 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::kvs::Sequences;
+use crate::sql::value::Value;
+
+/// Returns the next value of a sequence
+pub async fn nextval((ctx, opt): (&Context, &Options), (name,): (String,)) -> Result<Value, Error> {
+	let (ns, db) = opt.ns_db()?;
+	let txn = ctx.tx();
+	let sq = txn.get_db_sequence(ns, db, &name).await?;
+	// Values of a gap-free sequence are taken within this transaction
+	if sq.batch == 1 {
+		let val = Sequences::next_val_in(&txn, ns, db, &sq).await?;
+		txn.sequence_values().set(ns, db, &sq.name, val);
+		return Ok(val.into());
+	}
+	match ctx.get_sequences() {
+		Some(sequences) => {
+			let val = sequences.next_val(ns, db, &sq).await?;
+			txn.sequence_values().set(ns, db, &sq.name, val);
+			Ok(val.into())
+		}
+		None => Err(fail!("The sequence value allocator is not available")),
+	}
+}
+
+/// Returns the last value of a sequence returned by `sequence::nextval` in the current transaction
+pub async fn currval((ctx, opt): (&Context, &Options), (name,): (String,)) -> Result<Value, Error> {
+	let (ns, db) = opt.ns_db()?;
+	let txn = ctx.tx();
+	let sq = txn.get_db_sequence(ns, db, &name).await?;
+	Ok(txn.sequence_values().get(ns, db, &sq.name).into())
+}
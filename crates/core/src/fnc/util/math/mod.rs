@@ -11,6 +11,7 @@ pub mod midhinge;
 pub mod mode;
 pub mod nearestrank;
 pub mod percentile;
+pub mod sketch;
 pub mod spread;
 pub mod top;
 pub mod trimean;
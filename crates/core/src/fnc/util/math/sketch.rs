@@ -0,0 +1,203 @@
+use crate::sql::number::Number;
+use crate::sql::object::Object;
+use crate::sql::part::Part;
+use crate::sql::value::Value;
+use std::collections::BTreeMap;
+
+/// The relative accuracy of the values returned from a sketch
+const ACCURACY: f64 = 0.01;
+
+/// The internal name of the function which computes a percentile from a sketch
+pub(crate) const PERCENTILE_FUNCTION: &str = "math::percentile::sketch";
+
+/// A mergeable sketch for approximating percentiles.
+///
+/// Values are counted in logarithmically sized buckets, so
+/// that any value returned from the sketch is within 1% of
+/// the exact value. Values can be removed from the sketch
+/// as well as added, and two sketches can be merged by
+/// adding the bucket counts together.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Sketch {
+	/// The number of values in each negative bucket
+	neg: BTreeMap<i32, u64>,
+	/// The number of values which are zero
+	zero: u64,
+	/// The number of values in each positive bucket
+	pos: BTreeMap<i32, u64>,
+}
+
+impl Sketch {
+	fn gamma() -> f64 {
+		(1.0 + ACCURACY) / (1.0 - ACCURACY)
+	}
+	/// Get the bucket which a non-zero value is counted in
+	fn bucket(val: f64) -> i32 {
+		(val.abs().ln() / Self::gamma().ln()).ceil() as i32
+	}
+	/// Get the value which represents all values in a bucket
+	fn value(bucket: i32) -> f64 {
+		2.0 * Self::gamma().powi(bucket) / (Self::gamma() + 1.0)
+	}
+	/// Get the path of the count for a value, within a sketch stored as an object
+	pub fn path(val: f64) -> Vec<Part> {
+		if val > 0.0 {
+			vec![Part::from("p"), Part::from(Self::bucket(val).to_string())]
+		} else if val < 0.0 {
+			vec![Part::from("n"), Part::from(Self::bucket(val).to_string())]
+		} else {
+			vec![Part::from("z")]
+		}
+	}
+	/// Get the total number of values in the sketch
+	pub fn count(&self) -> u64 {
+		self.neg.values().sum::<u64>() + self.zero + self.pos.values().sum::<u64>()
+	}
+	/// Add a value to the sketch
+	pub fn add(&mut self, val: f64) {
+		if val > 0.0 {
+			*self.pos.entry(Self::bucket(val)).or_default() += 1;
+		} else if val < 0.0 {
+			*self.neg.entry(Self::bucket(val)).or_default() += 1;
+		} else {
+			self.zero += 1;
+		}
+	}
+	/// Remove a previously added value from the sketch
+	pub fn sub(&mut self, val: f64) {
+		fn remove(map: &mut BTreeMap<i32, u64>, bucket: i32) {
+			if let Some(c) = map.get_mut(&bucket) {
+				*c = c.saturating_sub(1);
+				if *c == 0 {
+					map.remove(&bucket);
+				}
+			}
+		}
+		if val > 0.0 {
+			remove(&mut self.pos, Self::bucket(val));
+		} else if val < 0.0 {
+			remove(&mut self.neg, Self::bucket(val));
+		} else {
+			self.zero = self.zero.saturating_sub(1);
+		}
+	}
+	/// Get the value at the specified rank, in ascending order
+	fn rank(&self, mut rank: u64) -> f64 {
+		for (b, c) in self.neg.iter().rev() {
+			if rank < *c {
+				return -Self::value(*b);
+			}
+			rank -= c;
+		}
+		if rank < self.zero {
+			return 0.0;
+		}
+		rank -= self.zero;
+		for (b, c) in self.pos.iter() {
+			if rank < *c {
+				return Self::value(*b);
+			}
+			rank -= c;
+		}
+		f64::NAN
+	}
+	/// Gets the approximate N percentile, averaging neighboring values if non-exact
+	pub fn percentile(&self, perc: Number) -> f64 {
+		// If an empty set, then return NaN
+		let count = self.count();
+		if count == 0 {
+			return f64::NAN;
+		}
+		// If an invalid percentile, then return NaN
+		let perc = perc.to_float();
+		if !(0.0..=100.0).contains(&perc) {
+			return f64::NAN;
+		}
+		// Get the index of the specified percentile
+		let fract_index = (count - 1) as f64 * perc * (1.0 / 100.0);
+		let floor = self.rank(fract_index.floor() as u64);
+		let fract = fract_index.fract();
+
+		if fract.abs() <= f64::EPSILON {
+			floor
+		} else {
+			let ceil = self.rank(fract_index.ceil() as u64);
+			floor + (ceil - floor) * fract
+		}
+	}
+}
+
+impl From<&Value> for Sketch {
+	fn from(v: &Value) -> Self {
+		fn buckets(v: Value) -> BTreeMap<i32, u64> {
+			match v {
+				Value::Object(v) => v
+					.iter()
+					.filter_map(|(b, c)| match (b.parse(), c) {
+						(Ok(b), Value::Number(c)) => Some((b, c.to_int() as u64)),
+						_ => None,
+					})
+					.collect(),
+				_ => BTreeMap::new(),
+			}
+		}
+		Self {
+			neg: buckets(v.pick(&["n".into()])),
+			zero: match v.pick(&["z".into()]) {
+				Value::Number(c) => c.to_int() as u64,
+				_ => 0,
+			},
+			pos: buckets(v.pick(&["p".into()])),
+		}
+	}
+}
+
+impl From<Sketch> for Value {
+	fn from(v: Sketch) -> Self {
+		fn buckets(v: BTreeMap<i32, u64>) -> Value {
+			Value::Object(Object(
+				v.into_iter().map(|(b, c)| (b.to_string(), Value::from(c as i64))).collect(),
+			))
+		}
+		Value::from(map! {
+			"n".to_string() => buckets(v.neg),
+			"z".to_string() => Value::from(v.zero as i64),
+			"p".to_string() => buckets(v.pos),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn sketch_percentile() {
+		let mut sketch = Sketch::default();
+		for v in 1..=1000 {
+			sketch.add(v as f64);
+		}
+		let p = sketch.percentile(Number::from(50));
+		assert!((p - 500.5).abs() / 500.5 < ACCURACY);
+		let p = sketch.percentile(Number::from(99));
+		assert!((p - 990.01).abs() / 990.01 < ACCURACY);
+	}
+
+	#[test]
+	fn sketch_sub() {
+		let mut a = Sketch::default();
+		let mut b = Sketch::default();
+		for v in -50..=50 {
+			a.add(v as f64);
+			a.add(v as f64);
+			b.add(v as f64);
+		}
+		assert_eq!(a.count(), 202);
+		for v in -50..=50 {
+			a.sub(v as f64);
+		}
+		assert_eq!(a, b);
+		assert_eq!(Sketch::from(&Value::from(b.clone())), b);
+		assert_eq!(b.percentile(Number::from(50)), 0.0);
+	}
+}
@@ -0,0 +1,32 @@
+use crate::err::Error;
+use crate::sql::Value;
+
+/// Window functions are computed once all of the selected rows are known,
+/// so they can not be called outside of the `OVER` clause of a field.
+fn outside_window(name: &str) -> Result<Value, Error> {
+	Err(Error::InvalidFunction {
+		name: name.to_owned(),
+		message: "This function can only be used with an OVER clause in a SELECT statement"
+			.to_owned(),
+	})
+}
+
+/// Returns the value of a previous row in the window.
+pub fn lag(_: Vec<Value>) -> Result<Value, Error> {
+	outside_window("window::lag")
+}
+
+/// Returns the value of a following row in the window.
+pub fn lead(_: Vec<Value>) -> Result<Value, Error> {
+	outside_window("window::lead")
+}
+
+/// Returns the rank of the row in the window, with gaps for equal rows.
+pub fn rank(_: Vec<Value>) -> Result<Value, Error> {
+	outside_window("window::rank")
+}
+
+/// Returns the number of the row in the window, starting at 1.
+pub fn row_number(_: Vec<Value>) -> Result<Value, Error> {
+	outside_window("window::row_number")
+}
@@ -7,7 +7,7 @@ use async_graphql::dynamic::Schema;
 use crate::dbs::Session;
 use crate::kvs::Datastore;
 
-use super::{error::GqlError, schema::generate_schema};
+use super::{error::GqlError, schema::generate_schema, subscriptions::Subscriptions};
 
 pub trait Invalidator: Debug + Clone + Send + Sync + 'static {
 	type MetaData: Debug + Clone + Send + Sync + Hash;
@@ -17,6 +17,7 @@ pub trait Invalidator: Debug + Clone + Send + Sync + 'static {
 	fn generate(
 		datastore: &Arc<Datastore>,
 		session: &Session,
+		subscriptions: &Subscriptions,
 	) -> impl std::future::Future<Output = Result<(Schema, Self::MetaData), GqlError>> + std::marker::Send;
 }
 
@@ -32,8 +33,9 @@ impl Invalidator for Pessimistic {
 	async fn generate(
 		datastore: &Arc<Datastore>,
 		session: &Session,
+		subscriptions: &Subscriptions,
 	) -> Result<(Schema, Self::MetaData), GqlError> {
-		let schema = generate_schema(datastore, session).await?;
+		let schema = generate_schema(datastore, session, subscriptions).await?;
 		Ok((schema, ()))
 	}
 }
@@ -50,8 +52,9 @@ impl Invalidator for Optimistic {
 	async fn generate(
 		datastore: &Arc<Datastore>,
 		session: &Session,
+		subscriptions: &Subscriptions,
 	) -> Result<(Schema, Self::MetaData), GqlError> {
-		let schema = generate_schema(datastore, session).await?;
+		let schema = generate_schema(datastore, session, subscriptions).await?;
 		Ok((schema, ()))
 	}
 }
@@ -61,6 +64,7 @@ pub struct SchemaCache<I: Invalidator = Pessimistic> {
 	#[allow(clippy::type_complexity)]
 	inner: Arc<RwLock<BTreeMap<(String, String), (Schema, I::MetaData)>>>,
 	pub datastore: Arc<Datastore>,
+	pub subscriptions: Subscriptions,
 	_invalidator: PhantomData<I>,
 }
 
@@ -75,9 +79,14 @@ impl<I: Invalidator + Debug> Debug for SchemaCache<I> {
 
 impl<I: Invalidator> SchemaCache<I> {
 	pub fn new(datastore: Arc<Datastore>) -> Self {
+		Self::with_subscriptions(datastore, Subscriptions::default())
+	}
+	/// Creates a schema cache whose subscriptions receive notifications from the specified router
+	pub fn with_subscriptions(datastore: Arc<Datastore>, subscriptions: Subscriptions) -> Self {
 		SchemaCache {
 			inner: Default::default(),
 			datastore,
+			subscriptions,
 			_invalidator: PhantomData,
 		}
 	}
@@ -93,7 +102,7 @@ impl<I: Invalidator> SchemaCache<I> {
 			}
 		};
 
-		let (schema, meta) = I::generate(&self.datastore, session).await?;
+		let (schema, meta) = I::generate(&self.datastore, session, &self.subscriptions).await?;
 
 		{
 			let mut guard = self.inner.write().await;
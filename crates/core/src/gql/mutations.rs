@@ -0,0 +1,258 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::dbs::Session;
+use crate::gql::schema::{gql_to_sql_kind, kind_to_input_type};
+use crate::gql::utils::{field_val_erase_owned, GQLTx, GqlValueUtils};
+use crate::kvs::{Datastore, Transaction};
+use crate::sql::statements::{
+	CreateStatement, DefineFieldStatement, DefineTableStatement, DeleteStatement, UpdateStatement,
+	UpsertStatement,
+};
+use crate::sql::{self, Data, Fields, Idiom, Kind, Output, Part, Query, Table, TableType, Thing};
+use crate::sql::{Value as SqlValue, Values};
+use async_graphql::dynamic::indexmap::IndexMap;
+use async_graphql::dynamic::{Field, FieldFuture, InputObject, InputValue, Object, Type, TypeRef};
+use async_graphql::{Name, Value as GqlValue};
+
+use super::error::{internal_error, resolver_error, GqlError};
+
+/// The kinds of mutation which are generated for each table
+#[derive(Clone, Copy)]
+enum Mutation {
+	Create,
+	Update,
+	Upsert,
+	Delete,
+}
+
+impl Mutation {
+	/// The name of the generated mutation field for a table
+	fn name(&self, tb_name: &str) -> String {
+		let op = match self {
+			Mutation::Create => "create",
+			Mutation::Update => "update",
+			Mutation::Upsert => "upsert",
+			Mutation::Delete => "delete",
+		};
+		let mut chars = tb_name.chars();
+		match chars.next() {
+			Some(c) => format!("{op}{}{}", c.to_uppercase(), chars.as_str()),
+			None => op.to_string(),
+		}
+	}
+}
+
+fn input_name_from_table(tb_name: &str) -> String {
+	format!("_input_{tb_name}")
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn process_tb_mutations(
+	tbs: Arc<[DefineTableStatement]>,
+	mut mutation: Object,
+	types: &mut Vec<Type>,
+	tx: &Transaction,
+	ns: &str,
+	db: &str,
+	session: &Session,
+	datastore: &Arc<Datastore>,
+) -> Result<Object, GqlError> {
+	for tb in tbs.iter() {
+		// Views can not be written to
+		if tb.view.is_some() {
+			continue;
+		}
+		trace!("Adding mutations for table: {}", tb.name);
+		let tb_name = tb.name.to_string();
+		let fds = tx.all_tb_fields(ns, db, &tb.name.0, None).await?;
+		// Only top-level fields can be set directly
+		let fds: Arc<[DefineFieldStatement]> = fds
+			.iter()
+			.filter(|fd| matches!(fd.name.0.as_slice(), [Part::Field(f)] if f.as_str() != "id"))
+			.filter(|fd| !matches!(fd.kind, None | Some(Kind::References(_, _))))
+			.cloned()
+			.collect();
+		// Generate the input type from the field kinds
+		let data_ty = if fds.is_empty() {
+			TypeRef::named("object")
+		} else {
+			let input_name = input_name_from_table(&tb_name);
+			let mut input = InputObject::new(&input_name).description(format!(
+				"Generated from `{}` the fields which can be written by a mutation",
+				tb.name
+			));
+			for fd in fds.iter() {
+				let Some(ref kind) = fd.kind else {
+					continue;
+				};
+				let ty = kind_to_input_type(kind.clone(), types)?;
+				input = input.field(InputValue::new(fd.name.to_string(), ty));
+			}
+			types.push(Type::InputObject(input));
+			TypeRef::named(input_name)
+		};
+		// Relations can only be created with a RELATE statement
+		let ops: &[Mutation] = match tb.kind {
+			TableType::Relation(_) => &[Mutation::Update, Mutation::Delete],
+			_ => &[Mutation::Create, Mutation::Update, Mutation::Upsert, Mutation::Delete],
+		};
+		for op in ops.iter().copied() {
+			let field = make_mutation_field(op, &tb_name, fds.clone(), session, datastore);
+			let field = match op {
+				Mutation::Create => field
+					.description(format!(
+						"Generated from table `{}`\nallows creating a record",
+						tb.name
+					))
+					.argument(InputValue::new("id", TypeRef::named(TypeRef::ID)))
+					.argument(InputValue::new("data", TypeRef::NonNull(Box::new(data_ty.clone())))),
+				Mutation::Update => field
+					.description(format!(
+						"Generated from table `{}`\nallows updating an existing record",
+						tb.name
+					))
+					.argument(InputValue::new("id", TypeRef::named_nn(TypeRef::ID)))
+					.argument(InputValue::new("data", TypeRef::NonNull(Box::new(data_ty.clone())))),
+				Mutation::Upsert => field
+					.description(format!(
+						"Generated from table `{}`\nallows updating or creating a record",
+						tb.name
+					))
+					.argument(InputValue::new("id", TypeRef::named_nn(TypeRef::ID)))
+					.argument(InputValue::new("data", TypeRef::NonNull(Box::new(data_ty.clone())))),
+				Mutation::Delete => field
+					.description(format!(
+						"Generated from table `{}`\nallows deleting a record",
+						tb.name
+					))
+					.argument(InputValue::new("id", TypeRef::named_nn(TypeRef::ID))),
+			};
+			mutation = mutation.field(field);
+		}
+	}
+
+	Ok(mutation)
+}
+
+fn make_mutation_field(
+	op: Mutation,
+	tb_name: &str,
+	fds: Arc<[DefineFieldStatement]>,
+	session: &Session,
+	datastore: &Arc<Datastore>,
+) -> Field {
+	let ty = match op {
+		Mutation::Delete => TypeRef::named_nn(TypeRef::BOOLEAN),
+		_ => TypeRef::named(tb_name),
+	};
+	let name = op.name(tb_name);
+	let tb_name = tb_name.to_string();
+	let sess1 = session.to_owned();
+	let kvs1 = datastore.clone();
+	Field::new(name, ty, move |ctx| {
+		let tb_name = tb_name.clone();
+		let fds = fds.clone();
+		let sess1 = sess1.clone();
+		let kvs1 = kvs1.clone();
+		FieldFuture::new(async move {
+			let args = ctx.args.as_index_map();
+			trace!("received mutation with args: {args:?}");
+			// Parse the record id, or use the table
+			let what = match args.get("id").and_then(GqlValueUtils::as_string) {
+				Some(id) => SqlValue::Thing(thing_from_id(&tb_name, id)?),
+				None => SqlValue::Table(Table::from(tb_name.clone())),
+			};
+			// Parse the record data
+			let data = match args.get("data") {
+				Some(GqlValue::Object(o)) if fds.is_empty() => {
+					gql_to_sql_kind(&GqlValue::Object(o.clone()), Kind::Object)?
+				}
+				Some(GqlValue::Object(o)) => data_from_input(o, &fds)?,
+				Some(v) => return Err(resolver_error(format!("Invalid mutation data: {v}")).into()),
+				None => SqlValue::None,
+			};
+			// Only return the id of the modified record
+			let output = Some(Output::Fields(Fields(
+				vec![sql::Field::Single {
+					expr: SqlValue::Idiom(Idiom::from("id")),
+					alias: None,
+				}],
+				// this means the `value` keyword
+				true,
+			)));
+			let what = Values(vec![what]);
+			let ast: Query = match op {
+				Mutation::Create => CreateStatement {
+					what,
+					data: Some(Data::ContentExpression(data)),
+					output,
+					..Default::default()
+				}
+				.into(),
+				Mutation::Update => UpdateStatement {
+					what,
+					data: Some(Data::MergeExpression(data)),
+					output,
+					..Default::default()
+				}
+				.into(),
+				Mutation::Upsert => UpsertStatement {
+					what,
+					data: Some(Data::MergeExpression(data)),
+					output,
+					..Default::default()
+				}
+				.into(),
+				Mutation::Delete => DeleteStatement {
+					what,
+					output: Some(Output::Before),
+					..Default::default()
+				}
+				.into(),
+			};
+			trace!("generated mutation ast: {ast:?}");
+			// Run the mutation in its own transaction
+			let mut res = kvs1.process(ast, &sess1, None).await.map_err(GqlError::from)?;
+			let res = match res.pop() {
+				Some(res) => res.result.map_err(GqlError::from)?.first(),
+				None => return Err(internal_error("expected a mutation result").into()),
+			};
+			match (op, res) {
+				(Mutation::Delete, res) => Ok(Some(GqlValue::Boolean(!res.is_none()).into())),
+				(_, SqlValue::Thing(t)) => {
+					// Resolve the record fields after the mutation has been committed
+					let gtx = GQLTx::new(&kvs1, &sess1).await?;
+					Ok(Some(field_val_erase_owned((gtx, t))))
+				}
+				_ => Ok(None),
+			}
+		})
+	})
+}
+
+fn thing_from_id(tb_name: &str, id: String) -> Result<Thing, GqlError> {
+	match Thing::try_from(id.clone()) {
+		Ok(t) if t.tb == tb_name => Ok(t),
+		Ok(t) => Err(resolver_error(format!("Record `{t}` is not in table `{tb_name}`"))),
+		// Parse the id on its own, so that numeric ids are not treated as strings
+		Err(_) => match Thing::try_from(format!("{tb_name}:{id}")) {
+			Ok(t) if t.tb == tb_name => Ok(t),
+			_ => Ok(Thing::from((tb_name.to_string(), id))),
+		},
+	}
+}
+
+fn data_from_input(
+	input: &IndexMap<Name, GqlValue>,
+	fds: &[DefineFieldStatement],
+) -> Result<SqlValue, GqlError> {
+	let mut out = BTreeMap::new();
+	for (k, v) in input.iter() {
+		let Some(fd) = fds.iter().find(|fd| fd.name.to_string() == k.as_str()) else {
+			return Err(resolver_error(format!("Field `{k}` not found")));
+		};
+		out.insert(k.to_string(), gql_to_sql_kind(v, fd.kind.clone().unwrap_or_default())?);
+	}
+	Ok(SqlValue::Object(out.into()))
+}
@@ -3,6 +3,8 @@ use std::sync::Arc;
 
 use crate::dbs::Session;
 use crate::gql::functions::process_fns;
+use crate::gql::mutations::process_tb_mutations;
+use crate::gql::subscriptions::{process_tb_subscriptions, Subscriptions};
 use crate::gql::tables::process_tbs;
 use crate::kvs::Datastore;
 use crate::sql;
@@ -14,6 +16,7 @@ use async_graphql::dynamic::Interface;
 use async_graphql::dynamic::InterfaceField;
 use async_graphql::dynamic::Object;
 use async_graphql::dynamic::Schema;
+use async_graphql::dynamic::Subscription;
 use async_graphql::dynamic::{Enum, Type, Union};
 use async_graphql::dynamic::{Scalar, TypeRef};
 use async_graphql::Name;
@@ -34,6 +37,7 @@ use crate::sql::Value as SqlValue;
 pub async fn generate_schema(
 	datastore: &Arc<Datastore>,
 	session: &Session,
+	subscriptions: &Subscriptions,
 ) -> Result<Schema, GqlError> {
 	let kvs = datastore;
 	let tx = kvs.transaction(TransactionType::Read, LockType::Optimistic).await?;
@@ -89,13 +93,46 @@ pub async fn generate_schema(
 	}
 
 	let mut query = Object::new("Query");
+	let mut mutation = None;
+	let mut subscription = None;
 	let mut types: Vec<Type> = Vec::new();
 
 	trace!(ns, db, ?tbs, ?fns, "generating schema");
 
 	match tbs {
 		Some(tbs) if !tbs.is_empty() => {
-			query = process_tbs(tbs, query, &mut types, &tx, ns, db, session, datastore).await?;
+			query = process_tbs(tbs.clone(), query, &mut types, &tx, ns, db, session, datastore)
+				.await?;
+			// Views are read-only, so only add mutations if there are other tables
+			if tbs.iter().any(|tb| tb.view.is_none()) {
+				let obj = Object::new("Mutation");
+				let obj = process_tb_mutations(
+					tbs.clone(),
+					obj,
+					&mut types,
+					&tx,
+					ns,
+					db,
+					session,
+					datastore,
+				)
+				.await?;
+				mutation = Some(obj);
+			}
+			let obj = Subscription::new("Subscription");
+			let obj = process_tb_subscriptions(
+				tbs,
+				obj,
+				&mut types,
+				&tx,
+				ns,
+				db,
+				session,
+				datastore,
+				subscriptions,
+			)
+			.await?;
+			subscription = Some(obj);
 		}
 		_ => {}
 	}
@@ -106,7 +143,18 @@ pub async fn generate_schema(
 
 	trace!("current Query object for schema: {:?}", query);
 
-	let mut schema = Schema::build("Query", None, None).register(query);
+	let mut schema = Schema::build(
+		"Query",
+		mutation.as_ref().map(|m| m.type_name()),
+		subscription.as_ref().map(|s| s.type_name()),
+	)
+	.register(query);
+	if let Some(mutation) = mutation {
+		schema = schema.register(mutation);
+	}
+	if let Some(subscription) = subscription {
+		schema = schema.register(subscription);
+	}
 	for ty in types {
 		trace!("adding type: {ty:?}");
 		schema = schema.register(ty);
@@ -334,6 +382,21 @@ pub fn kind_to_type(kind: Kind, types: &mut Vec<Type>) -> Result<TypeRef, GqlErr
 	Ok(out)
 }
 
+/// Converts a field kind into a nullable type which can be used in input objects
+pub fn kind_to_input_type(kind: Kind, types: &mut Vec<Type>) -> Result<TypeRef, GqlError> {
+	let out = match kind {
+		Kind::Option(k) => kind_to_input_type(*k, types)?,
+		Kind::Record(_) => TypeRef::named(TypeRef::ID),
+		Kind::Array(k, _) => TypeRef::List(Box::new(kind_to_input_type(*k, types)?)),
+		// Unions can not be used as inputs, but enums can
+		Kind::Either(ks) if !ks.iter().all(|k| matches!(k, Kind::Literal(Literal::String(_)))) => {
+			TypeRef::named("any")
+		}
+		k => unwrap_type(kind_to_type(k, types)?),
+	};
+	Ok(out)
+}
+
 pub fn unwrap_type(ty: TypeRef) -> TypeRef {
 	match ty {
 		TypeRef::NonNull(t) => unwrap_type(*t),
@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::dbs::{Action, Notification, Session};
+use crate::gql::tables::{cond_from_filter, filter_name_from_table};
+use crate::gql::utils::{field_val_erase_owned, GQLTx};
+use crate::kvs::{Datastore, Transaction};
+use crate::sql::statements::{DefineTableStatement, LiveStatement};
+use crate::sql::{Fields, Table, Thing, Value as SqlValue};
+use async_channel::{Receiver, Sender};
+use async_graphql::dynamic::{
+	Enum, Field, FieldFuture, FieldValue, InputValue, Object, Subscription, SubscriptionField,
+	SubscriptionFieldFuture, Type, TypeRef,
+};
+use async_graphql::{Name, Value as GqlValue};
+use futures::stream;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::error::{internal_error, resolver_error, GqlError};
+
+/// Routes live query notifications to the GraphQL subscriptions which started them
+#[derive(Clone, Default)]
+pub struct Subscriptions {
+	inner: Arc<RwLock<HashMap<Uuid, Sender<Notification>>>>,
+}
+
+impl Subscriptions {
+	/// Delivers a notification to the subscription which started its live query,
+	/// returning `false` if the notification does not belong to any subscription
+	pub async fn deliver(&self, notification: Notification) -> bool {
+		let sender = self.inner.read().await.get(&notification.id.0).cloned();
+		match sender {
+			Some(sender) => sender.send(notification).await.is_ok(),
+			None => false,
+		}
+	}
+
+	async fn register(&self, id: Uuid) -> Receiver<Notification> {
+		let (send, recv) = async_channel::unbounded();
+		self.inner.write().await.insert(id, send);
+		recv
+	}
+}
+
+/// Kills the live query of a subscription once its stream is dropped
+struct LiveGuard {
+	id: Uuid,
+	datastore: Arc<Datastore>,
+	subscriptions: Subscriptions,
+}
+
+impl Drop for LiveGuard {
+	fn drop(&mut self) {
+		let id = self.id;
+		let kvs = self.datastore.clone();
+		let subs = self.subscriptions.clone();
+		tokio::spawn(async move {
+			subs.inner.write().await.remove(&id);
+			if let Err(e) = kvs.delete_queries(vec![id]).await {
+				error!("Error killing GraphQL subscription {id}: {e}");
+			}
+		});
+	}
+}
+
+/// A single change to a record, delivered to a subscription
+type LiveEvent = (Action, Thing, Option<GQLTx>);
+
+fn live_name_from_table(tb_name: &str) -> String {
+	format!("_live_{tb_name}")
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn process_tb_subscriptions(
+	tbs: Arc<[DefineTableStatement]>,
+	mut subscription: Subscription,
+	types: &mut Vec<Type>,
+	tx: &Transaction,
+	ns: &str,
+	db: &str,
+	session: &Session,
+	datastore: &Arc<Datastore>,
+	subscriptions: &Subscriptions,
+) -> Result<Subscription, GqlError> {
+	types.push(Type::Enum(
+		Enum::new("_live_action")
+			.description("The action which caused a subscription event")
+			.items(["CREATE", "UPDATE", "DELETE"]),
+	));
+
+	for tb in tbs.iter() {
+		trace!("Adding subscription for table: {}", tb.name);
+		let tb_name = tb.name.to_string();
+		let live_name = live_name_from_table(&tb_name);
+		let fds = tx.all_tb_fields(ns, db, &tb.name.0, None).await?;
+
+		let live_ty_obj = Object::new(&live_name)
+			.description(format!("Generated from `{}` a change to a record", tb.name))
+			.field(Field::new("action", TypeRef::named_nn("_live_action"), |ctx| {
+				FieldFuture::new(async move {
+					let (action, _, _) = downcast_event(&ctx)?;
+					Ok(Some(FieldValue::value(GqlValue::Enum(Name::new(action.to_string())))))
+				})
+			}))
+			.field(Field::new("id", TypeRef::named_nn(TypeRef::ID), |ctx| {
+				FieldFuture::new(async move {
+					let (_, rid, _) = downcast_event(&ctx)?;
+					Ok(Some(FieldValue::value(GqlValue::String(rid.to_string()))))
+				})
+			}))
+			.field(Field::new("record", TypeRef::named(&tb_name), |ctx| {
+				FieldFuture::new(async move {
+					match downcast_event(&ctx)? {
+						(_, rid, Some(gtx)) => {
+							Ok(Some(field_val_erase_owned((gtx.clone(), rid.clone()))))
+						}
+						(_, _, None) => Ok(None),
+					}
+				})
+			}));
+		types.push(Type::Object(live_ty_obj));
+
+		let sess1 = session.to_owned();
+		let kvs1 = datastore.clone();
+		let subs1 = subscriptions.clone();
+		subscription = subscription.field(
+			SubscriptionField::new(tb.name.to_string(), TypeRef::named_nn(&live_name), move |ctx| {
+				let tb_name = tb_name.clone();
+				let fds = fds.clone();
+				let sess1 = sess1.clone();
+				let kvs1 = kvs1.clone();
+				let subs1 = subs1.clone();
+				SubscriptionFieldFuture::new(async move {
+					let args = ctx.args.as_index_map();
+					trace!("received subscription with args: {args:?}");
+					let cond = match args.get("filter") {
+						Some(GqlValue::Object(o)) => Some(cond_from_filter(o, &fds)?),
+						Some(f) => {
+							error!("Found filter {f}, which should be object and should have been rejected by async graphql.");
+							return Err("Value in cond doesn't fit schema".into());
+						}
+						None => None,
+					};
+					// Start a live query for this subscription
+					let mut stm = LiveStatement::new_from_what_expr(
+						Fields::all(),
+						SqlValue::Table(Table::from(tb_name)),
+					);
+					stm.cond = cond;
+					let sess = sess1.clone().with_rt(true);
+					let mut res = kvs1.process(stm.into(), &sess, None).await.map_err(GqlError::from)?;
+					let id = match res.pop().map(|r| r.result) {
+						Some(Ok(SqlValue::Uuid(id))) => id.0,
+						Some(Err(e)) => return Err(GqlError::from(e).into()),
+						_ => return Err(internal_error("expected a live query id").into()),
+					};
+					let recv = subs1.register(id).await;
+					let guard = LiveGuard {
+						id,
+						datastore: kvs1.clone(),
+						subscriptions: subs1,
+					};
+					// Resolve each notification into an event
+					Ok(stream::unfold((recv, guard), move |(recv, guard)| {
+						let kvs1 = kvs1.clone();
+						let sess1 = sess1.clone();
+						async move {
+							let notification = recv.recv().await.ok()?;
+							let event = event_from_notification(&kvs1, &sess1, notification).await;
+							Some((event, (recv, guard)))
+						}
+					}))
+				})
+			})
+			.description(format!("Generated from table `{}`\nallows subscribing to changes in a table with filters", tb.name))
+			.argument(InputValue::new("filter", TypeRef::named(filter_name_from_table(&tb.name)))),
+		);
+	}
+
+	Ok(subscription)
+}
+
+async fn event_from_notification(
+	kvs: &Arc<Datastore>,
+	sess: &Session,
+	notification: Notification,
+) -> async_graphql::Result<FieldValue<'static>> {
+	let SqlValue::Thing(rid) = notification.record else {
+		return Err(
+			internal_error(format!("expected thing, found: {:?}", notification.record)).into()
+		);
+	};
+	// Deleted records can no longer be fetched
+	let gtx = match notification.action {
+		Action::Delete => None,
+		_ => {
+			let gtx = GQLTx::new(kvs, sess).await?;
+			// The record may have been deleted since this notification was sent
+			match gtx.get_record_field(rid.clone(), "id").await? {
+				SqlValue::Thing(_) => Some(gtx),
+				_ => None,
+			}
+		}
+	};
+	let event: LiveEvent = (notification.action, rid, gtx);
+	Ok(FieldValue::owned_any(event))
+}
+
+fn downcast_event<'a>(
+	ctx: &'a async_graphql::dynamic::ResolverContext<'_>,
+) -> Result<&'a LiveEvent, GqlError> {
+	ctx.parent_value
+		.downcast_ref::<LiveEvent>()
+		.ok_or_else(|| resolver_error("failed to downcast subscription event"))
+}
@@ -60,7 +60,7 @@ macro_rules! id_input {
 	};
 }
 
-fn filter_name_from_table(tb_name: impl Display) -> String {
+pub(crate) fn filter_name_from_table(tb_name: impl Display) -> String {
 	format!("_filter_{tb_name}")
 }
 
@@ -493,7 +493,7 @@ fn filter_from_type(
 	Ok(filter)
 }
 
-fn cond_from_filter(
+pub(crate) fn cond_from_filter(
 	filter: &IndexMap<Name, GqlValue>,
 	fds: &[DefineFieldStatement],
 ) -> Result<Cond, GqlError> {
@@ -1,4 +1,5 @@
 use crate::sql::statements::{DefineAccessStatement, DefineUserStatement};
+use cedar_policy::PolicySet;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 
@@ -90,7 +91,28 @@ impl Auth {
 
 	/// Checks if the current auth is allowed to perform an action on a given resource
 	pub fn is_allowed(&self, action: Action, res: &Resource) -> Result<(), Error> {
-		is_allowed(&self.actor, &action, res, None)
+		is_allowed(&self.actor, &action, res, None, None)
+	}
+
+	/// Checks if the current auth is allowed to perform an action on a given resource,
+	/// given the compiled policies of the custom roles which are assigned to the actor
+	pub fn is_allowed_with(
+		&self,
+		action: Action,
+		res: &Resource,
+		policies: &PolicySet,
+	) -> Result<(), Error> {
+		is_allowed(&self.actor, &action, res, Some(policies), None)
+	}
+
+	/// Returns the names of the custom roles assigned to the current actor
+	pub fn custom_roles(&self) -> &[String] {
+		self.actor.custom_roles()
+	}
+
+	/// Checks if the current actor has any custom roles
+	pub fn has_custom_roles(&self) -> bool {
+		self.actor.has_custom_roles()
 	}
 
 	/// Checks if the current actor has a given role
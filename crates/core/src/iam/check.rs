@@ -1,5 +1,12 @@
 use crate::dbs::Session;
 use crate::err::Error;
+use crate::iam::{policies, Actor, Auth, Error as IamError, Level, Role};
+use crate::kvs::{Datastore, LockType::*, Transaction, TransactionType::*};
+use crate::sql::statements::DefineRoleStatement;
+use crate::sql::Ident;
+use cedar_policy::PolicySet;
+use std::str::FromStr;
+use std::sync::Arc;
 
 pub fn check_ns_db(sess: &Session) -> Result<(String, String), Error> {
 	// Ensure that a namespace was specified
@@ -15,3 +22,87 @@ pub fn check_ns_db(sess: &Session) -> Result<(String, String), Error> {
 	// All ok
 	Ok((ns, db))
 }
+
+/// Resolves the roles assigned to a system user into built-in and custom roles.
+///
+/// Custom roles are looked up on the level of the user, and database users
+/// can also be assigned custom roles which are defined on their namespace.
+pub async fn resolve_roles(
+	tx: &Transaction,
+	roles: &[Ident],
+	level: &Level,
+) -> Result<(Vec<Role>, Vec<DefineRoleStatement>), Error> {
+	let mut builtin = Vec::new();
+	let mut custom = Vec::new();
+	for role in roles {
+		// Check if this is one of the built-in roles
+		if let Ok(role) = Role::from_str(role) {
+			builtin.push(role);
+			continue;
+		}
+		// Otherwise look up the custom role definition
+		let def = match level {
+			Level::Namespace(ns) => tx.get_ns_role(ns, role).await,
+			Level::Database(ns, db) => match tx.get_db_role(ns, db, role).await {
+				Err(Error::RoleDbNotFound {
+					..
+				}) => tx.get_ns_role(ns, role).await,
+				res => res,
+			},
+			_ => return Err(Error::IamError(IamError::InvalidRole(role.to_string()))),
+		};
+		// Only a missing definition makes the role invalid
+		match def {
+			Ok(def) => custom.push(def.as_ref().clone()),
+			Err(Error::RoleNsNotFound {
+				..
+			}) => return Err(Error::IamError(IamError::InvalidRole(role.to_string()))),
+			Err(e) => return Err(e),
+		}
+	}
+	Ok((builtin, custom))
+}
+
+/// Creates the authentication for a system user with the given roles.
+pub async fn user_auth(
+	kvs: &Datastore,
+	id: &str,
+	roles: &[Ident],
+	level: Level,
+) -> Result<Auth, Error> {
+	// Only fetch custom roles when the user has any
+	if roles.iter().all(|r| Role::from_str(r).is_ok()) {
+		let roles = roles.iter().map(Role::try_from).collect::<Result<_, _>>()?;
+		return Ok(Auth::new(Actor::new(id.to_string(), roles, level)));
+	}
+	// Create a new readonly transaction
+	let tx = kvs.transaction(Read, Optimistic).await?;
+	// Resolve the built-in and custom roles
+	let res = resolve_roles(&tx, roles, &level).await;
+	// Ensure that the transaction is cancelled
+	tx.cancel().await?;
+	let (roles, custom) = res?;
+	let custom = custom.into_iter().map(|r| r.name.0).collect();
+	Ok(Auth::new(Actor::new(id.to_string(), roles, level).with_custom_roles(custom)))
+}
+
+/// Compiles the custom roles assigned to the authenticated actor into a policy set.
+///
+/// Only the names of the custom roles are stored in a session or token, so the
+/// current role definitions are looked up whenever a transaction is started.
+/// A role which no longer exists no longer grants any permissions.
+pub async fn role_policies(tx: &Transaction, auth: &Auth) -> Result<Option<Arc<PolicySet>>, Error> {
+	// Only fetch custom roles when the actor has any
+	if !auth.has_custom_roles() {
+		return Ok(None);
+	}
+	let mut custom = Vec::new();
+	for role in auth.custom_roles() {
+		match resolve_roles(tx, &[Ident::from(role.as_str())], auth.level()).await {
+			Ok((_, mut def)) => custom.append(&mut def),
+			Err(Error::IamError(IamError::InvalidRole(_))) => continue,
+			Err(e) => return Err(e),
+		}
+	}
+	Ok(Some(Arc::new(policies::role_policies(&custom)?)))
+}
@@ -1,13 +1,18 @@
 use std::str::FromStr;
 
 use cedar_policy::{Entity, EntityId, EntityTypeName, EntityUid};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
 
 use crate::dbs::Statement;
 
 // TODO(sgirones): For now keep it simple. In the future, we will allow for custom roles and policies using a more exhaustive list of actions and resources.
-#[derive(Clone, Debug, Eq, PartialEq, PartialOrd)]
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub enum Action {
+	#[default]
 	View,
 	Edit,
 }
@@ -13,13 +13,15 @@ use crate::sql::statements::{DefineAccessStatement, DefineUserStatement};
 //
 // User
 //
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub struct Actor {
 	res: Resource,
 	roles: Vec<Role>,
+	#[revision(start = 2)]
+	custom: Vec<String>,
 }
 
 impl Default for Actor {
@@ -27,6 +29,7 @@ impl Default for Actor {
 		Self {
 			res: ResourceKind::Actor.on_level(Level::No),
 			roles: Vec::new(),
+			custom: Vec::new(),
 		}
 	}
 }
@@ -43,7 +46,12 @@ impl std::fmt::Display for Actor {
 			self.res.level(),
 			self.res.kind(),
 			self.res.id(),
-			self.roles.iter().map(|r| format!("{}", r)).collect::<Vec<String>>().join(", ")
+			self.roles
+				.iter()
+				.map(|r| r.to_string())
+				.chain(self.custom.iter().cloned())
+				.collect::<Vec<String>>()
+				.join(", ")
 		)
 	}
 }
@@ -53,9 +61,28 @@ impl Actor {
 		Self {
 			res: Resource::new(id, super::ResourceKind::Actor, level),
 			roles,
+			custom: Vec::new(),
 		}
 	}
 
+	/// Assigns custom roles to the actor, in addition to the built-in roles.
+	/// Only the role names are stored, as the role definitions are looked
+	/// up again whenever a transaction checks the permissions of the actor.
+	pub fn with_custom_roles(mut self, custom: Vec<String>) -> Self {
+		self.custom = custom;
+		self
+	}
+
+	/// Returns the names of the custom roles assigned to the actor.
+	pub fn custom_roles(&self) -> &[String] {
+		&self.custom
+	}
+
+	/// Checks if the actor has any custom roles.
+	pub fn has_custom_roles(&self) -> bool {
+		!self.custom.is_empty()
+	}
+
 	/// Checks if the actor has the given role.
 	pub fn has_role(&self, role: Role) -> bool {
 		self.roles.contains(&role)
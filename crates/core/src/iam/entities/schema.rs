@@ -11,6 +11,7 @@ pub static DEFAULT_CEDAR_SCHEMA: LazyLock<serde_json::Value> = LazyLock::new(||
 						"type": "Record",
 						"attributes": {
 							"type": { "type": "String", "required": true },
+							"name": { "type": "String", "required": true },
 							"level" : { "type": "Entity", "name": "Level", "required": true },
 						}
 					},
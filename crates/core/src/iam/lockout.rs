@@ -0,0 +1,367 @@
+//! Tracks failed authentication attempts, so that subjects can be locked out
+use crate::cnf::{
+	AUTH_LOCKOUT_ATTEMPTS, AUTH_LOCKOUT_DURATION, AUTH_LOCKOUT_RECORD_IDENTIFIER,
+	AUTH_LOCKOUT_WINDOW, EXPIRY_BATCH_SIZE,
+};
+use crate::err::Error;
+use crate::key::database::access::lo;
+use crate::kvs::{Datastore, Key, LockType::*, Transaction, TransactionType::*};
+use crate::sql::{Object, Value};
+use chrono::Utc;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The number of times a conflicting write of the lockout state is retried
+const MAX_WRITE_ATTEMPTS: usize = 10;
+
+/// The failed authentication state which is stored for a subject
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Attempts {
+	/// The number of failed attempts within the current window
+	pub failures: u32,
+	/// The start of the current window, in milliseconds since the epoch
+	pub since: i64,
+	/// The end of the lockout, in milliseconds since the epoch
+	pub until: Option<i64>,
+}
+
+/// The lockout policy which is applied to authentication attempts
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Lockout {
+	/// The number of failed attempts after which a subject is locked
+	attempts: u32,
+	/// The window within which failed attempts are counted
+	window: Duration,
+	/// The duration for which a subject remains locked
+	duration: Duration,
+}
+
+impl Default for Lockout {
+	fn default() -> Self {
+		Self::new(
+			*AUTH_LOCKOUT_ATTEMPTS,
+			Duration::from_secs(*AUTH_LOCKOUT_WINDOW),
+			Duration::from_secs(*AUTH_LOCKOUT_DURATION),
+		)
+	}
+}
+
+impl Lockout {
+	pub(crate) fn new(attempts: u32, window: Duration, duration: Duration) -> Self {
+		Self {
+			attempts,
+			window,
+			duration,
+		}
+	}
+
+	/// The lockout policy for subjects signing in with a record access method.
+	///
+	/// Record access attempts are only tracked when the operator has configured
+	/// the signin variable which identifies the subject.
+	pub(crate) fn record() -> Self {
+		match AUTH_LOCKOUT_RECORD_IDENTIFIER.is_empty() {
+			true => Self::new(0, Duration::ZERO, Duration::ZERO),
+			false => Self::default(),
+		}
+	}
+
+	/// Check if failed attempts are tracked at all
+	pub(crate) fn enabled(&self) -> bool {
+		self.attempts > 0
+	}
+
+	/// Records an authentication attempt for the subject, before the credentials are verified.
+	///
+	/// The attempt counts as a failure until it is cleared by a successful authentication,
+	/// so that concurrent attempts are all counted. Returns an error if the subject is locked.
+	pub(crate) async fn attempt(&self, ds: &Datastore, key: &Key) -> Result<(), Error> {
+		// Skip recording if lockout is disabled
+		if !self.enabled() {
+			return Ok(());
+		}
+		let mut attempt = 0;
+		loop {
+			let tx = ds.transaction(Write, Optimistic).await?;
+			let res = run!(tx, self.attempt_in(&tx, key).await);
+			match res {
+				Err(Error::TxRetryable) if attempt < MAX_WRITE_ATTEMPTS => attempt += 1,
+				res => return res,
+			}
+		}
+	}
+
+	/// Clears the failed authentication state after a successful attempt
+	pub(crate) async fn success(&self, ds: &Datastore, key: &Key) {
+		// Skip the write if lockout is disabled
+		if !self.enabled() {
+			return;
+		}
+		// The authentication succeeds regardless
+		if let Err(e) = self.clear(ds, key).await {
+			warn!("Failed to clear failed authentication attempts: {e}");
+		}
+	}
+
+	/// Returns the identifier of a subject signing in with a record access method.
+	///
+	/// The subject is identified by a hash of the configured signin variable, so that
+	/// client-supplied values are bounded in size. If the variable is missing, the
+	/// attempt is attributed to the access method itself.
+	pub(crate) fn subject(vars: &Object) -> String {
+		let id = vars
+			.get(AUTH_LOCKOUT_RECORD_IDENTIFIER.as_str())
+			.map(Value::to_raw_string)
+			.unwrap_or_default();
+		blake3::hash(id.as_bytes()).to_hex().to_string()
+	}
+
+	/// Checks if the failed authentication state no longer has any effect
+	fn stale(&self, state: &Attempts, now: i64) -> bool {
+		match state.until {
+			// The subject is still locked out
+			Some(until) if until > now => false,
+			// A previous lockout has ended
+			Some(_) => true,
+			// The window has elapsed
+			None => now - state.since >= self.window.as_millis() as i64,
+		}
+	}
+
+	async fn clear(&self, ds: &Datastore, key: &Key) -> Result<(), Error> {
+		let mut attempt = 0;
+		loop {
+			let tx = ds.transaction(Write, Optimistic).await?;
+			let res = run!(tx, tx.del(key.clone()).await);
+			match res {
+				Err(Error::TxRetryable) if attempt < MAX_WRITE_ATTEMPTS => attempt += 1,
+				res => return res,
+			}
+		}
+	}
+
+	async fn attempt_in(&self, tx: &Transaction, key: &Key) -> Result<(), Error> {
+		let now = now();
+		// Fetch the current failed authentication state
+		let mut state = match tx.get(key.clone(), None).await? {
+			Some(v) => revision::from_slice::<Attempts>(&v)?,
+			None => Attempts::default(),
+		};
+		// Ensure that the subject is not currently locked out
+		if state.until.is_some_and(|until| until > now) {
+			return Err(Error::AccountLocked);
+		}
+		// Start a new window if the previous one elapsed or a previous lockout ended
+		if self.stale(&state, now) {
+			state = Attempts {
+				failures: 0,
+				since: now,
+				until: None,
+			};
+		}
+		// Lock the subject once the threshold is reached
+		state.failures = state.failures.saturating_add(1);
+		if state.failures >= self.attempts {
+			state.until = Some(now + self.duration.as_millis() as i64);
+		}
+		// Store the updated state
+		tx.set(key.clone(), revision::to_vec(&state)?, None).await
+	}
+}
+
+fn now() -> i64 {
+	Utc::now().timestamp_millis()
+}
+
+impl Datastore {
+	/// Deletes the failed authentication state of record access subjects which no longer
+	/// has any effect, as subjects are chosen by clients and are otherwise never cleared.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub(crate) async fn lockout_cleanup(&self) -> Result<(), Error> {
+		let lockout = Lockout::record();
+		// Create a new transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Find the access methods
+		let accesses = catch!(txn, accesses(&txn).await);
+		// Cancel the transaction
+		txn.cancel().await?;
+		// Delete the stale state of each access method
+		for (ns, db, ac) in accesses {
+			let beg = lo::prefix(&ns, &db, &ac)?;
+			let end = lo::suffix(&ns, &db, &ac)?;
+			let mut next = Some(beg..end);
+			while let Some(rng) = next {
+				// Create a new transaction
+				let txn = self.transaction(Write, Optimistic).await?;
+				// Fetch the next batch of state
+				let batch = catch!(txn, txn.batch_keys_vals(rng, *EXPIRY_BATCH_SIZE, None).await);
+				next = batch.next;
+				// Delete the state which no longer has any effect
+				let now = now();
+				for (k, v) in batch.result {
+					let state =
+						catch!(txn, revision::from_slice::<Attempts>(&v).map_err(Error::from));
+					if lockout.stale(&state, now) {
+						catch!(txn, txn.del(k).await);
+					}
+				}
+				txn.commit().await?;
+				// Pause execution
+				yield_now!();
+			}
+		}
+		// Everything ok
+		Ok(())
+	}
+}
+
+/// Finds the access methods on databases, along with
+/// the namespace and database which they belong to.
+async fn accesses(txn: &Transaction) -> Result<Vec<(String, String, String)>, Error> {
+	let mut accesses = Vec::new();
+	for ns in txn.all_ns().await?.iter() {
+		for db in txn.all_db(&ns.name).await?.iter() {
+			for ac in txn.all_db_accesses(&ns.name, &db.name).await?.iter() {
+				accesses.push((ns.name.to_raw(), db.name.to_raw(), ac.name.to_raw()));
+			}
+		}
+	}
+	Ok(accesses)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::Session;
+	use crate::kvs::KeyEncode;
+
+	fn key() -> Key {
+		crate::key::root::lo::new("test").encode().unwrap()
+	}
+
+	#[tokio::test]
+	async fn test_lockout_disabled() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let lockout = Lockout::new(0, Duration::from_secs(60), Duration::from_secs(60));
+		for _ in 0..10 {
+			lockout.attempt(&ds, &key()).await.unwrap();
+		}
+	}
+
+	#[tokio::test]
+	async fn test_lockout_after_attempts() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let lockout = Lockout::new(3, Duration::from_secs(60), Duration::from_secs(60));
+		// The subject is not locked before the threshold
+		for _ in 0..3 {
+			lockout.attempt(&ds, &key()).await.unwrap();
+		}
+		// The subject is locked once the threshold is reached
+		assert!(matches!(lockout.attempt(&ds, &key()).await, Err(Error::AccountLocked)));
+	}
+
+	#[tokio::test]
+	async fn test_lockout_concurrent_attempts() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let lockout = Lockout::new(3, Duration::from_secs(60), Duration::from_secs(60));
+		// Every concurrent attempt is counted
+		let key = key();
+		let res = futures::future::join_all((0..10).map(|_| lockout.attempt(&ds, &key))).await;
+		assert_eq!(res.iter().filter(|r| r.is_ok()).count(), 3);
+		assert!(matches!(lockout.attempt(&ds, &key).await, Err(Error::AccountLocked)));
+	}
+
+	#[tokio::test]
+	async fn test_lockout_cleared_on_success() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let lockout = Lockout::new(3, Duration::from_secs(60), Duration::from_secs(60));
+		for _ in 0..2 {
+			lockout.attempt(&ds, &key()).await.unwrap();
+		}
+		lockout.success(&ds, &key()).await;
+		// The count starts again after a successful attempt
+		for _ in 0..3 {
+			lockout.attempt(&ds, &key()).await.unwrap();
+		}
+		assert!(lockout.attempt(&ds, &key()).await.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_lockout_expires() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let lockout = Lockout::new(1, Duration::from_secs(60), Duration::ZERO);
+		// The lockout ends immediately
+		for _ in 0..3 {
+			lockout.attempt(&ds, &key()).await.unwrap();
+		}
+	}
+
+	#[tokio::test]
+	async fn test_lockout_window_elapsed() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let lockout = Lockout::new(2, Duration::ZERO, Duration::from_secs(60));
+		// Every attempt starts a new window, so the threshold is never reached
+		for _ in 0..5 {
+			lockout.attempt(&ds, &key()).await.unwrap();
+		}
+	}
+
+	#[tokio::test]
+	async fn test_lockout_cleanup() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		ds.execute("DEFINE ACCESS user ON DATABASE TYPE RECORD", &ses, None).await.unwrap();
+		let set = |id: &str, state: Attempts| {
+			let ds = &ds;
+			let key = lo::new("test", "test", "user", id).encode().unwrap();
+			async move {
+				let tx = ds.transaction(Write, Optimistic).await.unwrap();
+				tx.set(key, revision::to_vec(&state).unwrap(), None).await.unwrap();
+				tx.commit().await.unwrap();
+			}
+		};
+		let count = || async {
+			let tx = ds.transaction(Read, Optimistic).await.unwrap();
+			let beg = lo::prefix("test", "test", "user").unwrap();
+			let end = lo::suffix("test", "test", "user").unwrap();
+			tx.keys(beg..end, 10, None).await.unwrap().len()
+		};
+		// A lapsed window, an ended lockout, and an ongoing lockout
+		set(
+			"lapsed",
+			Attempts {
+				failures: 1,
+				since: 0,
+				until: None,
+			},
+		)
+		.await;
+		set(
+			"ended",
+			Attempts {
+				failures: 3,
+				since: 0,
+				until: Some(0),
+			},
+		)
+		.await;
+		set(
+			"locked",
+			Attempts {
+				failures: 3,
+				since: now(),
+				until: Some(i64::MAX),
+			},
+		)
+		.await;
+		// Only the ongoing lockout is kept
+		ds.lockout_cleanup().await.unwrap();
+		assert_eq!(count().await, 1);
+		// Unlocking the access method clears all subjects
+		ds.execute("ALTER ACCESS user ON DATABASE UNLOCK", &ses, None).await.unwrap();
+		assert_eq!(count().await, 0);
+	}
+}
@@ -1,4 +1,4 @@
-use cedar_policy::Context;
+use cedar_policy::{Context, PolicySet};
 pub use entities::Level;
 use thiserror::Error;
 
@@ -12,6 +12,7 @@ pub(crate) mod file;
 pub mod issue;
 #[cfg(feature = "jwks")]
 pub mod jwks;
+pub(crate) mod lockout;
 pub mod policies;
 pub mod reset;
 pub mod signin;
@@ -28,6 +29,12 @@ pub enum Error {
 	#[error("Invalid role '{0}'")]
 	InvalidRole(String),
 
+	#[error("Invalid policy for role '{role}': {message}")]
+	InvalidPolicy {
+		role: String,
+		message: String,
+	},
+
 	#[error("Not enough permissions to perform this action")]
 	NotAllowed {
 		actor: String,
@@ -46,9 +53,15 @@ pub fn is_allowed(
 	actor: &Actor,
 	action: &Action,
 	resource: &Resource,
+	policy_set: Option<&PolicySet>,
 	ctx: Option<Context>,
 ) -> Result<(), Error> {
-	match policies::is_allowed(actor, action, resource, ctx.unwrap_or(Context::empty())) {
+	let ctx = ctx.unwrap_or(Context::empty());
+	let res = match policy_set {
+		Some(policy_set) => policies::is_allowed_with(actor, action, resource, ctx, policy_set),
+		None => policies::is_allowed(actor, action, resource, ctx),
+	};
+	match res {
 		(allowed, _) if allowed => Ok(()),
 		_ => {
 			let err = Error::NotAllowed {
@@ -1,9 +1,15 @@
-use cedar_policy::{Authorizer, Context, Decision, Entities, Entity, EntityUid, Request, Response};
+use cedar_policy::{
+	Authorizer, Context, Decision, Entities, Entity, EntityUid, PolicySet, Request, Response,
+};
 
 mod policy_set;
+mod role;
 
 use policy_set::*;
 
+pub use role::compile as compile_role;
+pub use role::policies as role_policies;
+
 use crate::iam::{Action, Actor, Resource};
 
 /// Checks if the actor is allowed to do the action on the resource, given the context and based on the default policy set.
@@ -12,6 +18,17 @@ pub fn is_allowed(
 	action: &Action,
 	resource: &Resource,
 	context: Context,
+) -> (bool, Response) {
+	is_allowed_with(actor, action, resource, context, &POLICY_SET)
+}
+
+/// Checks if the actor is allowed to do the action on the resource, given the context and based on the given policy set, such as one compiled from custom roles.
+pub fn is_allowed_with(
+	actor: &Actor,
+	action: &Action,
+	resource: &Resource,
+	context: Context,
+	policy_set: &PolicySet,
 ) -> (bool, Response) {
 	_is_allowed(
 		Some(actor.into()),
@@ -19,6 +36,7 @@ pub fn is_allowed(
 		Some(resource.into()),
 		Entities::from_entities(_get_entities(Some(actor), Some(resource))).unwrap(),
 		context,
+		policy_set,
 	)
 }
 
@@ -39,14 +57,13 @@ fn _is_allowed(
 	resource: Option<EntityUid>,
 	entities: Entities,
 	context: Context,
+	policy_set: &PolicySet,
 ) -> (bool, Response) {
-	let policy_set = POLICY_SET.to_owned();
-
 	let authorizer = Authorizer::new();
 
 	let req = Request::new(actor, action, resource, context);
 
-	let res = authorizer.is_authorized(&req, &policy_set, &entities);
+	let res = authorizer.is_authorized(&req, policy_set, &entities);
 
 	(res.decision() == Decision::Allow, res)
 }
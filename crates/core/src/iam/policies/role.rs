@@ -0,0 +1,261 @@
+use cedar_policy::{Policy, PolicySet};
+use std::fmt::Write;
+
+use super::POLICY_SET;
+use crate::iam::{Error, ResourceKind};
+use crate::sql::statements::DefineRoleStatement;
+use crate::sql::{Expression, Idiom, Operator, Part, Subquery, Value};
+
+/// Compiles the given custom role definitions into a policy set,
+/// which also contains the default policies for the built-in roles.
+pub fn policies(roles: &[DefineRoleStatement]) -> Result<PolicySet, Error> {
+	let mut policies = POLICY_SET.to_owned();
+	for (i, role) in roles.iter().enumerate() {
+		compile(role, format!("role{i}"))
+			.and_then(|policy| policies.add(policy).map_err(|e| e.to_string()))
+			.map_err(|message| Error::InvalidPolicy {
+				role: role.name.to_string(),
+				message,
+			})?;
+	}
+	Ok(policies)
+}
+
+/// Compiles a custom role definition into a Cedar policy.
+///
+/// The policy permits the role's action on resources of the role's
+/// resource kind, on the same level hierarchy as the actor or below.
+/// Any `WHEN` clause is translated into an additional Cedar condition.
+pub fn compile(role: &DefineRoleStatement, id: String) -> Result<Policy, String> {
+	// Resources must be within the actor's level hierarchy
+	let mut conditions = vec!["resource.level in principal.level".to_owned()];
+	// Restrict the policy to a specific resource kind
+	if role.resource != ResourceKind::Any {
+		conditions.push(format!("resource.type == {}", literal(&role.resource.to_string())));
+	}
+	// Translate the custom condition, if specified
+	if let Some(cond) = &role.when {
+		conditions.push(format!("({})", condition(cond)?));
+	}
+	// Build the final policy source
+	let src = format!(
+		"permit(principal, action == Action::{}, resource) when {{ {} }};",
+		literal(&role.action.to_string()),
+		conditions.join(" && ")
+	);
+	Policy::parse(Some(id), src).map_err(|e| e.to_string())
+}
+
+/// Translates a role condition into a Cedar expression
+fn condition(val: &Value) -> Result<String, String> {
+	match val {
+		Value::Bool(v) => Ok(v.to_string()),
+		Value::Strand(v) => Ok(literal(v.as_str())),
+		Value::Array(v) => {
+			let items = v.iter().map(condition).collect::<Result<Vec<_>, _>>()?;
+			Ok(format!("[{}]", items.join(", ")))
+		}
+		Value::Idiom(v) => attribute(v),
+		Value::Subquery(v) => match v.as_ref() {
+			Subquery::Value(v) => Ok(format!("({})", condition(v)?)),
+			_ => Err(format!("unsupported expression `{v}`")),
+		},
+		Value::Expression(v) => match v.as_ref() {
+			Expression::Unary {
+				o: Operator::Not,
+				v,
+			} => Ok(format!("!({})", condition(v)?)),
+			Expression::Binary {
+				l,
+				o,
+				r,
+			} => {
+				let (l, r) = (condition(l)?, condition(r)?);
+				match o {
+					Operator::Equal | Operator::Exact => Ok(format!("{l} == {r}")),
+					Operator::NotEqual => Ok(format!("{l} != {r}")),
+					Operator::And => Ok(format!("{l} && {r}")),
+					Operator::Or => Ok(format!("{l} || {r}")),
+					Operator::Contain => Ok(format!("{l}.contains({r})")),
+					Operator::NotContain => Ok(format!("!{l}.contains({r})")),
+					Operator::Inside => Ok(format!("{r}.contains({l})")),
+					Operator::NotInside => Ok(format!("!{r}.contains({l})")),
+					o => Err(format!("unsupported operator `{o}`")),
+				}
+			}
+			v => Err(format!("unsupported expression `{v}`")),
+		},
+		v => Err(format!("unsupported value `{v}`")),
+	}
+}
+
+/// Translates a `$resource` attribute into a Cedar attribute
+fn attribute(idiom: &Idiom) -> Result<String, String> {
+	match idiom.0.as_slice() {
+		[Part::Start(Value::Param(p)), Part::Field(f)] if p.as_str() == "resource" => {
+			match f.to_lowercase().as_str() {
+				"type" => Ok("resource.type".to_owned()),
+				"name" => Ok("resource.name".to_owned()),
+				"ns" => Ok("resource.level.ns".to_owned()),
+				"db" => Ok("resource.level.db".to_owned()),
+				_ => Err(format!("unknown resource attribute `{f}`")),
+			}
+		}
+		_ => Err(format!(
+			"unsupported value `{idiom}`, expected $resource.type, $resource.name, $resource.ns, or $resource.db"
+		)),
+	}
+}
+
+/// Formats a string as a Cedar string literal.
+///
+/// Quotes and backslashes are escaped, and any other character which
+/// is not printable ASCII is written as a unicode escape, which Cedar
+/// accepts in the same way for every character.
+fn literal(v: &str) -> String {
+	let mut out = String::with_capacity(v.len() + 2);
+	out.push('"');
+	for c in v.chars() {
+		match c {
+			'"' => out.push_str("\\\""),
+			'\\' => out.push_str("\\\\"),
+			' '..='~' => out.push(c),
+			c => {
+				let _ = write!(out, "\\u{{{:x}}}", c as u32);
+			}
+		}
+	}
+	out.push('"');
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::Session;
+	use crate::iam::check::user_auth;
+	use crate::iam::{policies::is_allowed_with, Action, Actor, Level, Resource, Role};
+	use crate::kvs::Datastore;
+	use crate::syn;
+	use cedar_policy::Context;
+
+	fn role(sql: &str) -> DefineRoleStatement {
+		match syn::parse(sql).unwrap().0 .0.pop().unwrap() {
+			crate::sql::Statement::Define(crate::sql::statements::DefineStatement::Role(v)) => v,
+			_ => panic!("not a role definition"),
+		}
+	}
+
+	#[test]
+	fn compile_role() {
+		let ro = role("DEFINE ROLE reader ON NAMESPACE PERMIT VIEW ON ANY");
+		compile(&ro, "test".into()).unwrap();
+		let ro = role("DEFINE ROLE writer ON NAMESPACE PERMIT EDIT ON RECORD WHEN $resource.db IN ['app', 'web'] AND $resource.db != 'audit'");
+		compile(&ro, "test".into()).unwrap();
+		let ro =
+			role("DEFINE ROLE invalid ON NAMESPACE PERMIT EDIT ON RECORD WHEN $resource.id = 1");
+		compile(&ro, "test".into()).unwrap_err();
+		policies(&[ro]).unwrap_err();
+	}
+
+	#[test]
+	fn compile_role_literal() {
+		assert_eq!(literal("audit"), r#""audit""#);
+		assert_eq!(literal(r#"a"b\c"#), r#""a\"b\\c""#);
+		assert_eq!(literal("a\nb\u{7f}é"), r#""a\u{a}b\u{7f}\u{e9}""#);
+		// Names with quotes and escapes are compiled into valid policies
+		let ro = role(
+			r#"DEFINE ROLE quoted ON DATABASE PERMIT VIEW ON TABLE WHEN $resource.name = "a\"b\\c\nd""#,
+		);
+		let set = policies(&[ro]).unwrap();
+		let actor = Actor::new("test".into(), vec![], Level::Database("ns".into(), "app".into()))
+			.with_custom_roles(vec!["quoted".into()]);
+		let res = Resource::new("a\"b\\c\nd".into(), ResourceKind::Table, ("ns", "app").into());
+		let (allowed, _) = is_allowed_with(&actor, &Action::View, &res, Context::empty(), &set);
+		assert!(allowed);
+		let res = Resource::new("a\"b\\c".into(), ResourceKind::Table, ("ns", "app").into());
+		let (allowed, _) = is_allowed_with(&actor, &Action::View, &res, Context::empty(), &set);
+		assert!(!allowed);
+	}
+
+	#[test]
+	fn custom_role_is_allowed() {
+		let set = policies(&[role(
+			"DEFINE ROLE records ON DATABASE PERMIT EDIT ON RECORD WHEN $resource.db != 'audit'",
+		)])
+		.unwrap();
+		let actor = Actor::new("test".into(), vec![], Level::Database("ns".into(), "app".into()))
+			.with_custom_roles(vec!["records".into()]);
+		// The custom role allows editing records
+		let res = ResourceKind::Record.on_db("ns", "app");
+		let (allowed, _) = is_allowed_with(&actor, &Action::Edit, &res, Context::empty(), &set);
+		assert!(allowed);
+		// The custom role does not allow editing functions
+		let res = ResourceKind::Function.on_db("ns", "app");
+		let (allowed, _) = is_allowed_with(&actor, &Action::Edit, &res, Context::empty(), &set);
+		assert!(!allowed);
+		// The custom role does not allow viewing records
+		let res = ResourceKind::Record.on_db("ns", "app");
+		let (allowed, _) = is_allowed_with(&actor, &Action::View, &res, Context::empty(), &set);
+		assert!(!allowed);
+		// The condition excludes the audit database
+		let set = policies(&[role(
+			"DEFINE ROLE records ON NAMESPACE PERMIT EDIT ON RECORD WHEN $resource.db != 'audit'",
+		)])
+		.unwrap();
+		let actor = Actor::new("test".into(), vec![Role::Viewer], Level::Namespace("ns".into()))
+			.with_custom_roles(vec!["records".into()]);
+		let res = ResourceKind::Record.on_db("ns", "audit");
+		let (allowed, _) = is_allowed_with(&actor, &Action::Edit, &res, Context::empty(), &set);
+		assert!(!allowed);
+		let (allowed, _) = is_allowed_with(&actor, &Action::View, &res, Context::empty(), &set);
+		assert!(allowed);
+		// The condition excludes the audit table
+		let set = policies(&[role(
+			"DEFINE ROLE tables ON DATABASE PERMIT VIEW ON TABLE WHEN $resource.name != 'audit'",
+		)])
+		.unwrap();
+		let actor = Actor::new("test".into(), vec![], Level::Database("ns".into(), "app".into()))
+			.with_custom_roles(vec!["tables".into()]);
+		let res = Resource::new("person".into(), ResourceKind::Table, ("ns", "app").into());
+		let (allowed, _) = is_allowed_with(&actor, &Action::View, &res, Context::empty(), &set);
+		assert!(allowed);
+		let res = Resource::new("audit".into(), ResourceKind::Table, ("ns", "app").into());
+		let (allowed, _) = is_allowed_with(&actor, &Action::View, &res, Context::empty(), &set);
+		assert!(!allowed);
+	}
+
+	#[tokio::test]
+	async fn custom_role_is_resolved_per_transaction() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let owner = Session::owner().with_ns("ns").with_db("db");
+		let sql = "
+			DEFINE ROLE records ON DATABASE PERMIT EDIT ON RECORD;
+			DEFINE USER editor ON DATABASE PASSWORD 'test' ROLES records;
+		";
+		for res in ds.execute(sql, &owner, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		// The session only stores the name of the custom role
+		let mut sess = Session::default().with_ns("ns").with_db("db");
+		sess.au = user_auth(
+			&ds,
+			"editor",
+			&["records".into()],
+			Level::Database("ns".into(), "db".into()),
+		)
+		.await
+		.unwrap()
+		.into();
+		let res = ResourceKind::Record.on_db("ns", "db");
+		ds.check_with_roles(&sess, Action::Edit, res.clone()).await.unwrap();
+		let mut res_info = ds.execute("INFO FOR DB", &sess, None).await.unwrap();
+		res_info.remove(0).result.unwrap_err();
+		// Redefining the role applies to the existing session
+		let sql = "DEFINE ROLE OVERWRITE records ON DATABASE PERMIT VIEW ON ANY";
+		ds.execute(sql, &owner, None).await.unwrap().remove(0).result.unwrap();
+		ds.check_with_roles(&sess, Action::Edit, res).await.unwrap_err();
+		let mut res_info = ds.execute("INFO FOR DB", &sess, None).await.unwrap();
+		res_info.remove(0).result.unwrap();
+	}
+}
@@ -3,15 +3,17 @@ use super::access::{
 	revoke_refresh_token_record,
 };
 use super::verify::{verify_db_creds, verify_ns_creds, verify_root_creds};
-use super::{Actor, Level, Role};
+use super::{Actor, Level};
 use crate::cnf::{INSECURE_FORWARD_ACCESS_ERRORS, SERVER_NAME};
 use crate::dbs::capabilities::ExperimentalTarget;
 use crate::dbs::Session;
 use crate::err::Error;
+use crate::iam::check::user_auth;
 use crate::iam::issue::{config, expiration};
+use crate::iam::lockout::Lockout;
 use crate::iam::token::{Claims, HEADER};
 use crate::iam::Auth;
-use crate::kvs::{Datastore, LockType::*, TransactionType::*};
+use crate::kvs::{Datastore, KeyEncode, LockType::*, TransactionType::*};
 use crate::sql::statements::{access, AccessGrant, DefineAccessStatement};
 use crate::sql::{access_type, AccessType, Datetime, Object, Value};
 use chrono::Utc;
@@ -184,6 +186,25 @@ pub async fn db_access(
 					match &at.signin {
 						// This record access allows signin
 						Some(val) => {
+							// Record the attempt, ensuring that the subject is not locked out
+							let lockout = Lockout::record();
+							let lo = crate::key::database::access::lo::new(
+								&ns,
+								&db,
+								&ac,
+								&Lockout::subject(&vars),
+							)
+							.encode()?;
+							match lockout.attempt(kvs, &lo).await {
+								// Return a generic error unless it should be forwarded
+								Err(Error::AccountLocked) if !*INSECURE_FORWARD_ACCESS_ERRORS => {
+									debug!(
+										"Record user signin failed as the subject is locked out"
+									);
+									return Err(Error::InvalidAuth);
+								}
+								res => res?,
+							}
 							// Setup the query params
 							let vars = Some(vars.0);
 							// Setup the system session for finding the signin record
@@ -191,7 +212,7 @@ pub async fn db_access(
 							sess.ip.clone_from(&session.ip);
 							sess.or.clone_from(&session.or);
 							// Compute the value with the params
-							match kvs.evaluate(val, &sess, vars).await {
+							let res = match kvs.evaluate(val, &sess, vars).await {
 								// The signin value succeeded
 								Ok(val) => {
 									match val.record() {
@@ -299,7 +320,12 @@ pub async fn db_access(
 										}
 									}
 								},
+							};
+							// Clear the failed attempts once the signin succeeds
+							if res.is_ok() {
+								lockout.success(kvs, &lo).await;
 							}
+							res
 						}
 						_ => Err(Error::AccessRecordNoSignin),
 					}
@@ -353,7 +379,10 @@ pub async fn db_user(
 			session.ns = Some(ns.to_owned());
 			session.db = Some(db.to_owned());
 			session.exp = expiration(u.duration.session)?;
-			session.au = Arc::new((&u, Level::Database(ns.to_owned(), db.to_owned())).try_into()?);
+			session.au = Arc::new(
+				user_auth(kvs, &u.name, &u.roles, Level::Database(ns.to_owned(), db.to_owned()))
+					.await?,
+			);
 			// Check the authentication token
 			match enc {
 				// The auth token was created successfully
@@ -436,7 +465,8 @@ pub async fn ns_user(
 			session.tk = Some((&val).into());
 			session.ns = Some(ns.to_owned());
 			session.exp = expiration(u.duration.session)?;
-			session.au = Arc::new((&u, Level::Namespace(ns.to_owned())).try_into()?);
+			session.au =
+				Arc::new(user_auth(kvs, &u.name, &u.roles, Level::Namespace(ns.to_owned())).await?);
 			// Check the authentication token
 			match enc {
 				// The auth token was created successfully
@@ -484,7 +514,7 @@ pub async fn root_user(
 			// Set the authentication on the session
 			session.tk = Some(val.into());
 			session.exp = expiration(u.duration.session)?;
-			session.au = Arc::new((&u, Level::Root).try_into()?);
+			session.au = Arc::new(user_auth(kvs, &u.name, &u.roles, Level::Root).await?);
 			// Check the authentication token
 			match enc {
 				// The auth token was created successfully
@@ -682,16 +712,13 @@ pub async fn signin_bearer(
 	session.exp = expiration(av.duration.session)?;
 	match &gr.subject {
 		access::Subject::User(user) => {
-			session.au = Arc::new(Auth::new(Actor::new(
-				user.to_string(),
-				roles.iter().map(Role::try_from).collect::<Result<_, _>>()?,
-				match (ns, db) {
-					(Some(ns), Some(db)) => Level::Database(ns, db),
-					(Some(ns), None) => Level::Namespace(ns),
-					(None, None) => Level::Root,
-					(None, Some(_)) => return Err(Error::NsEmpty),
-				},
-			)));
+			let level = match (ns, db) {
+				(Some(ns), Some(db)) => Level::Database(ns, db),
+				(Some(ns), None) => Level::Namespace(ns),
+				(None, None) => Level::Root,
+				(None, Some(_)) => return Err(Error::NsEmpty),
+			};
+			session.au = Arc::new(user_auth(kvs, user, &roles, level).await?);
 		}
 		access::Subject::Record(rid) => {
 			session.au = Arc::new(Auth::new(Actor::new(
@@ -4108,11 +4135,8 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 	#[tokio::test]
 	async fn test_signin_nonexistent_role() {
 		use crate::iam::Error as IamError;
-		use crate::sql::{
-			statements::{define::DefineStatement, DefineUserStatement},
-			user::UserDuration,
-			Base, Statement,
-		};
+		use crate::kvs::KeyEncode;
+		use crate::sql::{statements::DefineUserStatement, user::UserDuration, Base};
 		let test_levels = vec![
 			TestLevel {
 				level: "ROOT",
@@ -4133,7 +4157,6 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 
 		for level in &test_levels {
 			let ds = Datastore::new("memory").await.unwrap();
-			let sess = Session::owner().with_ns("test").with_db("test");
 
 			let base = match level.level {
 				"ROOT" => Base::Root,
@@ -4154,12 +4177,19 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 				comment: None,
 				if_not_exists: false,
 				overwrite: false,
+				..Default::default()
 			};
 
-			// Use pre-parsed definition, which bypasses the existent role check during parsing.
-			ds.process(Statement::Define(DefineStatement::User(user)).into(), &sess, None)
-				.await
-				.unwrap();
+			// Store the definition directly, which bypasses the role check when defining users.
+			let key = match level.level {
+				"ROOT" => crate::key::root::us::new("user").encode().unwrap(),
+				"NS" => crate::key::namespace::us::new("test", "user").encode().unwrap(),
+				"DB" => crate::key::database::us::new("test", "test", "user").encode().unwrap(),
+				_ => panic!("Unsupported level"),
+			};
+			let tx = ds.transaction(Write, Optimistic).await.unwrap();
+			tx.set(key, revision::to_vec(&user).unwrap(), None).await.unwrap();
+			tx.commit().await.unwrap();
 
 			let mut sess = Session {
 				ns: level.ns.map(String::from),
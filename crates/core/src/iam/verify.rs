@@ -1,10 +1,13 @@
+use crate::cnf::INSECURE_FORWARD_ACCESS_ERRORS;
 use crate::dbs::Session;
 use crate::err::Error;
 use crate::iam::access::{authenticate_generic, authenticate_record};
+use crate::iam::check::user_auth;
 #[cfg(feature = "jwks")]
 use crate::iam::jwks;
+use crate::iam::lockout::Lockout;
 use crate::iam::{issue::expiration, token::Claims, Actor, Auth, Level, Role};
-use crate::kvs::{Datastore, LockType::*, TransactionType::*};
+use crate::kvs::{Datastore, Key, KeyEncode, LockType::*, TransactionType::*};
 use crate::sql::access_type::{AccessType, Jwt, JwtAccessVerify};
 use crate::sql::{statements::DefineUserStatement, Algorithm, Value};
 use crate::syn;
@@ -95,8 +98,15 @@ pub async fn basic(
 			Ok(u) => {
 				debug!("Authenticated as database user '{}'", user);
 				session.exp = expiration(u.duration.session)?;
-				session.au =
-					Arc::new((&u, Level::Database(ns.to_owned(), db.to_owned())).try_into()?);
+				session.au = Arc::new(
+					user_auth(
+						kvs,
+						&u.name,
+						&u.roles,
+						Level::Database(ns.to_owned(), db.to_owned()),
+					)
+					.await?,
+				);
 				Ok(())
 			}
 			Err(err) => Err(err),
@@ -106,7 +116,9 @@ pub async fn basic(
 			Ok(u) => {
 				debug!("Authenticated as namespace user '{}'", user);
 				session.exp = expiration(u.duration.session)?;
-				session.au = Arc::new((&u, Level::Namespace(ns.to_owned())).try_into()?);
+				session.au = Arc::new(
+					user_auth(kvs, &u.name, &u.roles, Level::Namespace(ns.to_owned())).await?,
+				);
 				Ok(())
 			}
 			Err(err) => Err(err),
@@ -116,7 +128,7 @@ pub async fn basic(
 			Ok(u) => {
 				debug!("Authenticated as root user '{}'", user);
 				session.exp = expiration(u.duration.session)?;
-				session.au = Arc::new((&u, Level::Root).try_into()?);
+				session.au = Arc::new(user_auth(kvs, &u.name, &u.roles, Level::Root).await?);
 				Ok(())
 			}
 			Err(err) => Err(err),
@@ -364,11 +376,10 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			session.ns = Some(ns.to_owned());
 			session.db = Some(db.to_owned());
 			session.exp = expiration(de.duration.session)?;
-			session.au = Arc::new(Auth::new(Actor::new(
-				id.to_string(),
-				de.roles.iter().map(Role::try_from).collect::<Result<_, _>>()?,
-				Level::Database(ns.to_string(), db.to_string()),
-			)));
+			session.au = Arc::new(
+				user_auth(kvs, id, &de.roles, Level::Database(ns.to_string(), db.to_string()))
+					.await?,
+			);
 			Ok(())
 		}
 		// Check if this is namespace access
@@ -466,11 +477,8 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			session.tk = Some(value);
 			session.ns = Some(ns.to_owned());
 			session.exp = expiration(de.duration.session)?;
-			session.au = Arc::new(Auth::new(Actor::new(
-				id.to_string(),
-				de.roles.iter().map(Role::try_from).collect::<Result<_, _>>()?,
-				Level::Namespace(ns.to_string()),
-			)));
+			session.au =
+				Arc::new(user_auth(kvs, id, &de.roles, Level::Namespace(ns.to_string())).await?);
 			Ok(())
 		}
 		// Check if this is root access
@@ -560,11 +568,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			// Set the session
 			session.tk = Some(value);
 			session.exp = expiration(de.duration.session)?;
-			session.au = Arc::new(Auth::new(Actor::new(
-				id.to_string(),
-				de.roles.iter().map(Role::try_from).collect::<Result<_, _>>()?,
-				Level::Root,
-			)));
+			session.au = Arc::new(user_auth(kvs, id, &de.roles, Level::Root).await?);
 			Ok(())
 		}
 		// There was an auth error
@@ -586,8 +590,9 @@ pub async fn verify_root_creds(
 	})?;
 	// Ensure that the transaction is cancelled
 	tx.cancel().await?;
-	// Verify the specified password for the user
-	verify_pass(pass, user.hash.as_ref())?;
+	// Verify the specified password, tracking failed attempts
+	let key = crate::key::root::lo::new(&user.name).encode()?;
+	verify_pass_with_lockout(ds, &key, pass, user.hash.as_ref()).await?;
 	// Clone the cached user object
 	let user = (*user).clone();
 	// Return the verified user object
@@ -609,8 +614,9 @@ pub async fn verify_ns_creds(
 	})?;
 	// Ensure that the transaction is cancelled
 	tx.cancel().await?;
-	// Verify the specified password for the user
-	verify_pass(pass, user.hash.as_ref())?;
+	// Verify the specified password, tracking failed attempts
+	let key = crate::key::namespace::lo::new(ns, &user.name).encode()?;
+	verify_pass_with_lockout(ds, &key, pass, user.hash.as_ref()).await?;
 	// Clone the cached user object
 	let user = (*user).clone();
 	// Return the verified user object
@@ -633,14 +639,38 @@ pub async fn verify_db_creds(
 	})?;
 	// Ensure that the transaction is cancelled
 	tx.cancel().await?;
-	// Verify the specified password for the user
-	verify_pass(pass, user.hash.as_ref())?;
+	// Verify the specified password, tracking failed attempts
+	let key = crate::key::database::lo::new(ns, db, &user.name).encode()?;
+	verify_pass_with_lockout(ds, &key, pass, user.hash.as_ref()).await?;
 	// Clone the cached user object
 	let user = (*user).clone();
 	// Return the verified user object
 	Ok(user)
 }
 
+async fn verify_pass_with_lockout(
+	ds: &Datastore,
+	key: &Key,
+	pass: &str,
+	hash: &str,
+) -> Result<(), Error> {
+	let lockout = Lockout::default();
+	// Record the attempt, ensuring that the user is not locked out
+	match lockout.attempt(ds, key).await {
+		// Don't reveal that the user exists unless errors should be forwarded
+		Err(Error::AccountLocked) if !*INSECURE_FORWARD_ACCESS_ERRORS => {
+			debug!("Authentication attempt failed as the user is locked out");
+			return Err(Error::InvalidAuth);
+		}
+		res => res?,
+	}
+	// Verify the password for the user
+	verify_pass(pass, hash)?;
+	// Clear the failed attempts once the password is verified
+	lockout.success(ds, key).await;
+	Ok(())
+}
+
 fn verify_pass(pass: &str, hash: &str) -> Result<(), Error> {
 	// Compute the hash and verify the password
 	let hash = PasswordHash::new(hash).unwrap();
@@ -823,11 +853,8 @@ mod tests {
 	#[tokio::test]
 	async fn test_basic_nonexistent_role() {
 		use crate::iam::Error as IamError;
-		use crate::sql::{
-			statements::{define::DefineStatement, DefineUserStatement},
-			user::UserDuration,
-			Base, Statement,
-		};
+		use crate::kvs::KeyEncode;
+		use crate::sql::{statements::DefineUserStatement, user::UserDuration, Base};
 		let test_levels = vec![
 			TestLevel {
 				level: "ROOT",
@@ -848,7 +875,6 @@ mod tests {
 
 		for level in &test_levels {
 			let ds = Datastore::new("memory").await.unwrap();
-			let sess = Session::owner().with_ns("test").with_db("test");
 
 			let base = match level.level {
 				"ROOT" => Base::Root,
@@ -869,12 +895,19 @@ mod tests {
 				comment: None,
 				if_not_exists: false,
 				overwrite: false,
+				..Default::default()
 			};
 
-			// Use pre-parsed definition, which bypasses the existent role check during parsing.
-			ds.process(Statement::Define(DefineStatement::User(user)).into(), &sess, None)
-				.await
-				.unwrap();
+			// Store the definition directly, which bypasses the role check when defining users.
+			let key = match level.level {
+				"ROOT" => crate::key::root::us::new("user").encode().unwrap(),
+				"NS" => crate::key::namespace::us::new("test", "user").encode().unwrap(),
+				"DB" => crate::key::database::us::new("test", "test", "user").encode().unwrap(),
+				_ => panic!("Unsupported level"),
+			};
+			let tx = ds.transaction(Write, Optimistic).await.unwrap();
+			tx.set(key, revision::to_vec(&user).unwrap(), None).await.unwrap();
+			tx.commit().await.unwrap();
 
 			let mut sess = Session {
 				ns: level.ns.map(String::from),
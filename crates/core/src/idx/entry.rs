@@ -0,0 +1,54 @@
+use crate::err::Error;
+use crate::kvs::Val;
+use crate::sql::statements::DefineIndexStatement;
+use crate::sql::{Thing, Value};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+
+/// The value stored against an entry of a standard or unique index.
+///
+/// Indexes without any `INCLUDE` fields only store the record id. When an
+/// index includes additional fields, the entry also holds the indexed and
+/// included values, so that queries which only touch these fields can be
+/// answered from the index alone, without fetching the record.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub(crate) struct IndexEntry {
+	pub(crate) rid: Thing,
+	/// The values of the indexed fields, followed by the included fields
+	pub(crate) values: Vec<Value>,
+}
+
+impl IndexEntry {
+	/// Encodes the entry for the given record and index values
+	pub(crate) fn encode(
+		ix: &DefineIndexStatement,
+		rid: &Thing,
+		values: &[Value],
+	) -> Result<Val, Error> {
+		if ix.include.is_empty() {
+			return Ok(revision::to_vec(rid)?);
+		}
+		Ok(revision::to_vec(&Self {
+			rid: rid.clone(),
+			values: values.to_vec(),
+		})?)
+	}
+
+	/// Decodes an entry which was stored by the given index
+	pub(crate) fn decode(ix: &DefineIndexStatement, val: &[u8]) -> Result<Self, Error> {
+		Self::decode_with(!ix.include.is_empty(), val)
+	}
+
+	/// Decodes an entry, which only holds the record id unless it is `covering`
+	pub(crate) fn decode_with(covering: bool, val: &[u8]) -> Result<Self, Error> {
+		if !covering {
+			return Ok(Self {
+				rid: revision::from_slice(val)?,
+				values: vec![],
+			});
+		}
+		Ok(revision::from_slice(val)?)
+	}
+}
@@ -3,6 +3,7 @@
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::err::Error;
+use crate::idx::entry::IndexEntry;
 use crate::idx::ft::FtIndex;
 use crate::idx::trees::mtree::MTreeIndex;
 use crate::idx::IndexKeyBase;
@@ -70,10 +71,11 @@ impl<'a> IndexOperation<'a> {
 		let mut txn = tx.lock().await;
 		// Delete the old index data
 		if let Some(o) = self.o.take() {
+			let entry = IndexEntry::encode(self.ix, self.rid, &o)?;
 			let i = Indexable::new(o, self.ix);
 			for o in i {
 				let key = self.get_unique_index_key(&o)?;
-				match txn.delc(key, Some(revision::to_vec(self.rid)?)).await {
+				match txn.delc(key, Some(entry.clone())).await {
 					Err(Error::TxConditionNotMet) => Ok(()),
 					Err(e) => Err(e),
 					Ok(v) => Ok(v),
@@ -82,14 +84,15 @@ impl<'a> IndexOperation<'a> {
 		}
 		// Create the new index data
 		if let Some(n) = self.n.take() {
+			let entry = IndexEntry::encode(self.ix, self.rid, &n)?;
 			let i = Indexable::new(n, self.ix);
 			for n in i {
 				if !n.is_all_none_or_null() {
 					let key = self.get_unique_index_key(&n)?;
-					if txn.putc(key, revision::to_vec(self.rid)?, None).await.is_err() {
+					if txn.putc(key, entry.clone(), None).await.is_err() {
 						let key = self.get_unique_index_key(&n)?;
 						let val = txn.get(key, None).await?.unwrap();
-						let rid: Thing = revision::from_slice(&val)?;
+						let rid = IndexEntry::decode(self.ix, &val)?.rid;
 						return self.err_index_exists(rid, n);
 					}
 				}
@@ -104,10 +107,11 @@ impl<'a> IndexOperation<'a> {
 		let mut txn = tx.lock().await;
 		// Delete the old index data
 		if let Some(o) = self.o.take() {
+			let entry = IndexEntry::encode(self.ix, self.rid, &o)?;
 			let i = Indexable::new(o, self.ix);
 			for o in i {
 				let key = self.get_non_unique_index_key(&o)?;
-				match txn.delc(key, Some(revision::to_vec(self.rid)?)).await {
+				match txn.delc(key, Some(entry.clone())).await {
 					Err(Error::TxConditionNotMet) => Ok(()),
 					Err(e) => Err(e),
 					Ok(v) => Ok(v),
@@ -116,13 +120,14 @@ impl<'a> IndexOperation<'a> {
 		}
 		// Create the new index data
 		if let Some(n) = self.n.take() {
+			let entry = IndexEntry::encode(self.ix, self.rid, &n)?;
 			let i = Indexable::new(n, self.ix);
 			for n in i {
 				let key = self.get_non_unique_index_key(&n)?;
-				if txn.putc(key, revision::to_vec(self.rid)?, None).await.is_err() {
+				if txn.putc(key, entry.clone(), None).await.is_err() {
 					let key = self.get_non_unique_index_key(&n)?;
 					let val = txn.get(key, None).await?.unwrap();
-					let rid: Thing = revision::from_slice(&val)?;
+					let rid = IndexEntry::decode(self.ix, &val)?.rid;
 					return self.err_index_exists(rid, n);
 				}
 			}
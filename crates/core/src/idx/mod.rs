@@ -1,4 +1,5 @@
 pub mod docids;
+pub(crate) mod entry;
 pub(crate) mod ft;
 pub(crate) mod index;
 pub mod planner;
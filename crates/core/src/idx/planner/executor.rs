@@ -25,7 +25,7 @@ use crate::idx::planner::knn::{KnnBruteForceResult, KnnPriorityList};
 use crate::idx::planner::plan::IndexOperator::Matches;
 use crate::idx::planner::plan::{IndexOperator, IndexOption, RangeValue};
 use crate::idx::planner::tree::{IdiomPosition, IndexReference};
-use crate::idx::planner::IterationStage;
+use crate::idx::planner::{IterationStage, RecordStrategy};
 use crate::idx::trees::mtree::MTreeIndex;
 use crate::idx::trees::store::hnsw::SharedHnswIndex;
 use crate::idx::IndexKeyBase;
@@ -329,12 +329,16 @@ impl QueryExecutor {
 		&self,
 		opt: &Options,
 		ir: IteratorRef,
+		rs: RecordStrategy,
 	) -> Result<Option<ThingIterator>, Error> {
+		let index_only = matches!(rs, RecordStrategy::IndexOnly);
 		if let Some(it_entry) = self.0.it_entries.get(ir) {
 			match it_entry {
-				IteratorEntry::Single(_, io) => self.new_single_iterator(opt, ir, io).await,
+				IteratorEntry::Single(_, io) => {
+					self.new_single_iterator(opt, ir, io, index_only).await
+				}
 				IteratorEntry::Range(_, ixr, from, to) => {
-					Ok(self.new_range_iterator(ir, opt, ixr, from, to)?)
+					Ok(self.new_range_iterator(ir, opt, ixr, from, to, index_only)?)
 				}
 			}
 		} else {
@@ -347,11 +351,16 @@ impl QueryExecutor {
 		opt: &Options,
 		irf: IteratorRef,
 		io: &IndexOption,
+		index_only: bool,
 	) -> Result<Option<ThingIterator>, Error> {
 		let ixr = io.ix_ref();
 		match ixr.index {
-			Index::Idx => Ok(self.new_index_iterator(opt, irf, ixr, io.clone()).await?),
-			Index::Uniq => Ok(self.new_unique_index_iterator(opt, irf, ixr, io.clone()).await?),
+			Index::Idx => {
+				Ok(self.new_index_iterator(opt, irf, ixr, io.clone(), index_only).await?)
+			}
+			Index::Uniq => {
+				Ok(self.new_unique_index_iterator(opt, irf, ixr, io.clone(), index_only).await?)
+			}
 			Index::Search {
 				..
 			} => self.new_search_index_iterator(irf, io.clone()).await,
@@ -366,16 +375,17 @@ impl QueryExecutor {
 		ir: IteratorRef,
 		ix: &IndexReference,
 		io: IndexOption,
+		index_only: bool,
 	) -> Result<Option<ThingIterator>, Error> {
 		Ok(match io.op() {
 			IndexOperator::Equality(value) => {
 				let variants = Self::get_equal_variants_from_value(value);
 				if variants.len() == 1 {
-					Some(Self::new_index_equal_iterator(ir, opt, ix, &variants[0])?)
+					Some(Self::new_index_equal_iterator(ir, opt, ix, &variants[0], index_only)?)
 				} else {
 					let (ns, db) = opt.ns_db()?;
 					Some(ThingIterator::IndexUnion(IndexUnionThingIterator::new(
-						ir, ns, db, ix, &variants,
+						ir, ns, db, ix, &variants, index_only,
 					)?))
 				}
 			}
@@ -383,13 +393,18 @@ impl QueryExecutor {
 				let variants = Self::get_equal_variants_from_values(values);
 				let (ns, db) = opt.ns_db()?;
 				Some(ThingIterator::IndexUnion(IndexUnionThingIterator::new(
-					ir, ns, db, ix, &variants,
+					ir, ns, db, ix, &variants, index_only,
 				)?))
 			}
 			IndexOperator::Join(ios) => {
 				let iterators = self.build_iterators(opt, ir, ios).await?;
-				let index_join =
-					Box::new(IndexJoinThingIterator::new(ir, opt, ix.clone(), iterators)?);
+				let index_join = Box::new(IndexJoinThingIterator::new(
+					ir,
+					opt,
+					ix.clone(),
+					iterators,
+					index_only,
+				)?);
 				Some(ThingIterator::IndexJoin(index_join))
 			}
 			IndexOperator::Order(reverse) => {
@@ -402,6 +417,7 @@ impl QueryExecutor {
 								opt.ns()?,
 								opt.db()?,
 								ix,
+								index_only,
 							)?,
 						))
 					}
@@ -413,6 +429,7 @@ impl QueryExecutor {
 						opt.ns()?,
 						opt.db()?,
 						ix,
+						index_only,
 					)?))
 				}
 			}
@@ -484,9 +501,12 @@ impl QueryExecutor {
 		opt: &Options,
 		ix: &DefineIndexStatement,
 		array: &Array,
+		index_only: bool,
 	) -> Result<ThingIterator, Error> {
 		let (ns, db) = opt.ns_db()?;
-		Ok(ThingIterator::IndexEqual(IndexEqualThingIterator::new(irf, ns, db, ix, array)?))
+		Ok(ThingIterator::IndexEqual(IndexEqualThingIterator::new(
+			irf, ns, db, ix, array, index_only,
+		)?))
 	}
 
 	/// This function takes a reference to a `Number` enum and a conversion function `float_to_int`.
@@ -714,16 +734,19 @@ impl QueryExecutor {
 		ix: &DefineIndexStatement,
 		from: &RangeValue,
 		to: &RangeValue,
+		index_only: bool,
 	) -> Result<Option<ThingIterator>, Error> {
 		match ix.index {
 			Index::Idx => {
 				let ranges = Self::get_ranges_variants(from, to);
 				if let Some(ranges) = ranges {
 					if ranges.len() == 1 {
-						return Ok(Some(Self::new_index_range_iterator(ir, opt, ix, &ranges[0])?));
+						return Ok(Some(Self::new_index_range_iterator(
+							ir, opt, ix, &ranges[0], index_only,
+						)?));
 					} else {
 						return Ok(Some(Self::new_multiple_index_range_iterator(
-							ir, opt, ix, &ranges,
+							ir, opt, ix, &ranges, index_only,
 						)?));
 					}
 				}
@@ -732,16 +755,19 @@ impl QueryExecutor {
 					opt,
 					ix,
 					&IteratorRange::new_ref(ValueType::None, from, to),
+					index_only,
 				)?));
 			}
 			Index::Uniq => {
 				let ranges = Self::get_ranges_variants(from, to);
 				if let Some(ranges) = ranges {
 					if ranges.len() == 1 {
-						return Ok(Some(Self::new_unique_range_iterator(ir, opt, ix, &ranges[0])?));
+						return Ok(Some(Self::new_unique_range_iterator(
+							ir, opt, ix, &ranges[0], index_only,
+						)?));
 					} else {
 						return Ok(Some(Self::new_multiple_unique_range_iterator(
-							ir, opt, ix, &ranges,
+							ir, opt, ix, &ranges, index_only,
 						)?));
 					}
 				}
@@ -750,6 +776,7 @@ impl QueryExecutor {
 					opt,
 					ix,
 					&IteratorRange::new_ref(ValueType::None, from, to),
+					index_only,
 				)?));
 			}
 			_ => {}
@@ -780,9 +807,12 @@ impl QueryExecutor {
 		opt: &Options,
 		ix: &DefineIndexStatement,
 		range: &IteratorRange,
+		index_only: bool,
 	) -> Result<ThingIterator, Error> {
 		let (ns, db) = opt.ns_db()?;
-		Ok(ThingIterator::IndexRange(IndexRangeThingIterator::new(ir, ns, db, ix, range)?))
+		Ok(ThingIterator::IndexRange(IndexRangeThingIterator::new(
+			ir, ns, db, ix, range, index_only,
+		)?))
 	}
 
 	fn new_unique_range_iterator(
@@ -790,9 +820,12 @@ impl QueryExecutor {
 		opt: &Options,
 		ix: &DefineIndexStatement,
 		range: &IteratorRange<'_>,
+		index_only: bool,
 	) -> Result<ThingIterator, Error> {
 		let (ns, db) = opt.ns_db()?;
-		Ok(ThingIterator::UniqueRange(UniqueRangeThingIterator::new(ir, ns, db, ix, range)?))
+		Ok(ThingIterator::UniqueRange(UniqueRangeThingIterator::new(
+			ir, ns, db, ix, range, index_only,
+		)?))
 	}
 
 	fn new_multiple_index_range_iterator(
@@ -800,10 +833,11 @@ impl QueryExecutor {
 		opt: &Options,
 		ix: &DefineIndexStatement,
 		ranges: &[IteratorRange],
+		index_only: bool,
 	) -> Result<ThingIterator, Error> {
 		let mut iterators = VecDeque::with_capacity(ranges.len());
 		for range in ranges {
-			iterators.push_back(Self::new_index_range_iterator(ir, opt, ix, range)?);
+			iterators.push_back(Self::new_index_range_iterator(ir, opt, ix, range, index_only)?);
 		}
 		Ok(ThingIterator::Multiples(Box::new(MultipleIterators::new(iterators))))
 	}
@@ -813,10 +847,11 @@ impl QueryExecutor {
 		opt: &Options,
 		ix: &DefineIndexStatement,
 		ranges: &[IteratorRange<'_>],
+		index_only: bool,
 	) -> Result<ThingIterator, Error> {
 		let mut iterators = VecDeque::with_capacity(ranges.len());
 		for range in ranges {
-			iterators.push_back(Self::new_unique_range_iterator(ir, opt, ix, range)?);
+			iterators.push_back(Self::new_unique_range_iterator(ir, opt, ix, range, index_only)?);
 		}
 		Ok(ThingIterator::Multiples(Box::new(MultipleIterators::new(iterators))))
 	}
@@ -827,28 +862,34 @@ impl QueryExecutor {
 		irf: IteratorRef,
 		ixr: &IndexReference,
 		io: IndexOption,
+		index_only: bool,
 	) -> Result<Option<ThingIterator>, Error> {
 		Ok(match io.op() {
 			IndexOperator::Equality(values) => {
 				let variants = Self::get_equal_variants_from_value(values);
 				if variants.len() == 1 {
-					Some(Self::new_unique_equal_iterator(irf, opt, ixr, &variants[0])?)
+					Some(Self::new_unique_equal_iterator(irf, opt, ixr, &variants[0], index_only)?)
 				} else {
 					Some(ThingIterator::UniqueUnion(UniqueUnionThingIterator::new(
-						irf, opt, ixr, &variants,
+						irf, opt, ixr, &variants, index_only,
 					)?))
 				}
 			}
 			IndexOperator::Union(values) => {
 				let variants = Self::get_equal_variants_from_values(values);
 				Some(ThingIterator::UniqueUnion(UniqueUnionThingIterator::new(
-					irf, opt, ixr, &variants,
+					irf, opt, ixr, &variants, index_only,
 				)?))
 			}
 			IndexOperator::Join(ios) => {
 				let iterators = self.build_iterators(opt, irf, ios).await?;
-				let unique_join =
-					Box::new(UniqueJoinThingIterator::new(irf, opt, ixr.clone(), iterators)?);
+				let unique_join = Box::new(UniqueJoinThingIterator::new(
+					irf,
+					opt,
+					ixr.clone(),
+					iterators,
+					index_only,
+				)?);
 				Some(ThingIterator::UniqueJoin(unique_join))
 			}
 			IndexOperator::Order(reverse) => {
@@ -861,6 +902,7 @@ impl QueryExecutor {
 								opt.ns()?,
 								opt.db()?,
 								ixr,
+								index_only,
 							)?,
 						))
 					}
@@ -872,6 +914,7 @@ impl QueryExecutor {
 						opt.ns()?,
 						opt.db()?,
 						ixr,
+						index_only,
 					)?))
 				}
 			}
@@ -884,15 +927,20 @@ impl QueryExecutor {
 		opt: &Options,
 		ix: &DefineIndexStatement,
 		array: &Array,
+		index_only: bool,
 	) -> Result<ThingIterator, Error> {
 		let (ns, db) = opt.ns_db()?;
 		if ix.cols.len() > 1 {
 			// If the index is unique and the index is a composite index,
 			// then we have the opportunity to iterate on the first column of the index
 			// and consider it as a standard index (rather than a unique one)
-			Ok(ThingIterator::IndexEqual(IndexEqualThingIterator::new(irf, ns, db, ix, array)?))
+			Ok(ThingIterator::IndexEqual(IndexEqualThingIterator::new(
+				irf, ns, db, ix, array, index_only,
+			)?))
 		} else {
-			Ok(ThingIterator::UniqueEqual(UniqueEqualThingIterator::new(irf, ns, db, ix, array)?))
+			Ok(ThingIterator::UniqueEqual(UniqueEqualThingIterator::new(
+				irf, ns, db, ix, array, index_only,
+			)?))
 		}
 	}
 
@@ -943,7 +991,8 @@ impl QueryExecutor {
 	) -> Result<VecDeque<ThingIterator>, Error> {
 		let mut iterators = VecDeque::with_capacity(ios.len());
 		for io in ios {
-			if let Some(it) = Box::pin(self.new_single_iterator(opt, irf, io)).await? {
+			// The remote iterators only collect the record ids
+			if let Some(it) = Box::pin(self.new_single_iterator(opt, irf, io, false)).await? {
 				iterators.push_back(it);
 			}
 		}
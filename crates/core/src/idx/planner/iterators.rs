@@ -2,6 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::err::Error;
 use crate::idx::docids::DocId;
+use crate::idx::entry::IndexEntry;
 use crate::idx::ft::termdocs::TermsDocs;
 use crate::idx::ft::{FtIndex, HitsIterator};
 use crate::idx::planner::plan::RangeValue;
@@ -9,8 +10,9 @@ use crate::idx::planner::tree::IndexReference;
 use crate::key::index::Index;
 use crate::kvs::{Key, Val};
 use crate::kvs::{KeyEncode, Transaction};
+use crate::sql::paths::ID;
 use crate::sql::statements::DefineIndexStatement;
-use crate::sql::{Array, Ident, Number, Thing, Value};
+use crate::sql::{Array, Ident, Idiom, Number, Thing, Value};
 use radix_trie::Trie;
 use rust_decimal::Decimal;
 use std::borrow::Cow;
@@ -210,8 +212,46 @@ impl IndexItemRecord {
 	}
 }
 
+/// Turns the entries of a standard or unique index into records
+#[derive(Clone)]
+pub(crate) struct IndexEntryReader {
+	/// Whether the entries hold the indexed and included values
+	covering: bool,
+	/// The indexed and included fields, when the documents are rebuilt from the entries
+	fields: Option<Arc<[Idiom]>>,
+}
+
+impl IndexEntryReader {
+	pub(super) fn new(ix: &DefineIndexStatement, index_only: bool) -> Self {
+		let covering = !ix.include.is_empty();
+		let fields = (covering && index_only)
+			.then(|| ix.cols.iter().chain(ix.include.iter()).cloned().collect());
+		Self {
+			covering,
+			fields,
+		}
+	}
+
+	fn record(&self, val: &[u8], irf: IteratorRef) -> Result<IndexItemRecord, Error> {
+		let entry = IndexEntry::decode_with(self.covering, val)?;
+		let Some(fields) = &self.fields else {
+			return Ok(IndexItemRecord::new_key(entry.rid, irf.into()));
+		};
+		// Rebuild the document from the indexed and included values
+		let mut doc = Value::base();
+		for (field, v) in fields.iter().zip(entry.values) {
+			if !v.is_none() {
+				doc.put(field, v);
+			}
+		}
+		doc.put(&*ID, entry.rid.clone().into());
+		Ok(IndexItemRecord::KeyValue(Arc::new(entry.rid), Arc::new(doc), irf.into()))
+	}
+}
+
 pub(crate) struct IndexEqualThingIterator {
 	irf: IteratorRef,
+	reader: IndexEntryReader,
 	beg: Vec<u8>,
 	end: Vec<u8>,
 }
@@ -223,10 +263,12 @@ impl IndexEqualThingIterator {
 		db: &str,
 		ix: &DefineIndexStatement,
 		a: &Array,
+		index_only: bool,
 	) -> Result<Self, Error> {
 		let (beg, end) = Self::get_beg_end(ns, db, ix, a)?;
 		Ok(Self {
 			irf,
+			reader: IndexEntryReader::new(ix, index_only),
 			beg,
 			end,
 		})
@@ -271,6 +313,7 @@ impl IndexEqualThingIterator {
 	async fn next_scan_batch<B: IteratorBatch>(
 		tx: &Transaction,
 		irf: IteratorRef,
+		reader: &IndexEntryReader,
 		beg: &mut Vec<u8>,
 		end: &[u8],
 		limit: u32,
@@ -278,7 +321,7 @@ impl IndexEqualThingIterator {
 		let res = Self::next_scan(tx, beg, end, limit).await?;
 		let mut records = B::with_capacity(res.len());
 		res.into_iter().try_for_each(|(_, val)| -> Result<(), Error> {
-			records.add(IndexItemRecord::new_key(revision::from_slice(&val)?, irf.into()));
+			records.add(reader.record(&val, irf)?);
 			Ok(())
 		})?;
 		Ok(records)
@@ -289,7 +332,7 @@ impl IndexEqualThingIterator {
 		tx: &Transaction,
 		limit: u32,
 	) -> Result<B, Error> {
-		Self::next_scan_batch(tx, self.irf, &mut self.beg, &self.end, limit).await
+		Self::next_scan_batch(tx, self.irf, &self.reader, &mut self.beg, &self.end, limit).await
 	}
 
 	async fn next_count(&mut self, tx: &Transaction, limit: u32) -> Result<usize, Error> {
@@ -479,6 +522,7 @@ impl ValueType {
 
 pub(crate) struct IndexRangeThingIterator {
 	irf: IteratorRef,
+	reader: IndexEntryReader,
 	r: RangeScan,
 }
 
@@ -489,9 +533,11 @@ impl IndexRangeThingIterator {
 		db: &str,
 		ix: &DefineIndexStatement,
 		range: &IteratorRange<'_>,
+		index_only: bool,
 	) -> Result<Self, Error> {
 		Ok(Self {
 			irf,
+			reader: IndexEntryReader::new(ix, index_only),
 			r: Self::range_scan(ns, db, ix, range)?,
 		})
 	}
@@ -501,9 +547,10 @@ impl IndexRangeThingIterator {
 		ns: &str,
 		db: &str,
 		ix: &DefineIndexStatement,
+		index_only: bool,
 	) -> Result<Self, Error> {
 		let range = full_iterator_range();
-		Self::new(irf, ns, db, ix, &range)
+		Self::new(irf, ns, db, ix, &range, index_only)
 	}
 
 	fn range_scan(
@@ -582,7 +629,7 @@ impl IndexRangeThingIterator {
 		let mut records = B::with_capacity(res.len());
 		res.into_iter().filter(|(k, _)| self.r.matches(k)).try_for_each(
 			|(_, v)| -> Result<(), Error> {
-				records.add(IndexItemRecord::new_key(revision::from_slice(&v)?, self.irf.into()));
+				records.add(self.reader.record(&v, self.irf)?);
 				Ok(())
 			},
 		)?;
@@ -599,6 +646,7 @@ impl IndexRangeThingIterator {
 #[cfg(any(feature = "kv-rocksdb", feature = "kv-tikv"))]
 pub(crate) struct IndexRangeReverseThingIterator {
 	irf: IteratorRef,
+	reader: IndexEntryReader,
 	r: ReverseRangeScan,
 }
 
@@ -610,9 +658,11 @@ impl IndexRangeReverseThingIterator {
 		db: &str,
 		ix: &DefineIndexStatement,
 		range: &IteratorRange<'_>,
+		index_only: bool,
 	) -> Result<Self, Error> {
 		Ok(Self {
 			irf,
+			reader: IndexEntryReader::new(ix, index_only),
 			r: ReverseRangeScan::new(IndexRangeThingIterator::range_scan(ns, db, ix, range)?),
 		})
 	}
@@ -622,9 +672,10 @@ impl IndexRangeReverseThingIterator {
 		ns: &str,
 		db: &str,
 		ix: &DefineIndexStatement,
+		index_only: bool,
 	) -> Result<Self, Error> {
 		let range = full_iterator_range();
-		Self::new(irf, ns, db, ix, &range)
+		Self::new(irf, ns, db, ix, &range, index_only)
 	}
 	async fn check_batch_ending(
 		&mut self,
@@ -637,7 +688,7 @@ impl IndexRangeReverseThingIterator {
 		self.r.r.end_excl_match_checked = true;
 		if let Some(v) = tx.get(&self.r.r.end, None).await? {
 			*limit -= 1;
-			Ok(Some(IndexItemRecord::new_key(revision::from_slice(&v)?, self.irf.into())))
+			Ok(Some(self.reader.record(&v, self.irf)?))
 		} else {
 			Ok(None)
 		}
@@ -689,7 +740,7 @@ impl IndexRangeReverseThingIterator {
 		// Feed the result
 		res.into_iter().filter(|(k, _)| self.r.r.matches(k)).try_for_each(
 			|(_, v)| -> Result<(), Error> {
-				records.add(IndexItemRecord::new_key(revision::from_slice(&v)?, self.irf.into()));
+				records.add(self.reader.record(&v, self.irf)?);
 				Ok(())
 			},
 		)?;
@@ -735,6 +786,7 @@ impl IndexRangeReverseThingIterator {
 
 pub(crate) struct IndexUnionThingIterator {
 	irf: IteratorRef,
+	reader: IndexEntryReader,
 	values: VecDeque<(Vec<u8>, Vec<u8>)>,
 	current: Option<(Vec<u8>, Vec<u8>)>,
 }
@@ -746,6 +798,7 @@ impl IndexUnionThingIterator {
 		db: &str,
 		ix: &DefineIndexStatement,
 		arrays: &[Array],
+		index_only: bool,
 	) -> Result<Self, Error> {
 		// We create a VecDeque to hold the prefix keys (begin and end) for each value in the array.
 		let mut values: VecDeque<(Vec<u8>, Vec<u8>)> = VecDeque::with_capacity(arrays.len());
@@ -757,6 +810,7 @@ impl IndexUnionThingIterator {
 		let current = values.pop_front();
 		Ok(Self {
 			irf,
+			reader: IndexEntryReader::new(ix, index_only),
 			values,
 			current,
 		})
@@ -772,9 +826,15 @@ impl IndexUnionThingIterator {
 			if ctx.is_done(true) {
 				break;
 			}
-			let records: B =
-				IndexEqualThingIterator::next_scan_batch(tx, self.irf, &mut r.0, &r.1, limit)
-					.await?;
+			let records: B = IndexEqualThingIterator::next_scan_batch(
+				tx,
+				self.irf,
+				&self.reader,
+				&mut r.0,
+				&r.1,
+				limit,
+			)
+			.await?;
 			if !records.is_empty() {
 				return Ok(records);
 			}
@@ -812,6 +872,8 @@ struct JoinThingIterator {
 	current_remote_batch: VecDeque<IndexItemRecord>,
 	current_local: Option<ThingIterator>,
 	distinct: Trie<Key, bool>,
+	/// Whether the local iterators rebuild the values from the index entries
+	index_only: bool,
 }
 
 impl JoinThingIterator {
@@ -819,6 +881,7 @@ impl JoinThingIterator {
 		opt: &Options,
 		ix: IndexReference,
 		remote_iterators: VecDeque<ThingIterator>,
+		index_only: bool,
 	) -> Result<Self, Error> {
 		let (ns, db) = opt.ns_db()?;
 		Ok(Self {
@@ -830,6 +893,7 @@ impl JoinThingIterator {
 			remote_iterators,
 			current_local: None,
 			distinct: Default::default(),
+			index_only,
 		})
 	}
 }
@@ -864,7 +928,7 @@ impl JoinThingIterator {
 		new_iter: F,
 	) -> Result<bool, Error>
 	where
-		F: Fn(&str, &str, &DefineIndexStatement, Value) -> Result<ThingIterator, Error>,
+		F: Fn(&str, &str, &DefineIndexStatement, Value, bool) -> Result<ThingIterator, Error>,
 	{
 		while !ctx.is_done(true) {
 			let mut count = 0;
@@ -876,7 +940,8 @@ impl JoinThingIterator {
 				let k: Key = revision::to_vec(thing)?;
 				let value = Value::from(thing.clone());
 				if self.distinct.insert(k, true).is_none() {
-					self.current_local = Some(new_iter(&self.ns, &self.db, &self.ix, value)?);
+					self.current_local =
+						Some(new_iter(&self.ns, &self.db, &self.ix, value, self.index_only)?);
 					return Ok(true);
 				}
 				count += 1;
@@ -896,7 +961,8 @@ impl JoinThingIterator {
 		new_iter: F,
 	) -> Result<B, Error>
 	where
-		F: Fn(&str, &str, &DefineIndexStatement, Value) -> Result<ThingIterator, Error> + Copy,
+		F: Fn(&str, &str, &DefineIndexStatement, Value, bool) -> Result<ThingIterator, Error>
+			+ Copy,
 	{
 		while !ctx.is_done(true) {
 			if let Some(current_local) = &mut self.current_local {
@@ -920,7 +986,8 @@ impl JoinThingIterator {
 		new_iter: F,
 	) -> Result<usize, Error>
 	where
-		F: Fn(&str, &str, &DefineIndexStatement, Value) -> Result<ThingIterator, Error> + Copy,
+		F: Fn(&str, &str, &DefineIndexStatement, Value, bool) -> Result<ThingIterator, Error>
+			+ Copy,
 	{
 		while !ctx.is_done(true) {
 			if let Some(current_local) = &mut self.current_local {
@@ -945,8 +1012,9 @@ impl IndexJoinThingIterator {
 		opt: &Options,
 		ix: IndexReference,
 		remote_iterators: VecDeque<ThingIterator>,
+		index_only: bool,
 	) -> Result<Self, Error> {
-		Ok(Self(irf, JoinThingIterator::new(opt, ix, remote_iterators)?))
+		Ok(Self(irf, JoinThingIterator::new(opt, ix, remote_iterators, index_only)?))
 	}
 
 	async fn next_batch<B: IteratorBatch>(
@@ -955,11 +1023,12 @@ impl IndexJoinThingIterator {
 		tx: &Transaction,
 		limit: u32,
 	) -> Result<B, Error> {
-		let new_iter = |ns: &str, db: &str, ix: &DefineIndexStatement, value: Value| {
-			let array = Array::from(value);
-			let it = IndexEqualThingIterator::new(self.0, ns, db, ix, &array)?;
-			Ok(ThingIterator::IndexEqual(it))
-		};
+		let new_iter =
+			|ns: &str, db: &str, ix: &DefineIndexStatement, value: Value, index_only: bool| {
+				let array = Array::from(value);
+				let it = IndexEqualThingIterator::new(self.0, ns, db, ix, &array, index_only)?;
+				Ok(ThingIterator::IndexEqual(it))
+			};
 		self.1.next_batch(ctx, tx, limit, new_iter).await
 	}
 
@@ -969,17 +1038,19 @@ impl IndexJoinThingIterator {
 		tx: &Transaction,
 		limit: u32,
 	) -> Result<usize, Error> {
-		let new_iter = |ns: &str, db: &str, ix: &DefineIndexStatement, value: Value| {
-			let array = Array::from(value);
-			let it = IndexEqualThingIterator::new(self.0, ns, db, ix, &array)?;
-			Ok(ThingIterator::IndexEqual(it))
-		};
+		let new_iter =
+			|ns: &str, db: &str, ix: &DefineIndexStatement, value: Value, index_only: bool| {
+				let array = Array::from(value);
+				let it = IndexEqualThingIterator::new(self.0, ns, db, ix, &array, index_only)?;
+				Ok(ThingIterator::IndexEqual(it))
+			};
 		self.1.next_count(ctx, tx, limit, new_iter).await
 	}
 }
 
 pub(crate) struct UniqueEqualThingIterator {
 	irf: IteratorRef,
+	reader: IndexEntryReader,
 	key: Option<Key>,
 }
 
@@ -990,10 +1061,12 @@ impl UniqueEqualThingIterator {
 		db: &str,
 		ix: &DefineIndexStatement,
 		a: &Array,
+		index_only: bool,
 	) -> Result<Self, Error> {
 		let key = Index::new(ns, db, &ix.what, &ix.name, a, None).encode()?;
 		Ok(Self {
 			irf,
+			reader: IndexEntryReader::new(ix, index_only),
 			key: Some(key),
 		})
 	}
@@ -1001,8 +1074,7 @@ impl UniqueEqualThingIterator {
 	async fn next_batch<B: IteratorBatch>(&mut self, tx: &Transaction) -> Result<B, Error> {
 		if let Some(key) = self.key.take() {
 			if let Some(val) = tx.get(key, None).await? {
-				let rid: Thing = revision::from_slice(&val)?;
-				let record = IndexItemRecord::new_key(rid, self.irf.into());
+				let record = self.reader.record(&val, self.irf)?;
 				return Ok(B::from_one(record));
 			}
 		}
@@ -1033,6 +1105,7 @@ fn full_iterator_range<'a>() -> IteratorRange<'a> {
 
 pub(crate) struct UniqueRangeThingIterator {
 	irf: IteratorRef,
+	reader: IndexEntryReader,
 	r: RangeScan,
 	done: bool,
 }
@@ -1055,10 +1128,12 @@ impl UniqueRangeThingIterator {
 		db: &str,
 		ix: &DefineIndexStatement,
 		r: &IteratorRange<'_>,
+		index_only: bool,
 	) -> Result<Self, Error> {
 		let r = Self::range_scan(ns, db, ix, r)?;
 		Ok(Self {
 			irf,
+			reader: IndexEntryReader::new(ix, index_only),
 			r,
 			done: false,
 		})
@@ -1069,9 +1144,10 @@ impl UniqueRangeThingIterator {
 		ns: &str,
 		db: &str,
 		ix: &DefineIndexStatement,
+		index_only: bool,
 	) -> Result<Self, Error> {
 		let rng = full_iterator_range();
-		Self::new(irf, ns, db, ix, &rng)
+		Self::new(irf, ns, db, ix, &rng, index_only)
 	}
 
 	fn compute_beg(
@@ -1120,15 +1196,13 @@ impl UniqueRangeThingIterator {
 				return Ok(records);
 			}
 			if self.r.matches(&k) {
-				let rid: Thing = revision::from_slice(&v)?;
-				records.add(IndexItemRecord::new_key(rid, self.irf.into()));
+				records.add(self.reader.record(&v, self.irf)?);
 			}
 		}
 
 		if self.r.matches_end() {
 			if let Some(v) = tx.get(&self.r.end, None).await? {
-				let rid: Thing = revision::from_slice(&v)?;
-				records.add(IndexItemRecord::new_key(rid, self.irf.into()));
+				records.add(self.reader.record(&v, self.irf)?);
 			}
 		}
 		self.done = true;
@@ -1163,6 +1237,7 @@ impl UniqueRangeThingIterator {
 #[cfg(any(feature = "kv-rocksdb", feature = "kv-tikv"))]
 pub(crate) struct UniqueRangeReverseThingIterator {
 	irf: IteratorRef,
+	reader: IndexEntryReader,
 	r: ReverseRangeScan,
 	done: bool,
 }
@@ -1174,11 +1249,13 @@ impl UniqueRangeReverseThingIterator {
 		ns: &str,
 		db: &str,
 		ix: &DefineIndexStatement,
+		index_only: bool,
 	) -> Result<Self, Error> {
 		let r = full_iterator_range();
 		let r = ReverseRangeScan::new(UniqueRangeThingIterator::range_scan(ns, db, ix, &r)?);
 		Ok(Self {
 			irf,
+			reader: IndexEntryReader::new(ix, index_only),
 			r,
 			done: false,
 		})
@@ -1198,8 +1275,7 @@ impl UniqueRangeReverseThingIterator {
 			self.r.end_incl = false;
 			// tx.scanr is end exclusive, so we have to manually collect the value using a get
 			if let Some(v) = tx.get(&self.r.r.end, None).await? {
-				let rid: Thing = revision::from_slice(&v)?;
-				let record = IndexItemRecord::new_key(rid, self.irf.into());
+				let record = self.reader.record(&v, self.irf)?;
 				limit -= 1;
 				if limit == 0 {
 					return Ok(B::from_one(record));
@@ -1230,8 +1306,7 @@ impl UniqueRangeReverseThingIterator {
 			records.add(record);
 		}
 		for (_, v) in res {
-			let rid: Thing = revision::from_slice(&v)?;
-			records.add(IndexItemRecord::new_key(rid, self.irf.into()));
+			records.add(self.reader.record(&v, self.irf)?);
 		}
 		Ok(records)
 	}
@@ -1274,6 +1349,7 @@ impl UniqueRangeReverseThingIterator {
 
 pub(crate) struct UniqueUnionThingIterator {
 	irf: IteratorRef,
+	reader: IndexEntryReader,
 	keys: VecDeque<Key>,
 }
 
@@ -1283,6 +1359,7 @@ impl UniqueUnionThingIterator {
 		opt: &Options,
 		ix: &DefineIndexStatement,
 		vals: &[Array],
+		index_only: bool,
 	) -> Result<Self, Error> {
 		// We create a VecDeque to hold the key for each value in the array.
 		let mut keys = VecDeque::with_capacity(vals.len());
@@ -1293,6 +1370,7 @@ impl UniqueUnionThingIterator {
 		}
 		Ok(Self {
 			irf,
+			reader: IndexEntryReader::new(ix, index_only),
 			keys,
 		})
 	}
@@ -1312,8 +1390,7 @@ impl UniqueUnionThingIterator {
 			}
 			if let Some(val) = tx.get(key, None).await? {
 				count += 1;
-				let rid: Thing = revision::from_slice(&val)?;
-				results.add(IndexItemRecord::new_key(rid, self.irf.into()));
+				results.add(self.reader.record(&val, self.irf)?);
 				if results.len() >= limit {
 					break;
 				}
@@ -1353,8 +1430,9 @@ impl UniqueJoinThingIterator {
 		opt: &Options,
 		ix: IndexReference,
 		remote_iterators: VecDeque<ThingIterator>,
+		index_only: bool,
 	) -> Result<Self, Error> {
-		Ok(Self(irf, JoinThingIterator::new(opt, ix, remote_iterators)?))
+		Ok(Self(irf, JoinThingIterator::new(opt, ix, remote_iterators, index_only)?))
 	}
 
 	async fn next_batch<B: IteratorBatch>(
@@ -1363,11 +1441,12 @@ impl UniqueJoinThingIterator {
 		tx: &Transaction,
 		limit: u32,
 	) -> Result<B, Error> {
-		let new_iter = |ns: &str, db: &str, ix: &DefineIndexStatement, value: Value| {
-			let array = Array::from(value.clone());
-			let it = UniqueEqualThingIterator::new(self.0, ns, db, ix, &array)?;
-			Ok(ThingIterator::UniqueEqual(it))
-		};
+		let new_iter =
+			|ns: &str, db: &str, ix: &DefineIndexStatement, value: Value, index_only: bool| {
+				let array = Array::from(value.clone());
+				let it = UniqueEqualThingIterator::new(self.0, ns, db, ix, &array, index_only)?;
+				Ok(ThingIterator::UniqueEqual(it))
+			};
 		self.1.next_batch(ctx, tx, limit, new_iter).await
 	}
 
@@ -1377,11 +1456,12 @@ impl UniqueJoinThingIterator {
 		tx: &Transaction,
 		limit: u32,
 	) -> Result<usize, Error> {
-		let new_iter = |ns: &str, db: &str, ix: &DefineIndexStatement, value: Value| {
-			let array = Array::from(value.clone());
-			let it = UniqueEqualThingIterator::new(self.0, ns, db, ix, &array)?;
-			Ok(ThingIterator::UniqueEqual(it))
-		};
+		let new_iter =
+			|ns: &str, db: &str, ix: &DefineIndexStatement, value: Value, index_only: bool| {
+				let array = Array::from(value.clone());
+				let it = UniqueEqualThingIterator::new(self.0, ns, db, ix, &array, index_only)?;
+				Ok(ThingIterator::UniqueEqual(it))
+			};
 		self.1.next_count(ctx, tx, limit, new_iter).await
 	}
 }
@@ -9,13 +9,16 @@ pub(in crate::idx) mod tree;
 use crate::ctx::Context;
 use crate::dbs::{Iterable, Iterator, Options, Statement};
 use crate::err::Error;
+use crate::iam::ResourceKind;
 use crate::idx::planner::executor::{InnerQueryExecutor, IteratorEntry, QueryExecutor};
 use crate::idx::planner::iterators::IteratorRef;
 use crate::idx::planner::knn::KnnBruteForceResults;
 use crate::idx::planner::plan::{Plan, PlanBuilder, PlanBuilderParameters};
-use crate::idx::planner::tree::Tree;
+use crate::idx::planner::tree::{IndexReference, Tree};
+use crate::sql::statements::SelectStatement;
 use crate::sql::with::With;
-use crate::sql::{order::Ordering, Cond, Fields, Groups, Table};
+use crate::sql::{order::Ordering, Cond, Field, Fields, Groups, Idiom, Part, Table};
+use crate::sql::{Expression, Function, Subquery, Value};
 use reblessive::tree::Stk;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
@@ -35,7 +38,6 @@ pub(crate) struct StatementContext<'a> {
 	pub(crate) order: Option<&'a Ordering>,
 	pub(crate) cond: Option<&'a Cond>,
 	pub(crate) group: Option<&'a Groups>,
-	is_perm: bool,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -43,6 +45,8 @@ pub(crate) enum RecordStrategy {
 	Count,
 	KeysOnly,
 	KeysAndValues,
+	/// The values are rebuilt from the index entries, without fetching the records
+	IndexOnly,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -75,7 +79,6 @@ impl<'a> StatementContext<'a> {
 		opt: &'a Options,
 		stm: &'a Statement<'a>,
 	) -> Result<Self, Error> {
-		let is_perm = opt.check_perms(stm.into())?;
 		let (ns, db) = opt.ns_db()?;
 		Ok(Self {
 			ctx,
@@ -88,7 +91,6 @@ impl<'a> StatementContext<'a> {
 			order: stm.order(),
 			cond: stm.cond(),
 			group: stm.group(),
-			is_perm,
 		})
 	}
 
@@ -96,27 +98,32 @@ impl<'a> StatementContext<'a> {
 		&self,
 		tb: &str,
 	) -> Result<GrantedPermission, Error> {
-		if !self.is_perm {
-			return Ok(GrantedPermission::Full);
-		}
 		// Get the table for this planner
 		match self.ctx.tx().get_tb(self.ns, self.db, tb).await {
 			Ok(table) => {
-				// TODO(tobiemh): we should really
-				// not even get here if the table
-				// permissions are NONE, because
-				// there is no point in processing
-				// a table which we can't access.
-				let perms = self.stm.permissions(&table, false);
-				// If permissions are specific, we
-				// need to fetch the record content.
-				if perms.is_specific() {
-					return Ok(GrantedPermission::Specific);
+				if self.opt.check_perms(self.stm.into(), ResourceKind::Table, tb)? {
+					// TODO(tobiemh): we should really
+					// not even get here if the table
+					// permissions are NONE, because
+					// there is no point in processing
+					// a table which we can't access.
+					let perms = self.stm.permissions(&table, false);
+					// If permissions are specific, we
+					// need to fetch the record content.
+					if perms.is_specific() {
+						return Ok(GrantedPermission::Specific);
+					}
+					// If permissions are NONE, we also
+					// need to fetch the record content.
+					if perms.is_none() {
+						return Ok(GrantedPermission::None);
+					}
 				}
-				// If permissions are NONE, we also
+				// If records can expire, then each
+				// record needs to be checked, so we
 				// need to fetch the record content.
-				if perms.is_none() {
-					return Ok(GrantedPermission::None);
+				if table.expire.is_some() {
+					return Ok(GrantedPermission::Specific);
 				}
 			}
 			Err(Error::TbNotFound {
@@ -202,6 +209,67 @@ impl<'a> StatementContext<'a> {
 		Ok(RecordStrategy::KeysOnly)
 	}
 
+	/// Checks if the values can be rebuilt from the entries of the given indexes,
+	/// rather than fetching the records. This is only possible for SELECT statements
+	/// which don't check permissions, when each index includes additional fields,
+	/// and the statement only touches the indexed and included fields.
+	fn check_index_only(
+		&self,
+		rs: RecordStrategy,
+		ixs: &[&IndexReference],
+	) -> Result<RecordStrategy, Error> {
+		if !matches!(rs, RecordStrategy::KeysAndValues) || ixs.is_empty() {
+			return Ok(rs);
+		}
+		// Only the current version of the records is indexed
+		let Statement::Select(stm) = self.stm else {
+			return Ok(rs);
+		};
+		if stm.version.is_some() {
+			return Ok(rs);
+		}
+		for ix in ixs {
+			// Permissions on the table need the records
+			if self.opt.check_perms(self.stm.into(), ResourceKind::Table, &ix.what)? {
+				return Ok(rs);
+			}
+			// The index entries must hold plain fields
+			if ix.include.is_empty() {
+				return Ok(rs);
+			}
+			let fields: Vec<&Idiom> = ix.cols.iter().chain(ix.include.iter()).collect();
+			if !fields.iter().all(|i| i.iter().all(|p| matches!(p, Part::Field(_)))) {
+				return Ok(rs);
+			}
+			if !Self::is_covered_by(stm, &fields) {
+				return Ok(rs);
+			}
+		}
+		Ok(RecordStrategy::IndexOnly)
+	}
+
+	/// Checks that every clause of the statement only touches the given fields
+	fn is_covered_by(stm: &SelectStatement, fields: &[&Idiom]) -> bool {
+		let projection = stm.expr.iter().all(|f| match f {
+			Field::Single {
+				expr,
+				..
+			} => is_covered(expr, fields),
+			Field::All
+			| Field::Window {
+				..
+			} => false,
+		});
+		projection
+			&& stm.cond.iter().all(|c| is_covered(&c.0, fields))
+			&& stm.split.iter().flat_map(|s| s.iter()).all(|s| is_idiom_covered(&s.0, fields))
+			&& stm.group.iter().flat_map(|g| g.iter()).all(|g| is_idiom_covered(&g.0, fields))
+			&& match &stm.order {
+				Some(Ordering::Order(o)) => o.iter().all(|o| is_idiom_covered(&o.value, fields)),
+				_ => true,
+			} && stm.fetch.iter().flat_map(|f| f.iter()).all(|f| is_covered(&f.0, fields))
+	}
+
 	/// Determines the scan direction.
 	/// This is used for Table and Range iterators.
 	/// The direction is reversed if the first element of order is ID descending.
@@ -219,6 +287,64 @@ impl<'a> StatementContext<'a> {
 	}
 }
 
+/// Checks that the value only reads the given fields from the current document
+fn is_covered(v: &Value, fields: &[&Idiom]) -> bool {
+	match v {
+		Value::Idiom(i) => is_idiom_covered(i, fields),
+		Value::Array(a) => a.iter().all(|v| is_covered(v, fields)),
+		Value::Object(o) => o.values().all(|v| is_covered(v, fields)),
+		Value::Cast(c) => is_covered(&c.1, fields),
+		Value::Function(f) => match f.as_ref() {
+			Function::Normal(name, args) => {
+				!name.starts_with("search::") && args.iter().all(|v| is_covered(v, fields))
+			}
+			_ => false,
+		},
+		Value::Expression(e) => match e.as_ref() {
+			Expression::Unary {
+				v,
+				..
+			} => is_covered(v, fields),
+			Expression::Binary {
+				l,
+				r,
+				..
+			} => is_covered(l, fields) && is_covered(r, fields),
+		},
+		Value::Subquery(s) => match s.as_ref() {
+			Subquery::Value(v) => is_covered(v, fields),
+			_ => false,
+		},
+		Value::Param(_)
+		| Value::Mock(_)
+		| Value::Block(_)
+		| Value::Range(_)
+		| Value::Edges(_)
+		| Value::Future(_)
+		| Value::Query(_)
+		| Value::Model(_)
+		| Value::Closure(_)
+		| Value::Refs(_)
+		| Value::Match(_) => false,
+		_ => true,
+	}
+}
+
+/// Checks that the idiom starts with one of the given fields, or with the record id
+fn is_idiom_covered(i: &Idiom, fields: &[&Idiom]) -> bool {
+	let head = match i.first() {
+		Some(Part::Field(f)) if f.is_id() => true,
+		Some(Part::Field(_)) => fields.iter().any(|f| i.starts_with(f)),
+		_ => false,
+	};
+	head && i.iter().all(|p| match p {
+		Part::Where(v) | Part::Value(v) | Part::Start(v) => is_covered(v, fields),
+		Part::Method(_, a) => a.iter().all(|v| is_covered(v, fields)),
+		Part::Graph(_) | Part::Recurse(..) | Part::RepeatRecurse | Part::Doc => false,
+		_ => true,
+	})
+}
+
 pub(crate) struct QueryPlanner {
 	/// There is one executor per table
 	executors: HashMap<String, QueryExecutor>,
@@ -252,18 +378,15 @@ impl QueryPlanner {
 		ctx: &StatementContext<'_>,
 		tb: &str,
 	) -> Result<GrantedPermission, Error> {
-		if ctx.is_perm {
-			if let Some(p) = self.granted_permissions.get(tb) {
-				return Ok(*p);
-			}
-			let p = ctx.check_table_permission(tb).await?;
-			self.granted_permissions.insert(tb.to_string(), p);
-			if matches!(p, GrantedPermission::Specific) {
-				self.any_specific_permission = true;
-			}
-			return Ok(p);
+		if let Some(p) = self.granted_permissions.get(tb) {
+			return Ok(*p);
 		}
-		Ok(GrantedPermission::Full)
+		let p = ctx.check_table_permission(tb).await?;
+		self.granted_permissions.insert(tb.to_string(), p);
+		if matches!(p, GrantedPermission::Specific) {
+			self.any_specific_permission = true;
+		}
+		Ok(p)
 	}
 
 	pub(crate) async fn add_iterables(
@@ -79,6 +79,8 @@ impl PlanBuilder {
 			if let Some((_, io)) = compound_index {
 				// Evaluate if we can use keys only
 				let record_strategy = ctx.check_record_strategy(true, p.gp)?;
+				// Evaluate if the index entries are enough
+				let record_strategy = ctx.check_index_only(record_strategy, &[io.ix_ref()])?;
 				// Return the plan
 				return Ok(Plan::SingleIndex(None, io, record_strategy));
 			}
@@ -88,6 +90,8 @@ impl PlanBuilder {
 				if let Some((ir, rq)) = group.take_first_range() {
 					// Evaluate the record strategy
 					let record_strategy = ctx.check_record_strategy(true, p.gp)?;
+					// Evaluate if the index entries are enough
+					let record_strategy = ctx.check_index_only(record_strategy, &[&ir])?;
 					// Return the plan
 					return Ok(Plan::SingleIndexRange(ir, rq, record_strategy));
 				}
@@ -97,6 +101,8 @@ impl PlanBuilder {
 			if let Some((e, i)) = b.non_range_indexes.pop() {
 				// Evaluate the record strategy
 				let record_strategy = ctx.check_record_strategy(true, p.gp)?;
+				// Evaluate if the index entries are enough
+				let record_strategy = ctx.check_index_only(record_strategy, &[i.ix_ref()])?;
 				// Return the plan
 				return Ok(Plan::SingleIndex(Some(e), i, record_strategy));
 			}
@@ -104,6 +110,8 @@ impl PlanBuilder {
 			if let Some(o) = p.order_limit {
 				// Evaluate the record strategy
 				let record_strategy = ctx.check_record_strategy(true, p.gp)?;
+				// Evaluate if the index entries are enough
+				let record_strategy = ctx.check_index_only(record_strategy, &[o.ix_ref()])?;
 				// Check it is compatible with the reverse scan capability
 				if Self::check_order_scan(p.reverse_scan, o.op()) {
 					// Return the plan
@@ -123,6 +131,14 @@ impl PlanBuilder {
 			}
 			// Evaluate the record strategy
 			let record_strategy = ctx.check_record_strategy(true, p.gp)?;
+			// Evaluate if the index entries are enough
+			let ixs: Vec<&IndexReference> = b
+				.non_range_indexes
+				.iter()
+				.map(|(_, io)| io.ix_ref())
+				.chain(ranges.iter().map(|(ixr, _)| ixr))
+				.collect();
+			let record_strategy = ctx.check_index_only(record_strategy, &ixs)?;
 			// Return the plan
 			return Ok(Plan::MultiIndex(b.non_range_indexes, ranges, record_strategy));
 		}
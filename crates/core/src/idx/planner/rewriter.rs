@@ -30,7 +30,8 @@ impl<'a> KnnConditionRewriter<'a> {
 			| Value::Block(_)
 			| Value::Future(_)
 			| Value::Subquery(_)
-			| Value::Query(_) => None,
+			| Value::Query(_)
+			| Value::Match(_) => None,
 			Value::Function(f) => self.eval_value_function(f),
 			Value::Expression(e) => self.eval_value_expression(e),
 			Value::Model(m) => self.eval_value_model(m),
@@ -177,6 +177,11 @@ impl<'a> TreeBuilder<'a> {
 		group: GroupRef,
 		v: &Value,
 	) -> Result<Node, Error> {
+		// Check if the value matches the expression of an index
+		if let Some(n) = self.eval_indexed_expression(v).await? {
+			self.leaf_nodes_count += 1;
+			return Ok(n);
+		}
 		match v {
 			Value::Expression(e) => self.eval_expression(stk, group, e).await,
 			Value::Idiom(i) => self.eval_idiom(stk, group, i).await,
@@ -241,6 +246,36 @@ impl<'a> TreeBuilder<'a> {
 		Ok(n)
 	}
 
+	/// Resolves a value which is not an idiom, such as a function call,
+	/// against the indexes which are defined on an expression.
+	async fn eval_indexed_expression(&mut self, v: &Value) -> Result<Option<Node>, Error> {
+		if !matches!(
+			v,
+			Value::Function(_) | Value::Expression(_) | Value::Subquery(_) | Value::Cast(_)
+		) {
+			return Ok(None);
+		}
+		let tx = self.ctx.ctx.tx();
+		self.lazy_load_schema_resolver(&tx, self.table).await?;
+		let Some(schema) = self.schemas.get(self.table).cloned() else {
+			return Ok(None);
+		};
+		// Check if there is any index on an expression
+		if !schema
+			.indexes
+			.iter()
+			.any(|ix| ix.cols.iter().any(|c| matches!(c.0.first(), Some(Part::Start(_)))))
+		{
+			return Ok(None);
+		}
+		let i = Idiom(vec![Part::Start(v.clone())]);
+		let irs = self.resolve_indexes(self.table, &i, &schema);
+		if irs.is_empty() {
+			return Ok(None);
+		}
+		Ok(Some(Node::IndexedField(Arc::new(i), irs)))
+	}
+
 	async fn resolve_idiom(&mut self, i: &Idiom) -> Result<Node, Error> {
 		let tx = self.ctx.ctx.tx();
 		self.lazy_load_schema_resolver(&tx, self.table).await?;
@@ -274,7 +309,11 @@ impl<'a> TreeBuilder<'a> {
 		}
 		let mut irs = Vec::new();
 		for (idx, ix) in schema.indexes.iter().enumerate() {
-			if let Some(idiom_index) = ix.cols.iter().position(|p| p.eq(i)) {
+			// Partial indexes can only be used if the query only matches indexed records
+			if !self.is_index_cond_implied(t, ix) {
+				continue;
+			}
+			if let Some(idiom_index) = ix.cols.iter().position(|p| Self::is_same_col(p, i)) {
 				let ixr = schema.new_reference(idx);
 				// Check if the WITH clause allow the index to be used
 				if let Some(With::Index(ixs)) = &self.ctx.with {
@@ -298,6 +337,71 @@ impl<'a> TreeBuilder<'a> {
 		irs
 	}
 
+	/// Checks if an indexed column matches an idiom, ignoring
+	/// any parentheses around the expression of the column.
+	fn is_same_col(col: &Idiom, i: &Idiom) -> bool {
+		match (col.0.as_slice(), i.0.as_slice()) {
+			([Part::Start(a)], [Part::Start(b)]) => {
+				Self::unwrap_parens(a) == Self::unwrap_parens(b)
+			}
+			_ => col == i,
+		}
+	}
+
+	/// Removes any parentheses around a value
+	fn unwrap_parens(mut v: &Value) -> &Value {
+		while let Value::Subquery(s) = v {
+			match s.as_ref() {
+				Subquery::Value(inner) => v = inner,
+				_ => break,
+			}
+		}
+		v
+	}
+
+	/// Checks if the condition of a partial index is implied by the query condition,
+	/// which is the case when each of the AND terms of the index condition is also
+	/// one of the top level AND terms of the query condition.
+	fn is_index_cond_implied(&self, t: &Table, ix: &DefineIndexStatement) -> bool {
+		let Some(cond) = &ix.cond else {
+			return true;
+		};
+		// The query condition does not apply to the records of remote tables
+		if t != self.table {
+			return false;
+		}
+		let Some(query) = self.ctx.cond else {
+			return false;
+		};
+		let mut terms = Vec::new();
+		Self::and_terms(&query.0, &mut terms);
+		let mut required = Vec::new();
+		Self::and_terms(&cond.0, &mut required);
+		required.iter().all(|r| terms.contains(r))
+	}
+
+	/// Collects the terms of a condition which are joined by AND
+	fn and_terms<'v>(v: &'v Value, terms: &mut Vec<&'v Value>) {
+		match v {
+			Value::Expression(e) => match e.as_ref() {
+				Expression::Binary {
+					l,
+					o: Operator::And,
+					r,
+				} => {
+					Self::and_terms(l, terms);
+					Self::and_terms(r, terms);
+				}
+				_ => terms.push(v),
+			},
+			Value::Subquery(s) => match s.as_ref() {
+				Subquery::Value(v) => Self::and_terms(v, terms),
+				_ => terms.push(v),
+			},
+			_ => terms.push(v),
+		}
+	}
+
 	async fn resolve_record_field(
 		&mut self,
 		tx: &Transaction,
@@ -12,6 +12,8 @@ pub(crate) trait Categorise {
 pub enum Category {
 	/// crate::key::storage::version         /sv
 	Version,
+	/// crate::key::encryption               !e
+	Encryption,
 	/// crate::key::root::all                /
 	Root,
 	/// crate::key::root::access::ac         /!ac{ac}
@@ -26,8 +28,12 @@ pub enum Category {
 	NamespaceIdentifier,
 	/// crate::key::root::ns                 /!ns{ns}
 	Namespace,
+	/// crate::key::root::rs                 /!rs
+	Restore,
 	/// crate::key::root::us                 /!us{us}
 	User,
+	/// crate::key::root::lo                 /!lo{us}
+	UserLockout,
 	///
 	/// ------------------------------
 	///
@@ -55,8 +61,12 @@ pub enum Category {
 	NamespaceAccessRoot,
 	/// crate::key::namespace::access::gr    /*{ns}*{ac}!gr{gr}
 	NamespaceAccessGrant,
+	/// crate::key::namespace::ro            /*{ns}!ro{ro}
+	NamespaceRole,
 	/// crate::key::namespace::us            /*{ns}!us{us}
 	NamespaceUser,
+	/// crate::key::namespace::lo            /*{ns}!lo{us}
+	NamespaceUserLockout,
 	///
 	/// ------------------------------
 	///
@@ -68,22 +78,34 @@ pub enum Category {
 	DatabaseAccessRoot,
 	/// crate::key::database::access::gr     /*{ns}*{db}*ac!gr{gr}
 	DatabaseAccessGrant,
+	/// crate::key::database::access::lo     /*{ns}*{db}*ac!lo{id}
+	DatabaseAccessLockout,
 	/// crate::key::database::ap             /*{ns}*{db}!ap{ap}
 	DatabaseApi,
 	/// crate::key::database::az             /*{ns}*{db}!az{az}
 	DatabaseAnalyzer,
+	/// crate::key::database::cw             /*{ns}*{db}!cw
+	DatabaseChangefeedWatermark,
 	/// crate::key::database::fc             /*{ns}*{db}!fn{fc}
 	DatabaseFunction,
 	/// crate::key::database::ml             /*{ns}*{db}!ml{ml}{vn}
 	DatabaseModel,
 	/// crate::key::database::pa             /*{ns}*{db}!pa{pa}
 	DatabaseParameter,
+	/// crate::key::database::ro             /*{ns}*{db}!ro{ro}
+	DatabaseRole,
+	/// crate::key::database::sq             /*{ns}*{db}!sq{sq}
+	DatabaseSequence,
+	/// crate::key::database::sv             /*{ns}*{db}!sv{sq}
+	DatabaseSequenceState,
 	/// crate::key::database::tb             /*{ns}*{db}!tb{tb}
 	DatabaseTable,
 	/// crate::key::database::ts             /*{ns}*{db}!ts{ts}
 	DatabaseTimestamp,
 	/// crate::key::database::us             /*{ns}*{db}!us{us}
 	DatabaseUser,
+	/// crate::key::database::lo             /*{ns}*{db}!lo{us}
+	DatabaseUserLockout,
 	/// crate::key::database::vs             /*{ns}*{db}!vs
 	DatabaseVersionstamp,
 	/// crate::key::database::cg             /*{ns}*{db}!cg{ty}
@@ -95,6 +117,10 @@ pub enum Category {
 	TableRoot,
 	/// crate::key::table::ev                /*{ns}*{db}*{tb}!ev{ev}
 	TableEvent,
+	/// crate::key::table::ex                /*{ns}*{db}*{tb}!ex{id}
+	TableExpiry,
+	/// crate::key::table::et                /*{ns}*{db}*{tb}!et{ts}{id}
+	TableExpiryTime,
 	/// crate::key::table::fd                /*{ns}*{db}*{tb}!fd{fd}
 	TableField,
 	/// crate::key::table::ft                /*{ns}*{db}*{tb}!ft{ft}
@@ -170,6 +196,7 @@ impl Display for Category {
 	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
 		let name = match self {
 			Self::Version => "StorageVersion",
+			Self::Encryption => "EncryptionCheck",
 			Self::Root => "Root",
 			Self::Access => "Access",
 			Self::AccessRoot => "AccessRoot",
@@ -177,7 +204,9 @@ impl Display for Category {
 			Self::Node => "Node",
 			Self::NamespaceIdentifier => "NamespaceIdentifier",
 			Self::Namespace => "Namespace",
+			Self::Restore => "Restore",
 			Self::User => "User",
+			Self::UserLockout => "UserLockout",
 			Self::NodeRoot => "NodeRoot",
 			Self::NodeLiveQuery => "NodeLiveQuery",
 			Self::NamespaceRoot => "NamespaceRoot",
@@ -186,24 +215,34 @@ impl Display for Category {
 			Self::NamespaceAccess => "NamespaceAccess",
 			Self::NamespaceAccessRoot => "NamespaceAccessRoot",
 			Self::NamespaceAccessGrant => "NamespaceAccessGrant",
+			Self::NamespaceRole => "NamespaceRole",
 			Self::NamespaceUser => "NamespaceUser",
+			Self::NamespaceUserLockout => "NamespaceUserLockout",
 			Self::DatabaseRoot => "DatabaseRoot",
 			Self::DatabaseAccess => "DatabaseAccess",
 			Self::DatabaseAccessRoot => "DatabaseAccessRoot",
 			Self::DatabaseAccessGrant => "DatabaseAccessGrant",
+			Self::DatabaseAccessLockout => "DatabaseAccessLockout",
 			Self::DatabaseApi => "DatabaseApi",
 			Self::DatabaseAnalyzer => "DatabaseAnalyzer",
+			Self::DatabaseChangefeedWatermark => "DatabaseChangefeedWatermark",
 			Self::DatabaseFunction => "DatabaseFunction",
 			Self::DatabaseModel => "DatabaseModel",
 			Self::DatabaseParameter => "DatabaseParameter",
+			Self::DatabaseRole => "DatabaseRole",
+			Self::DatabaseSequence => "DatabaseSequence",
+			Self::DatabaseSequenceState => "DatabaseSequenceState",
 			Self::DatabaseTable => "DatabaseTable",
 			Self::DatabaseTableIdentifier => "DatabaseTableIdentifier",
 			Self::DatabaseTimestamp => "DatabaseTimestamp",
 			Self::DatabaseUser => "DatabaseUser",
+			Self::DatabaseUserLockout => "DatabaseUserLockout",
 			Self::DatabaseVersionstamp => "DatabaseVersionstamp",
 			Self::DatabaseConfig => "DatabaseConfig",
 			Self::TableRoot => "TableRoot",
 			Self::TableEvent => "TableEvent",
+			Self::TableExpiry => "TableExpiry",
+			Self::TableExpiryTime => "TableExpiryTime",
 			Self::TableField => "TableField",
 			Self::TableView => "TableView",
 			Self::IndexDefinition => "IndexDefinition",
@@ -0,0 +1,83 @@
+//! Stores the failed authentication state of a subject signing in with a record access method
+use crate::err::Error;
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{impl_key, KeyEncode};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Lo<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub ac: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub id: &'a str,
+}
+impl_key!(Lo<'a>);
+
+pub fn new<'a>(ns: &'a str, db: &'a str, ac: &'a str, id: &'a str) -> Lo<'a> {
+	Lo::new(ns, db, ac, id)
+}
+
+pub fn prefix(ns: &str, db: &str, ac: &str) -> Result<Vec<u8>, Error> {
+	let mut k = super::all::new(ns, db, ac).encode()?;
+	k.extend_from_slice(b"!lo\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: &str, db: &str, ac: &str) -> Result<Vec<u8>, Error> {
+	let mut k = super::all::new(ns, db, ac).encode()?;
+	k.extend_from_slice(b"!lo\xff");
+	Ok(k)
+}
+
+impl Categorise for Lo<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseAccessLockout
+	}
+}
+
+impl<'a> Lo<'a> {
+	pub fn new(ns: &'a str, db: &'a str, ac: &'a str, id: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'&',
+			ac,
+			_d: b'!',
+			_e: b'l',
+			_f: b'o',
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::KeyDecode;
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Lo::new(
+			"testns",
+			"testdb",
+			"testac",
+			"testid",
+		);
+		let enc = Lo::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00*testdb\x00&testac\x00!lotestid\x00");
+		let dec = Lo::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
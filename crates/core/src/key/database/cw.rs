@@ -0,0 +1,63 @@
+//! Stores the database change feed watermark
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::impl_key;
+use serde::{Deserialize, Serialize};
+
+// Cw stands for Changefeed Watermark
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Cw<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+}
+impl_key!(Cw<'a>);
+
+pub fn new<'a>(ns: &'a str, db: &'a str) -> Cw<'a> {
+	Cw::new(ns, db)
+}
+
+impl Categorise for Cw<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseChangefeedWatermark
+	}
+}
+
+impl<'a> Cw<'a> {
+	pub fn new(ns: &'a str, db: &'a str) -> Self {
+		Cw {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'c',
+			_e: b'w',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::{KeyDecode, KeyEncode};
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Cw::new(
+			"test",
+			"test",
+		);
+		let enc = Cw::encode(&val).unwrap();
+		assert_eq!(enc, b"/*test\0*test\0!cw");
+		let dec = Cw::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
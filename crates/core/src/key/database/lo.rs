@@ -0,0 +1,65 @@
+//! Stores the failed authentication state of a DATABASE user
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::impl_key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Lo<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub user: &'a str,
+}
+impl_key!(Lo<'a>);
+
+pub fn new<'a>(ns: &'a str, db: &'a str, user: &'a str) -> Lo<'a> {
+	Lo::new(ns, db, user)
+}
+
+impl Categorise for Lo<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseUserLockout
+	}
+}
+
+impl<'a> Lo<'a> {
+	pub fn new(ns: &'a str, db: &'a str, user: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'l',
+			_e: b'o',
+			user,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::{KeyDecode, KeyEncode};
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Lo::new(
+			"testns",
+			"testdb",
+			"testuser",
+		);
+		let enc = Lo::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00*testdb\x00!lotestuser\x00");
+		let dec = Lo::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
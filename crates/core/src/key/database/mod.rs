@@ -4,9 +4,14 @@ pub mod all;
 pub mod ap;
 pub mod az;
 pub mod cg;
+pub mod cw;
 pub mod fc;
+pub mod lo;
 pub mod ml;
 pub mod pa;
+pub mod ro;
+pub mod sq;
+pub mod sv;
 pub mod tb;
 pub mod ti;
 pub mod ts;
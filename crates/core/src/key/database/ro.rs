@@ -0,0 +1,90 @@
+//! Stores a DEFINE ROLE ON DATABASE config definition
+use crate::err::Error;
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{impl_key, KeyEncode};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Ro<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub ro: &'a str,
+}
+impl_key!(Ro<'a>);
+
+pub fn new<'a>(ns: &'a str, db: &'a str, ro: &'a str) -> Ro<'a> {
+	Ro::new(ns, db, ro)
+}
+
+pub fn prefix(ns: &str, db: &str) -> Result<Vec<u8>, Error> {
+	let mut k = super::all::new(ns, db).encode()?;
+	k.extend_from_slice(b"!ro\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: &str, db: &str) -> Result<Vec<u8>, Error> {
+	let mut k = super::all::new(ns, db).encode()?;
+	k.extend_from_slice(b"!ro\xff");
+	Ok(k)
+}
+
+impl Categorise for Ro<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseRole
+	}
+}
+
+impl<'a> Ro<'a> {
+	pub fn new(ns: &'a str, db: &'a str, ro: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'r',
+			_e: b'o',
+			ro,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::KeyDecode;
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Ro::new(
+			"testns",
+			"testdb",
+			"testrole",
+		);
+		let enc = Ro::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00*testdb\x00!rotestrole\x00");
+		let dec = Ro::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns", "testdb").unwrap();
+		assert_eq!(val, b"/*testns\0*testdb\0!ro\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix("testns", "testdb").unwrap();
+		assert_eq!(val, b"/*testns\0*testdb\0!ro\xff");
+	}
+}
@@ -0,0 +1,56 @@
+//! Stores the encryption key check
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::impl_key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Encryption {
+	__: u8,
+	_a: u8,
+}
+
+impl_key!(Encryption);
+
+pub fn new() -> Encryption {
+	Encryption::new()
+}
+
+impl Categorise for Encryption {
+	fn categorise(&self) -> Category {
+		Category::Encryption
+	}
+}
+
+impl Encryption {
+	pub fn new() -> Self {
+		Self {
+			__: b'!',
+			_a: b'e',
+		}
+	}
+}
+
+impl Default for Encryption {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::{KeyDecode, KeyEncode};
+
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Encryption::new();
+		let enc = Encryption::encode(&val).unwrap();
+		assert_eq!(enc, b"!e");
+
+		let dec = Encryption::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
@@ -1,7 +1,8 @@
 //! Stores the key prefix for all keys under an index
+use crate::err::Error;
 use crate::key::category::Categorise;
 use crate::key::category::Category;
-use crate::kvs::impl_key;
+use crate::kvs::{impl_key, KeyEncode};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
@@ -23,6 +24,18 @@ pub fn new<'a>(ns: &'a str, db: &'a str, tb: &'a str, ix: &'a str) -> All<'a> {
 	All::new(ns, db, tb, ix)
 }
 
+pub fn tbprefix(ns: &str, db: &str, tb: &str) -> Result<Vec<u8>, Error> {
+	let mut k = crate::key::table::all::new(ns, db, tb).encode()?;
+	k.extend_from_slice(b"+\x00");
+	Ok(k)
+}
+
+pub fn tbsuffix(ns: &str, db: &str, tb: &str) -> Result<Vec<u8>, Error> {
+	let mut k = crate::key::table::all::new(ns, db, tb).encode()?;
+	k.extend_from_slice(b"+\xff");
+	Ok(k)
+}
+
 impl Categorise for All<'_> {
 	fn categorise(&self) -> Category {
 		Category::IndexRoot
@@ -47,7 +60,7 @@ impl<'a> All<'a> {
 
 #[cfg(test)]
 mod tests {
-	use crate::kvs::{KeyDecode, KeyEncode};
+	use crate::kvs::KeyDecode;
 	#[test]
 	fn key() {
 		use super::*;
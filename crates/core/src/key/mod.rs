@@ -1,12 +1,15 @@
 //! How the keys are structured in the key value store
 ///
 /// crate::key::version                  !v
+/// crate::key::encryption               !e
 ///
 /// crate::key::root::all                /
 /// crate::key::root::ac                 /!ac{ac}
+/// crate::key::root::lo                 /!lo{us}
 /// crate::key::root::nd                 /!nd{nd}
 /// crate::key::root::ni                 /!ni
 /// crate::key::root::ns                 /!ns{ns}
+/// crate::key::root::rs                 /!rs
 /// crate::key::root::us                 /!us{us}
 ///
 /// crate::key::node::all                /${nd}
@@ -20,6 +23,8 @@
 /// crate::key::namespace::db            /*{ns}!db{db}
 /// crate::key::namespace::di            /+{ns id}!di
 /// crate::key::namespace::lg            /*{ns}!lg{lg}
+/// crate::key::namespace::lo            /*{ns}!lo{us}
+/// crate::key::namespace::ro            /*{ns}!ro{ro}
 /// crate::key::namespace::us            /*{ns}!us{us}
 ///
 /// crate::key::namespace::access::all   /*{ns}&{ac}
@@ -28,9 +33,14 @@
 /// crate::key::database::all            /*{ns}*{db}
 /// crate::key::database::ac             /*{ns}*{db}!ac{ac}
 /// crate::key::database::az             /*{ns}*{db}!az{az}
+/// crate::key::database::cw             /*{ns}*{db}!cw
 /// crate::key::database::fc             /*{ns}*{db}!fn{fc}
+/// crate::key::database::lo             /*{ns}*{db}!lo{us}
 /// crate::key::database::ml             /*{ns}*{db}!ml{ml}{vn}
 /// crate::key::database::pa             /*{ns}*{db}!pa{pa}
+/// crate::key::database::ro             /*{ns}*{db}!ro{ro}
+/// crate::key::database::sq             /*{ns}*{db}!sq{sq}
+/// crate::key::database::sv             /*{ns}*{db}!sv{sq}
 /// crate::key::database::tb             /*{ns}*{db}!tb{tb}
 /// crate::key::database::ti             /+{ns id}*{db id}!ti
 /// crate::key::database::ts             /*{ns}*{db}!ts{ts}
@@ -40,9 +50,12 @@
 ///
 /// crate::key::database::access::all    /*{ns}*{db}&{ac}
 /// crate::key::database::access::gr     /*{ns}*{db}&{ac}!gr{gr}
+/// crate::key::database::access::lo     /*{ns}*{db}&{ac}!lo{id}
 ///
 /// crate::key::table::all               /*{ns}*{db}*{tb}
+/// crate::key::table::et                /*{ns}*{db}*{tb}!et{ts}{id}
 /// crate::key::table::ev                /*{ns}*{db}*{tb}!ev{ev}
+/// crate::key::table::ex                /*{ns}*{db}*{tb}!ex{id}
 /// crate::key::table::fd                /*{ns}*{db}*{tb}!fd{fd}
 /// crate::key::table::ft                /*{ns}*{db}*{tb}!ft{ft}
 /// crate::key::table::ix                /*{ns}*{db}*{tb}!ix{ix}
@@ -73,6 +86,7 @@ pub(crate) mod category;
 pub(crate) mod change;
 pub(crate) mod database;
 pub(crate) mod debug;
+pub(crate) mod encryption;
 pub(crate) mod graph;
 pub(crate) mod index;
 pub(crate) mod namespace;
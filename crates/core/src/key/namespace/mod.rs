@@ -3,4 +3,6 @@ pub mod access;
 pub mod all;
 pub mod db;
 pub mod di;
+pub mod lo;
+pub mod ro;
 pub mod us;
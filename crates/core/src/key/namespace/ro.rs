@@ -0,0 +1,85 @@
+//! Stores a DEFINE ROLE ON NAMESPACE config definition
+use crate::err::Error;
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{impl_key, KeyEncode};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Ro<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	_c: u8,
+	_d: u8,
+	pub ro: &'a str,
+}
+impl_key!(Ro<'a>);
+
+pub fn new<'a>(ns: &'a str, ro: &'a str) -> Ro<'a> {
+	Ro::new(ns, ro)
+}
+
+pub fn prefix(ns: &str) -> Result<Vec<u8>, Error> {
+	let mut k = super::all::new(ns).encode()?;
+	k.extend_from_slice(b"!ro\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: &str) -> Result<Vec<u8>, Error> {
+	let mut k = super::all::new(ns).encode()?;
+	k.extend_from_slice(b"!ro\xff");
+	Ok(k)
+}
+
+impl Categorise for Ro<'_> {
+	fn categorise(&self) -> Category {
+		Category::NamespaceRole
+	}
+}
+
+impl<'a> Ro<'a> {
+	pub fn new(ns: &'a str, ro: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'!',
+			_c: b'r',
+			_d: b'o',
+			ro,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::KeyDecode;
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Ro::new(
+			"testns",
+			"testrole",
+		);
+		let enc = Ro::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00!rotestrole\x00");
+		let dec = Ro::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns").unwrap();
+		assert_eq!(val, b"/*testns\0!ro\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix("testns").unwrap();
+		assert_eq!(val, b"/*testns\0!ro\xff");
+	}
+}
@@ -19,6 +19,12 @@ pub fn new(nd: Uuid) -> All {
 	All::new(nd)
 }
 
+pub fn prefix() -> Vec<u8> {
+	let mut k = crate::key::root::all::kv();
+	k.push(b'$');
+	k
+}
+
 impl Categorise for All {
 	fn categorise(&self) -> Category {
 		Category::NodeRoot
@@ -53,4 +59,10 @@ mod tests {
 		let dec = All::decode(&enc).unwrap();
 		assert_eq!(val, dec);
 	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix();
+		assert_eq!(val, b"/$");
+	}
 }
@@ -138,6 +138,18 @@ pub fn new<'a>(
 	Ref::new(ns, db, tb, id.to_owned(), ft, ff, fk.to_owned())
 }
 
+pub fn tbprefix(ns: &str, db: &str, tb: &str) -> Result<Vec<u8>, Error> {
+	let mut k = crate::key::table::all::new(ns, db, tb).encode()?;
+	k.extend_from_slice(b"&\x00");
+	Ok(k)
+}
+
+pub fn tbsuffix(ns: &str, db: &str, tb: &str) -> Result<Vec<u8>, Error> {
+	let mut k = crate::key::table::all::new(ns, db, tb).encode()?;
+	k.extend_from_slice(b"&\xff");
+	Ok(k)
+}
+
 pub fn prefix(ns: &str, db: &str, tb: &str, id: &Id) -> Result<Vec<u8>, Error> {
 	let mut k = Prefix::new(ns, db, tb, id).encode_owned()?;
 	k.extend_from_slice(&[0x00]);
@@ -0,0 +1,53 @@
+//! Stores the failed authentication state of a ROOT user
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::impl_key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Lo<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub user: &'a str,
+}
+impl_key!(Lo<'a>);
+
+pub fn new(user: &str) -> Lo<'_> {
+	Lo::new(user)
+}
+
+impl Categorise for Lo<'_> {
+	fn categorise(&self) -> Category {
+		Category::UserLockout
+	}
+}
+
+impl<'a> Lo<'a> {
+	pub fn new(user: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'l',
+			_c: b'o',
+			user,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::{KeyDecode, KeyEncode};
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Lo::new("testuser");
+		let enc = Lo::encode(&val).unwrap();
+		assert_eq!(enc, b"/!lotestuser\x00");
+		let dec = Lo::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
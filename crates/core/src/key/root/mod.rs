@@ -1,7 +1,9 @@
 pub mod ac;
 pub mod access;
 pub mod all;
+pub mod lo;
 pub mod nd;
 pub mod ni;
 pub mod ns;
+pub mod rs;
 pub mod us;
@@ -0,0 +1,56 @@
+//! Stores the marker of a restore which is in progress
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::impl_key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Rs {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+}
+impl_key!(Rs);
+
+pub fn new() -> Rs {
+	Rs::new()
+}
+
+impl Default for Rs {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Categorise for Rs {
+	fn categorise(&self) -> Category {
+		Category::Restore
+	}
+}
+
+impl Rs {
+	pub fn new() -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'r',
+			_c: b's',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::{KeyDecode, KeyEncode};
+	#[test]
+	fn key() {
+		use super::*;
+		let val = Rs::new();
+		let enc = Rs::encode(&val).unwrap();
+		assert_eq!(enc, b"/!rs");
+		let dec = Rs::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
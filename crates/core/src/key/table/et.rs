@@ -0,0 +1,120 @@
+//! Stores the records on a table with an EXPIRE clause, ordered by expiry time
+use crate::err::Error;
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{impl_key, KeyEncode};
+use crate::sql::{Datetime, Id};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Et<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub ts: u64,
+	pub id: Id,
+}
+impl_key!(Et<'a>);
+
+pub fn new<'a>(ns: &'a str, db: &'a str, tb: &'a str, at: &Datetime, id: &Id) -> Et<'a> {
+	Et::new(ns, db, tb, timestamp(at), id.to_owned())
+}
+
+pub fn prefix(ns: &str, db: &str, tb: &str) -> Result<Vec<u8>, Error> {
+	let mut k = super::all::new(ns, db, tb).encode()?;
+	k.extend_from_slice(b"!et\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str) -> Result<Vec<u8>, Error> {
+	let mut k = super::all::new(ns, db, tb).encode()?;
+	k.extend_from_slice(b"!et\xff");
+	Ok(k)
+}
+
+/// Returns the end of the range of records which expired at or before the given datetime
+pub fn until(ns: &str, db: &str, tb: &str, at: &Datetime) -> Result<Vec<u8>, Error> {
+	let mut k = super::all::new(ns, db, tb).encode()?;
+	k.extend_from_slice(b"!et");
+	k.extend_from_slice(&timestamp(at).saturating_add(1).to_be_bytes());
+	Ok(k)
+}
+
+/// Converts an expiry datetime into the timestamp used to order the keys
+fn timestamp(at: &Datetime) -> u64 {
+	match at.to_i64() {
+		Some(v) => v.max(0) as u64,
+		None if at.timestamp() < 0 => 0,
+		None => u64::MAX,
+	}
+}
+
+impl Categorise for Et<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableExpiryTime
+	}
+}
+
+impl<'a> Et<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, ts: u64, id: Id) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'e',
+			_f: b't',
+			ts,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::{KeyDecode, KeyEncode};
+	#[test]
+	fn key() {
+		use super::*;
+		let val = Et::new("testns", "testdb", "testtb", 123, Id::from("testid"));
+		let enc = Et::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\0*testdb\0*testtb\0!et\0\0\0\0\0\0\0\x7b\0\0\0\x01testid\0");
+
+		let dec = Et::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn prefix() {
+		let val = super::prefix("testns", "testdb", "testtb").unwrap();
+		assert_eq!(val, b"/*testns\0*testdb\0*testtb\0!et\0");
+	}
+
+	#[test]
+	fn suffix() {
+		let val = super::suffix("testns", "testdb", "testtb").unwrap();
+		assert_eq!(val, b"/*testns\0*testdb\0*testtb\0!et\xff");
+	}
+
+	#[test]
+	fn until() {
+		let at = crate::sql::Datetime::try_from((0, 123)).unwrap();
+		let val = super::until("testns", "testdb", "testtb", &at).unwrap();
+		assert_eq!(val, b"/*testns\0*testdb\0*testtb\0!et\0\0\0\0\0\0\0\x7c");
+		// Records which expired at the given datetime are included
+		let key = super::new("testns", "testdb", "testtb", &at, &"testid".into()).encode().unwrap();
+		assert!(key < val);
+	}
+}
@@ -0,0 +1,91 @@
+//! Stores the expiry time of a record on a table with an EXPIRE clause
+use crate::err::Error;
+use crate::key::category::Categorise;
+use crate::key::category::Category;
+use crate::kvs::{impl_key, KeyEncode};
+use crate::sql::Id;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Ex<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub id: Id,
+}
+impl_key!(Ex<'a>);
+
+pub fn new<'a>(ns: &'a str, db: &'a str, tb: &'a str, id: &Id) -> Ex<'a> {
+	Ex::new(ns, db, tb, id.to_owned())
+}
+
+pub fn prefix(ns: &str, db: &str, tb: &str) -> Result<Vec<u8>, Error> {
+	let mut k = super::all::new(ns, db, tb).encode()?;
+	k.extend_from_slice(b"!ex\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str) -> Result<Vec<u8>, Error> {
+	let mut k = super::all::new(ns, db, tb).encode()?;
+	k.extend_from_slice(b"!ex\xff");
+	Ok(k)
+}
+
+impl Categorise for Ex<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableExpiry
+	}
+}
+
+impl<'a> Ex<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, id: Id) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'e',
+			_f: b'x',
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::KeyDecode;
+	#[test]
+	fn key() {
+		use super::*;
+		let val = Ex::new("testns", "testdb", "testtb", Id::from("testid"));
+		let enc = Ex::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\0*testdb\0*testtb\0!ex\0\0\0\x01testid\0");
+
+		let dec = Ex::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn prefix() {
+		let val = super::prefix("testns", "testdb", "testtb").unwrap();
+		assert_eq!(val, b"/*testns\0*testdb\0*testtb\0!ex\0");
+	}
+
+	#[test]
+	fn suffix() {
+		let val = super::suffix("testns", "testdb", "testtb").unwrap();
+		assert_eq!(val, b"/*testns\0*testdb\0*testtb\0!ex\xff");
+	}
+}
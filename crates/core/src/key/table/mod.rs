@@ -1,5 +1,7 @@
 pub mod all;
+pub mod et;
 pub mod ev;
+pub mod ex;
 pub mod fd;
 pub mod ft;
 pub mod ix;
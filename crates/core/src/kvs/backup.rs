@@ -0,0 +1,482 @@
+//! Consistent, storage engine independent backups of the key-value store.
+//!
+//! A backup archive starts with a [`MAGIC`] header, followed by a [`Manifest`]
+//! describing the archive, a sequence of [`Entry`] items, and a final
+//! [`Entry::End`] marker. All items are serialized using revisioned encoding.
+//!
+//! A full backup contains every key-value pair in the datastore, read from a
+//! single snapshot transaction. An incremental backup contains the change feed
+//! entries recorded since the versionstamps of a previous backup manifest, and
+//! therefore requires every table to have a `CHANGEFEED`, either directly or
+//! through its database, and requires the change feed entries since that
+//! backup to still be retained. Changes to definitions are not recorded in the
+//! change feed, so a full backup is required whenever the definitions change.
+use super::Transaction;
+use super::{Datastore, LockType::*, TransactionType::*};
+use crate::cf::ChangeSet;
+use crate::cnf::EXPORT_BATCH_SIZE;
+use crate::dbs::Session;
+use crate::err::Error;
+use revision::revisioned;
+use revision::Revisioned;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+/// The header which every backup archive starts with
+pub const MAGIC: &[u8; 16] = b"SURREALDB-BACKUP";
+
+/// The versionstamp of a single database at the point of a backup
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Versionstamp {
+	pub ns: String,
+	pub db: String,
+	pub vs: u64,
+}
+
+/// Describes the contents of a backup archive
+#[revisioned(revision = 2)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Manifest {
+	/// The time at which the backup was taken, in seconds since the unix epoch
+	pub created: i64,
+	/// The versionstamp of each database at the point of the backup
+	pub versionstamps: Vec<Versionstamp>,
+	/// The versionstamps which an incremental backup follows on from
+	pub since: Option<Vec<Versionstamp>>,
+	/// A digest of the definitions at the point of the backup
+	#[revision(start = 2)]
+	pub definitions: String,
+}
+
+impl Manifest {
+	/// Check if this manifest describes an incremental backup
+	pub fn is_incremental(&self) -> bool {
+		self.since.is_some()
+	}
+
+	/// Reads the manifest from the start of a backup archive
+	pub fn read<R: Read>(r: &mut R) -> Result<Manifest, Error> {
+		// Check the archive header
+		let mut magic = [0u8; 16];
+		r.read_exact(&mut magic)?;
+		if &magic != MAGIC {
+			return Err(Error::InvalidBackup("the archive header is missing".to_owned()));
+		}
+		// Read the archive manifest
+		Ok(Manifest::deserialize_revisioned(r)?)
+	}
+
+	/// Retrieves the versionstamp recorded for a database
+	fn versionstamp(&self, ns: &str, db: &str) -> Option<u64> {
+		self.versionstamps.iter().find(|v| v.ns == ns && v.db == db).map(|v| v.vs)
+	}
+}
+
+/// A single item stored in a backup archive
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum Entry {
+	/// A raw key-value pair, stored in a full backup
+	Kv(Vec<u8>, Vec<u8>),
+	/// A change set for a database, stored in an incremental backup
+	Changes(String, String, ChangeSet),
+	/// Marks the end of the archive
+	End,
+}
+
+impl Datastore {
+	/// Writes a consistent backup of the datastore to a writer
+	///
+	/// When a previous manifest is provided, only the changes recorded in the
+	/// change feed since that backup are written, otherwise a full backup of
+	/// every key in the datastore is written. The manifest of this backup is
+	/// returned, so that it can be used for a subsequent incremental backup.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::backup", skip_all)]
+	pub async fn backup<W: Write>(
+		&self,
+		since: Option<&Manifest>,
+		mut w: W,
+	) -> Result<Manifest, Error> {
+		// Create a new readonly transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Write the backup from a single snapshot
+		let res = txn.backup(since, &mut w).await;
+		// Close the transaction
+		txn.cancel().await?;
+		// Flush any buffered output
+		w.flush()?;
+		// Return the manifest
+		res
+	}
+
+	/// Restores a backup archive from a reader into the datastore
+	///
+	/// Full backups can only be restored into an empty datastore, after which
+	/// any incremental backups should be restored in the order they were taken.
+	/// The archive is restored in batches of separate transactions, and the
+	/// datastore is marked as being restored until the whole archive has been
+	/// applied, so that a failed or truncated restore can be detected, and any
+	/// further restore into the datastore is refused.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::backup", skip_all)]
+	pub async fn restore<R: Read>(&self, mut r: R) -> Result<Manifest, Error> {
+		// Read the archive manifest
+		let manifest = Manifest::read(&mut r)?;
+		// Ensure the storage version is initialised
+		self.check_version().await?;
+		// Mark the datastore as being restored
+		let txn = self.transaction(Write, Optimistic).await?;
+		catch!(txn, txn.restore_begin(&manifest).await);
+		txn.commit().await?;
+		// Restore the archive entries in batches
+		let batch = *EXPORT_BATCH_SIZE as usize;
+		let mut done = false;
+		while !done {
+			// Create a new writeable transaction
+			let txn = self.transaction(Write, Optimistic).await?.enclose();
+			// Restore each of the archive entries in this batch
+			for _ in 0..batch {
+				match catch!(txn, Entry::deserialize_revisioned(&mut r).map_err(Error::from)) {
+					// Store the raw key-value pair
+					Entry::Kv(k, v) => {
+						catch!(txn, txn.set(k, v, None).await);
+					}
+					// Apply the changes to the database
+					Entry::Changes(ns, db, changes) => {
+						let ses = Session::owner().with_ns(&ns).with_db(&db);
+						let sql = Transaction::sync_sql(vec![changes]);
+						let ast = catch!(txn, crate::syn::parse(&sql));
+						let res = self.process_with_transaction(ast, &ses, None, txn.clone(), None);
+						let res = catch!(txn, res.await);
+						if let Some(Err(e)) = res.into_iter().map(|r| r.result).find(Result::is_err)
+						{
+							txn.cancel().await?;
+							return Err(e);
+						}
+					}
+					// The archive is complete
+					Entry::End => {
+						catch!(txn, txn.del(crate::key::root::rs::new()).await);
+						done = true;
+						break;
+					}
+				}
+				// Pause execution
+				yield_now!();
+			}
+			// Complete the changes and commit
+			let mut lock = txn.lock().await;
+			if let Err(e) = lock.complete_changes(false).await {
+				lock.cancel().await?;
+				return Err(e);
+			}
+			lock.commit().await?;
+		}
+		// Return the manifest
+		Ok(manifest)
+	}
+}
+
+impl Transaction {
+	/// Writes a backup archive from this transaction
+	async fn backup<W: Write>(
+		&self,
+		since: Option<&Manifest>,
+		w: &mut W,
+	) -> Result<Manifest, Error> {
+		// Create the manifest for this backup
+		let manifest = Manifest {
+			created: chrono::Utc::now().timestamp(),
+			versionstamps: self.backup_versionstamps().await?,
+			since: since.map(|m| m.versionstamps.clone()),
+			definitions: self.backup_definitions().await?,
+		};
+		// Changed definitions are not recorded in the change feed
+		if since.is_some_and(|since| since.definitions != manifest.definitions) {
+			return Err(Error::BackupDefinitionsChanged);
+		}
+		// Output the archive header
+		w.write_all(MAGIC)?;
+		manifest.serialize_revisioned(w)?;
+		// Output the archive contents
+		match since {
+			Some(since) => self.backup_changes(&manifest, since, w).await?,
+			None => self.backup_keys(w).await?,
+		}
+		// Output the end of the archive
+		Entry::End.serialize_revisioned(w)?;
+		// Return the manifest
+		Ok(manifest)
+	}
+
+	/// Fetches the current versionstamp of every database
+	async fn backup_versionstamps(&self) -> Result<Vec<Versionstamp>, Error> {
+		let mut res = Vec::new();
+		// Fetch all namespaces
+		for ns in self.all_ns().await?.iter() {
+			// Fetch all databases
+			for db in self.all_db(&ns.name).await?.iter() {
+				// Fetch the database versionstamp
+				let vs = self.sync_versionstamp(&ns.name, &db.name).await?;
+				res.push(Versionstamp {
+					ns: ns.name.to_raw(),
+					db: db.name.to_raw(),
+					vs: vs.into_u64_lossy(),
+				});
+			}
+		}
+		Ok(res)
+	}
+
+	/// Computes a digest of every definition in the datastore
+	async fn backup_definitions(&self) -> Result<String, Error> {
+		let mut hasher = blake3::Hasher::new();
+		let mut update = |v: String| {
+			hasher.update(v.as_bytes());
+			hasher.update(&[0]);
+		};
+		self.all_root_users().await?.iter().for_each(|v| update(v.to_string()));
+		self.all_root_accesses().await?.iter().for_each(|v| update(v.to_string()));
+		for ns in self.all_ns().await?.iter() {
+			update(ns.to_string());
+			self.all_ns_roles(&ns.name).await?.iter().for_each(|v| update(v.to_string()));
+			self.all_ns_users(&ns.name).await?.iter().for_each(|v| update(v.to_string()));
+			self.all_ns_accesses(&ns.name).await?.iter().for_each(|v| update(v.to_string()));
+			for db in self.all_db(&ns.name).await?.iter() {
+				update(db.to_string());
+				update(self.sync_definitions(&ns.name, &db.name).await?);
+			}
+		}
+		Ok(hasher.finalize().to_hex().to_string())
+	}
+
+	/// Writes every key-value pair in the datastore
+	async fn backup_keys<W: Write>(&self, w: &mut W) -> Result<(), Error> {
+		// Scan all keys under the root of the keyspace
+		let beg = crate::key::root::all::kv();
+		let mut end = beg.clone();
+		end.push(0xff);
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = self.batch_keys_vals(rng, *EXPORT_BATCH_SIZE, None).await?;
+			next = batch.next;
+			for (k, v) in batch.result {
+				// Skip any cluster node information
+				if Self::is_node_key(&k) {
+					continue;
+				}
+				Entry::Kv(k, v).serialize_revisioned(w)?;
+			}
+			// Pause execution
+			yield_now!();
+		}
+		Ok(())
+	}
+
+	/// Writes the changes recorded since a previous backup
+	async fn backup_changes<W: Write>(
+		&self,
+		manifest: &Manifest,
+		since: &Manifest,
+		w: &mut W,
+	) -> Result<(), Error> {
+		for Versionstamp {
+			ns,
+			db,
+			..
+		} in manifest.versionstamps.iter()
+		{
+			// Ensure that every change was recorded
+			self.sync_check_changefeeds(ns, db).await?;
+			// Continue from the versionstamp of the previous backup
+			let mut from = since.versionstamp(ns, db).map(|v| v + 1).unwrap_or_default();
+			// Ensure that no changes have been removed since then
+			self.sync_check_retention(ns, db, from).await?;
+			// Read the change feed in batches of whole change sets
+			loop {
+				let batch = Some(*EXPORT_BATCH_SIZE);
+				let (changes, next) = self.sync_changesets(ns, db, from, batch).await?;
+				if changes.is_empty() {
+					break;
+				}
+				for changes in changes {
+					Entry::Changes(ns.clone(), db.clone(), changes).serialize_revisioned(w)?;
+				}
+				from = next;
+				// Pause execution
+				yield_now!();
+			}
+		}
+		Ok(())
+	}
+
+	/// Marks the start of a restore into the datastore
+	async fn restore_begin(&self, manifest: &Manifest) -> Result<(), Error> {
+		// Calculate the restore marker key
+		let key = crate::key::root::rs::new();
+		// Refuse to continue from a restore which did not complete
+		if self.exists(key.clone(), None).await? {
+			return Err(Error::RestoreIncomplete);
+		}
+		// A full backup replaces the entire datastore
+		if !manifest.is_incremental() {
+			let empty = self.all_ns().await?.is_empty()
+				&& self.all_root_users().await?.is_empty()
+				&& self.all_root_accesses().await?.is_empty();
+			if !empty {
+				return Err(Error::RestoreNotEmpty);
+			}
+		}
+		// Store the time of the backup being restored
+		self.set(key, manifest.created.to_be_bytes().to_vec(), None).await
+	}
+
+	/// Check if a key stores cluster node information
+	fn is_node_key(k: &[u8]) -> bool {
+		let nodes = crate::key::root::nd::prefix()..crate::key::root::nd::suffix();
+		k.starts_with(&crate::key::node::all::prefix()) || nodes.contains(&k.to_vec())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KeyEncode;
+
+	#[tokio::test]
+	async fn backup_and_restore() {
+		let ses = Session::owner().with_ns("test").with_db("test");
+		// Create some data in the source datastore
+		let src = Datastore::new("memory").await.unwrap();
+		let sql = "DEFINE TABLE person CHANGEFEED 1h; CREATE person:one SET name = 'one';";
+		src.execute(sql, &ses, None).await.unwrap();
+		// Take a full backup
+		let mut full = Vec::new();
+		let manifest = src.backup(None, &mut full).await.unwrap();
+		assert!(!manifest.is_incremental());
+		assert_eq!(manifest.versionstamps.len(), 1);
+		// Make some further changes
+		let sql = "CREATE person:two SET name = 'two'; DELETE person:one;";
+		src.execute(sql, &ses, None).await.unwrap();
+		// Take an incremental backup
+		let mut incr = Vec::new();
+		let next = src.backup(Some(&manifest), &mut incr).await.unwrap();
+		assert!(next.is_incremental());
+		// Restore both backups into a new datastore
+		let dst = Datastore::new("memory").await.unwrap();
+		dst.restore(full.as_slice()).await.unwrap();
+		let res = dst.execute("SELECT VALUE name FROM person", &ses, None).await.unwrap();
+		assert_eq!(res.into_iter().next().unwrap().result.unwrap().to_string(), "['one']");
+		dst.restore(incr.as_slice()).await.unwrap();
+		let res = dst.execute("SELECT VALUE name FROM person", &ses, None).await.unwrap();
+		assert_eq!(res.into_iter().next().unwrap().result.unwrap().to_string(), "['two']");
+	}
+
+	#[tokio::test]
+	async fn backup_requires_changefeeds() {
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let src = Datastore::new("memory").await.unwrap();
+		let sql = "DEFINE TABLE person CHANGEFEED 1h; DEFINE TABLE other;";
+		src.execute(sql, &ses, None).await.unwrap();
+		let manifest = src.backup(None, &mut Vec::new()).await.unwrap();
+		// An incremental backup can not include tables without a change feed
+		let res = src.backup(Some(&manifest), &mut Vec::new()).await;
+		assert!(matches!(res, Err(Error::ChangefeedMissing { name }) if name == "other"));
+	}
+
+	#[tokio::test]
+	async fn backup_requires_unchanged_definitions() {
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let src = Datastore::new("memory").await.unwrap();
+		let sql = "DEFINE TABLE person CHANGEFEED 1h; CREATE person:one;";
+		src.execute(sql, &ses, None).await.unwrap();
+		let manifest = src.backup(None, &mut Vec::new()).await.unwrap();
+		// Changed data can be backed up incrementally
+		src.execute("CREATE person:two", &ses, None).await.unwrap();
+		let manifest = src.backup(Some(&manifest), &mut Vec::new()).await.unwrap();
+		// Changed definitions require a full backup
+		for sql in [
+			"DEFINE FIELD name ON person TYPE string",
+			"DEFINE INDEX name ON person FIELDS name",
+			"DEFINE FUNCTION fn::one() { 1 }",
+			"DEFINE USER test ON DATABASE PASSWORD 'test'",
+			"DEFINE DATABASE other",
+		] {
+			src.execute(sql, &ses, None).await.unwrap();
+			let res = src.backup(Some(&manifest), &mut Vec::new()).await;
+			assert!(matches!(res, Err(Error::BackupDefinitionsChanged)), "{sql}");
+		}
+	}
+
+	#[tokio::test]
+	async fn backup_requires_retained_changes() {
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let src = Datastore::new("memory").await.unwrap();
+		let sql = "DEFINE TABLE person CHANGEFEED 1h; CREATE person:one;";
+		src.execute(sql, &ses, None).await.unwrap();
+		let manifest = src.backup(None, &mut Vec::new()).await.unwrap();
+		src.execute("CREATE person:two", &ses, None).await.unwrap();
+		// Remove the change feed entries up to the latest change
+		let txn = src.transaction(Write, Optimistic).await.unwrap();
+		let vs = txn.sync_versionstamp("test", "test").await.unwrap();
+		crate::cf::gc_range(&txn, "test", "test", vs.next().unwrap()).await.unwrap();
+		txn.commit().await.unwrap();
+		// An incremental backup can not skip the removed changes
+		let res = src.backup(Some(&manifest), &mut Vec::new()).await;
+		assert!(matches!(res, Err(Error::ChangefeedExpired { .. })));
+	}
+
+	#[tokio::test]
+	async fn restore_truncated_archive() {
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let src = Datastore::new("memory").await.unwrap();
+		let sql = "DEFINE TABLE person CHANGEFEED 1h; CREATE person:one SET name = 'one';";
+		src.execute(sql, &ses, None).await.unwrap();
+		let mut full = Vec::new();
+		src.backup(None, &mut full).await.unwrap();
+		// An incomplete archive fails to restore
+		let dst = Datastore::new("memory").await.unwrap();
+		let res = dst.restore(&full[..full.len() - 1]).await;
+		assert!(res.is_err());
+		// The datastore is left marked as partially restored
+		let res = dst.restore(full.as_slice()).await;
+		assert!(matches!(res, Err(Error::RestoreIncomplete)));
+	}
+
+	#[tokio::test]
+	async fn restore_requires_empty_datastore() {
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let src = Datastore::new("memory").await.unwrap();
+		let sql = "DEFINE TABLE person CHANGEFEED 1h; CREATE |person:2500| RETURN NONE;";
+		src.execute(sql, &ses, None).await.unwrap();
+		let mut full = Vec::new();
+		src.backup(None, &mut full).await.unwrap();
+		// A full backup is restored in several batches
+		let dst = Datastore::new("memory").await.unwrap();
+		dst.restore(full.as_slice()).await.unwrap();
+		let sql = "SELECT count() FROM person GROUP ALL";
+		let mut res = dst.execute(sql, &ses, None).await.unwrap();
+		assert_eq!(res.remove(0).result.unwrap().to_string(), "[{ count: 2500 }]");
+		// A full backup is not restored over existing data
+		let res = dst.restore(full.as_slice()).await;
+		assert!(matches!(res, Err(Error::RestoreNotEmpty)));
+	}
+
+	#[test]
+	fn node_keys() {
+		let nd = uuid::Uuid::nil();
+		assert!(Transaction::is_node_key(&crate::key::root::nd::new(nd).encode().unwrap()));
+		assert!(Transaction::is_node_key(&crate::key::node::all::new(nd).encode().unwrap()));
+		assert!(!Transaction::is_node_key(&crate::key::root::ns::new("test").encode().unwrap()));
+	}
+
+	#[tokio::test]
+	async fn restore_invalid_archive() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let res = ds.restore(&b"INVALID"[..]).await;
+		assert!(res.is_err());
+	}
+}
@@ -20,6 +20,8 @@ pub(crate) enum Lookup<'a> {
 	Lvs(&'a str, &'a str, &'a str, Uuid),
 	/// A cache key for live queries version (on a table)
 	Lvv(&'a str, &'a str, &'a str),
+	/// A cache key for API middleware state (on a database)
+	Api(&'a str, &'a str, &'a str),
 }
 
 impl Equivalent<Key> for Lookup<'_> {
@@ -34,6 +36,7 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Ixs(la, lb, lc, ld), Key::Ixs(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Lvs(la, lb, lc, ld), Key::Lvs(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Lvv(la, lb, lc), Key::Lvv(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Api(la, lb, lc), Key::Api(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			_ => false,
 		}
 	}
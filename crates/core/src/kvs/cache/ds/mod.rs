@@ -3,6 +3,7 @@ mod key;
 mod lookup;
 mod weight;
 
+use crate::api::limit::RateLimits;
 use crate::err::Error;
 pub(crate) use entry::Entry;
 pub(crate) use lookup::Lookup;
@@ -13,6 +14,8 @@ pub(crate) type Cache = quick_cache::sync::Cache<key::Key, Entry, weight::Weight
 pub struct DatastoreCache {
 	/// Store the cache entries
 	cache: Cache,
+	/// Store the API rate limit windows
+	limits: RateLimits,
 }
 
 impl DatastoreCache {
@@ -25,6 +28,7 @@ impl DatastoreCache {
 		);
 		Self {
 			cache,
+			limits: RateLimits::default(),
 		}
 	}
 
@@ -38,6 +42,11 @@ impl DatastoreCache {
 		self.cache.insert(lookup.into(), entry);
 	}
 
+	/// Fetches the API rate limit windows
+	pub(crate) fn rate_limits(&self) -> &RateLimits {
+		&self.limits
+	}
+
 	/// Clear the cache entry for a table
 	pub(crate) fn clear_tb(&self, ns: &str, db: &str, tb: &str) {
 		let key = Lookup::Tb(ns, db, tb);
@@ -13,6 +13,8 @@ use crate::sql::statements::DefineIndexStatement;
 use crate::sql::statements::DefineModelStatement;
 use crate::sql::statements::DefineNamespaceStatement;
 use crate::sql::statements::DefineParamStatement;
+use crate::sql::statements::DefineRoleStatement;
+use crate::sql::statements::DefineSequenceStatement;
 use crate::sql::statements::DefineTableStatement;
 use crate::sql::statements::DefineUserStatement;
 use crate::sql::statements::LiveStatement;
@@ -39,6 +41,8 @@ pub(crate) enum Entry {
 	Nss(Arc<[DefineNamespaceStatement]>),
 	/// A slice of DefineUserStatement specified on a namespace.
 	Nus(Arc<[DefineUserStatement]>),
+	/// A slice of DefineRoleStatement specified on a namespace.
+	Nrs(Arc<[DefineRoleStatement]>),
 	/// A slice of DefineAccessStatement specified on a namespace.
 	Nas(Arc<[DefineAccessStatement]>),
 	/// A slice of AccessGrant specified at on a namespace.
@@ -55,6 +59,8 @@ pub(crate) enum Entry {
 	Dag(Arc<[AccessGrant]>),
 	/// A slice of DefineUserStatement specified on a database.
 	Dus(Arc<[DefineUserStatement]>),
+	/// A slice of DefineRoleStatement specified on a database.
+	Drs(Arc<[DefineRoleStatement]>),
 	/// A slice of DefineFunctionStatement specified on a database.
 	Fcs(Arc<[DefineFunctionStatement]>),
 	/// A slice of DefineTableStatement specified on a database.
@@ -65,6 +71,8 @@ pub(crate) enum Entry {
 	Cgs(Arc<[DefineConfigStatement]>),
 	/// A slice of DefineParamStatement specified on a database.
 	Pas(Arc<[DefineParamStatement]>),
+	/// A slice of DefineSequenceStatement specified on a database.
+	Sqs(Arc<[DefineSequenceStatement]>),
 	/// A slice of DefineEventStatement specified on a table.
 	Evs(Arc<[DefineEventStatement]>),
 	/// A slice of DefineFieldStatement specified on a table.
@@ -152,6 +160,14 @@ impl Entry {
 			_ => Err(fail!("Unable to convert type into Entry::Nus")),
 		}
 	}
+	/// Converts this cache entry into a slice of [`DefineRoleStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Nrs`].
+	pub(crate) fn try_into_nrs(self) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		match self {
+			Entry::Nrs(v) => Ok(v),
+			_ => Err(fail!("Unable to convert type into Entry::Nrs")),
+		}
+	}
 	/// Converts this cache entry into a slice of [`DefineDatabaseStatement`].
 	/// This panics if called on a cache entry that is not an [`Entry::Dbs`].
 	pub(crate) fn try_into_dbs(self) -> Result<Arc<[DefineDatabaseStatement]>, Error> {
@@ -184,6 +200,14 @@ impl Entry {
 			_ => Err(fail!("Unable to convert type into Entry::Dus")),
 		}
 	}
+	/// Converts this cache entry into a slice of [`DefineRoleStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Drs`].
+	pub(crate) fn try_into_drs(self) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		match self {
+			Entry::Drs(v) => Ok(v),
+			_ => Err(fail!("Unable to convert type into Entry::Drs")),
+		}
+	}
 	/// Converts this cache entry into a slice of [`ApiDefinition`].
 	/// This panics if called on a cache entry that is not an [`Entry::Aps`].
 	pub(crate) fn try_into_aps(self) -> Result<Arc<[ApiDefinition]>, Error> {
@@ -216,6 +240,14 @@ impl Entry {
 			_ => Err(fail!("Unable to convert type into Entry::Pas")),
 		}
 	}
+	/// Converts this cache entry into a slice of [`DefineSequenceStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Sqs`].
+	pub(crate) fn try_into_sqs(self) -> Result<Arc<[DefineSequenceStatement]>, Error> {
+		match self {
+			Entry::Sqs(v) => Ok(v),
+			_ => Err(fail!("Unable to convert type into Entry::Sqs")),
+		}
+	}
 	/// Converts this cache entry into a slice of [`DefineModelStatement`].
 	/// This panics if called on a cache entry that is not an [`Entry::Mls`].
 	pub(crate) fn try_into_mls(self) -> Result<Arc<[DefineModelStatement]>, Error> {
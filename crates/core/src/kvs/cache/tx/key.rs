@@ -16,6 +16,8 @@ pub(crate) enum Key {
 	Nss,
 	/// A cache key for namespace users
 	Nus(String),
+	/// A cache key for namespace roles
+	Nrs(String),
 	/// A cache key for namespace accesses
 	Nas(String),
 	/// A cache key for namespace access grants
@@ -24,6 +26,8 @@ pub(crate) enum Key {
 	Dbs(String),
 	/// A cache key for database users
 	Dus(String, String),
+	/// A cache key for database roles
+	Drs(String, String),
 	/// A cache key for database accesses
 	Das(String, String),
 	/// A cache key for database access grants
@@ -40,6 +44,8 @@ pub(crate) enum Key {
 	Cgs(String, String),
 	/// A cache key for parameters (on a database)
 	Pas(String, String),
+	/// A cache key for sequences (on a database)
+	Sqs(String, String),
 	/// A cache key for tables
 	Tbs(String, String),
 	/// A cache key for events (on a table)
@@ -64,6 +70,8 @@ pub(crate) enum Key {
 	Ns(String),
 	/// A cache key for a namespace user
 	Nu(String, String),
+	/// A cache key for a namespace role
+	Nr(String, String),
 	/// A cache key for a namespace access
 	Na(String, String),
 	/// A cache key for a namespace access grant
@@ -72,6 +80,8 @@ pub(crate) enum Key {
 	Db(String, String),
 	/// A cache key for a database user
 	Du(String, String, String),
+	/// A cache key for a database role
+	Dr(String, String, String),
 	/// A cache key for a database access
 	Da(String, String, String),
 	/// A cache key for a database access grant
@@ -88,6 +98,8 @@ pub(crate) enum Key {
 	Cg(String, String, String),
 	/// A cache key for a parameter (on a database)
 	Pa(String, String, String),
+	/// A cache key for a sequence (on a database)
+	Sq(String, String, String),
 	/// A cache key for a table
 	Tb(String, String, String),
 	/// A cache key for an event (on a table)
@@ -111,10 +123,12 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Rgs(a) => Key::Rgs(a.to_string()),
 			Lookup::Nss => Key::Nss,
 			Lookup::Nus(a) => Key::Nus(a.to_string()),
+			Lookup::Nrs(a) => Key::Nrs(a.to_string()),
 			Lookup::Nas(a) => Key::Nas(a.to_string()),
 			Lookup::Ngs(a, b) => Key::Ngs(a.to_string(), b.to_string()),
 			Lookup::Dbs(a) => Key::Dbs(a.to_string()),
 			Lookup::Dus(a, b) => Key::Dus(a.to_string(), b.to_string()),
+			Lookup::Drs(a, b) => Key::Drs(a.to_string(), b.to_string()),
 			Lookup::Das(a, b) => Key::Das(a.to_string(), b.to_string()),
 			Lookup::Dgs(a, b, c) => Key::Dgs(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Aps(a, b) => Key::Aps(a.to_string(), b.to_string()),
@@ -123,6 +137,7 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Mls(a, b) => Key::Mls(a.to_string(), b.to_string()),
 			Lookup::Cgs(a, b) => Key::Cgs(a.to_string(), b.to_string()),
 			Lookup::Pas(a, b) => Key::Pas(a.to_string(), b.to_string()),
+			Lookup::Sqs(a, b) => Key::Sqs(a.to_string(), b.to_string()),
 			Lookup::Tbs(a, b) => Key::Tbs(a.to_string(), b.to_string()),
 			Lookup::Evs(a, b, c) => Key::Evs(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Fds(a, b, c) => Key::Fds(a.to_string(), b.to_string(), c.to_string()),
@@ -136,10 +151,12 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Rg(a, b) => Key::Rg(a.to_string(), b.to_string()),
 			Lookup::Ns(a) => Key::Ns(a.to_string()),
 			Lookup::Nu(a, b) => Key::Nu(a.to_string(), b.to_string()),
+			Lookup::Nr(a, b) => Key::Nr(a.to_string(), b.to_string()),
 			Lookup::Na(a, b) => Key::Na(a.to_string(), b.to_string()),
 			Lookup::Ng(a, b, c) => Key::Ng(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Db(a, b) => Key::Db(a.to_string(), b.to_string()),
 			Lookup::Du(a, b, c) => Key::Du(a.to_string(), b.to_string(), c.to_string()),
+			Lookup::Dr(a, b, c) => Key::Dr(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Da(a, b, c) => Key::Da(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Dg(a, b, c, d) => Key::Dg(a.to_string(), b.to_string(), c.to_string(), d.to_string()),
 			Lookup::Ap(a, b, c) => Key::Ap(a.to_string(), b.to_string(), c.to_string()),
@@ -148,6 +165,7 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Ml(a, b, c, d) => Key::Ml(a.to_string(), b.to_string(), c.to_string(), d.to_string()),
 			Lookup::Cg(a, b, c) => Key::Cg(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Pa(a, b, c) => Key::Pa(a.to_string(), b.to_string(), c.to_string()),
+			Lookup::Sq(a, b, c) => Key::Sq(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Tb(a, b, c) => Key::Tb(a.to_string(), b.to_string(), c.to_string()),
 			Lookup::Ev(a, b, c, d) => Key::Ev(a.to_string(), b.to_string(), c.to_string(), d.to_string()),
 			Lookup::Fd(a, b, c, d) => Key::Fd(a.to_string(), b.to_string(), c.to_string(), d.to_string()),
@@ -17,6 +17,8 @@ pub(crate) enum Lookup<'a> {
 	Nss,
 	/// A cache key for namespace users
 	Nus(&'a str),
+	/// A cache key for namespace roles
+	Nrs(&'a str),
 	/// A cache key for namespace accesses
 	Nas(&'a str),
 	/// A cache key for namespace access grants
@@ -25,6 +27,8 @@ pub(crate) enum Lookup<'a> {
 	Dbs(&'a str),
 	/// A cache key for database users
 	Dus(&'a str, &'a str),
+	/// A cache key for database roles
+	Drs(&'a str, &'a str),
 	/// A cache key for database accesses
 	Das(&'a str, &'a str),
 	/// A cache key for database access grants
@@ -41,6 +45,8 @@ pub(crate) enum Lookup<'a> {
 	Cgs(&'a str, &'a str),
 	/// A cache key for parameters (on a database)
 	Pas(&'a str, &'a str),
+	/// A cache key for sequences (on a database)
+	Sqs(&'a str, &'a str),
 	/// A cache key for tables
 	Tbs(&'a str, &'a str),
 	/// A cache key for events (on a table)
@@ -65,6 +71,8 @@ pub(crate) enum Lookup<'a> {
 	Ns(&'a str),
 	/// A cache key for a namespace user
 	Nu(&'a str, &'a str),
+	/// A cache key for a namespace role
+	Nr(&'a str, &'a str),
 	/// A cache key for a namespace access
 	Na(&'a str, &'a str),
 	/// A cache key for a namespace access grant
@@ -73,6 +81,8 @@ pub(crate) enum Lookup<'a> {
 	Db(&'a str, &'a str),
 	/// A cache key for a database user
 	Du(&'a str, &'a str, &'a str),
+	/// A cache key for a database role
+	Dr(&'a str, &'a str, &'a str),
 	/// A cache key for a database access
 	Da(&'a str, &'a str, &'a str),
 	/// A cache key for a database access grant
@@ -89,6 +99,8 @@ pub(crate) enum Lookup<'a> {
 	Cg(&'a str, &'a str, &'a str),
 	/// A cache key for a parameter (on a database)
 	Pa(&'a str, &'a str, &'a str),
+	/// A cache key for a sequence (on a database)
+	Sq(&'a str, &'a str, &'a str),
 	/// A cache key for a table
 	Tb(&'a str, &'a str, &'a str),
 	/// A cache key for an event (on a table)
@@ -112,10 +124,12 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Rgs(la), Key::Rgs(ka)) => la == ka,
 			(Self::Nss, Key::Nss) => true,
 			(Self::Nus(la), Key::Nus(ka)) => la == ka,
+			(Self::Nrs(la), Key::Nrs(ka)) => la == ka,
 			(Self::Nas(la), Key::Nas(ka)) => la == ka,
 			(Self::Ngs(la, lb), Key::Ngs(ka, kb)) => la == ka && lb == kb,
 			(Self::Dbs(la), Key::Dbs(ka)) => la == ka,
 			(Self::Dus(la, lb), Key::Dus(ka, kb)) => la == ka && lb == kb,
+			(Self::Drs(la, lb), Key::Drs(ka, kb)) => la == ka && lb == kb,
 			(Self::Das(la, lb), Key::Das(ka, kb)) => la == ka && lb == kb,
 			(Self::Dgs(la, lb, lc), Key::Dgs(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Aps(la, lb), Key::Aps(ka, kb)) => la == ka && lb == kb,
@@ -124,6 +138,7 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Mls(la, lb), Key::Mls(ka, kb)) => la == ka && lb == kb,
 			(Self::Cgs(la, lb), Key::Cgs(ka, kb)) => la == ka && lb == kb,
 			(Self::Pas(la, lb), Key::Pas(ka, kb)) => la == ka && lb == kb,
+			(Self::Sqs(la, lb), Key::Sqs(ka, kb)) => la == ka && lb == kb,
 			(Self::Tbs(la, lb), Key::Tbs(ka, kb)) => la == ka && lb == kb,
 			(Self::Evs(la, lb, lc), Key::Evs(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Fds(la, lb, lc), Key::Fds(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
@@ -137,10 +152,12 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Rg(la, lb), Key::Rg(ka, kb)) => la == ka && lb == kb,
 			(Self::Ns(la), Key::Ns(ka)) => la == ka,
 			(Self::Nu(la, lb), Key::Nu(ka, kb)) => la == ka && lb == kb,
+			(Self::Nr(la, lb), Key::Nr(ka, kb)) => la == ka && lb == kb,
 			(Self::Na(la, lb), Key::Na(ka, kb)) => la == ka && lb == kb,
 			(Self::Ng(la, lb, lc), Key::Ng(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Db(la, lb), Key::Db(ka, kb)) => la == ka && lb == kb,
 			(Self::Du(la, lb, lc), Key::Du(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Dr(la, lb, lc), Key::Dr(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Da(la, lb, lc), Key::Da(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Dg(la, lb, lc, ld), Key::Dg(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Ap(la, lb, lc), Key::Ap(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
@@ -149,6 +166,7 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Ml(la, lb, lc, ld), Key::Ml(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Cg(la, lb, lc), Key::Cg(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Pa(la, lb, lc), Key::Pa(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Sq(la, lb, lc), Key::Sq(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Tb(la, lb, lc), Key::Tb(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Ev(la, lb, lc, ld), Key::Ev(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Fd(la, lb, lc, ld), Key::Fd(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
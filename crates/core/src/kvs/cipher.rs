@@ -0,0 +1,248 @@
+//! Transparent encryption of the values stored in the key-value store.
+//!
+//! Keys are stored in plaintext, so that they remain ordered and range scans
+//! continue to work on every storage engine. Each value is encrypted using
+//! AES-256-GCM with a random nonce, and is stored with a small header:
+//!
+//! ```text
+//! | MAGIC (1 byte) | key id (4 bytes) | nonce (12 bytes) | ciphertext and tag |
+//! ```
+//!
+//! The encryption key is 32 random bytes, encoded as hex or base64. Keys are
+//! not stretched, so passphrases and other low entropy keys are rejected.
+//!
+//! The key id is derived from the encryption key, which allows values
+//! encrypted with a previous key to be found and rewritten with a new key.
+//! The key under which a value is stored is authenticated along with the
+//! value, so that an encrypted value can not be moved to another key.
+//!
+//! Versionstamped keys are only completed by the storage engine when the
+//! transaction commits, so their values are authenticated against the key
+//! without the versionstamp, and are stored with a different header which
+//! records the position of the versionstamp within the key:
+//!
+//! ```text
+//! | MAGIC_VERSIONSTAMPED (1 byte) | position (4 bytes) | key id (4 bytes) | nonce (12 bytes) | ciphertext and tag |
+//! ```
+
+use crate::err::Error;
+use crate::iam::base::{Engine, BASE64};
+use crate::kvs::Val;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::sync::Arc;
+
+/// The first byte of every encrypted value
+const MAGIC: u8 = 0xec;
+
+/// The first byte of every encrypted value stored under a versionstamped key
+const MAGIC_VERSIONSTAMPED: u8 = 0xed;
+
+/// The length of the position of the versionstamp in a versionstamped key
+const POSITION_LEN: usize = 4;
+
+/// The length of the versionstamp in a versionstamped key
+const VERSIONSTAMP_LEN: usize = 10;
+
+/// The length of the decoded encryption key
+const KEY_LEN: usize = 32;
+
+/// The length of the key id stored in the value header
+const KEY_ID_LEN: usize = 4;
+
+/// The length of the key id and nonce which precede the ciphertext
+const HEADER_LEN: usize = KEY_ID_LEN + NONCE_LEN;
+
+/// The value which is stored to check the encryption key of a datastore
+const CHECK: &[u8] = b"surrealdb encryption key check";
+
+/// The context used when deriving the cipher key from the encryption key
+const CIPHER_CONTEXT: &str = "surrealdb 2025 kvs value encryption key";
+
+/// The context used when deriving the key id from the encryption key
+const KEY_ID_CONTEXT: &str = "surrealdb 2025 kvs value encryption key id";
+
+#[derive(Clone)]
+struct EncryptionKey {
+	/// The identifier which is stored with each encrypted value
+	id: [u8; KEY_ID_LEN],
+	/// The key used for sealing and opening values
+	key: Arc<LessSafeKey>,
+}
+
+/// Decodes an encryption key, which is 32 bytes encoded as hex or base64
+pub fn decode_encryption_key(key: &str) -> Result<[u8; KEY_LEN], Error> {
+	let bytes = match key.len() {
+		64 => hex::decode(key).ok(),
+		_ => BASE64.decode(key).ok(),
+	};
+	bytes.and_then(|v| v.try_into().ok()).ok_or_else(|| {
+		Error::Ds(
+			"The datastore encryption key must be 32 random bytes, encoded as hex or base64".into(),
+		)
+	})
+}
+
+impl EncryptionKey {
+	fn new(key: &str) -> Result<Self, Error> {
+		// Decode the encryption key
+		let key = decode_encryption_key(key)?;
+		// Derive the cipher key from the encryption key
+		let bytes = blake3::derive_key(CIPHER_CONTEXT, &key);
+		let unbound = UnboundKey::new(&AES_256_GCM, &bytes)
+			.map_err(|_| Error::Ds("Unable to initialise the datastore encryption key".into()))?;
+		// Derive the key id from the encryption key
+		let mut id = [0; KEY_ID_LEN];
+		id.copy_from_slice(&blake3::derive_key(KEY_ID_CONTEXT, &key)[..KEY_ID_LEN]);
+		Ok(Self {
+			id,
+			key: Arc::new(LessSafeKey::new(unbound)),
+		})
+	}
+}
+
+/// Encrypts values before they are written, and decrypts them once read.
+pub(super) struct Cipher {
+	/// The key used to encrypt values, if values should be encrypted
+	current: Option<EncryptionKey>,
+	/// A previous key which values may still be encrypted with
+	previous: Option<EncryptionKey>,
+	/// Whether values which are not encrypted should be accepted
+	plaintext: bool,
+	/// The source of randomness for the nonces
+	rng: SystemRandom,
+}
+
+impl Cipher {
+	/// Creates a cipher which encrypts and decrypts values with the given key
+	pub(super) fn new(key: &str) -> Result<Self, Error> {
+		Ok(Self {
+			current: Some(EncryptionKey::new(key)?),
+			previous: None,
+			plaintext: false,
+			rng: SystemRandom::new(),
+		})
+	}
+
+	/// Creates a cipher for rewriting the values in a datastore.
+	///
+	/// Values are read using either the existing or the new key, so that an
+	/// interrupted rewrite can be run again, and are written using the new
+	/// key. When either key is missing, values which are not encrypted are
+	/// accepted. When there is no new key, values are written without any
+	/// encryption.
+	pub(super) fn rewrite(existing: Option<&Cipher>, key: Option<&str>) -> Result<Self, Error> {
+		let current = key.map(EncryptionKey::new).transpose()?;
+		let previous = existing.and_then(|c| c.current.clone());
+		if current.is_none() && previous.is_none() {
+			return Err(Error::Ds(
+				"An encryption key or a previous encryption key must be specified".into(),
+			));
+		}
+		Ok(Self {
+			plaintext: current.is_none() || previous.is_none(),
+			current,
+			previous,
+			rng: SystemRandom::new(),
+		})
+	}
+
+	/// Creates the value which is stored to check the encryption key of a datastore
+	pub(super) fn check(&self, key: &[u8]) -> Result<Val, Error> {
+		self.encrypt(key, CHECK.to_vec())
+	}
+
+	/// Checks if a stored check value was encrypted with one of the known keys
+	pub(super) fn verify(&self, key: &[u8], val: &[u8]) -> bool {
+		self.open(key, val).is_some_and(|v| v == CHECK)
+	}
+
+	/// Encrypts a value with the current key, authenticating the key it is stored under
+	pub(super) fn encrypt(&self, key: &[u8], val: Val) -> Result<Val, Error> {
+		self.seal(&[MAGIC], key, val)
+	}
+
+	/// Encrypts a value which is stored under a versionstamped key.
+	///
+	/// The versionstamp is filled in by the storage engine on commit, so the
+	/// value is only authenticated against the prefix and the suffix of the key.
+	pub(super) fn encrypt_versionstamped(
+		&self,
+		prefix: &[u8],
+		suffix: &[u8],
+		val: Val,
+	) -> Result<Val, Error> {
+		let pos = u32::try_from(prefix.len())
+			.map_err(|_| Error::Tx("Unable to encrypt a value".into()))?;
+		let mut header = vec![MAGIC_VERSIONSTAMPED];
+		header.extend_from_slice(&pos.to_be_bytes());
+		self.seal(&header, &[prefix, suffix].concat(), val)
+	}
+
+	/// Encrypts a value with the current key, prefixing it with the given header
+	fn seal(&self, header: &[u8], aad: &[u8], val: Val) -> Result<Val, Error> {
+		// Values are left unencrypted without a current key
+		let Some(current) = &self.current else {
+			return Ok(val);
+		};
+		// Generate a random nonce for this value
+		let mut nonce = [0; NONCE_LEN];
+		self.rng.fill(&mut nonce).map_err(|_| Error::Tx("Unable to encrypt a value".into()))?;
+		// Encrypt the value, appending the authentication tag
+		let mut data = val;
+		current
+			.key
+			.seal_in_place_append_tag(
+				Nonce::assume_unique_for_key(nonce),
+				Aad::from(aad),
+				&mut data,
+			)
+			.map_err(|_| Error::Tx("Unable to encrypt a value".into()))?;
+		// Prefix the ciphertext with the header
+		let mut res = Vec::with_capacity(header.len() + HEADER_LEN + data.len());
+		res.extend_from_slice(header);
+		res.extend_from_slice(&current.id);
+		res.extend_from_slice(&nonce);
+		res.extend_from_slice(&data);
+		Ok(res)
+	}
+
+	/// Decrypts a value with the current or previous key, checking the key it is stored under
+	pub(super) fn decrypt(&self, key: &[u8], val: Val) -> Result<Val, Error> {
+		// Encrypted values are never empty
+		if val.is_empty() {
+			return Ok(val);
+		}
+		match self.open(key, &val) {
+			Some(res) => Ok(res),
+			None if self.plaintext => Ok(val),
+			None => Err(Error::TxDecryptionFailed),
+		}
+	}
+
+	/// Opens a value, if it was encrypted with one of the known keys
+	fn open(&self, key: &[u8], val: &[u8]) -> Option<Val> {
+		let (aad, val) = match val.first() {
+			Some(&MAGIC) => (key.to_vec(), &val[1..]),
+			// The versionstamp is not authenticated along with the value
+			Some(&MAGIC_VERSIONSTAMPED) if val.len() > POSITION_LEN => {
+				let (pos, val) = val[1..].split_at(POSITION_LEN);
+				let pos = u32::from_be_bytes(pos.try_into().ok()?) as usize;
+				let suffix = key.get(pos.checked_add(VERSIONSTAMP_LEN)?..)?;
+				([&key[..pos], suffix].concat(), val)
+			}
+			_ => return None,
+		};
+		if val.len() < HEADER_LEN {
+			return None;
+		}
+		let (id, rest) = val.split_at(KEY_ID_LEN);
+		let (nonce, data) = rest.split_at(NONCE_LEN);
+		let cipher = [&self.current, &self.previous].into_iter().flatten().find(|k| k.id == id)?;
+		let nonce = Nonce::try_assume_unique_for_key(nonce).ok()?;
+		let mut data = data.to_vec();
+		let len = cipher.key.open_in_place(nonce, Aad::from(&aad), &mut data).ok()?.len();
+		data.truncate(len);
+		Some(data)
+	}
+}
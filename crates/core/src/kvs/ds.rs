@@ -1,8 +1,10 @@
 use super::export;
+use super::import;
 use super::tr::Transactor;
 use super::tx::Transaction;
 use super::version::Version;
 use crate::cf;
+use crate::cnf::{NORMAL_FETCH_SIZE, SYNC_APPLY_BATCH_SIZE, SYNC_APPLY_MAX_SIZE};
 use crate::ctx::MutableContext;
 #[cfg(feature = "jwks")]
 use crate::dbs::capabilities::NetTarget;
@@ -14,23 +16,30 @@ use crate::dbs::{
 	Attach, Capabilities, Executor, Notification, Options, Response, Session, Variables,
 };
 use crate::err::Error;
+use crate::iam::check::role_policies;
 #[cfg(feature = "jwks")]
 use crate::iam::jwks::JwksCache;
-use crate::iam::{Action, Auth, Error as IamError, Resource, Role};
+use crate::iam::{Action, Auth, Error as IamError, Resource, ResourceKind, Role};
 use crate::idx::trees::store::IndexStores;
+use crate::kvs::batch::Batch;
 use crate::kvs::cache::ds::DatastoreCache;
+use crate::kvs::cipher::Cipher;
 use crate::kvs::clock::SizedClock;
 #[allow(unused_imports)]
 use crate::kvs::clock::SystemClock;
 #[cfg(not(target_family = "wasm"))]
 use crate::kvs::index::IndexBuilder;
-use crate::kvs::{LockType, LockType::*, TransactionType, TransactionType::*};
-use crate::sql::{statements::DefineUserStatement, Base, Query, Value};
+use crate::kvs::sequences::Sequences;
+use crate::kvs::{Key, LockType, LockType::*, TransactionType, TransactionType::*, Val};
+use crate::kvs::{KeyDecode as _, KeyEncode as _};
+use crate::sql::{
+	statements::DefineUserStatement, Base, Datetime, Ident, Query, Statement, Statements, Value,
+};
 use crate::syn;
 use crate::syn::parser::{ParserSettings, StatementStream};
 use async_channel::{Receiver, Sender};
 use bytes::{Bytes, BytesMut};
-use futures::{Future, Stream};
+use futures::{Future, Stream, StreamExt};
 use reblessive::TreeStack;
 use std::fmt;
 #[cfg(storage)]
@@ -41,12 +50,16 @@ use std::task::{ready, Poll};
 use std::time::Duration;
 #[cfg(not(target_family = "wasm"))]
 use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(not(target_family = "wasm"))]
+use tokio::spawn;
 #[cfg(feature = "jwks")]
 use tokio::sync::RwLock;
 use tracing::instrument;
 use tracing::trace;
 use uuid::Uuid;
 #[cfg(target_family = "wasm")]
+use wasm_bindgen_futures::spawn_local as spawn;
+#[cfg(target_family = "wasm")]
 use wasmtimer::std::{SystemTime, UNIX_EPOCH};
 
 const TARGET: &str = "surrealdb::core::kvs::ds";
@@ -68,6 +81,8 @@ pub struct Datastore {
 	strict: bool,
 	/// Whether authentication is enabled on this datastore.
 	auth_enabled: bool,
+	/// Whether queries which write data are rejected on this datastore.
+	read_only: bool,
 	/// The maximum duration timeout for running multiple statements in a query.
 	query_timeout: Option<Duration>,
 	/// The maximum duration timeout for running multiple statements in a transaction.
@@ -83,6 +98,8 @@ pub struct Datastore {
 	// The index asynchronous builder
 	#[cfg(not(target_family = "wasm"))]
 	index_builder: IndexBuilder,
+	// The sequence value allocator
+	sequences: Sequences,
 	#[cfg(feature = "jwks")]
 	// The JWKS object cache
 	jwks_cache: Arc<RwLock<JwksCache>>,
@@ -97,9 +114,20 @@ pub(super) struct TransactionFactory {
 	clock: Arc<SizedClock>,
 	// The inner datastore type
 	flavor: Arc<DatastoreFlavor>,
+	// The cipher used to encrypt and decrypt stored values
+	cipher: Option<Arc<Cipher>>,
 }
 
 impl TransactionFactory {
+	/// Creates a transaction factory which reads and writes values without any encryption
+	fn unencrypted(&self) -> Self {
+		Self {
+			clock: self.clock.clone(),
+			flavor: self.flavor.clone(),
+			cipher: None,
+		}
+	}
+
 	#[allow(unreachable_code)]
 	pub async fn transaction(
 		&self,
@@ -167,6 +195,7 @@ impl TransactionFactory {
 				stash: super::stash::Stash::default(),
 				cf: cf::Writer::new(),
 				clock: self.clock.clone(),
+				cipher: self.cipher.clone(),
 			},
 		))
 	}
@@ -406,19 +435,22 @@ impl Datastore {
 			let tf = TransactionFactory {
 				clock,
 				flavor: Arc::new(flavor),
+				cipher: None,
 			};
 			Self {
 				id: Uuid::new_v4(),
 				transaction_factory: tf.clone(),
 				strict: false,
 				auth_enabled: false,
+				read_only: false,
 				query_timeout: None,
 				transaction_timeout: None,
 				notification_channel: None,
 				capabilities: Arc::new(Capabilities::default()),
 				index_stores: IndexStores::default(),
 				#[cfg(not(target_family = "wasm"))]
-				index_builder: IndexBuilder::new(tf),
+				index_builder: IndexBuilder::new(tf.clone()),
+				sequences: Sequences::new(tf),
 				#[cfg(feature = "jwks")]
 				jwks_cache: Arc::new(RwLock::new(JwksCache::new())),
 				#[cfg(storage)]
@@ -436,6 +468,7 @@ impl Datastore {
 			id: self.id,
 			strict: self.strict,
 			auth_enabled: self.auth_enabled,
+			read_only: self.read_only,
 			query_timeout: self.query_timeout,
 			transaction_timeout: self.transaction_timeout,
 			capabilities: self.capabilities,
@@ -443,6 +476,7 @@ impl Datastore {
 			index_stores: Default::default(),
 			#[cfg(not(target_family = "wasm"))]
 			index_builder: IndexBuilder::new(self.transaction_factory.clone()),
+			sequences: Sequences::new(self.transaction_factory.clone()),
 			#[cfg(feature = "jwks")]
 			jwks_cache: Arc::new(Default::default()),
 			#[cfg(storage)]
@@ -488,12 +522,37 @@ impl Datastore {
 		self
 	}
 
+	/// Set whether queries which write data are rejected on this Datastore
+	pub fn with_read_only(mut self, read_only: bool) -> Self {
+		self.read_only = read_only;
+		self
+	}
+
 	/// Set specific capabilities for this Datastore
 	pub fn with_capabilities(mut self, caps: Capabilities) -> Self {
 		self.capabilities = Arc::new(caps);
 		self
 	}
 
+	/// Set a key for encrypting the values stored in this Datastore
+	///
+	/// The key is checked against the values which are already stored, so
+	/// that an encrypted datastore is never opened with a different key or
+	/// without a key, and a datastore which is not encrypted is never opened
+	/// with a key. Use `rewrite_encryption` to change the key of a datastore.
+	pub async fn with_encryption_key(mut self, key: Option<String>) -> Result<Self, Error> {
+		self.transaction_factory.cipher = key.map(|k| Cipher::new(&k).map(Arc::new)).transpose()?;
+		// Check the key against the stored values
+		self.check_encryption().await?;
+		// Recreate the services which hold a copy of the transaction factory
+		#[cfg(not(target_family = "wasm"))]
+		{
+			self.index_builder = IndexBuilder::new(self.transaction_factory.clone());
+		}
+		self.sequences = Sequences::new(self.transaction_factory.clone());
+		Ok(self)
+	}
+
 	#[cfg(storage)]
 	/// Set a temporary directory for ordering of large result sets
 	pub fn with_temporary_directory(mut self, path: Option<PathBuf>) -> Self {
@@ -510,6 +569,11 @@ impl Datastore {
 		self.auth_enabled
 	}
 
+	/// Are queries which write data rejected on this Datastore?
+	pub fn is_read_only(&self) -> bool {
+		self.read_only
+	}
+
 	pub fn id(&self) -> Uuid {
 		self.id
 	}
@@ -627,6 +691,142 @@ impl Datastore {
 		Ok(val)
 	}
 
+	/// Check that the encryption key matches the stored values.
+	async fn check_encryption(&self) -> Result<(), Error> {
+		// Read the stored key check without decrypting it
+		let tf = self.transaction_factory.unencrypted();
+		let txn = tf.transaction(Write, Optimistic).await?.enclose();
+		let key = crate::key::encryption::new().encode()?;
+		let val = catch!(txn, txn.get(key.as_slice(), None).await);
+		match (self.transaction_factory.cipher.as_deref(), val) {
+			// The datastore is not encrypted
+			(None, None) => txn.cancel().await,
+			// The datastore is encrypted with this key
+			(Some(cipher), Some(val)) if cipher.verify(&key, &val) => txn.cancel().await,
+			// The datastore is encrypted with another key
+			(Some(_), Some(_)) => {
+				txn.cancel().await?;
+				Err(Error::Ds("The datastore is encrypted with a different encryption key".into()))
+			}
+			// The datastore is encrypted, but no key was specified
+			(None, Some(_)) => {
+				txn.cancel().await?;
+				Err(Error::Ds(
+					"The datastore is encrypted, but no encryption key was specified".into(),
+				))
+			}
+			// Only an empty datastore can be encrypted without rewriting it
+			(Some(cipher), None) => {
+				let keys = catch!(txn, txn.keys(vec![0x00]..vec![0xff], 1, None).await);
+				if !keys.is_empty() {
+					txn.cancel().await?;
+					return Err(Error::Ds(
+						"The datastore is not encrypted. Use the rekey command to encrypt the stored values".into(),
+					));
+				}
+				let val = catch!(txn, cipher.check(&key));
+				catch!(txn, txn.set(key, val, None).await);
+				txn.commit().await
+			}
+		}
+	}
+
+	/// Rewrite every stored value using a new encryption key.
+	///
+	/// Values are decrypted using either the current key of this datastore,
+	/// or the new `key`, and are written back using the new key. Without a
+	/// new key, all values are decrypted and stored in plaintext. On storage
+	/// engines which keep historical versions of values, every version is
+	/// rewritten. The key check is only updated once all values have been
+	/// rewritten, so an interrupted rewrite can be run again with the same
+	/// keys. Returns the number of values which were rewritten.
+	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn rewrite_encryption(&self, key: Option<String>) -> Result<usize, Error> {
+		// Create a cipher which accepts values from both keys
+		let cipher = Cipher::rewrite(self.transaction_factory.cipher.as_deref(), key.as_deref())?;
+		// Values are read and written without encryption, and are rewritten here
+		let tf = self.transaction_factory.unencrypted();
+		// Check if the storage engine keeps historical versions
+		let versioned = {
+			let txn = tf.transaction(Read, Optimistic).await?;
+			let res = txn.batch_keys_vals_versions(vec![0x00]..vec![0xff], 1).await;
+			txn.cancel().await?;
+			match res {
+				Ok(_) => true,
+				Err(Error::UnsupportedVersionedQueries) => false,
+				Err(e) => return Err(e),
+			}
+		};
+		// Rewrite the entire keyspace in batches
+		let mut count = 0;
+		let mut next = Some(vec![0x00]..vec![0xff]);
+		while let Some(rng) = next {
+			// Fetch the next batch of values, with all their versions
+			let txn = tf.transaction(Read, Optimistic).await?.enclose();
+			let res = match versioned {
+				true => txn.batch_keys_vals_versions(rng, *NORMAL_FETCH_SIZE).await,
+				false => txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await.map(|b| {
+					let res = b.result.into_iter().map(|(k, v)| (k, v, 0, false)).collect();
+					Batch::new(b.next, res)
+				}),
+			};
+			let mut batch = catch!(txn, res);
+			txn.cancel().await?;
+			next = batch.next;
+			// Only a single version of a key can be written in a transaction,
+			// so each version of a key is written in a separate transaction,
+			// starting with the oldest, so that the latest version stays last.
+			batch.result.sort_by(|a, b| a.0.cmp(&b.0).then(a.2.cmp(&b.2)));
+			let mut rounds: Vec<Vec<(Key, Val, u64, bool)>> = Vec::new();
+			let mut round = 0;
+			let mut last: Option<Key> = None;
+			for (k, v, ver, del) in batch.result {
+				if skip_rewrite(&k) {
+					continue;
+				}
+				match last.as_ref() == Some(&k) {
+					true => round += 1,
+					false => round = 0,
+				}
+				last = Some(k.clone());
+				if rounds.len() <= round {
+					rounds.push(Vec::new());
+				}
+				rounds[round].push((k, v, ver, del));
+			}
+			for values in rounds {
+				let txn = tf.transaction(Write, Optimistic).await?.enclose();
+				for (k, v, ver, del) in values {
+					if del {
+						// Keep deleted keys deleted
+						catch!(txn, txn.del(k).await);
+					} else {
+						let v = catch!(txn, cipher.decrypt(&k, v));
+						let v = catch!(txn, cipher.encrypt(&k, v));
+						let ver = versioned.then_some(ver);
+						catch!(txn, txn.set(k, v, ver).await);
+					}
+					count += 1;
+				}
+				catch!(txn, txn.commit().await);
+				yield_now!();
+			}
+		}
+		// Record the key which the values are now encrypted with
+		let txn = tf.transaction(Write, Optimistic).await?.enclose();
+		let check = crate::key::encryption::new().encode()?;
+		match key {
+			Some(_) => {
+				let val = catch!(txn, cipher.check(&check));
+				catch!(txn, txn.set(check, val, None).await);
+			}
+			None => catch!(txn, txn.del(check).await),
+		}
+		catch!(txn, txn.commit().await);
+		// Everything ok
+		Ok(count)
+	}
+
 	/// Setup the initial cluster access credentials
 	#[instrument(err, level = "trace", target = "surrealdb::core::kvs::ds", skip_all)]
 	pub async fn initialise_credentials(&self, user: &str, pass: &str) -> Result<(), Error> {
@@ -735,6 +935,23 @@ impl Datastore {
 		Ok(())
 	}
 
+	/// Run the background task to delete expired table records and lapsed lockout state
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn expiry_process(&self) -> Result<(), Error> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running record expiry cleanup");
+		// A read only replica follows the deletions of its primary
+		if self.read_only {
+			return Ok(());
+		}
+		// Delete records which have expired by now
+		self.expiry_cleanup(&Datetime::default()).await?;
+		// Delete failed authentication state which has lapsed
+		self.lockout_cleanup().await?;
+		// Everything ok
+		Ok(())
+	}
+
 	/// Run the datastore shutdown tasks, perfoming any necessary cleanup
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
 	pub async fn shutdown(&self) -> Result<(), Error> {
@@ -848,72 +1065,7 @@ impl Datastore {
 		// Store the query variables
 		vars.attach(&mut ctx)?;
 		// Process all statements
-
-		let parser_settings = ParserSettings {
-			references_enabled: ctx
-				.get_capabilities()
-				.allows_experimental(&ExperimentalTarget::RecordReferences),
-			bearer_access_enabled: ctx
-				.get_capabilities()
-				.allows_experimental(&ExperimentalTarget::BearerAccess),
-			define_api_enabled: ctx
-				.get_capabilities()
-				.allows_experimental(&ExperimentalTarget::DefineApi),
-			..Default::default()
-		};
-		let mut statements_stream = StatementStream::new_with_settings(parser_settings);
-		let mut buffer = BytesMut::new();
-		let mut parse_size = 4096;
-		let mut bytes_stream = pin!(query);
-		let mut complete = false;
-		let mut filling = true;
-
-		let stream = futures::stream::poll_fn(move |cx| loop {
-			// fill the buffer to at least parse_size when filling is required.
-			while filling {
-				let bytes = ready!(bytes_stream.as_mut().poll_next(cx));
-				let bytes = match bytes {
-					Some(Err(e)) => return Poll::Ready(Some(Err(e))),
-					Some(Ok(x)) => x,
-					None => {
-						complete = true;
-						filling = false;
-						break;
-					}
-				};
-
-				buffer.extend_from_slice(&bytes);
-				filling = buffer.len() < parse_size
-			}
-
-			// if we finished streaming we can parse with complete so that the parser can be sure
-			// of it's results.
-			if complete {
-				return match statements_stream.parse_complete(&mut buffer) {
-					Err(e) => Poll::Ready(Some(Err(Error::InvalidQuery(e)))),
-					Ok(None) => Poll::Ready(None),
-					Ok(Some(x)) => Poll::Ready(Some(Ok(x))),
-				};
-			}
-
-			// otherwise try to parse a single statement.
-			match statements_stream.parse_partial(&mut buffer) {
-				Err(e) => return Poll::Ready(Some(Err(Error::InvalidQuery(e)))),
-				Ok(Some(x)) => return Poll::Ready(Some(Ok(x))),
-				Ok(None) => {
-					// Couldn't parse a statement for sure.
-					if buffer.len() >= parse_size && parse_size < u32::MAX as usize {
-						// the buffer already contained more or equal to parse_size bytes
-						// this means we are trying to parse a statement of more then buffer size.
-						// so we need to increase the buffer size.
-						parse_size = (parse_size + 1).next_power_of_two();
-					}
-					// start filling the buffer again.
-					filling = true;
-				}
-			}
-		});
-
+		let stream = statement_stream(&ctx, query);
 		Executor::execute_stream(self, Arc::new(ctx), opt, stream).await
 	}
 
@@ -966,6 +1118,59 @@ impl Datastore {
 		Executor::execute(self, ctx.freeze(), opt, ast).await
 	}
 
+	/// Execute a pre-parsed SQL query within an existing transaction
+	///
+	/// The transaction is not committed or cancelled, and any notifications
+	/// are sent to the specified channel, so that they can be delivered
+	/// once the transaction has been committed.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub(crate) async fn process_with_transaction(
+		&self,
+		ast: Query,
+		sess: &Session,
+		vars: Variables,
+		txn: Arc<Transaction>,
+		sender: Option<Sender<Notification>>,
+	) -> Result<Vec<Response>, Error> {
+		// Check if the session has expired
+		if sess.expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Check if anonymous actors can execute queries when auth is enabled
+		// TODO(sgirones): Check this as part of the authorisation layer
+		self.check_anon(sess).map_err(|_| IamError::NotAllowed {
+			actor: "anonymous".to_string(),
+			action: "process".to_string(),
+			resource: "query".to_string(),
+		})?;
+		// Create a new query options
+		let mut opt = self.setup_options(sess);
+		// Buffer any notifications
+		opt.sender = sender;
+		// Create a default context
+		let mut ctx = self.setup_ctx()?;
+		// Start an execution context
+		sess.context(&mut ctx);
+		// Store the query variables
+		vars.attach(&mut ctx)?;
+		// Process all statements
+		Executor::execute_with_transaction(ctx.freeze(), opt, ast, txn).await
+	}
+
+	/// Deliver notifications which were buffered during a committed transaction
+	pub(crate) fn deliver_notifications(&self, recv: Receiver<Notification>) {
+		if let Some((sink, _)) = &self.notification_channel {
+			let sink = sink.clone();
+			spawn(async move {
+				while let Ok(x) = recv.recv().await {
+					if sink.send(x).await.is_err() {
+						break;
+					}
+				}
+			});
+		}
+	}
+
 	/// Ensure a SQL [`Value`] is fully computed
 	///
 	/// ```rust,no_run
@@ -1023,8 +1228,20 @@ impl Datastore {
 		sess.context(&mut ctx);
 		// Store the query variables
 		vars.attach(&mut ctx)?;
+		// Ensure that no data is written to a read only datastore
+		if opt.read_only && val.writeable() {
+			return Err(Error::ReadOnly);
+		}
 		// Start a new transaction
 		let txn = self.transaction(val.writeable().into(), Optimistic).await?.enclose();
+		// Resolve the custom role policies for the transaction
+		let opt = match role_policies(&txn, &opt.auth).await {
+			Ok(roles) => opt.with_roles(roles),
+			Err(e) => {
+				txn.cancel().await?;
+				return Err(e);
+			}
+		};
 		// Store the transaction
 		ctx.set_transaction(txn.clone());
 		// Freeze the context
@@ -1095,6 +1312,10 @@ impl Datastore {
 		sess.context(&mut ctx);
 		// Store the query variables
 		vars.attach(&mut ctx)?;
+		// Ensure that no data is written to a read only datastore
+		if opt.read_only && val.writeable() {
+			return Err(Error::ReadOnly);
+		}
 		// Start a new transaction
 		let txn = self.transaction(val.writeable().into(), Optimistic).await?.enclose();
 		// Store the transaction
@@ -1163,6 +1384,28 @@ impl Datastore {
 		self.execute_import(sess, None, stream).await
 	}
 
+	/// Performs a database import from SQL, CSV or NDJSON
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn import_with_config<S>(
+		&self,
+		sess: &Session,
+		cfg: import::Config,
+		stream: S,
+	) -> Result<Vec<Response>, Error>
+	where
+		S: Stream<Item = Result<Bytes, Error>>,
+	{
+		// Check if the session has expired
+		if sess.expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Execute the import in the desired format
+		match cfg.format {
+			export::Format::Surrealql => self.execute_import(sess, None, stream).await,
+			_ => self.import_records(sess, cfg, stream).await,
+		}
+	}
+
 	/// Performs a full database export as SQL
 	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
 	pub async fn export(
@@ -1189,6 +1432,10 @@ impl Datastore {
 		}
 		// Retrieve the provided NS and DB
 		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Check that records are exported from a single table
+		if cfg.format != export::Format::Surrealql {
+			cfg.records_table()?;
+		}
 		// Create a new readonly transaction
 		let txn = self.transaction(Read, Optimistic).await?;
 		// Return an async export job
@@ -1200,7 +1447,185 @@ impl Datastore {
 		})
 	}
 
+	/// Performs a consistent database snapshot for replication
+	///
+	/// The snapshot is written as SurrealQL, and is read from a single
+	/// transaction. The returned versionstamp is the point from which
+	/// subsequent changes should be requested using [`Datastore::sync_changes`],
+	/// and the returned digest identifies the definitions in the snapshot.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn sync_snapshot(
+		&self,
+		sess: &Session,
+		chn: Sender<Vec<u8>>,
+	) -> Result<(u64, String, impl Future<Output = Result<(), Error>>), Error> {
+		// Check if the session has expired
+		if sess.expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Create a new readonly transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Ensure that subsequent changes can be read
+		catch!(txn, txn.sync_check_changefeeds(&ns, &db).await);
+		// Fetch the versionstamp of this snapshot
+		let vs = catch!(txn, txn.sync_versionstamp(&ns, &db).await);
+		// Fetch the digest of the definitions in this snapshot
+		let defs = catch!(txn, txn.sync_definitions(&ns, &db).await);
+		// Return an async snapshot job
+		Ok((vs.into_u64_lossy() + 1, defs, async move {
+			// Process the export
+			let res = txn.export(&ns, &db, export::Config::default(), chn).await;
+			// Cancel the transaction
+			txn.cancel().await?;
+			// Return the result
+			res
+		}))
+	}
+
+	/// Reads the database changes following a versionstamp for replication
+	///
+	/// The changes are written as SurrealQL, and are read in batches of
+	/// whole change sets. The returned versionstamp is the point from which
+	/// the next set of changes should be requested, and the returned digest
+	/// identifies the definitions which the changes were made against.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn sync_changes(
+		&self,
+		sess: &Session,
+		since: u64,
+		chn: Sender<Vec<u8>>,
+	) -> Result<(u64, String, impl Future<Output = Result<(), Error>>), Error> {
+		// Check if the session has expired
+		if sess.expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Create a new readonly transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Ensure that every change was recorded
+		catch!(txn, txn.sync_check_changefeeds(&ns, &db).await);
+		// Ensure that no changes have been removed
+		catch!(txn, txn.sync_check_retention(&ns, &db, since).await);
+		// Read the changes from the change feed
+		let (changes, next) = catch!(txn, txn.sync_changesets(&ns, &db, since, None).await);
+		// Fetch the digest of the current definitions
+		let defs = catch!(txn, txn.sync_definitions(&ns, &db).await);
+		// Return an async changes job
+		Ok((next, defs, async move {
+			// Process the changes
+			let res = txn.sync_changes(changes, chn).await;
+			// Cancel the transaction
+			txn.cancel().await?;
+			// Return the result
+			res
+		}))
+	}
+
+	/// Applies a snapshot or a set of changes from another datastore
+	///
+	/// The statements are applied within a single transaction, so that a
+	/// failed or partial transfer leaves the database unchanged. They are
+	/// parsed as they are received, but the transaction holds every write
+	/// until it commits, so transfers larger than `SYNC_APPLY_MAX_SIZE` are
+	/// rejected. Applying a snapshot replaces the contents of the database
+	/// entirely. Changes are applied even when this datastore is read only.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn sync_apply<S>(
+		&self,
+		sess: &Session,
+		snapshot: bool,
+		stream: S,
+	) -> Result<(), Error>
+	where
+		S: Stream<Item = Result<Bytes, Error>>,
+	{
+		// Check if the session has expired
+		if sess.expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Check the permissions level
+		self.check_with_roles(sess, Action::Edit, ResourceKind::Any.on_db(&ns, &db)).await?;
+		// Create a new query options, which can write data as an import
+		let opt = self.setup_options(sess).with_read_only(false).with_import(true);
+		// Limit the size of the transfer applied in the transaction
+		let limit = *SYNC_APPLY_MAX_SIZE;
+		let mut size = 0u64;
+		let stream = stream.map(move |v| {
+			let v = v?;
+			size += v.len() as u64;
+			match size > limit {
+				true => Err(Error::SyncTooLarge {
+					limit,
+				}),
+				false => Ok(v),
+			}
+		});
+		// Parse the statements as they are received
+		let mut stream = pin!(statement_stream(&self.setup_ctx()?, stream));
+		// A snapshot replaces the database entirely
+		let mut statements = match snapshot {
+			true => syn::parse(&format!("REMOVE DATABASE IF EXISTS {};", Ident::from(db)))?.0 .0,
+			false => Vec::new(),
+		};
+		// Start a new writeable transaction
+		let txn = self.transaction(Write, Optimistic).await?.enclose();
+		// Apply the statements in batches
+		let batch = *SYNC_APPLY_BATCH_SIZE as usize;
+		let mut done = false;
+		while !done {
+			// Receive the next batch of statements
+			while statements.len() < batch {
+				match stream.next().await {
+					// The statements are applied in a single transaction
+					Some(Ok(Statement::Begin(_) | Statement::Commit(_))) => {}
+					Some(Ok(stm)) => statements.push(stm),
+					Some(Err(e)) => {
+						txn.cancel().await?;
+						return Err(e);
+					}
+					None => {
+						done = true;
+						break;
+					}
+				}
+			}
+			// Create a default context
+			let mut ctx = self.setup_ctx()?;
+			// Start an execution context
+			sess.context(&mut ctx);
+			// Process the statements in this batch
+			let qry = Query(Statements(std::mem::take(&mut statements)));
+			let res =
+				Executor::execute_with_transaction(ctx.freeze(), opt.clone(), qry, txn.clone())
+					.await;
+			// Ensure that every statement was applied
+			let res = match res {
+				Ok(res) => res.into_iter().try_for_each(|r| r.result.map(|_| ())),
+				Err(e) => Err(e),
+			};
+			if let Err(e) = res {
+				txn.cancel().await?;
+				return Err(e);
+			}
+		}
+		// Complete the changes and commit
+		let mut lock = txn.lock().await;
+		if let Err(e) = lock.complete_changes(false).await {
+			lock.cancel().await?;
+			return Err(e);
+		}
+		lock.commit().await
+	}
+
 	/// Checks the required permissions level for this session
+	///
+	/// Only the built-in roles of the session are taken into account. Use
+	/// [`Datastore::check_with_roles`] to also check any custom roles.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, sess))]
 	pub fn check(&self, sess: &Session, action: Action, resource: Resource) -> Result<(), Error> {
 		// Check if the session has expired
@@ -1216,6 +1641,38 @@ impl Datastore {
 		Ok(())
 	}
 
+	/// Checks the required permissions level for this session, including custom roles
+	///
+	/// The policies of any custom roles assigned to the session are fetched
+	/// from their current definitions before the permissions are checked.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, sess))]
+	pub async fn check_with_roles(
+		&self,
+		sess: &Session,
+		action: Action,
+		resource: Resource,
+	) -> Result<(), Error> {
+		// Only custom roles need to be fetched
+		if !sess.au.has_custom_roles() {
+			return self.check(sess, action, resource);
+		}
+		// Check if the session has expired
+		if sess.expired() {
+			return Err(Error::ExpiredSession);
+		}
+		// Fetch the current definitions of the custom roles
+		let txn = self.transaction(Read, Optimistic).await?;
+		let res = role_policies(&txn, &sess.au).await;
+		txn.cancel().await?;
+		// Check against the built-in and custom roles
+		match res? {
+			Some(roles) => sess.au.is_allowed_with(action, &resource, &roles)?,
+			None => sess.au.is_allowed(action, &resource)?,
+		}
+		// All ok
+		Ok(())
+	}
+
 	pub fn setup_options(&self, sess: &Session) -> Options {
 		Options::default()
 			.with_id(self.id)
@@ -1225,6 +1682,7 @@ impl Datastore {
 			.with_auth(sess.au.clone())
 			.with_strict(self.strict)
 			.with_auth_enabled(self.auth_enabled)
+			.with_read_only(self.read_only)
 	}
 	pub fn setup_ctx(&self) -> Result<MutableContext, Error> {
 		let mut ctx = MutableContext::from_ds(
@@ -1234,6 +1692,7 @@ impl Datastore {
 			self.cache.clone(),
 			#[cfg(not(target_family = "wasm"))]
 			self.index_builder.clone(),
+			self.sequences.clone(),
 			#[cfg(storage)]
 			self.temporary_directory.clone(),
 		)?;
@@ -1258,6 +1717,92 @@ impl Datastore {
 	}
 }
 
+/// Checks if the value of a key is not rewritten with the other stored values.
+///
+/// Versionstamps are written directly by the storage engine, and are never
+/// encrypted, while the key check is only written once all other values
+/// have been rewritten.
+fn skip_rewrite(key: &[u8]) -> bool {
+	use crate::key::database::vs::Vs;
+	key == crate::key::encryption::new().encode().unwrap_or_default()
+		|| Vs::decode(key).and_then(|vs| vs.encode()).is_ok_and(|vs| vs == key)
+}
+
+/// Parses a stream of SurrealQL into a stream of statements, so
+/// that the statements can be processed as they are received.
+fn statement_stream<S>(
+	ctx: &MutableContext,
+	query: S,
+) -> impl Stream<Item = Result<Statement, Error>>
+where
+	S: Stream<Item = Result<Bytes, Error>>,
+{
+	let parser_settings = ParserSettings {
+		references_enabled: ctx
+			.get_capabilities()
+			.allows_experimental(&ExperimentalTarget::RecordReferences),
+		bearer_access_enabled: ctx
+			.get_capabilities()
+			.allows_experimental(&ExperimentalTarget::BearerAccess),
+		define_api_enabled: ctx
+			.get_capabilities()
+			.allows_experimental(&ExperimentalTarget::DefineApi),
+		..Default::default()
+	};
+	let mut statements_stream = StatementStream::new_with_settings(parser_settings);
+	let mut buffer = BytesMut::new();
+	let mut parse_size = 4096;
+	let mut bytes_stream = Box::pin(query);
+	let mut complete = false;
+	let mut filling = true;
+
+	futures::stream::poll_fn(move |cx| loop {
+		// fill the buffer to at least parse_size when filling is required.
+		while filling {
+			let bytes = ready!(bytes_stream.as_mut().poll_next(cx));
+			let bytes = match bytes {
+				Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+				Some(Ok(x)) => x,
+				None => {
+					complete = true;
+					filling = false;
+					break;
+				}
+			};
+
+			buffer.extend_from_slice(&bytes);
+			filling = buffer.len() < parse_size
+		}
+
+		// if we finished streaming we can parse with complete so that the parser can be sure
+		// of it's results.
+		if complete {
+			return match statements_stream.parse_complete(&mut buffer) {
+				Err(e) => Poll::Ready(Some(Err(Error::InvalidQuery(e)))),
+				Ok(None) => Poll::Ready(None),
+				Ok(Some(x)) => Poll::Ready(Some(Ok(x))),
+			};
+		}
+
+		// otherwise try to parse a single statement.
+		match statements_stream.parse_partial(&mut buffer) {
+			Err(e) => return Poll::Ready(Some(Err(Error::InvalidQuery(e)))),
+			Ok(Some(x)) => return Poll::Ready(Some(Ok(x))),
+			Ok(None) => {
+				// Couldn't parse a statement for sure.
+				if buffer.len() >= parse_size && parse_size < u32::MAX as usize {
+					// the buffer already contained more or equal to parse_size bytes
+					// this means we are trying to parse a statement of more then buffer size.
+					// so we need to increase the buffer size.
+					parse_size = (parse_size + 1).next_power_of_two();
+				}
+				// start filling the buffer again.
+				filling = true;
+			}
+		}
+	})
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
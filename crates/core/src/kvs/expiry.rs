@@ -0,0 +1,129 @@
+use crate::cnf::EXPIRY_BATCH_SIZE;
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::key::table::et;
+use crate::kvs::Datastore;
+use crate::kvs::KeyDecode;
+use crate::kvs::Transaction;
+use crate::kvs::{LockType::*, TransactionType::*};
+use crate::sql::statements::DeleteStatement;
+use crate::sql::{Datetime, Query, Statement, Statements, Thing, Value, Values};
+
+impl Datastore {
+	/// Deletes all records which expired at or before the given datetime.
+	///
+	/// The expired records are found in expiry time order, and are deleted
+	/// in bounded batches, each within its own transaction. The records are
+	/// deleted with a `DELETE` statement, so that events, indexes, and change
+	/// feeds are kept consistent.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub(crate) async fn expiry_cleanup(&self, at: &Datetime) -> Result<(), Error> {
+		// Create a new transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Find the tables with an expiry
+		let tables = catch!(txn, expiring(&txn).await);
+		// Cancel the transaction
+		txn.cancel().await?;
+		// Delete the expired records in each table
+		for (ns, db, tb) in tables {
+			self.expiry_cleanup_table(&ns, &db, &tb, at).await?;
+		}
+		// Everything ok
+		Ok(())
+	}
+
+	/// Deletes the records in a table which expired at or before the given datetime.
+	async fn expiry_cleanup_table(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		at: &Datetime,
+	) -> Result<(), Error> {
+		// Prepare the session
+		let sess = Session::owner().with_ns(ns).with_db(db);
+		// Prepare the range of expired records
+		let beg = et::prefix(ns, db, tb)?;
+		let end = et::until(ns, db, tb, at)?;
+		// Delete the expired records in batches
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			// Create a new transaction
+			let txn = self.transaction(Write, Optimistic).await?.enclose();
+			// Fetch the next batch of expired records
+			let batch = catch!(txn, txn.batch_keys(rng, *EXPIRY_BATCH_SIZE, None).await);
+			next = batch.next;
+			// Check if there are any expired records
+			if batch.result.is_empty() {
+				txn.cancel().await?;
+				break;
+			}
+			// Collect the expired record ids
+			let mut things = Vec::with_capacity(batch.result.len());
+			for k in batch.result.iter() {
+				let key = catch!(txn, et::Et::decode(k));
+				things.push(Value::from(Thing::from((tb, key.id))));
+			}
+			// Trace for debugging
+			trace!("Deleting {} expired records in {ns}:{db}:{tb}", things.len());
+			// Delete the records through the document pipeline
+			let stm = DeleteStatement {
+				what: Values(things),
+				..Default::default()
+			};
+			let ast = Query(Statements(vec![Statement::Delete(stm)]));
+			// Buffer any notifications until the transaction is committed
+			let channel = self.notifications().map(|_| async_channel::unbounded());
+			let sender = channel.as_ref().map(|(send, _)| send.clone());
+			let res = self.process_with_transaction(ast, &sess, None, txn.clone(), sender);
+			let res = catch!(txn, res.await);
+			if let Some(Err(e)) = res.into_iter().map(|r| r.result).find(Result::is_err) {
+				txn.cancel().await?;
+				return Err(e);
+			}
+			// Complete the changes and commit
+			let mut lock = txn.lock().await;
+			if let Err(e) = lock.complete_changes(false).await {
+				lock.cancel().await?;
+				return Err(e);
+			}
+			lock.commit().await?;
+			// Deliver any buffered notifications
+			if let Some((send, recv)) = channel {
+				send.close();
+				self.deliver_notifications(recv);
+			}
+			// Pause execution
+			yield_now!();
+		}
+		// Everything ok
+		Ok(())
+	}
+}
+
+/// Finds the tables with an `EXPIRE` clause, along with
+/// the namespace and database which they belong to.
+async fn expiring(txn: &Transaction) -> Result<Vec<(String, String, String)>, Error> {
+	let mut tables = Vec::new();
+	// Fetch all namespaces
+	let nss = txn.all_ns().await?;
+	// Loop over all namespaces
+	for ns in nss.iter() {
+		// Get the namespace name
+		let ns = &ns.name;
+		// Fetch all databases
+		let dbs = txn.all_db(ns).await?;
+		// Loop over all databases
+		for db in dbs.iter() {
+			// Get the database name
+			let db = &db.name;
+			// Fetch all tables
+			let tbs = txn.all_tb(ns, db, None).await?;
+			// Loop over all tables with an expiry
+			for tb in tbs.iter().filter(|tb| tb.expire.is_some()) {
+				tables.push((ns.to_string(), db.to_string(), tb.name.to_raw()));
+			}
+		}
+	}
+	Ok(tables)
+}
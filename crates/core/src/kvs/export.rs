@@ -1,4 +1,5 @@
 use super::KeyDecode as _;
+use super::SequenceState;
 use super::Transaction;
 use crate::cnf::EXPORT_BATCH_SIZE;
 use crate::err::Error;
@@ -6,23 +7,29 @@ use crate::key::thing;
 use crate::sql::paths::EDGE;
 use crate::sql::paths::IN;
 use crate::sql::paths::OUT;
+use crate::sql::statements::DefineSequenceStatement;
 use crate::sql::statements::DefineTableStatement;
-use crate::sql::Value;
+use crate::sql::{Idiom, Object, Part, Value};
 use async_channel::Sender;
 use chrono::prelude::Utc;
 use chrono::TimeZone;
+use std::collections::BTreeSet;
 use std::fmt;
+use std::str::FromStr;
 
 #[derive(Clone, Debug)]
 pub struct Config {
 	pub users: bool,
 	pub accesses: bool,
 	pub params: bool,
+	pub sequences: bool,
 	pub functions: bool,
 	pub analyzers: bool,
 	pub tables: TableConfig,
 	pub versions: bool,
 	pub records: bool,
+	pub format: Format,
+	pub columns: Option<Vec<Column>>,
 }
 
 impl Default for Config {
@@ -31,11 +38,34 @@ impl Default for Config {
 			users: true,
 			accesses: true,
 			params: true,
+			sequences: true,
 			functions: true,
 			analyzers: true,
 			tables: TableConfig::default(),
 			versions: false,
 			records: true,
+			format: Format::default(),
+			columns: None,
+		}
+	}
+}
+
+impl Config {
+	/// Retrieves the single table to export as CSV or NDJSON
+	pub(crate) fn records_table(&self) -> Result<&str, Error> {
+		match &self.tables {
+			TableConfig::Some(v) if v.len() == 1 => Ok(&v[0]),
+			v => {
+				let v = match v {
+					TableConfig::All => Value::from(true),
+					TableConfig::None => Value::from(false),
+					TableConfig::Some(v) => Value::from(v.clone()),
+				};
+				Err(Error::InvalidExportConfig(
+					v,
+					format!("a single table for a {} export", self.format),
+				))
+			}
 		}
 	}
 }
@@ -46,6 +76,7 @@ impl From<Config> for Value {
 			"users" => config.users.into(),
 			"accesses" => config.accesses.into(),
 			"params" => config.params.into(),
+			"sequences" => config.sequences.into(),
 			"functions" => config.functions.into(),
 			"analyzers" => config.analyzers.into(),
 			"versions" => config.versions.into(),
@@ -54,9 +85,16 @@ impl From<Config> for Value {
 				TableConfig::All => true.into(),
 				TableConfig::None => false.into(),
 				TableConfig::Some(v) => v.into()
-			}
+			},
+			"format" => config.format.to_string().into(),
 		);
 
+		let mut obj = Object::from(obj);
+		if let Some(columns) = config.columns {
+			let columns: Vec<String> = columns.iter().map(ToString::to_string).collect();
+			obj.insert("columns".to_owned(), columns.into());
+		}
+
 		obj.into()
 	}
 }
@@ -88,6 +126,7 @@ impl TryFrom<&Value> for Config {
 				bool_prop!(users);
 				bool_prop!(accesses);
 				bool_prop!(params);
+				bool_prop!(sequences);
 				bool_prop!(functions);
 				bool_prop!(analyzers);
 				bool_prop!(versions);
@@ -97,6 +136,39 @@ impl TryFrom<&Value> for Config {
 					config.tables = v.try_into()?;
 				}
 
+				match obj.get("format") {
+					Some(Value::Strand(v)) => {
+						config.format = v.parse()?;
+					}
+					Some(v) => {
+						return Err(Error::InvalidExportConfig(v.to_owned(), "a string".into()))
+					}
+					_ => (),
+				}
+
+				match obj.get("columns") {
+					Some(Value::Array(v)) => {
+						config.columns = Some(
+							v.iter()
+								.map(|v| match v {
+									Value::Strand(v) => v.parse(),
+									v => Err(Error::InvalidExportConfig(
+										v.to_owned(),
+										"a string".into(),
+									)),
+								})
+								.collect::<Result<Vec<Column>, Error>>()?,
+						);
+					}
+					Some(Value::None | Value::Null) | None => (),
+					Some(v) => {
+						return Err(Error::InvalidExportConfig(
+							v.to_owned(),
+							"none, null or array<string>".into(),
+						))
+					}
+				}
+
 				Ok(config)
 			}
 			v => Err(Error::InvalidExportConfig(v.to_owned(), "an object".into())),
@@ -174,6 +246,105 @@ impl TableConfig {
 	}
 }
 
+/// The format in which records are imported or exported
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+	/// A SurrealQL script, including all definitions
+	#[default]
+	Surrealql,
+	/// Comma-separated values, with a header row, for a single table
+	Csv,
+	/// Newline-delimited JSON objects, for a single table
+	Ndjson,
+}
+
+impl fmt::Display for Format {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Surrealql => write!(f, "surrealql"),
+			Self::Csv => write!(f, "csv"),
+			Self::Ndjson => write!(f, "ndjson"),
+		}
+	}
+}
+
+impl FromStr for Format {
+	type Err = Error;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_ascii_lowercase().as_str() {
+			"surrealql" | "sql" => Ok(Self::Surrealql),
+			"csv" => Ok(Self::Csv),
+			"ndjson" | "jsonl" => Ok(Self::Ndjson),
+			_ => Err(Error::InvalidFormat(s.to_owned())),
+		}
+	}
+}
+
+/// Maps a CSV column or NDJSON key to a record field
+///
+/// A column is written as `column` when it maps to a field of the same
+/// name, or as `column=field` when it maps to a different, possibly
+/// nested, field path.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Column {
+	pub name: String,
+	pub field: Idiom,
+}
+
+impl Column {
+	/// The columns used when no mapping is specified
+	pub(crate) fn from_names<I: IntoIterator<Item = String>>(names: I) -> Vec<Column> {
+		names
+			.into_iter()
+			.map(|name| Column {
+				field: Idiom::from(vec![Part::from(name.as_str())]),
+				name,
+			})
+			.collect()
+	}
+}
+
+impl fmt::Display for Column {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.field.to_string() {
+			field if field == self.name => write!(f, "{}", self.name),
+			field => write!(f, "{}={field}", self.name),
+		}
+	}
+}
+
+impl FromStr for Column {
+	type Err = Error;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (name, field) = s.split_once('=').unwrap_or((s, s));
+		let (name, field) = (name.trim(), field.trim());
+		if name.is_empty() || field.is_empty() {
+			return Err(Error::InvalidColumn(s.to_owned()));
+		}
+		Ok(Column {
+			name: name.to_owned(),
+			field: crate::syn::idiom(field).map_err(|_| Error::InvalidColumn(s.to_owned()))?,
+		})
+	}
+}
+
+/// Converts a value into the text of a CSV field
+fn csv_value(v: Value) -> String {
+	match v {
+		Value::None | Value::Null => String::new(),
+		Value::Array(_) | Value::Object(_) => v.into_json().to_string(),
+		v => v.as_raw_string(),
+	}
+}
+
+/// Quotes a CSV field, if necessary
+fn csv_field(v: &str) -> String {
+	match v.contains([',', '"', '\n', '\r']) {
+		true => format!("\"{}\"", v.replace('"', "\"\"")),
+		false => v.to_owned(),
+	}
+}
+
 struct InlineCommentWriter<'a, F>(&'a mut F);
 impl<F: fmt::Write> fmt::Write for InlineCommentWriter<'_, F> {
 	fn write_str(&mut self, s: &str) -> fmt::Result {
@@ -214,13 +385,121 @@ impl Transaction {
 		cfg: Config,
 		chn: Sender<Vec<u8>>,
 	) -> Result<(), Error> {
-		// Output USERS, ACCESSES, PARAMS, FUNCTIONS, ANALYZERS
+		// Records can also be exported for a single table
+		if cfg.format != Format::Surrealql {
+			return self.export_records(ns, db, &cfg, &chn).await;
+		}
+		// Output USERS, ACCESSES, PARAMS, SEQUENCES, FUNCTIONS, ANALYZERS
 		self.export_metadata(&cfg, &chn, ns, db).await?;
 		// Output TABLES
 		self.export_tables(ns, db, &cfg, &chn).await?;
 		Ok(())
 	}
 
+	/// Writes the records of a single table as CSV or NDJSON.
+	async fn export_records(
+		&self,
+		ns: &str,
+		db: &str,
+		cfg: &Config,
+		chn: &Sender<Vec<u8>>,
+	) -> Result<(), Error> {
+		// Only a single table can be exported in this format
+		let tb = cfg.records_table()?;
+		// Check that the table exists
+		let table = self.get_tb(ns, db, tb).await?;
+		// Work out which columns to output
+		let columns = match &cfg.columns {
+			Some(v) => v.clone(),
+			None => self.export_columns(ns, db, &table).await?,
+		};
+		// Output the CSV header row
+		if cfg.format == Format::Csv {
+			let header: Vec<String> = columns.iter().map(|c| csv_field(&c.name)).collect();
+			chn.send(bytes!(header.join(","))).await?;
+		}
+		// Output the records in batches
+		let beg = crate::key::thing::prefix(ns, db, tb)?;
+		let end = crate::key::thing::suffix(ns, db, tb)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = self.batch_keys_vals(rng, *EXPORT_BATCH_SIZE, None).await?;
+			next = batch.next;
+			let mut out = String::new();
+			for (_, v) in batch.result {
+				let v: Value = revision::from_slice(&v)?;
+				match cfg.format {
+					Format::Csv => {
+						let row: Vec<String> = columns
+							.iter()
+							.map(|c| csv_field(&csv_value(v.pick(&c.field))))
+							.collect();
+						out.push_str(&row.join(","));
+					}
+					_ => {
+						let v = match &cfg.columns {
+							Some(columns) => Value::from(
+								columns
+									.iter()
+									.map(|c| (c.name.clone(), v.pick(&c.field)))
+									.collect::<Object>(),
+							),
+							None => v,
+						};
+						out.push_str(&v.into_json().to_string());
+					}
+				}
+				out.push('\n');
+			}
+			if !out.is_empty() {
+				chn.send(out.into_bytes()).await?;
+			}
+			// Pause execution
+			yield_now!();
+		}
+		Ok(())
+	}
+
+	/// Works out the columns to export when no mapping is specified.
+	///
+	/// Schemafull tables output the record id and every top-level field
+	/// definition, whereas schemaless tables output every top-level field
+	/// which is present on any of the records in the table.
+	async fn export_columns(
+		&self,
+		ns: &str,
+		db: &str,
+		table: &DefineTableStatement,
+	) -> Result<Vec<Column>, Error> {
+		let mut names = BTreeSet::new();
+		if table.full {
+			let fields = self.all_tb_fields(ns, db, &table.name, None).await?;
+			for field in fields.iter() {
+				if let [Part::Field(name)] = &field.name[..] {
+					names.insert(name.to_raw());
+				}
+			}
+		} else {
+			let beg = crate::key::thing::prefix(ns, db, &table.name)?;
+			let end = crate::key::thing::suffix(ns, db, &table.name)?;
+			let mut next = Some(beg..end);
+			while let Some(rng) = next {
+				let batch = self.batch_keys_vals(rng, *EXPORT_BATCH_SIZE, None).await?;
+				next = batch.next;
+				for (_, v) in batch.result {
+					if let Value::Object(v) = revision::from_slice(&v)? {
+						names.extend(v.0.into_keys());
+					}
+				}
+				// Pause execution
+				yield_now!();
+			}
+		}
+		// The record id is always the first column
+		names.remove("id");
+		Ok(Column::from_names(std::iter::once("id".to_owned()).chain(names)))
+	}
+
 	async fn export_metadata(
 		&self,
 		cfg: &Config,
@@ -233,6 +512,9 @@ impl Transaction {
 
 		// Output USERS
 		if cfg.users {
+			// Roles are exported first, as users can be assigned custom roles
+			let roles = self.all_db_roles(ns, db).await?;
+			self.export_section("ROLES", roles.to_vec(), chn).await?;
 			let users = self.all_db_users(ns, db).await?;
 			self.export_section("USERS", users.to_vec(), chn).await?;
 		}
@@ -249,6 +531,21 @@ impl Transaction {
 			self.export_section("PARAMS", params.to_vec(), chn).await?;
 		}
 
+		// Output SEQUENCES
+		if cfg.sequences {
+			let sequences = self.all_db_sequences(ns, db).await?;
+			let mut out = Vec::with_capacity(sequences.len());
+			for sq in sequences.iter() {
+				// Continue after the values which have already been reserved
+				let start = SequenceState::next(self, ns, db, sq).await?;
+				out.push(DefineSequenceStatement {
+					start,
+					..sq.clone()
+				});
+			}
+			self.export_section("SEQUENCES", out, chn).await?;
+		}
+
 		// Output FUNCTIONS
 		if cfg.functions {
 			let functions = self.all_db_functions(ns, db).await?;
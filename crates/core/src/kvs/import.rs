@@ -0,0 +1,493 @@
+//! Imports the records of a single table from CSV or NDJSON data.
+//!
+//! CSV data must start with a header row, and every following row is
+//! imported as a record. NDJSON data contains a single JSON object on each
+//! line. Columns are mapped onto record fields using the [`Column`] mapping
+//! in the import [`Config`], or onto fields of the same name by default.
+//!
+//! Values are converted to the types specified by the `DEFINE FIELD`
+//! statements on the table. Fields without a defined type keep the type
+//! found in the data, so every CSV value of such a field is imported as a
+//! string. Records are inserted in batches of [`EXPORT_BATCH_SIZE`].
+pub use super::export::{Column, Format};
+use super::{Datastore, LockType::*, TransactionType::*};
+use crate::cnf::EXPORT_BATCH_SIZE;
+use crate::dbs::{Response, Session};
+use crate::err::Error;
+use crate::sql::paths::ID;
+use crate::sql::statements::DefineFieldStatement;
+use crate::sql::{Idiom, Object, Part, Table, Value};
+use bytes::Bytes;
+use csv_core::{ReadRecordResult, Reader};
+use futures::{Stream, StreamExt};
+use std::mem;
+use std::pin::pin;
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+	/// The format of the imported data
+	pub format: Format,
+	/// The table to import CSV or NDJSON records into
+	pub table: Option<String>,
+	/// The mapping of columns onto record fields
+	pub columns: Option<Vec<Column>>,
+}
+
+impl Datastore {
+	/// Imports the records of a single table from CSV or NDJSON data
+	pub(crate) async fn import_records<S>(
+		&self,
+		sess: &Session,
+		cfg: Config,
+		stream: S,
+	) -> Result<Vec<Response>, Error>
+	where
+		S: Stream<Item = Result<Bytes, Error>>,
+	{
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Records are always imported into a single table
+		let Some(tb) = cfg.table else {
+			return Err(Error::InvalidImport(format!(
+				"a table must be specified for a {} import",
+				cfg.format
+			)));
+		};
+		// Fetch the field definitions for the table
+		let txn = self.transaction(Read, Optimistic).await?;
+		let fields = catch!(txn, txn.all_tb_fields(&ns, &db, &tb, None).await);
+		txn.cancel().await?;
+		// Prepare the record importer
+		let mut importer = Importer {
+			ds: self,
+			sess,
+			format: cfg.format,
+			table: Table::from(tb),
+			fields,
+			columns: cfg.columns,
+			mapping: None,
+			records: Vec::new(),
+			responses: Vec::new(),
+			count: 0,
+		};
+		let mut csv = CsvReader::default();
+		let mut rows = Vec::new();
+		let mut buffer = Vec::new();
+		// Process the data as it is received
+		let mut stream = pin!(stream);
+		while let Some(bytes) = stream.next().await {
+			let bytes = bytes?;
+			// Skip empty chunks, which signify the end of CSV data
+			if bytes.is_empty() {
+				continue;
+			}
+			match cfg.format {
+				Format::Csv => {
+					csv.read(&bytes, &mut rows)?;
+					for row in rows.drain(..) {
+						importer.csv_row(row).await?;
+					}
+				}
+				Format::Ndjson => {
+					buffer.extend_from_slice(&bytes);
+					while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+						let line: Vec<u8> = buffer.drain(..=pos).collect();
+						importer.json_row(&line).await?;
+					}
+				}
+				Format::Surrealql => return Err(Error::InvalidFormat(cfg.format.to_string())),
+			}
+		}
+		// Process any remaining data
+		match cfg.format {
+			Format::Csv => {
+				csv.read(&[], &mut rows)?;
+				for row in rows.drain(..) {
+					importer.csv_row(row).await?;
+				}
+			}
+			_ => importer.json_row(&buffer).await?,
+		}
+		// Insert any remaining records
+		importer.flush().await?;
+		// Return the import responses
+		Ok(importer.responses)
+	}
+}
+
+/// Converts parsed rows into records, and inserts them in batches
+struct Importer<'a> {
+	ds: &'a Datastore,
+	sess: &'a Session,
+	format: Format,
+	table: Table,
+	fields: Arc<[DefineFieldStatement]>,
+	columns: Option<Vec<Column>>,
+	/// The CSV header position and field of each mapped column,
+	/// which is set once the header row has been processed
+	mapping: Option<Vec<(usize, Idiom)>>,
+	/// The records waiting to be inserted
+	records: Vec<Value>,
+	responses: Vec<Response>,
+	/// The number of records processed so far
+	count: usize,
+}
+
+impl Importer<'_> {
+	/// Processes a row of CSV data
+	async fn csv_row(&mut self, row: Vec<String>) -> Result<(), Error> {
+		// The first row is the header
+		let Some(mapping) = &self.mapping else {
+			return self.csv_header(row);
+		};
+		self.count += 1;
+		let mut v = Value::from(Object::default());
+		for (idx, field) in mapping.iter() {
+			match row.get(*idx) {
+				// Empty values are imported as NONE
+				Some(cell) if !cell.is_empty() => v.put(field, cell.as_str().into()),
+				_ => continue,
+			}
+		}
+		self.push(v).await
+	}
+
+	/// Processes the header row of CSV data
+	fn csv_header(&mut self, row: Vec<String>) -> Result<(), Error> {
+		let columns = match self.columns.take() {
+			Some(v) => v,
+			None => Column::from_names(row.iter().cloned()),
+		};
+		let mut mapping = Vec::with_capacity(columns.len());
+		for column in columns {
+			match row.iter().position(|v| *v == column.name) {
+				Some(idx) => mapping.push((idx, column.field)),
+				None => {
+					return Err(Error::InvalidImport(format!(
+						"the column '{}' is not present in the CSV header",
+						column.name
+					)))
+				}
+			}
+		}
+		self.mapping = Some(mapping);
+		Ok(())
+	}
+
+	/// Processes a line of NDJSON data
+	async fn json_row(&mut self, line: &[u8]) -> Result<(), Error> {
+		// Skip any empty lines
+		let line = std::str::from_utf8(line).map_err(|e| self.error(e))?;
+		let line = line.trim();
+		if line.is_empty() {
+			return Ok(());
+		}
+		self.count += 1;
+		// Every line must contain a JSON object
+		let v = match crate::syn::json(line).map_err(|e| self.error(e))? {
+			v @ Value::Object(_) => v,
+			v => return Err(self.error(format!("expected an object but found {v}"))),
+		};
+		// Map the object keys onto the record fields
+		let v = match &self.columns {
+			Some(columns) => {
+				let mut out = Value::from(Object::default());
+				for column in columns {
+					match v.pick(&[Part::from(column.name.as_str())]) {
+						Value::None => continue,
+						val => out.put(&column.field, val),
+					}
+				}
+				out
+			}
+			None => v,
+		};
+		self.push(v).await
+	}
+
+	/// Converts a record, and inserts the batch of records if it is full
+	async fn push(&mut self, mut v: Value) -> Result<(), Error> {
+		// Convert the record id
+		if let Value::Strand(id) = v.pick(&*ID) {
+			if let Ok(id) = crate::syn::thing(&id) {
+				// Record ids must belong to the imported table
+				if id.tb != self.table.0 {
+					return Err(self.error(format!(
+						"the record id {id} does not belong to the table {}",
+						self.table
+					)));
+				}
+				v.put(&*ID, id.into());
+			} else if let (Format::Csv, Ok(id)) = (self.format, id.parse::<i64>()) {
+				v.put(&*ID, id.into());
+			}
+		}
+		// Convert the values to the defined field types
+		for field in self.fields.iter() {
+			// Only fields with a simple path can be converted
+			if !field.name.iter().all(|p| matches!(p, Part::Field(_))) {
+				continue;
+			}
+			let Some(kind) = &field.kind else {
+				continue;
+			};
+			let val = match v.pick(&field.name) {
+				Value::None => continue,
+				val => val,
+			};
+			let val = match val.convert_to(kind) {
+				Ok(val) => val,
+				// Values such as arrays and objects are written as JSON in CSV data
+				Err(Error::ConvertTo {
+					from: Value::Strand(s),
+					..
+				}) => match crate::syn::json(&s).and_then(|v| v.convert_to(kind)) {
+					Ok(val) => val,
+					Err(_) => {
+						return Err(self.error(format!(
+							"expected a {kind} for field {} but found '{s}'",
+							field.name
+						)))
+					}
+				},
+				Err(e) => return Err(self.error(e)),
+			};
+			v.put(&field.name, val);
+		}
+		// Insert the records once the batch is full
+		self.records.push(v);
+		if self.records.len() >= *EXPORT_BATCH_SIZE as usize {
+			self.flush().await?;
+		}
+		Ok(())
+	}
+
+	/// Inserts the batch of records into the table
+	async fn flush(&mut self) -> Result<(), Error> {
+		if self.records.is_empty() {
+			return Ok(());
+		}
+		let sql = format!("INSERT INTO {} $data RETURN NONE", self.table);
+		let vars = map! { String::from("data") => Value::from(mem::take(&mut self.records)) };
+		let res = self.ds.execute(&sql, self.sess, Some(vars)).await?;
+		// Stop the import on the first error
+		for res in res {
+			if res.result.is_err() {
+				return res.result.map(|_| ());
+			}
+			self.responses.push(res);
+		}
+		Ok(())
+	}
+
+	/// Creates an error for the current record
+	fn error(&self, e: impl ToString) -> Error {
+		Error::InvalidImport(format!("record {}: {}", self.count, e.to_string()))
+	}
+}
+
+/// An incremental CSV reader, which can be fed the data in chunks
+struct CsvReader {
+	reader: Reader,
+	/// The field data of the current record
+	output: Vec<u8>,
+	outlen: usize,
+	/// The field end positions of the current record
+	ends: Vec<usize>,
+	endlen: usize,
+}
+
+impl Default for CsvReader {
+	fn default() -> Self {
+		CsvReader {
+			reader: Reader::new(),
+			output: vec![0; 4096],
+			outlen: 0,
+			ends: vec![0; 64],
+			endlen: 0,
+		}
+	}
+}
+
+impl CsvReader {
+	/// Reads the complete rows from a chunk of data, with an
+	/// empty chunk signifying the end of the data.
+	fn read(&mut self, mut input: &[u8], rows: &mut Vec<Vec<String>>) -> Result<(), Error> {
+		loop {
+			let (res, nin, nout, nend) = self.reader.read_record(
+				input,
+				&mut self.output[self.outlen..],
+				&mut self.ends[self.endlen..],
+			);
+			input = &input[nin..];
+			self.outlen += nout;
+			self.endlen += nend;
+			match res {
+				ReadRecordResult::InputEmpty | ReadRecordResult::End => return Ok(()),
+				ReadRecordResult::OutputFull => self.output.resize(self.output.len() * 2, 0),
+				ReadRecordResult::OutputEndsFull => self.ends.resize(self.ends.len() * 2, 0),
+				ReadRecordResult::Record => rows.push(self.record()?),
+			}
+		}
+	}
+
+	/// Takes the fields of the current record
+	fn record(&mut self) -> Result<Vec<String>, Error> {
+		let mut row = Vec::with_capacity(self.endlen);
+		let mut beg = 0;
+		for &end in &self.ends[..self.endlen] {
+			let field = std::str::from_utf8(&self.output[beg..end])
+				.map_err(|e| Error::InvalidImport(e.to_string()))?;
+			row.push(field.to_owned());
+			beg = end;
+		}
+		self.outlen = 0;
+		self.endlen = 0;
+		Ok(row)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::export;
+
+	async fn import(ds: &Datastore, cfg: Config, data: &'static str) -> Result<(), Error> {
+		let ses = Session::owner().with_ns("test").with_db("test");
+		// Split the data into small chunks to exercise the incremental parsing
+		let chunks = data.as_bytes().chunks(7).map(|c| Ok(Bytes::from_static(c)));
+		ds.import_with_config(&ses, cfg, futures::stream::iter(chunks)).await.map(|_| ())
+	}
+
+	async fn export(ds: &Datastore, cfg: export::Config) -> String {
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let (snd, rcv) = async_channel::unbounded();
+		ds.export_with_config(&ses, snd, cfg).await.unwrap().await.unwrap();
+		let mut out = Vec::new();
+		while let Ok(v) = rcv.try_recv() {
+			out.extend(v);
+		}
+		String::from_utf8(out).unwrap()
+	}
+
+	#[tokio::test]
+	async fn csv_import_and_export() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let sql = "
+			DEFINE TABLE person SCHEMAFULL;
+			DEFINE FIELD name ON person TYPE string;
+			DEFINE FIELD age ON person TYPE option<int>;
+			DEFINE FIELD tags ON person TYPE array<string>;
+		";
+		ds.execute(sql, &ses, None).await.unwrap();
+		// Import CSV data with quoted values and a column mapping
+		let cfg = Config {
+			format: Format::Csv,
+			table: Some("person".to_owned()),
+			columns: Some(vec![
+				"id".parse().unwrap(),
+				"full_name=name".parse().unwrap(),
+				"years=age".parse().unwrap(),
+				"tags".parse().unwrap(),
+			]),
+		};
+		let data = "id,full_name,years,tags\none,\"Tobie, M\",35,\"[\"\"a\"\"]\"\ntwo,Jaime,,[]\n";
+		import(&ds, cfg, data).await.unwrap();
+		let res = ds.execute("SELECT * FROM person", &ses, None).await.unwrap();
+		let val = res.into_iter().next().unwrap().result.unwrap();
+		assert_eq!(
+			val.to_string(),
+			"[{ age: 35, id: person:one, name: 'Tobie, M', tags: ['a'] }, { id: person:two, name: 'Jaime', tags: [] }]"
+		);
+		// Export the same records as CSV
+		let cfg = export::Config {
+			format: Format::Csv,
+			tables: vec!["person"].into(),
+			..Default::default()
+		};
+		assert_eq!(
+			export(&ds, cfg).await,
+			"id,age,name,tags\nperson:one,35,\"Tobie, M\",\"[\"\"a\"\"]\"\nperson:two,,Jaime,[]\n"
+		);
+	}
+
+	#[tokio::test]
+	async fn ndjson_import_and_export() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let sql = "DEFINE FIELD born ON person TYPE datetime;";
+		ds.execute(sql, &ses, None).await.unwrap();
+		// Import NDJSON data, converting the defined fields
+		let cfg = Config {
+			format: Format::Ndjson,
+			table: Some("person".to_owned()),
+			columns: None,
+		};
+		let data = "{\"id\":\"person:one\",\"born\":\"2000-01-01T00:00:00Z\",\"address\":{\"city\":\"London\"}}\n\n{\"id\":2,\"born\":\"2001-01-01T00:00:00Z\"}";
+		import(&ds, cfg, data).await.unwrap();
+		let res = ds.execute("SELECT * FROM person", &ses, None).await.unwrap();
+		let val = res.into_iter().next().unwrap().result.unwrap();
+		assert_eq!(
+			val.to_string(),
+			"[{ born: d'2001-01-01T00:00:00Z', id: person:2 }, { address: { city: 'London' }, born: d'2000-01-01T00:00:00Z', id: person:one }]"
+		);
+		// Export a nested field with a column mapping
+		let cfg = export::Config {
+			format: Format::Ndjson,
+			tables: vec!["person"].into(),
+			columns: Some(vec!["id".parse().unwrap(), "city=address.city".parse().unwrap()]),
+			..Default::default()
+		};
+		assert_eq!(
+			export(&ds, cfg).await,
+			"{\"city\":null,\"id\":\"person:2\"}\n{\"city\":\"London\",\"id\":\"person:one\"}\n"
+		);
+	}
+
+	#[tokio::test]
+	async fn invalid_import() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		ds.execute("DEFINE FIELD age ON person TYPE int", &ses, None).await.unwrap();
+		// The values must match the defined field types
+		let cfg = Config {
+			format: Format::Csv,
+			table: Some("person".to_owned()),
+			columns: None,
+		};
+		let err = import(&ds, cfg.clone(), "id,age\none,1\ntwo,old\n").await.unwrap_err();
+		assert!(matches!(err, Error::InvalidImport(m) if m.starts_with("record 2:")));
+		// The mapped columns must be present
+		let cfg = Config {
+			columns: Some(vec!["name".parse().unwrap()]),
+			..cfg
+		};
+		let err = import(&ds, cfg.clone(), "id,age\none,1\n").await.unwrap_err();
+		assert!(matches!(err, Error::InvalidImport(_)));
+		// An empty header is only read once
+		let cfg = Config {
+			table: Some("other".to_owned()),
+			columns: Some(vec![]),
+			..cfg
+		};
+		import(&ds, cfg.clone(), "id,age\none,1\n").await.unwrap();
+		let res = ds.execute("SELECT count() FROM other GROUP ALL", &ses, None).await.unwrap();
+		assert_eq!(res.into_iter().next().unwrap().result.unwrap().to_string(), "[{ count: 1 }]");
+		// Record ids must belong to the imported table
+		let cfg = Config {
+			columns: None,
+			..cfg
+		};
+		let err = import(&ds, cfg, "id,age\nperson:one,1\n").await.unwrap_err();
+		assert!(matches!(err, Error::InvalidImport(m) if m.starts_with("record 1:")));
+		// A table must be specified
+		let cfg = Config {
+			format: Format::Ndjson,
+			..Default::default()
+		};
+		let err = import(&ds, cfg, "{}").await.unwrap_err();
+		assert!(matches!(err, Error::InvalidImport(_)));
+	}
+}
@@ -64,7 +64,7 @@ impl BuildingStatus {
 		matches!(self, Self::Error(_))
 	}
 
-	fn is_ready(&self) -> bool {
+	pub(crate) fn is_ready(&self) -> bool {
 		matches!(self, Self::Ready { .. })
 	}
 }
@@ -13,18 +13,24 @@
 //! - `tikv`: [TiKV](https://github.com/tikv/tikv) a distributed, and transactional key-value database
 //! - `mem`: in-memory database
 
+pub mod backup;
 pub mod export;
+pub mod import;
 
 mod api;
 mod batch;
 mod cf;
+mod cipher;
 mod clock;
 mod ds;
+mod expiry;
 mod key;
 mod live;
 mod node;
 mod scanner;
+mod sequences;
 mod stash;
+mod sync;
 mod threadpool;
 mod tr;
 mod tx;
@@ -56,11 +62,14 @@ mod util;
 pub(crate) use key::impl_key;
 pub use key::{KeyDecode, KeyDecodeOwned, KeyEncode};
 
+pub use cipher::decode_encryption_key;
 pub use ds::Datastore;
 pub use live::Live;
 pub use tr::{Check, LockType, TransactionType, Transactor};
 pub use tx::Transaction;
 
+pub(crate) use sequences::{SequenceState, Sequences};
+
 #[cfg(not(target_family = "wasm"))]
 pub(crate) use index::{ConsumeResult, IndexBuilder};
 
@@ -3,6 +3,7 @@
 use crate::err::Error;
 use crate::kvs::api::Transaction;
 use crate::kvs::{Key, Val};
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, VecDeque};
 
 type SavePoint = HashMap<Key, SavedValue>;
@@ -67,6 +68,24 @@ impl SavePoints {
 		}
 	}
 
+	/// Releases the current save point. The saved values are merged into
+	/// the enclosing save point, so that the changes made since this save
+	/// point was created are still rolled back with the enclosing one.
+	pub(super) fn release(&mut self) -> Result<(), Error> {
+		let sp = self.pop()?;
+		if let Some(current) = &mut self.current {
+			for (key, sv) in sp {
+				match current.entry(key) {
+					Entry::Occupied(mut e) => e.get_mut().last_operation = sv.last_operation,
+					Entry::Vacant(e) => {
+						e.insert(sv);
+					}
+				}
+			}
+		}
+		Ok(())
+	}
+
 	pub(super) fn is_saved_key(&self, key: &Key) -> Option<bool> {
 		self.current.as_ref().map(|current| current.contains_key(key))
 	}
@@ -130,8 +149,7 @@ pub(super) trait SavePointImpl: Transaction + Sized {
 	}
 
 	fn release_last_save_point(&mut self) -> Result<(), Error> {
-		self.get_save_points().pop()?;
-		Ok(())
+		self.get_save_points().release()
 	}
 
 	async fn save_point_prepare(
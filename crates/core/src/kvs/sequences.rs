@@ -0,0 +1,192 @@
+use crate::err::Error;
+use crate::key::database::sv;
+use crate::kvs::ds::TransactionFactory;
+use crate::kvs::{LockType::*, Transaction, TransactionType::*};
+use crate::sql::statements::DefineSequenceStatement;
+use dashmap::DashMap;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+/// How many times a batch reservation is retried when it
+/// conflicts with a reservation made by another node
+const MAX_RESERVE_ATTEMPTS: usize = 10;
+
+/// The stored state of a sequence, shared by all nodes
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SequenceState {
+	/// The first value which has not yet been reserved by any node
+	pub(crate) next: i64,
+}
+
+impl SequenceState {
+	/// Fetches the first value of a sequence which has not yet been reserved
+	///
+	/// Values which were reserved before the sequence was redefined
+	/// are never handed out again, even if the start value is lowered.
+	pub(crate) async fn next(
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+		sq: &DefineSequenceStatement,
+	) -> Result<i64, Error> {
+		let key = sv::new(ns, db, &sq.name);
+		match txn.get(key, None).await? {
+			Some(v) => Ok(revision::from_slice::<SequenceState>(&v)?.next.max(sq.start)),
+			None => Ok(sq.start),
+		}
+	}
+}
+
+#[derive(Hash, PartialEq, Eq)]
+struct SequenceKey {
+	ns: String,
+	db: String,
+	sq: String,
+}
+
+impl SequenceKey {
+	fn new(ns: &str, db: &str, sq: &str) -> Self {
+		Self {
+			ns: ns.to_owned(),
+			db: db.to_owned(),
+			sq: sq.to_owned(),
+		}
+	}
+}
+
+/// The range of values reserved by this node for a sequence
+#[derive(Default)]
+struct Batch {
+	/// The definition under which this range was reserved
+	defined: Uuid,
+	/// The next value to hand out
+	next: i64,
+	/// The end of the reserved range (exclusive)
+	end: i64,
+}
+
+/// The last values handed out by `sequence::nextval` within a transaction
+#[derive(Default)]
+pub(crate) struct LastValues(DashMap<SequenceKey, i64>);
+
+impl LastValues {
+	/// Records the last value handed out for a sequence
+	pub(crate) fn set(&self, ns: &str, db: &str, sq: &str, val: i64) {
+		self.0.insert(SequenceKey::new(ns, db, sq), val);
+	}
+
+	/// Returns the last value handed out for a sequence
+	pub(crate) fn get(&self, ns: &str, db: &str, sq: &str) -> Option<i64> {
+		self.0.get(&SequenceKey::new(ns, db, sq)).map(|v| *v)
+	}
+}
+
+/// Hands out sequence values on this node.
+///
+/// A sequence with a `BATCH` of 1 is strictly gap-free: each
+/// value is taken from the stored sequence state within the
+/// transaction which uses it, so the value is only consumed if
+/// that transaction commits, and concurrent transactions which
+/// take a value conflict with each other.
+///
+/// Otherwise each node reserves a batch of values at a time, in
+/// a separate transaction, so that the stored sequence state is
+/// only written once per batch. Values are unique across all
+/// nodes, and increase monotonically on each node, but values
+/// which are reserved and not used, or which are handed out in
+/// a transaction which is later cancelled, are skipped. When a
+/// sequence is redefined, each node discards its batch the next
+/// time the sequence is used, as the batch belongs to the
+/// previous definition.
+#[derive(Clone)]
+pub(crate) struct Sequences {
+	tf: TransactionFactory,
+	batches: Arc<DashMap<SequenceKey, Arc<Mutex<Batch>>>>,
+}
+
+impl Sequences {
+	pub(super) fn new(tf: TransactionFactory) -> Self {
+		Self {
+			tf,
+			batches: Default::default(),
+		}
+	}
+
+	/// Returns the next value of a sequence
+	pub(crate) async fn next_val(
+		&self,
+		ns: &str,
+		db: &str,
+		sq: &DefineSequenceStatement,
+	) -> Result<i64, Error> {
+		let key = SequenceKey::new(ns, db, &sq.name);
+		let batch = self.batches.entry(key).or_default().clone();
+		let mut batch = batch.lock().await;
+		// Reserve a new batch once the current one is used up,
+		// or if the sequence has been redefined since it was reserved
+		if batch.next >= batch.end || batch.defined != sq.cache_batches_ts {
+			let (next, end) = self.reserve(ns, db, sq).await?;
+			batch.defined = sq.cache_batches_ts;
+			batch.next = next;
+			batch.end = end;
+		}
+		let val = batch.next;
+		batch.next += 1;
+		Ok(val)
+	}
+
+	/// Takes the next value of a strictly gap-free sequence within the given transaction
+	pub(crate) async fn next_val_in(
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+		sq: &DefineSequenceStatement,
+	) -> Result<i64, Error> {
+		let (next, _) = Self::reserve_in(txn, ns, db, sq).await?;
+		Ok(next)
+	}
+
+	/// Reserves the next batch of values in the stored sequence state
+	async fn reserve(
+		&self,
+		ns: &str,
+		db: &str,
+		sq: &DefineSequenceStatement,
+	) -> Result<(i64, i64), Error> {
+		let mut attempt = 0;
+		loop {
+			let txn = self.tf.transaction(Write, Optimistic).await?;
+			let res = run!(txn, Self::reserve_in(&txn, ns, db, sq).await);
+			match res {
+				Err(Error::TxRetryable) if attempt < MAX_RESERVE_ATTEMPTS => attempt += 1,
+				res => return res,
+			}
+		}
+	}
+
+	async fn reserve_in(
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+		sq: &DefineSequenceStatement,
+	) -> Result<(i64, i64), Error> {
+		let next = SequenceState::next(txn, ns, db, sq).await?;
+		let end = next.checked_add(sq.batch.max(1) as i64).ok_or_else(|| Error::SqExhausted {
+			name: sq.name.to_raw(),
+		})?;
+		let key = sv::new(ns, db, &sq.name);
+		txn.set(
+			key,
+			revision::to_vec(&SequenceState {
+				next: end,
+			})?,
+			None,
+		)
+		.await?;
+		Ok((next, end))
+	}
+}
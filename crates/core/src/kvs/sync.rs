@@ -0,0 +1,411 @@
+use super::{KeyDecode as _, Transaction};
+use crate::cf::{ChangeSet, DatabaseMutation, TableMutation, TableMutations};
+use crate::cnf::SYNC_BATCH_SIZE;
+use crate::err::Error;
+use crate::sql::paths::EDGE;
+use crate::sql::paths::IN;
+use crate::sql::paths::OUT;
+use crate::sql::{Ident, Value};
+use crate::vs::VersionStamp;
+use async_channel::Sender;
+
+impl Transaction {
+	/// Retrieves the latest committed versionstamp for a database.
+	///
+	/// Any change which is committed after this transaction was started
+	/// will be recorded in the change feed with a greater versionstamp.
+	pub async fn sync_versionstamp(&self, ns: &str, db: &str) -> Result<VersionStamp, Error> {
+		// Calculate the database versionstamp key
+		let key = crate::key::database::vs::new(ns, db);
+		// Fetch the latest versionstamp for the database
+		match self.get(key, None).await? {
+			Some(v) => Ok(VersionStamp::from_slice(&v)?),
+			None => Ok(VersionStamp::ZERO),
+		}
+	}
+
+	/// Reads the change feed entries following a versionstamp.
+	///
+	/// Only whole change sets are returned, and the change feed is read in
+	/// batches, until at least `limit` change sets have been read. Returns
+	/// the change sets which were read, and the versionstamp from which the
+	/// next set of changes should be requested.
+	pub async fn sync_changesets(
+		&self,
+		ns: &str,
+		db: &str,
+		since: u64,
+		limit: Option<u32>,
+	) -> Result<(Vec<ChangeSet>, u64), Error> {
+		// Use the default batch size if no limit is specified
+		let limit = limit.unwrap_or(*SYNC_BATCH_SIZE).max(1);
+		// Calculate the change feed range
+		let beg = crate::key::change::prefix_ts(ns, db, VersionStamp::from_u64(since))?;
+		let end = crate::key::change::suffix(ns, db)?;
+		// The complete change sets which have been read
+		let mut res = Vec::new();
+		// The change set which is currently being read
+		let mut cur: Option<(VersionStamp, Vec<TableMutations>)> = None;
+		// Read the change feed in batches
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = self.batch_keys_vals(rng, limit, None).await?;
+			next = batch.next;
+			for (k, v) in batch.result {
+				// Decode the change feed entry
+				let vs = crate::key::change::Cf::decode(&k)?.vs;
+				let tb: TableMutations = revision::from_slice(&v)?;
+				// A change set is complete once the next one starts
+				match &mut cur {
+					Some((cvs, buf)) if *cvs == vs => buf.push(tb),
+					_ => {
+						if let Some((cvs, buf)) = cur.replace((vs, vec![tb])) {
+							res.push(ChangeSet(cvs, DatabaseMutation(buf)));
+						}
+					}
+				}
+			}
+			// Stop once enough whole change sets have been read
+			if res.len() >= limit as usize && next.is_some() {
+				break;
+			}
+			// Pause execution
+			yield_now!();
+		}
+		// The last change set is complete if the change feed was exhausted
+		if next.is_none() {
+			if let Some((cvs, buf)) = cur {
+				res.push(ChangeSet(cvs, DatabaseMutation(buf)));
+			}
+		}
+		// Calculate the versionstamp to continue from
+		let next = match res.last() {
+			Some(ChangeSet(vs, _)) => vs.into_u64_lossy() + 1,
+			None => since,
+		};
+		// Return the changes
+		Ok((res, next))
+	}
+
+	/// Computes a digest of the definitions which are exported in a snapshot.
+	///
+	/// Changes to fields, indexes, events, functions and other definitions
+	/// are not recorded in the change feed, so a replica compares this
+	/// digest to detect when a new snapshot needs to be applied.
+	pub async fn sync_definitions(&self, ns: &str, db: &str) -> Result<String, Error> {
+		let mut hasher = blake3::Hasher::new();
+		let mut update = |v: String| {
+			hasher.update(v.as_bytes());
+			hasher.update(&[0]);
+		};
+		self.all_db_roles(ns, db).await?.iter().for_each(|v| update(v.to_string()));
+		self.all_db_users(ns, db).await?.iter().for_each(|v| update(v.to_string()));
+		self.all_db_accesses(ns, db).await?.iter().for_each(|v| update(v.to_string()));
+		self.all_db_params(ns, db).await?.iter().for_each(|v| update(v.to_string()));
+		self.all_db_sequences(ns, db).await?.iter().for_each(|v| update(v.to_string()));
+		self.all_db_functions(ns, db).await?.iter().for_each(|v| update(v.to_string()));
+		self.all_db_analyzers(ns, db).await?.iter().for_each(|v| update(v.to_string()));
+		for tb in self.all_tb(ns, db, None).await?.iter() {
+			update(tb.to_string());
+			self.all_tb_fields(ns, db, &tb.name, None)
+				.await?
+				.iter()
+				.for_each(|v| update(v.to_string()));
+			self.all_tb_indexes(ns, db, &tb.name).await?.iter().for_each(|v| update(v.to_string()));
+			self.all_tb_events(ns, db, &tb.name).await?.iter().for_each(|v| update(v.to_string()));
+		}
+		Ok(hasher.finalize().to_hex().to_string())
+	}
+
+	/// Ensures that the changes following a versionstamp are still retained.
+	///
+	/// Change feed entries older than the garbage collection watermark have
+	/// been removed, so the changes since an earlier versionstamp can not be
+	/// read, and a new snapshot needs to be taken instead.
+	pub async fn sync_check_retention(&self, ns: &str, db: &str, since: u64) -> Result<(), Error> {
+		// Calculate the database watermark key
+		let key = crate::key::database::cw::new(ns, db);
+		// Fetch the garbage collection watermark for the database
+		let watermark = match self.get(key, None).await? {
+			Some(v) => VersionStamp::from_slice(&v)?.into_u64_lossy(),
+			None => 0,
+		};
+		// Check that no changes have been removed
+		match since < watermark {
+			true => Err(Error::ChangefeedExpired {
+				since,
+			}),
+			false => Ok(()),
+		}
+	}
+
+	/// Ensures that the changes to every table are recorded in a change feed.
+	pub async fn sync_check_changefeeds(&self, ns: &str, db: &str) -> Result<(), Error> {
+		// A database change feed records the changes to every table
+		if self.get_db(ns, db).await?.changefeed.is_some() {
+			return Ok(());
+		}
+		// Otherwise each table needs its own change feed
+		match self.all_tb(ns, db, None).await?.iter().find(|tb| tb.changefeed.is_none()) {
+			Some(tb) => Err(Error::ChangefeedMissing {
+				name: tb.name.to_raw(),
+			}),
+			None => Ok(()),
+		}
+	}
+
+	/// Writes a set of change feed entries as SurrealQL statements.
+	///
+	/// The statements are applied by the replica within a single transaction,
+	/// so that a replica which applies the output never observes a partial change.
+	pub async fn sync_changes(
+		&self,
+		changes: Vec<ChangeSet>,
+		chn: Sender<Vec<u8>>,
+	) -> Result<(), Error> {
+		// Output each of the statements
+		for sql in Self::sync_statements(changes) {
+			chn.send(bytes!(sql)).await?;
+			// Pause execution
+			yield_now!();
+		}
+		Ok(())
+	}
+
+	/// Converts a set of change feed entries into SurrealQL statements.
+	///
+	/// The statements are applied within a transaction managed by the caller.
+	pub(crate) fn sync_sql(changes: Vec<ChangeSet>) -> String {
+		let mut sql = String::new();
+		for v in Self::sync_statements(changes) {
+			sql.push_str(&v);
+			sql.push('\n');
+		}
+		sql
+	}
+
+	/// Iterates over the SurrealQL statements for a set of change feed entries.
+	fn sync_statements(changes: Vec<ChangeSet>) -> impl Iterator<Item = String> {
+		// Ensure the changes are applied as raw values
+		std::iter::once(String::from("OPTION IMPORT;")).chain(
+			changes
+				.into_iter()
+				.flat_map(|ChangeSet(_, mutations)| mutations.0)
+				.flat_map(|TableMutations(_, mutations)| mutations)
+				.map(Self::process_mutation),
+		)
+	}
+
+	/// Converts a change feed table mutation into a SurrealQL statement.
+	fn process_mutation(mutation: TableMutation) -> String {
+		match mutation {
+			TableMutation::Set(id, v) | TableMutation::SetWithDiff(id, v, _) => {
+				// Match on the value to determine if it is a graph edge record or a normal record.
+				match (v.pick(&*EDGE), v.pick(&*IN), v.pick(&*OUT)) {
+					(Value::Bool(true), Value::Thing(_), Value::Thing(_)) => {
+						format!("DELETE {id} RETURN NONE; INSERT RELATION {v} RETURN NONE;")
+					}
+					_ => format!("UPSERT {id} CONTENT {v} RETURN NONE;"),
+				}
+			}
+			TableMutation::Del(id) | TableMutation::DelWithOriginal(id, _) => {
+				format!("DELETE {id} RETURN NONE;")
+			}
+			TableMutation::Def(mut tb) => {
+				tb.id = None;
+				tb.overwrite = true;
+				format!("{tb};")
+			}
+			TableMutation::Truncate(tb) => {
+				format!("TRUNCATE TABLE {};", Ident::from(tb))
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::Session;
+	use crate::kvs::{Datastore, LockType::*, TransactionType::*};
+	use crate::sql::statements::DefineTableStatement;
+	use crate::sql::Thing;
+	use crate::syn::Parse;
+	use async_channel::Receiver;
+	use bytes::Bytes;
+	use futures::Stream;
+
+	/// Collects the output of a finished sync job as a request body
+	fn body(rcv: Receiver<Vec<u8>>) -> impl Stream<Item = Result<Bytes, Error>> {
+		let mut res = Vec::new();
+		while let Ok(v) = rcv.try_recv() {
+			res.push(Ok(Bytes::from(v)));
+		}
+		futures::stream::iter(res)
+	}
+
+	#[test]
+	fn process_mutations() {
+		let id = Thing::from(("person", "tobie"));
+		let val = Value::parse("{ id: person:tobie, name: 'Tobie' }");
+		let sql = Transaction::process_mutation(TableMutation::Set(id.clone(), val));
+		assert_eq!(
+			sql,
+			"UPSERT person:tobie CONTENT { id: person:tobie, name: 'Tobie' } RETURN NONE;"
+		);
+		let sql = Transaction::process_mutation(TableMutation::Del(id));
+		assert_eq!(sql, "DELETE person:tobie RETURN NONE;");
+		let id = Thing::from(("likes", "one"));
+		let val = Value::parse("{ __: true, id: likes:one, in: person:tobie, out: person:jaime }");
+		let sql = Transaction::process_mutation(TableMutation::Set(id, val));
+		assert_eq!(
+			sql,
+			"DELETE likes:one RETURN NONE; INSERT RELATION { __: true, id: likes:one, in: person:tobie, out: person:jaime } RETURN NONE;"
+		);
+		let sql =
+			Transaction::process_mutation(TableMutation::Def(Box::new(DefineTableStatement {
+				name: "person".into(),
+				..DefineTableStatement::default()
+			})));
+		assert!(sql.starts_with("DEFINE TABLE OVERWRITE person"));
+		let sql = Transaction::process_mutation(TableMutation::Truncate("person".to_string()));
+		assert_eq!(sql, "TRUNCATE TABLE person;");
+	}
+
+	#[tokio::test]
+	async fn sync_changesets_in_batches() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		ds.execute("DEFINE TABLE person CHANGEFEED 1h", &ses, None).await.unwrap();
+		ds.execute("DEFINE TABLE other CHANGEFEED 1h", &ses, None).await.unwrap();
+		for i in 0..5 {
+			ds.execute(&format!("CREATE person:{i}"), &ses, None).await.unwrap();
+		}
+		// A transaction which spans multiple tables
+		let sql = "BEGIN; CREATE person:5; CREATE other:5; COMMIT;";
+		ds.execute(sql, &ses, None).await.unwrap();
+		// Read the change feed one change set at a time
+		let tx = ds.transaction(Read, Optimistic).await.unwrap();
+		let mut since = 0;
+		let mut all: Vec<ChangeSet> = Vec::new();
+		loop {
+			let (changes, next) = tx.sync_changesets("test", "test", since, Some(1)).await.unwrap();
+			if changes.is_empty() {
+				assert_eq!(next, since);
+				break;
+			}
+			all.extend(changes);
+			since = next;
+		}
+		tx.cancel().await.unwrap();
+		// Every change set is returned whole, and only once
+		assert!(all.windows(2).all(|w| w[0].0 < w[1].0));
+		let sets = all
+			.iter()
+			.flat_map(|ChangeSet(_, m)| m.0.iter())
+			.flat_map(|TableMutations(_, m)| m.iter())
+			.filter(|m| matches!(m, TableMutation::Set(..)))
+			.count();
+		assert_eq!(sets, 7);
+	}
+
+	#[tokio::test]
+	async fn sync_apply_to_read_only_datastore() {
+		let primary = Datastore::new("memory").await.unwrap();
+		let replica = Datastore::new("memory").await.unwrap().with_read_only(true);
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let sql = "DEFINE TABLE person CHANGEFEED 1h; CREATE person:one";
+		primary.execute(sql, &ses, None).await.unwrap();
+		// Apply a snapshot to the replica
+		let (snd, rcv) = async_channel::unbounded();
+		let (next, defs, task) = primary.sync_snapshot(&ses, snd).await.unwrap();
+		task.await.unwrap();
+		replica.sync_apply(&ses, true, body(rcv)).await.unwrap();
+		let mut res = replica.execute("SELECT VALUE id FROM person", &ses, None).await.unwrap();
+		assert_eq!(res.remove(0).output().unwrap(), Value::parse("[person:one]"));
+		// Data can not be written to the replica
+		let mut res = replica.execute("CREATE person:two", &ses, None).await.unwrap();
+		assert!(matches!(res.remove(0).result, Err(Error::ReadOnly)));
+		// Apply the subsequent changes to the replica
+		let sql = "CREATE person:two; DELETE person:one";
+		primary.execute(sql, &ses, None).await.unwrap();
+		let (snd, rcv) = async_channel::unbounded();
+		let (_, changed, task) = primary.sync_changes(&ses, next, snd).await.unwrap();
+		task.await.unwrap();
+		assert_eq!(defs, changed);
+		replica.sync_apply(&ses, false, body(rcv)).await.unwrap();
+		let mut res = replica.execute("SELECT VALUE id FROM person", &ses, None).await.unwrap();
+		assert_eq!(res.remove(0).output().unwrap(), Value::parse("[person:two]"));
+		// Expired records are not deleted on the replica
+		let sql = "DEFINE TABLE OVERWRITE person CHANGEFEED 1h EXPIRE expires; UPDATE person:two SET expires = time::now() - 1h";
+		primary.execute(sql, &ses, None).await.unwrap();
+		let (snd, rcv) = async_channel::unbounded();
+		let (_, _, task) = primary.sync_snapshot(&ses, snd).await.unwrap();
+		task.await.unwrap();
+		replica.sync_apply(&ses, true, body(rcv)).await.unwrap();
+		replica.expiry_process().await.unwrap();
+		let tx = replica.transaction(Read, Optimistic).await.unwrap();
+		let val = tx.get_record("test", "test", "person", &"two".into(), None).await.unwrap();
+		assert!(val.is_some());
+		tx.cancel().await.unwrap();
+		// Changed definitions are detected
+		primary.execute("DEFINE FIELD name ON person", &ses, None).await.unwrap();
+		let (snd, _rcv) = async_channel::unbounded();
+		let (_, changed, _) = primary.sync_changes(&ses, next, snd).await.unwrap();
+		assert_ne!(defs, changed);
+		// Tables without a change feed are rejected
+		primary.execute("DEFINE TABLE other", &ses, None).await.unwrap();
+		let (snd, _rcv) = async_channel::unbounded();
+		let res = primary.sync_snapshot(&ses, snd).await;
+		assert!(matches!(res, Err(Error::ChangefeedMissing { .. })));
+	}
+
+	#[tokio::test]
+	async fn sync_apply_in_batches() {
+		let primary = Datastore::new("memory").await.unwrap();
+		let replica = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let sql = "DEFINE TABLE person CHANGEFEED 1h; CREATE |person:2500| RETURN NONE";
+		primary.execute(sql, &ses, None).await.unwrap();
+		// Apply a snapshot which spans several batches
+		let (snd, rcv) = async_channel::unbounded();
+		let (_, _, task) = primary.sync_snapshot(&ses, snd).await.unwrap();
+		task.await.unwrap();
+		replica.sync_apply(&ses, true, body(rcv)).await.unwrap();
+		let sql = "SELECT count() FROM person GROUP ALL";
+		let mut res = replica.execute(sql, &ses, None).await.unwrap();
+		assert_eq!(res.remove(0).output().unwrap(), Value::parse("[{ count: 2500 }]"));
+		// A failed transfer leaves the database unchanged
+		let mut body =
+			(0..2500).map(|i| Ok(Bytes::from(format!("DELETE person:{i};")))).collect::<Vec<_>>();
+		body.push(Err(Error::QueryStream("interrupted".to_string())));
+		let res = replica.sync_apply(&ses, false, futures::stream::iter(body)).await;
+		assert!(res.is_err());
+		let mut res = replica.execute(sql, &ses, None).await.unwrap();
+		assert_eq!(res.remove(0).output().unwrap(), Value::parse("[{ count: 2500 }]"));
+	}
+
+	#[tokio::test]
+	async fn sync_changes_removed_by_garbage_collection() {
+		let primary = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let sql = "DEFINE TABLE person CHANGEFEED 1h; CREATE person:one";
+		primary.execute(sql, &ses, None).await.unwrap();
+		let (snd, _rcv) = async_channel::unbounded();
+		let (since, _, _) = primary.sync_snapshot(&ses, snd).await.unwrap();
+		primary.execute("CREATE person:two", &ses, None).await.unwrap();
+		// Remove the change feed entries up to the latest change
+		let tx = primary.transaction(Write, Optimistic).await.unwrap();
+		let vs = tx.sync_versionstamp("test", "test").await.unwrap();
+		crate::cf::gc_range(&tx, "test", "test", vs.next().unwrap()).await.unwrap();
+		tx.commit().await.unwrap();
+		// The removed changes can not be read
+		let (snd, _rcv) = async_channel::unbounded();
+		let res = primary.sync_changes(&ses, since, snd).await;
+		assert!(matches!(res, Err(Error::ChangefeedExpired { .. })));
+		// The changes following the watermark can still be read
+		let (snd, _rcv) = async_channel::unbounded();
+		let next = vs.into_u64_lossy() + 1;
+		assert!(primary.sync_changes(&ses, next, snd).await.is_ok());
+	}
+}
@@ -0,0 +1,191 @@
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::{
+	dbs::node::Timestamp,
+	err::Error,
+	kvs::{
+		clock::{FakeClock, SizedClock},
+		LockType::*,
+		TransactionType::*,
+	},
+};
+
+use super::CreateDs;
+
+/// A hex encoded encryption key
+const KEY: &str = "8f1e2d3c4b5a69788796a5b4c3d2e1f00f1e2d3c4b5a69788796a5b4c3d2e1f0";
+
+/// The same encryption key, encoded as base64
+const KEY_BASE64: &str = "jx4tPEtaaXiHlqW0w9Lh8A8eLTxLWml4h5altMPS4fA=";
+
+/// A different hex encoded encryption key
+const OTHER_KEY: &str = "1f2e3d4c5b6a79880f1e2d3c4b5a69788796a5b4c3d2e1f08796a5b4c3d2e1f0";
+
+pub async fn encrypted(new_ds: impl CreateDs) {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("6d2f8f4e-1c55-4c2b-9a0e-5b1f2f0f7a61").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds.create_ds(node_id, clock).await;
+	let ds = ds.with_encryption_key(Some(KEY.to_string())).await.unwrap();
+	// Create a writeable transaction
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx.set("test1", "one", None).await.unwrap();
+	tx.set("test2", "two", None).await.unwrap();
+	tx.commit().await.unwrap();
+	// Create a writeable transaction
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx.putc("test1", "uno", Some("one")).await.unwrap();
+	let res = tx.putc("test2", "dos", Some("one")).await;
+	assert!(matches!(res, Err(Error::TxConditionNotMet)));
+	tx.delc("test2", Some("two")).await.unwrap();
+	tx.commit().await.unwrap();
+	// Create a readonly transaction
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap().inner();
+	let val = tx.scan("test0".."test9", u32::MAX, None).await.unwrap();
+	assert_eq!(val, vec![(b"test1".to_vec(), b"uno".to_vec())]);
+	tx.cancel().await.unwrap();
+	// Check the value is not stored in plaintext
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap().inner();
+	tx.cipher = None;
+	let val = tx.get("test1", None).await.unwrap().unwrap();
+	assert_ne!(val, b"uno");
+	assert!(!val.windows(3).any(|w| w == b"uno"));
+	tx.cancel().await.unwrap();
+	// Check the value can not be moved to another key
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx.cipher = None;
+	tx.set("test2", val, None).await.unwrap();
+	tx.commit().await.unwrap();
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap().inner();
+	let res = tx.get("test2", None).await;
+	assert!(matches!(res, Err(Error::TxDecryptionFailed)));
+	tx.cancel().await.unwrap();
+	// Check the same key can be specified as base64
+	let ds = ds.with_encryption_key(Some(KEY_BASE64.to_string())).await.unwrap();
+	// Check the datastore can not be opened with another key
+	let res = ds.with_encryption_key(Some(OTHER_KEY.to_string())).await;
+	assert!(matches!(res, Err(Error::Ds(_))));
+}
+
+pub async fn unencrypted(new_ds: impl CreateDs) {
+	// Create a new encrypted datastore
+	let node_id = Uuid::parse_str("2b7e4f0c-9d1a-4c86-b35e-7f08a1c6e942").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds.create_ds(node_id, clock).await;
+	let ds = ds.with_encryption_key(Some(KEY.to_string())).await.unwrap();
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx.set("test", "ok", None).await.unwrap();
+	tx.commit().await.unwrap();
+	// Check the datastore can not be opened without a key
+	let res = ds.with_encryption_key(None).await;
+	assert!(matches!(res, Err(Error::Ds(_))));
+}
+
+pub async fn versionstamped(new_ds: impl CreateDs) {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("5e0c7a92-8b14-4f3d-a6e1-c2d9b7f40e58").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds.create_ds(node_id, clock).await;
+	let ds = ds.with_encryption_key(Some(KEY.to_string())).await.unwrap();
+	// Store a value under a key which is completed on commit
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx.set_versionstamped("vs", "cf", "tb", "one").await.unwrap();
+	tx.commit().await.unwrap();
+	// Check the value can be read under the completed key
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap().inner();
+	let res = tx.scan("cf".."cg", u32::MAX, None).await.unwrap();
+	assert_eq!(res.len(), 1);
+	let (key, val) = res.into_iter().next().unwrap();
+	assert!(key.starts_with(b"cf") && key.ends_with(b"tb"));
+	assert_eq!(val, b"one");
+	tx.cancel().await.unwrap();
+	// Check the value can not be moved to another prefix
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx.cipher = None;
+	let val = tx.get(key.clone(), None).await.unwrap().unwrap();
+	let mut moved = b"cg".to_vec();
+	moved.extend_from_slice(&key[2..]);
+	tx.set(moved.clone(), val, None).await.unwrap();
+	tx.commit().await.unwrap();
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap().inner();
+	let res = tx.get(moved, None).await;
+	assert!(matches!(res, Err(Error::TxDecryptionFailed)));
+	tx.cancel().await.unwrap();
+}
+
+pub async fn rewrite(new_ds: impl CreateDs) {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("a3c1d6b0-3f47-4e51-8c3d-91f0e2b8d4c7").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds.create_ds(node_id, clock).await;
+	// Store a value without encryption
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx.set("test", "one", None).await.unwrap();
+	tx.commit().await.unwrap();
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap().inner();
+	tx.set("test", "ok", None).await.unwrap();
+	tx.commit().await.unwrap();
+	// Check that a passphrase is not accepted as a key
+	let res = ds.rewrite_encryption(Some("0123456789abcdef".to_string())).await;
+	assert!(matches!(res, Err(Error::Ds(_))));
+	// Encrypt the existing values
+	assert!(ds.rewrite_encryption(Some(KEY.to_string())).await.unwrap() > 0);
+	let ds = ds.with_encryption_key(Some(KEY.to_string())).await.unwrap();
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap().inner();
+	let val = tx.get("test", None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"ok")));
+	tx.cancel().await.unwrap();
+	// Rotate the encryption key
+	ds.rewrite_encryption(Some(OTHER_KEY.to_string())).await.unwrap();
+	let ds = ds.with_encryption_key(Some(OTHER_KEY.to_string())).await.unwrap();
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap().inner();
+	let val = tx.get("test", None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"ok")));
+	// Check every version of the value was rewritten
+	match tx.batch_keys_vals_versions("test".."tesu", u32::MAX).await {
+		Ok(res) => assert!(res.result.iter().any(|(_, v, _, _)| v == b"ok")),
+		Err(e) => assert!(matches!(e, Error::UnsupportedVersionedQueries)),
+	}
+	tx.cancel().await.unwrap();
+	// Decrypt the existing values
+	ds.rewrite_encryption(None).await.unwrap();
+	let ds = ds.with_encryption_key(None).await.unwrap();
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap().inner();
+	let val = tx.get("test", None).await.unwrap();
+	assert!(matches!(val.as_deref(), Some(b"ok")));
+	tx.cancel().await.unwrap();
+	// Check the datastore can not be opened with a key
+	let res = ds.with_encryption_key(Some(KEY.to_string())).await;
+	assert!(matches!(res, Err(Error::Ds(_))));
+}
+
+macro_rules! define_tests {
+	($new_ds:ident) => {
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn encrypted() {
+			super::encryption::encrypted($new_ds).await;
+		}
+
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn unencrypted() {
+			super::encryption::unencrypted($new_ds).await;
+		}
+
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn versionstamped_encryption() {
+			super::encryption::versionstamped($new_ds).await;
+		}
+
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn rewrite_encryption() {
+			super::encryption::rewrite($new_ds).await;
+		}
+	};
+}
+pub(crate) use define_tests;
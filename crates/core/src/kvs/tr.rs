@@ -11,6 +11,7 @@ use crate::err::Error;
 use crate::idg::u32::U32;
 use crate::key::debug::Sprintable;
 use crate::kvs::batch::Batch;
+use crate::kvs::cipher::Cipher;
 use crate::kvs::clock::SizedClock;
 #[cfg(any(
 	feature = "kv-tikv",
@@ -81,6 +82,7 @@ pub struct Transactor {
 	pub(super) stash: Stash,
 	pub(super) cf: cf::Writer,
 	pub(super) clock: Arc<SizedClock>,
+	pub(super) cipher: Option<Arc<Cipher>>,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -159,7 +161,7 @@ impl Transactor {
 			feature = "kv-fdb",
 			feature = "kv-surrealkv",
 		)),
-		allow(unused_variables)
+		allow(unused_variables, unreachable_code)
 	)]
 	// --------------------------------------------------
 	// Integral methods
@@ -227,7 +229,8 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), version = version, "Get");
-		expand_inner!(&mut self.inner, v => { v.get(key, version).await })
+		let val = expand_inner!(&mut self.inner, v => { v.get(key.as_slice(), version).await })?;
+		val.map(|val| self.decrypt(&key, val)).transpose()
 	}
 
 	/// Fetch many keys from the datastore.
@@ -241,7 +244,12 @@ impl Transactor {
 			keys_encoded.push(k.encode_owned()?);
 		}
 		trace!(target: TARGET, keys = keys_encoded.sprint(), "GetM");
-		expand_inner!(&mut self.inner, v => { v.getm(keys_encoded).await })
+		let vals = expand_inner!(&mut self.inner, v => { v.getm(keys_encoded.clone()).await })?;
+		keys_encoded
+			.iter()
+			.zip(vals)
+			.map(|(key, val)| val.map(|val| self.decrypt(key, val)).transpose())
+			.collect()
 	}
 
 	/// Retrieve a specific range of keys from the datastore.
@@ -260,7 +268,8 @@ impl Transactor {
 		let end: Key = rng.end.encode_owned()?;
 		let rng = beg.as_slice()..end.as_slice();
 		trace!(target: TARGET, rng = rng.sprint(), version = version, "GetR");
-		expand_inner!(&mut self.inner, v => { v.getr(beg..end, version).await })
+		let res = expand_inner!(&mut self.inner, v => { v.getr(beg..end, version).await })?;
+		self.decrypt_pairs(res)
 	}
 
 	/// Retrieve a specific prefixed range of keys from the datastore.
@@ -273,7 +282,8 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), "GetP");
-		expand_inner!(&mut self.inner, v => { v.getp(key).await })
+		let res = expand_inner!(&mut self.inner, v => { v.getp(key).await })?;
+		self.decrypt_pairs(res)
 	}
 
 	/// Insert or update a key in the datastore.
@@ -285,6 +295,7 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), version = version, "Set");
+		let val = self.encrypt(&key, val)?;
 		expand_inner!(&mut self.inner, v => { v.set(key, val, version).await })
 	}
 
@@ -297,6 +308,7 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), "Replace");
+		let val = self.encrypt(&key, val)?;
 		expand_inner!(&mut self.inner, v => { v.replace(key, val).await })
 	}
 
@@ -309,6 +321,7 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), version = version, "Put");
+		let val = self.encrypt(&key, val)?;
 		expand_inner!(&mut self.inner, v => { v.put(key, val, version).await })
 	}
 
@@ -321,7 +334,11 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), "PutC");
-		expand_inner!(&mut self.inner, v => { v.putc(key, val, chk).await })
+		let val = self.encrypt(&key, val)?;
+		match self.condition(&key, chk).await? {
+			Some(chk) => expand_inner!(&mut self.inner, v => { v.putc(key, val, chk).await }),
+			None => expand_inner!(&mut self.inner, v => { v.set(key, val, None).await }),
+		}
 	}
 
 	/// Delete a key from the datastore.
@@ -344,7 +361,10 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), "DelC");
-		expand_inner!(&mut self.inner, v => { v.delc(key, chk).await })
+		match self.condition(&key, chk).await? {
+			Some(chk) => expand_inner!(&mut self.inner, v => { v.delc(key, chk).await }),
+			None => expand_inner!(&mut self.inner, v => { v.del(key).await }),
+		}
 	}
 
 	/// Delete a range of keys from the datastore.
@@ -395,7 +415,10 @@ impl Transactor {
 	{
 		let key = key.encode_owned()?;
 		trace!(target: TARGET, key = key.sprint(), "ClrC");
-		expand_inner!(&mut self.inner, v => { v.clrc(key, chk).await })
+		match self.condition(&key, chk).await? {
+			Some(chk) => expand_inner!(&mut self.inner, v => { v.clrc(key, chk).await }),
+			None => expand_inner!(&mut self.inner, v => { v.clr(key).await }),
+		}
 	}
 
 	/// Delete all versions of a range of keys from the datastore.
@@ -492,7 +515,8 @@ impl Transactor {
 		if beg > end {
 			return Ok(vec![]);
 		}
-		expand_inner!(&mut self.inner, v => { v.scan(beg..end, limit, version).await })
+		let res = expand_inner!(&mut self.inner, v => { v.scan(beg..end, limit, version).await })?;
+		self.decrypt_pairs(res)
 	}
 
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tr", skip_all)]
@@ -512,7 +536,8 @@ impl Transactor {
 		if beg > end {
 			return Ok(vec![]);
 		}
-		expand_inner!(&mut self.inner, v => { v.scanr(beg..end, limit, version).await })
+		let res = expand_inner!(&mut self.inner, v => { v.scanr(beg..end, limit, version).await })?;
+		self.decrypt_pairs(res)
 	}
 
 	/// Retrieve a batched scan over a specific range of keys in the datastore.
@@ -567,7 +592,8 @@ impl Transactor {
 		let end: Key = rng.end.encode_owned()?;
 		let rng = beg.as_slice()..end.as_slice();
 		trace!(target: TARGET, rng = rng.sprint(), version = version, "Batch");
-		expand_inner!(&mut self.inner, v => { v.batch_keys_vals(beg..end, batch, version).await })
+		let res = expand_inner!(&mut self.inner, v => { v.batch_keys_vals(beg..end, batch, version).await })?;
+		Ok(Batch::new(res.next, self.decrypt_pairs(res.result)?))
 	}
 
 	/// Retrieve a batched scan of all versions over a specific range of keys in the datastore.
@@ -586,7 +612,16 @@ impl Transactor {
 		let end: Key = rng.end.encode_owned()?;
 		let rng = beg.as_slice()..end.as_slice();
 		trace!(target: TARGET, rng = rng.sprint(), "BatchVersions");
-		expand_inner!(&mut self.inner, v => { v.batch_keys_vals_versions(beg..end, batch).await })
+		let res = expand_inner!(&mut self.inner, v => { v.batch_keys_vals_versions(beg..end, batch).await })?;
+		let result = res
+			.result
+			.into_iter()
+			.map(|(k, v, ver, del)| {
+				let v = self.decrypt(&k, v)?;
+				Ok((k, v, ver, del))
+			})
+			.collect::<Result<_, Error>>()?;
+		Ok(Batch::new(res.next, result))
 	}
 
 	/// Obtain a new change timestamp for a key
@@ -617,9 +652,82 @@ impl Transactor {
 		let ts_key = ts_key.encode_owned()?;
 		let prefix = prefix.encode_owned()?;
 		let suffix = suffix.encode_owned()?;
+		// The versionstamp is filled in by the storage engine on commit,
+		// so encrypted values are authenticated against the rest of the key
+		let val = match &self.cipher {
+			Some(cipher) => cipher.encrypt_versionstamped(&prefix, &suffix, val.into())?,
+			None => val.into(),
+		};
 		expand_inner!(&mut self.inner, v => { v.set_versionstamp(ts_key, prefix, suffix, val).await })
 	}
 
+	// --------------------------------------------------
+	// Encryption methods
+	// --------------------------------------------------
+
+	/// Encrypts a value before it is written to the datastore, if encryption is enabled.
+	fn encrypt<V>(&self, key: &[u8], val: V) -> Result<Val, Error>
+	where
+		V: Into<Val>,
+	{
+		match &self.cipher {
+			Some(cipher) => cipher.encrypt(key, val.into()),
+			None => Ok(val.into()),
+		}
+	}
+
+	/// Decrypts a value after it is read from the datastore, if encryption is enabled.
+	fn decrypt(&self, key: &[u8], val: Val) -> Result<Val, Error> {
+		match &self.cipher {
+			Some(cipher) => cipher.decrypt(key, val),
+			None => Ok(val),
+		}
+	}
+
+	/// Decrypts the values of a set of key-value pairs, if encryption is enabled.
+	fn decrypt_pairs(&self, res: Vec<(Key, Val)>) -> Result<Vec<(Key, Val)>, Error> {
+		match &self.cipher {
+			Some(cipher) => res
+				.into_iter()
+				.map(|(k, v)| {
+					let v = cipher.decrypt(&k, v)?;
+					Ok((k, v))
+				})
+				.collect(),
+			None => Ok(res),
+		}
+	}
+
+	/// Checks the condition of a conditional operation.
+	///
+	/// Encrypted values use a random nonce, so the same value never encrypts
+	/// to the same bytes, and the storage engine is unable to compare them.
+	/// Instead the current value is fetched and compared here, within this
+	/// transaction, after which `None` is returned, and the operation is run
+	/// unconditionally. Without encryption, the condition is returned, so
+	/// that the storage engine can check it directly. A missing condition
+	/// requires the key to not exist, which the storage engine checks itself.
+	async fn condition<V>(
+		&mut self,
+		key: &Key,
+		chk: Option<V>,
+	) -> Result<Option<Option<Val>>, Error>
+	where
+		V: Into<Val>,
+	{
+		let Some(cipher) = self.cipher.clone() else {
+			return Ok(Some(chk.map(Into::into)));
+		};
+		let Some(chk) = chk.map(Into::into) else {
+			return Ok(Some(None));
+		};
+		let cur = expand_inner!(&mut self.inner, v => { v.get(key.as_slice(), None).await })?;
+		match cur.map(|cur| cipher.decrypt(key, cur)).transpose()? {
+			Some(cur) if cur == chk => Ok(None),
+			_ => Err(Error::TxConditionNotMet),
+		}
+	}
+
 	// --------------------------------------------------
 	// Additional methods
 	// --------------------------------------------------
@@ -664,6 +772,11 @@ impl Transactor {
 		self.cf.define_table(ns, db, tb, dt)
 	}
 
+	// Records the removal of all of the records in a table in the changefeed if enabled.
+	pub(crate) fn record_table_truncate(&mut self, ns: &str, db: &str, tb: &str) {
+		self.cf.truncate_table(ns, db, tb)
+	}
+
 	pub(crate) async fn get_idg(&mut self, key: &Key) -> Result<U32, Error> {
 		Ok(if let Some(v) = self.stash.get(key) {
 			v
@@ -836,14 +949,19 @@ impl Transactor {
 	}
 
 	pub(crate) async fn new_save_point(&mut self) {
+		self.cf.new_save_point();
 		expand_inner!(&mut self.inner, v => { v.new_save_point() })
 	}
 
+	/// Rolls back the changes made since the last save point,
+	/// including any buffered change feed mutations
 	pub(crate) async fn rollback_to_save_point(&mut self) -> Result<(), Error> {
+		self.cf.rollback_to_save_point()?;
 		expand_inner!(&mut self.inner, v => { v.rollback_to_save_point().await })
 	}
 
 	pub(crate) async fn release_last_save_point(&mut self) -> Result<(), Error> {
+		self.cf.release_last_save_point()?;
 		expand_inner!(&mut self.inner, v => { v.release_last_save_point() })
 	}
 }
@@ -13,6 +13,7 @@ use crate::idx::trees::store::cache::IndexTreeCaches;
 use crate::kvs::cache;
 use crate::kvs::cache::tx::TransactionCache;
 use crate::kvs::scanner::Scanner;
+use crate::kvs::sequences::LastValues;
 use crate::kvs::Transactor;
 use crate::sql::statements::define::ApiDefinition;
 use crate::sql::statements::define::DefineConfigStatement;
@@ -27,9 +28,12 @@ use crate::sql::statements::DefineIndexStatement;
 use crate::sql::statements::DefineModelStatement;
 use crate::sql::statements::DefineNamespaceStatement;
 use crate::sql::statements::DefineParamStatement;
+use crate::sql::statements::DefineRoleStatement;
+use crate::sql::statements::DefineSequenceStatement;
 use crate::sql::statements::DefineTableStatement;
 use crate::sql::statements::DefineUserStatement;
 use crate::sql::statements::LiveStatement;
+use crate::sql::Datetime;
 use crate::sql::Id;
 use crate::sql::Permissions;
 use crate::sql::Value;
@@ -51,6 +55,8 @@ pub struct Transaction {
 	cache: TransactionCache,
 	/// Cache the index updates
 	index_caches: IndexTreeCaches,
+	/// The last sequence values handed out in this transaction
+	sequence_values: LastValues,
 	/// Does this supports reverse scan
 	reverse_scan: bool,
 }
@@ -63,6 +69,7 @@ impl Transaction {
 			tx: Mutex::new(tx),
 			cache: TransactionCache::new(),
 			index_caches: IndexTreeCaches::default(),
+			sequence_values: LastValues::default(),
 			reverse_scan,
 		}
 	}
@@ -570,6 +577,24 @@ impl Transaction {
 		}
 	}
 
+	/// Retrieve all namespace role definitions for a specific namespace.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_ns_roles(&self, ns: &str) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		let qey = cache::tx::Lookup::Nrs(ns);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_nrs(),
+			None => {
+				let beg = crate::key::namespace::ro::prefix(ns)?;
+				let end = crate::key::namespace::ro::suffix(ns)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Nrs(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
 	/// Retrieve all namespace access definitions for a specific namespace.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn all_ns_accesses(&self, ns: &str) -> Result<Arc<[DefineAccessStatement]>, Error> {
@@ -650,6 +675,28 @@ impl Transaction {
 		}
 	}
 
+	/// Retrieve all database role definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_db_roles(
+		&self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		let qey = cache::tx::Lookup::Drs(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_drs(),
+			None => {
+				let beg = crate::key::database::ro::prefix(ns, db)?;
+				let end = crate::key::database::ro::suffix(ns, db)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Drs(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
 	/// Retrieve all database access definitions for a specific database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn all_db_accesses(
@@ -780,6 +827,28 @@ impl Transaction {
 		}
 	}
 
+	/// Retrieve all sequence definitions for a specific database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn all_db_sequences(
+		&self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<[DefineSequenceStatement]>, Error> {
+		let qey = cache::tx::Lookup::Sqs(ns, db);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_sqs(),
+			None => {
+				let beg = crate::key::database::sq::prefix(ns, db)?;
+				let end = crate::key::database::sq::suffix(ns, db)?;
+				let val = self.getr(beg..end, None).await?;
+				let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+				let entry = cache::tx::Entry::Sqs(val.clone());
+				self.cache.insert(qey, entry);
+				Ok(val)
+			}
+		}
+	}
+
 	/// Retrieve all model definitions for a specific database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn all_db_models(
@@ -1090,6 +1159,27 @@ impl Transaction {
 		}
 	}
 
+	/// Retrieve a specific namespace role definition.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_ns_role(&self, ns: &str, ro: &str) -> Result<Arc<DefineRoleStatement>, Error> {
+		let qey = cache::tx::Lookup::Nr(ns, ro);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type(),
+			None => {
+				let key = crate::key::namespace::ro::new(ns, ro).encode()?;
+				let val = self.get(key, None).await?.ok_or_else(|| Error::RoleNsNotFound {
+					name: ro.to_owned(),
+					ns: ns.to_owned(),
+				})?;
+				let val: DefineRoleStatement = revision::from_slice(&val)?;
+				let val = Arc::new(val);
+				let entr = cache::tx::Entry::Any(val.clone());
+				self.cache.insert(qey, entr);
+				Ok(val)
+			}
+		}
+	}
+
 	/// Retrieve a specific namespace access definition.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_ns_access(
@@ -1190,6 +1280,33 @@ impl Transaction {
 		}
 	}
 
+	/// Retrieve a specific database role definition.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_db_role(
+		&self,
+		ns: &str,
+		db: &str,
+		ro: &str,
+	) -> Result<Arc<DefineRoleStatement>, Error> {
+		let qey = cache::tx::Lookup::Dr(ns, db, ro);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type(),
+			None => {
+				let key = crate::key::database::ro::new(ns, db, ro).encode()?;
+				let val = self.get(key, None).await?.ok_or_else(|| Error::RoleDbNotFound {
+					name: ro.to_owned(),
+					ns: ns.to_owned(),
+					db: db.to_owned(),
+				})?;
+				let val: DefineRoleStatement = revision::from_slice(&val)?;
+				let val = Arc::new(val);
+				let entr = cache::tx::Entry::Any(val.clone());
+				self.cache.insert(qey, entr);
+				Ok(val)
+			}
+		}
+	}
+
 	/// Retrieve a specific database access definition.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_db_access(
@@ -1373,6 +1490,31 @@ impl Transaction {
 		}
 	}
 
+	/// Retrieve a specific sequence definition from a database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn get_db_sequence(
+		&self,
+		ns: &str,
+		db: &str,
+		sq: &str,
+	) -> Result<Arc<DefineSequenceStatement>, Error> {
+		let qey = cache::tx::Lookup::Sq(ns, db, sq);
+		match self.cache.get(&qey) {
+			Some(val) => val.try_into_type(),
+			None => {
+				let key = crate::key::database::sq::new(ns, db, sq).encode()?;
+				let val = self.get(key, None).await?.ok_or_else(|| Error::SqNotFound {
+					name: sq.to_owned(),
+				})?;
+				let val: DefineSequenceStatement = revision::from_slice(&val)?;
+				let val = Arc::new(val);
+				let entr = cache::tx::Entry::Any(val.clone());
+				self.cache.insert(qey, entr);
+				Ok(val)
+			}
+		}
+	}
+
 	/// Retrieve a specific config definition from a database.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_db_config(
@@ -1613,6 +1755,54 @@ impl Transaction {
 		Ok(())
 	}
 
+	/// Checks if a record on a table with an `EXPIRE` clause has expired,
+	/// but has not yet been deleted by the record expiry task.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn is_record_expired(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		id: &Id,
+	) -> Result<bool, Error> {
+		let key = crate::key::table::ex::new(ns, db, tb, id);
+		match self.get(&key, None).await? {
+			Some(val) => Ok(revision::from_slice::<Datetime>(&val)? <= Datetime::default()),
+			None => Ok(false),
+		}
+	}
+
+	/// Sets or removes the expiry time of a record on a table with an `EXPIRE` clause.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn set_record_expiry(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		id: &Id,
+		at: Option<&Datetime>,
+	) -> Result<(), Error> {
+		let key = crate::key::table::ex::new(ns, db, tb, id);
+		// Remove the previous expiry time from the expiry order
+		if let Some(val) = self.get(&key, None).await? {
+			let val: Datetime = revision::from_slice(&val)?;
+			if Some(&val) == at {
+				return Ok(());
+			}
+			self.del(crate::key::table::et::new(ns, db, tb, &val, id)).await?;
+		}
+		// Store the new expiry time
+		match at {
+			Some(at) => {
+				self.set(&key, revision::to_vec(at)?, None).await?;
+				self.set(crate::key::table::et::new(ns, db, tb, at, id), vec![], None).await?;
+			}
+			None => self.del(&key).await?,
+		}
+		// Return nothing
+		Ok(())
+	}
+
 	/// Get or add a namespace with a default configuration, only if we are in dynamic mode.
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
 	pub async fn get_or_add_ns(
@@ -1916,4 +2106,8 @@ impl Transaction {
 	pub(crate) fn index_caches(&self) -> &IndexTreeCaches {
 		&self.index_caches
 	}
+
+	pub(crate) fn sequence_values(&self) -> &LastValues {
+		&self.sequence_values
+	}
 }
@@ -4,14 +4,17 @@ use std::sync::Arc;
 use tokio::sync::Semaphore;
 use uuid::Uuid;
 
+use super::args::Take;
 use super::Data;
 use super::Method;
 use super::RpcError;
 use super::RpcProtocolV1;
 use super::RpcProtocolV2;
-use crate::dbs::Session;
+use super::Transactions;
+use crate::dbs::{Response, Session};
 use crate::kvs::Datastore;
-use crate::sql::Array;
+use crate::sql::{Array, Query, Value};
+use std::collections::BTreeMap;
 
 #[allow(async_fn_in_trait)]
 pub trait RpcContext {
@@ -46,6 +49,17 @@ pub trait RpcContext {
 		async { unimplemented!("cleanup_lqs function must be implemented if LQ_SUPPORT = true") }
 	}
 
+	// ------------------------------
+	// Transactions
+	// ------------------------------
+
+	/// Returns the interactive transactions opened by this RPC context,
+	/// or `None` if interactive transactions are not supported, which
+	/// is the default
+	fn transactions(&self) -> Option<&Transactions> {
+		None
+	}
+
 	// ------------------------------
 	// GraphQL
 	// ------------------------------
@@ -68,6 +82,7 @@ pub trait RpcContext {
 	async fn execute(
 		&self,
 		version: Option<u8>,
+		txn: Option<Uuid>,
 		method: Method,
 		params: Array,
 	) -> Result<Data, RpcError>
@@ -76,9 +91,95 @@ pub trait RpcContext {
 		Self: RpcProtocolV2,
 	{
 		match version {
-			Some(1) => RpcProtocolV1::execute(self, method, params).await,
-			Some(2) => RpcProtocolV2::execute(self, method, params).await,
-			_ => RpcProtocolV1::execute(self, method, params).await,
+			Some(1) => RpcProtocolV1::execute(self, txn, method, params).await,
+			Some(2) => RpcProtocolV2::execute(self, txn, method, params).await,
+			_ => RpcProtocolV1::execute(self, txn, method, params).await,
+		}
+	}
+
+	// ------------------------------
+	// Methods for transactions
+	// ------------------------------
+
+	/// Checks if a method can be executed within the specified transaction
+	fn check_transaction(&self, txn: Option<Uuid>, method: Method) -> Result<(), RpcError> {
+		if txn.is_some() {
+			if self.transactions().is_none() {
+				return Err(RpcError::BadTxnConfig);
+			}
+			if !method.is_transactional() {
+				return Err(RpcError::InvalidRequest);
+			}
+		}
+		Ok(())
+	}
+
+	async fn begin(&self, params: Array) -> Result<Data, RpcError> {
+		// Check if the user is allowed to query
+		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
+			return Err(RpcError::MethodNotAllowed);
+		}
+		// Check if transactions are supported
+		let Some(txs) = self.transactions() else {
+			return Err(RpcError::BadTxnConfig);
+		};
+		// This method does not accept arguments
+		if !params.is_empty() {
+			return Err(RpcError::InvalidParams);
 		}
+		// Start a new transaction
+		let id = txs.begin(self.kvs()).await?;
+		// Return the transaction id
+		Ok(Value::Uuid(id.into()).into())
+	}
+
+	async fn commit(&self, params: Array) -> Result<Data, RpcError> {
+		// Check if transactions are supported
+		let Some(txs) = self.transactions() else {
+			return Err(RpcError::BadTxnConfig);
+		};
+		// Commit the transaction
+		txs.commit(self.kvs(), &transaction_id(params)?).await?;
+		// Return nothing on success
+		Ok(Value::None.into())
+	}
+
+	async fn cancel(&self, params: Array) -> Result<Data, RpcError> {
+		// Check if transactions are supported
+		let Some(txs) = self.transactions() else {
+			return Err(RpcError::BadTxnConfig);
+		};
+		// Cancel the transaction
+		txs.cancel(&transaction_id(params)?).await?;
+		// Return nothing on success
+		Ok(Value::None.into())
+	}
+
+	/// Executes a query, within the specified transaction if provided
+	async fn process_inner(
+		&self,
+		txn: Option<Uuid>,
+		query: Query,
+		vars: Option<BTreeMap<String, Value>>,
+	) -> Result<Vec<Response>, RpcError> {
+		match (txn, self.transactions()) {
+			// Execute the query within the specified transaction
+			(Some(id), Some(txs)) => {
+				Ok(txs.process(self.kvs(), &id, query, &self.session(), vars).await?)
+			}
+			// Transactions are not supported
+			(Some(_), None) => Err(RpcError::BadTxnConfig),
+			// Execute the query in its own transaction
+			(None, _) => Ok(self.kvs().process(query, &self.session(), vars).await?),
+		}
+	}
+}
+
+/// Parses the transaction id argument of a transaction method
+fn transaction_id(params: Array) -> Result<Uuid, RpcError> {
+	match params.needs_one() {
+		Ok(Value::Uuid(v)) => Ok(v.0),
+		Ok(Value::Strand(v)) => Uuid::try_parse(&v).map_err(|_| RpcError::InvalidParams),
+		_ => Err(RpcError::InvalidParams),
 	}
 }
@@ -23,6 +23,8 @@ pub enum RpcError {
 	BadLQConfig,
 	#[error("A GraphQL request was made, but GraphQL is not supported by the context")]
 	BadGQLConfig,
+	#[error("A transaction was used, but transactions are not supported by the context")]
+	BadTxnConfig,
 	#[error("Error: {0}")]
 	Thrown(String),
 }
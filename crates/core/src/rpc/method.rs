@@ -28,6 +28,9 @@ pub enum Method {
 	Run,
 	GraphQL,
 	InsertRelation,
+	Begin,
+	Commit,
+	Cancel,
 }
 
 impl Method {
@@ -79,6 +82,9 @@ impl Method {
 			"run" => Self::Run,
 			"graphql" => Self::GraphQL,
 			"insert_relation" => Self::InsertRelation,
+			"begin" => Self::Begin,
+			"commit" => Self::Commit,
+			"cancel" => Self::Cancel,
 			_ => Self::Unknown,
 		}
 	}
@@ -114,6 +120,9 @@ impl Method {
 			Self::Run => "run",
 			Self::GraphQL => "graphql",
 			Self::InsertRelation => "insert_relation",
+			Self::Begin => "begin",
+			Self::Commit => "commit",
+			Self::Cancel => "cancel",
 		}
 	}
 }
@@ -129,4 +138,23 @@ impl Method {
 	pub fn is_valid(&self) -> bool {
 		!matches!(self, Self::Unknown)
 	}
+
+	/// Checks if the method can be executed within an interactive transaction
+	pub fn is_transactional(&self) -> bool {
+		matches!(
+			self,
+			Self::Select
+				| Self::Insert
+				| Self::InsertRelation
+				| Self::Create
+				| Self::Upsert
+				| Self::Update
+				| Self::Merge
+				| Self::Patch
+				| Self::Delete
+				| Self::Relate
+				| Self::Query
+				| Self::Run
+		)
+	}
 }
@@ -4,6 +4,7 @@ mod error;
 mod method;
 mod protocol;
 mod response;
+mod transactions;
 
 pub mod format;
 pub mod request;
@@ -14,6 +15,7 @@ pub use format::Format;
 pub use method::Method;
 pub use request::Request;
 pub use response::Data;
+pub use transactions::Transactions;
 
 pub use protocol::v1::RpcProtocolV1;
 pub use protocol::v2::RpcProtocolV2;
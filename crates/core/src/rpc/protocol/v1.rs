@@ -2,6 +2,7 @@
 use async_graphql::BatchRequest;
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use uuid::Uuid;
 
 #[cfg(all(not(target_family = "wasm"), surrealdb_unstable))]
 use crate::dbs::capabilities::ExperimentalTarget;
@@ -10,6 +11,7 @@ use crate::rpc::Data;
 use crate::rpc::Method;
 use crate::rpc::RpcContext;
 use crate::rpc::RpcError;
+use crate::syn;
 use crate::{
 	dbs::{capabilities::MethodTarget, QueryType, Response},
 	rpc::args::Take,
@@ -29,7 +31,12 @@ pub trait RpcProtocolV1: RpcContext {
 	// ------------------------------
 
 	/// Executes a method on this RPC implementation
-	async fn execute(&self, method: Method, params: Array) -> Result<Data, RpcError> {
+	async fn execute(
+		&self,
+		txn: Option<Uuid>,
+		method: Method,
+		params: Array,
+	) -> Result<Data, RpcError> {
 		// Check if capabilities allow executing the requested RPC method
 		if !self.kvs().allows_rpc_method(&MethodTarget {
 			method,
@@ -37,6 +44,8 @@ pub trait RpcProtocolV1: RpcContext {
 			warn!("Capabilities denied RPC method call attempt, target: '{method}'");
 			return Err(RpcError::MethodNotAllowed);
 		}
+		// Check if the method can run within a transaction
+		self.check_transaction(txn, method)?;
 		// Execute the desired method
 		match method {
 			Method::Ping => Ok(Value::None.into()),
@@ -51,20 +60,23 @@ pub trait RpcProtocolV1: RpcContext {
 			Method::Live => self.live(params).await,
 			Method::Set => self.set(params).await,
 			Method::Unset => self.unset(params).await,
-			Method::Select => self.select(params).await,
-			Method::Insert => self.insert(params).await,
-			Method::Create => self.create(params).await,
-			Method::Upsert => self.upsert(params).await,
-			Method::Update => self.update(params).await,
-			Method::Merge => self.merge(params).await,
-			Method::Patch => self.patch(params).await,
-			Method::Delete => self.delete(params).await,
+			Method::Select => self.select(txn, params).await,
+			Method::Insert => self.insert(txn, params).await,
+			Method::Create => self.create(txn, params).await,
+			Method::Upsert => self.upsert(txn, params).await,
+			Method::Update => self.update(txn, params).await,
+			Method::Merge => self.merge(txn, params).await,
+			Method::Patch => self.patch(txn, params).await,
+			Method::Delete => self.delete(txn, params).await,
 			Method::Version => self.version(params).await,
-			Method::Query => self.query(params).await,
-			Method::Relate => self.relate(params).await,
-			Method::Run => self.run(params).await,
+			Method::Query => self.query(txn, params).await,
+			Method::Relate => self.relate(txn, params).await,
+			Method::Run => self.run(txn, params).await,
 			Method::GraphQL => self.graphql(params).await,
-			Method::InsertRelation => self.insert_relation(params).await,
+			Method::InsertRelation => self.insert_relation(txn, params).await,
+			Method::Begin => self.begin(params).await,
+			Method::Commit => self.commit(params).await,
+			Method::Cancel => self.cancel(params).await,
 			Method::Unknown => Err(RpcError::MethodNotFound),
 		}
 	}
@@ -344,7 +356,7 @@ pub trait RpcProtocolV1: RpcContext {
 		// Specify the query parameters
 		let var = Some(self.session().parameters.clone());
 		// Execute the query on the database
-		let mut res = self.query_inner(Value::Query(sql), var).await?;
+		let mut res = self.query_inner(None, Value::Query(sql), var).await?;
 		// Extract the first query result
 		Ok(res.remove(0).result?.into())
 	}
@@ -368,7 +380,7 @@ pub trait RpcProtocolV1: RpcContext {
 		// Specify the query parameters
 		let var = Some(self.session().parameters.clone());
 		// Execute the query on the database
-		let mut res = self.query_inner(Value::Query(sql), var).await?;
+		let mut res = self.query_inner(None, Value::Query(sql), var).await?;
 		// Extract the first query result
 		Ok(res.remove(0).result?.into())
 	}
@@ -377,7 +389,7 @@ pub trait RpcProtocolV1: RpcContext {
 	// Methods for selecting
 	// ------------------------------
 
-	async fn select(&self, params: Array) -> Result<Data, RpcError> {
+	async fn select(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Check if the user is allowed to query
 		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
 			return Err(RpcError::MethodNotAllowed);
@@ -397,7 +409,7 @@ pub trait RpcProtocolV1: RpcContext {
 		// Specify the query parameters
 		let var = Some(self.session().parameters.clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, &self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -413,7 +425,7 @@ pub trait RpcProtocolV1: RpcContext {
 	// Methods for inserting
 	// ------------------------------
 
-	async fn insert(&self, params: Array) -> Result<Data, RpcError> {
+	async fn insert(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Check if the user is allowed to query
 		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
 			return Err(RpcError::MethodNotAllowed);
@@ -436,7 +448,7 @@ pub trait RpcProtocolV1: RpcContext {
 		// Specify the query parameters
 		let var = Some(self.session().parameters.clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, &self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -448,7 +460,7 @@ pub trait RpcProtocolV1: RpcContext {
 			.into())
 	}
 
-	async fn insert_relation(&self, params: Array) -> Result<Data, RpcError> {
+	async fn insert_relation(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Check if the user is allowed to query
 		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
 			return Err(RpcError::MethodNotAllowed);
@@ -472,7 +484,7 @@ pub trait RpcProtocolV1: RpcContext {
 		// Specify the query parameters
 		let var = Some(self.session().parameters.clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, &self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -488,7 +500,7 @@ pub trait RpcProtocolV1: RpcContext {
 	// Methods for creating
 	// ------------------------------
 
-	async fn create(&self, params: Array) -> Result<Data, RpcError> {
+	async fn create(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Check if the user is allowed to query
 		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
 			return Err(RpcError::MethodNotAllowed);
@@ -511,7 +523,7 @@ pub trait RpcProtocolV1: RpcContext {
 		}
 		.into();
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, &self.session(), None).await?;
+		let mut res = self.process_inner(txn, sql, None).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -527,7 +539,7 @@ pub trait RpcProtocolV1: RpcContext {
 	// Methods for upserting
 	// ------------------------------
 
-	async fn upsert(&self, params: Array) -> Result<Data, RpcError> {
+	async fn upsert(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Check if the user is allowed to query
 		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
 			return Err(RpcError::MethodNotAllowed);
@@ -551,7 +563,7 @@ pub trait RpcProtocolV1: RpcContext {
 		// Specify the query parameters
 		let var = Some(self.session().parameters.clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, &self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -567,7 +579,7 @@ pub trait RpcProtocolV1: RpcContext {
 	// Methods for updating
 	// ------------------------------
 
-	async fn update(&self, params: Array) -> Result<Data, RpcError> {
+	async fn update(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Check if the user is allowed to query
 		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
 			return Err(RpcError::MethodNotAllowed);
@@ -591,7 +603,7 @@ pub trait RpcProtocolV1: RpcContext {
 		// Specify the query parameters
 		let var = Some(self.session().parameters.clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, &self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -607,7 +619,7 @@ pub trait RpcProtocolV1: RpcContext {
 	// Methods for merging
 	// ------------------------------
 
-	async fn merge(&self, params: Array) -> Result<Data, RpcError> {
+	async fn merge(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Check if the user is allowed to query
 		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
 			return Err(RpcError::MethodNotAllowed);
@@ -631,7 +643,7 @@ pub trait RpcProtocolV1: RpcContext {
 		// Specify the query parameters
 		let var = Some(self.session().parameters.clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, &self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -647,7 +659,7 @@ pub trait RpcProtocolV1: RpcContext {
 	// Methods for patching
 	// ------------------------------
 
-	async fn patch(&self, params: Array) -> Result<Data, RpcError> {
+	async fn patch(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Check if the user is allowed to query
 		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
 			return Err(RpcError::MethodNotAllowed);
@@ -671,7 +683,7 @@ pub trait RpcProtocolV1: RpcContext {
 		// Specify the query parameters
 		let var = Some(self.session().parameters.clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, &self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -687,7 +699,7 @@ pub trait RpcProtocolV1: RpcContext {
 	// Methods for relating
 	// ------------------------------
 
-	async fn relate(&self, params: Array) -> Result<Data, RpcError> {
+	async fn relate(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Check if the user is allowed to query
 		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
 			return Err(RpcError::MethodNotAllowed);
@@ -713,7 +725,7 @@ pub trait RpcProtocolV1: RpcContext {
 		// Specify the query parameters
 		let var = Some(self.session().parameters.clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, &self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -729,7 +741,7 @@ pub trait RpcProtocolV1: RpcContext {
 	// Methods for deleting
 	// ------------------------------
 
-	async fn delete(&self, params: Array) -> Result<Data, RpcError> {
+	async fn delete(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Check if the user is allowed to query
 		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
 			return Err(RpcError::MethodNotAllowed);
@@ -749,7 +761,7 @@ pub trait RpcProtocolV1: RpcContext {
 		// Specify the query parameters
 		let var = Some(self.session().parameters.clone());
 		// Execute the query on the database
-		let mut res = self.kvs().process(sql, &self.session(), var).await?;
+		let mut res = self.process_inner(txn, sql, var).await?;
 		// Extract the first query result
 		Ok(res
 			.remove(0)
@@ -776,7 +788,7 @@ pub trait RpcProtocolV1: RpcContext {
 	// Methods for querying
 	// ------------------------------
 
-	async fn query(&self, params: Array) -> Result<Data, RpcError> {
+	async fn query(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Check if the user is allowed to query
 		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
 			return Err(RpcError::MethodNotAllowed);
@@ -796,14 +808,14 @@ pub trait RpcProtocolV1: RpcContext {
 			_ => return Err(RpcError::InvalidParams),
 		};
 		// Execute the specified query
-		self.query_inner(query, vars).await.map(Into::into)
+		self.query_inner(txn, query, vars).await.map(Into::into)
 	}
 
 	// ------------------------------
 	// Methods for running functions
 	// ------------------------------
 
-	async fn run(&self, params: Array) -> Result<Data, RpcError> {
+	async fn run(&self, txn: Option<Uuid>, params: Array) -> Result<Data, RpcError> {
 		// Check if the user is allowed to query
 		if !self.kvs().allows_query_by_subject(self.session().au.as_ref()) {
 			return Err(RpcError::MethodNotAllowed);
@@ -843,7 +855,7 @@ pub trait RpcProtocolV1: RpcContext {
 		// Specify the query parameters
 		let var = Some(self.session().parameters.clone());
 		// Execute the function on the database
-		let mut res = self.kvs().process(func, &self.session(), var).await?;
+		let mut res = self.process_inner(txn, func, var).await?;
 		// Extract the first query result
 		Ok(res.remove(0).result?.into())
 	}
@@ -976,6 +988,7 @@ pub trait RpcProtocolV1: RpcContext {
 
 	async fn query_inner(
 		&self,
+		txn: Option<Uuid>,
 		query: Value,
 		vars: Option<BTreeMap<String, Value>>,
 	) -> Result<Vec<Response>, RpcError> {
@@ -984,10 +997,14 @@ pub trait RpcProtocolV1: RpcContext {
 			return Err(RpcError::BadLQConfig);
 		}
 		// Execute the query on the database
-		let res = match query {
-			Value::Query(sql) => self.kvs().process(sql, &self.session(), vars).await?,
-			Value::Strand(sql) => self.kvs().execute(&sql, &self.session(), vars).await?,
-			_ => return Err(fail!("Unexpected query type: {query:?}").into()),
+		let res = match (query, txn) {
+			(Value::Query(sql), txn) => self.process_inner(txn, sql, vars).await?,
+			(Value::Strand(sql), None) => self.kvs().execute(&sql, &self.session(), vars).await?,
+			(Value::Strand(sql), txn) => {
+				let sql = syn::parse_with_capabilities(&sql, self.kvs().get_capabilities())?;
+				self.process_inner(txn, sql, vars).await?
+			}
+			(query, _) => return Err(fail!("Unexpected query type: {query:?}").into()),
 		};
 
 		// Post-process hooks for web layer
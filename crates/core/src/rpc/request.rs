@@ -7,11 +7,13 @@ use crate::sql::Number;
 use crate::sql::Part;
 use crate::sql::Value;
 use std::sync::LazyLock;
+use uuid::Uuid;
 
 pub static ID: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("id")]);
 pub static METHOD: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("method")]);
 pub static PARAMS: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("params")]);
 pub static VERSION: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("version")]);
+pub static TXN: LazyLock<[Part; 1]> = LazyLock::new(|| [Part::from("txn")]);
 
 #[derive(Debug)]
 pub struct Request {
@@ -19,6 +21,7 @@ pub struct Request {
 	pub version: Option<u8>,
 	pub method: Method,
 	pub params: Array,
+	pub txn: Option<Uuid>,
 }
 
 impl TryFrom<Cbor> for Request {
@@ -59,6 +62,17 @@ impl TryFrom<Value> for Request {
 			},
 			_ => return Err(RpcError::InvalidRequest),
 		};
+		// Fetch the 'txn' argument
+		let txn = match val.pick(&*TXN) {
+			v if v.is_none() => None,
+			v if v.is_null() => None,
+			Value::Uuid(v) => Some(v.0),
+			Value::Strand(v) => match Uuid::try_parse(v.as_str()) {
+				Ok(v) => Some(v),
+				_ => return Err(RpcError::InvalidRequest),
+			},
+			_ => return Err(RpcError::InvalidRequest),
+		};
 		// Fetch the 'method' argument
 		let method = match val.pick(&*METHOD) {
 			Value::Strand(v) => v.to_raw(),
@@ -77,6 +91,7 @@ impl TryFrom<Value> for Request {
 			method,
 			params,
 			version,
+			txn,
 		})
 	}
 }
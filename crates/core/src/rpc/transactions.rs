@@ -0,0 +1,279 @@
+use crate::dbs::{Notification, Response, Session, Variables};
+use crate::err::Error;
+use crate::kvs::{Datastore, LockType, Transaction, TransactionType};
+use crate::sql::Query;
+use async_channel::{Receiver, Sender};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
+use trice::Instant;
+use uuid::Uuid;
+
+/// An interactive transaction which spans multiple RPC requests
+struct Entry {
+	/// The underlying datastore transaction
+	txn: Arc<Transaction>,
+	/// The time at which this transaction was last used
+	used: std::sync::Mutex<Instant>,
+	/// Ensures requests on this transaction are processed one at a time
+	lock: Mutex<()>,
+	/// The channel used to buffer notifications until commit
+	channel: Option<(Sender<Notification>, Receiver<Notification>)>,
+}
+
+impl Entry {
+	/// Marks this transaction as having just been used
+	fn touch(&self) {
+		*self.used.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+	}
+
+	/// Checks if this transaction has been idle for longer than the timeout
+	fn idle(&self, timeout: Duration) -> bool {
+		self.used.lock().unwrap_or_else(|e| e.into_inner()).elapsed() > timeout
+	}
+}
+
+/// The interactive transactions opened by a single RPC connection
+pub struct Transactions {
+	/// The maximum duration a transaction can remain idle
+	timeout: Duration,
+	/// The transactions which are currently open
+	entries: RwLock<HashMap<Uuid, Arc<Entry>>>,
+}
+
+impl Transactions {
+	/// Creates a new transaction registry with the specified timeout
+	pub fn new(timeout: Duration) -> Self {
+		Self {
+			timeout,
+			entries: RwLock::new(HashMap::new()),
+		}
+	}
+
+	/// Starts a new transaction, returning its unique id
+	pub(crate) async fn begin(&self, kvs: &Datastore) -> Result<Uuid, Error> {
+		// Start a new writeable transaction
+		let txn = kvs.transaction(TransactionType::Write, LockType::Optimistic).await?;
+		// Buffer notifications if the datastore supports them
+		let channel = kvs.notifications().map(|_| async_channel::unbounded());
+		// Store the transaction
+		let id = Uuid::new_v4();
+		self.entries.write().await.insert(
+			id,
+			Arc::new(Entry {
+				txn: txn.enclose(),
+				used: std::sync::Mutex::new(Instant::now()),
+				lock: Mutex::new(()),
+				channel,
+			}),
+		);
+		// Return the transaction id
+		Ok(id)
+	}
+
+	/// Executes a query within the specified transaction
+	///
+	/// If any statement fails, the transaction is cancelled, as
+	/// its changes can no longer be committed consistently.
+	pub(crate) async fn process(
+		&self,
+		kvs: &Datastore,
+		id: &Uuid,
+		ast: Query,
+		sess: &Session,
+		vars: Variables,
+	) -> Result<Vec<Response>, Error> {
+		// Fetch the transaction
+		let entry = self.get(id).await?;
+		// Process requests one at a time
+		let _guard = entry.lock.lock().await;
+		// Check the transaction has not since finished
+		if entry.txn.closed().await {
+			return Err(Error::TxNotFound(id.to_string()));
+		}
+		// Execute the query within the transaction
+		let sender = entry.channel.as_ref().map(|(send, _)| send.clone());
+		let res = kvs.process_with_transaction(ast, sess, vars, entry.txn.clone(), sender).await;
+		// The transaction is idle from the end of this request
+		entry.touch();
+		// Cancel the transaction if anything failed
+		let failed = match &res {
+			Ok(res) => res.iter().any(|r| r.result.is_err()),
+			Err(_) => true,
+		};
+		if failed {
+			self.entries.write().await.remove(id);
+			let _ = entry.txn.cancel().await;
+		}
+		res
+	}
+
+	/// Commits the specified transaction
+	pub(crate) async fn commit(&self, kvs: &Datastore, id: &Uuid) -> Result<(), Error> {
+		// Fetch and remove the transaction
+		let entry = self.remove(id).await?;
+		// Wait for any running request to finish
+		let _guard = entry.lock.lock().await;
+		// Complete the changes and commit
+		let mut lock = entry.txn.lock().await;
+		if let Err(e) = lock.complete_changes(false).await {
+			let _ = lock.cancel().await;
+			return Err(e);
+		}
+		lock.commit().await?;
+		// Deliver any buffered notifications
+		if let Some((send, recv)) = &entry.channel {
+			send.close();
+			kvs.deliver_notifications(recv.clone());
+		}
+		Ok(())
+	}
+
+	/// Cancels the specified transaction
+	pub(crate) async fn cancel(&self, id: &Uuid) -> Result<(), Error> {
+		// Fetch and remove the transaction
+		let entry = self.remove(id).await?;
+		// Wait for any running request to finish
+		let _guard = entry.lock.lock().await;
+		// Cancel the transaction
+		entry.txn.cancel().await
+	}
+
+	/// Cancels any transactions which have been idle for longer than the timeout
+	///
+	/// Transactions which are currently processing a request are
+	/// left alone, and are cancelled when they are next used.
+	pub async fn expire(&self) {
+		let expired: Vec<Uuid> = self
+			.entries
+			.read()
+			.await
+			.iter()
+			.filter(|(_, v)| v.idle(self.timeout) && v.lock.try_lock().is_ok())
+			.map(|(k, _)| *k)
+			.collect();
+		for id in expired {
+			trace!("Cancelling transaction {id} as it exceeded the timeout");
+			let _ = self.cancel(&id).await;
+		}
+	}
+
+	/// Cancels all open transactions
+	pub async fn cancel_all(&self) {
+		let entries: Vec<_> = self.entries.write().await.drain().collect();
+		for (id, entry) in entries {
+			trace!("Cancelling transaction {id}");
+			let _guard = entry.lock.lock().await;
+			let _ = entry.txn.cancel().await;
+		}
+	}
+
+	/// Fetches the specified transaction, checking that it has not been idle
+	/// for longer than the timeout, and marking it as used
+	async fn get(&self, id: &Uuid) -> Result<Arc<Entry>, Error> {
+		let entry = match self.entries.read().await.get(id) {
+			Some(v) => v.clone(),
+			None => return Err(Error::TxNotFound(id.to_string())),
+		};
+		if entry.idle(self.timeout) {
+			let _ = self.cancel(id).await;
+			return Err(Error::TxTimedout(id.to_string()));
+		}
+		entry.touch();
+		Ok(entry)
+	}
+
+	/// Removes the specified transaction, checking that it has not been idle
+	/// for longer than the timeout
+	async fn remove(&self, id: &Uuid) -> Result<Arc<Entry>, Error> {
+		let entry = match self.entries.write().await.remove(id) {
+			Some(v) => v,
+			None => return Err(Error::TxNotFound(id.to_string())),
+		};
+		if entry.idle(self.timeout) {
+			let _guard = entry.lock.lock().await;
+			let _ = entry.txn.cancel().await;
+			return Err(Error::TxTimedout(id.to_string()));
+		}
+		Ok(entry)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Transactions;
+	use crate::dbs::Session;
+	use crate::err::Error;
+	use crate::kvs::Datastore;
+	use crate::sql::Value;
+	use crate::syn;
+	use crate::syn::Parse;
+	use std::time::Duration;
+
+	#[tokio::test]
+	async fn commit_and_cancel() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let txs = Transactions::new(Duration::from_secs(60));
+		// Changes are only visible once committed
+		let id = txs.begin(&ds).await.unwrap();
+		let sql = syn::parse("CREATE person:one").unwrap();
+		txs.process(&ds, &id, sql, &ses, None).await.unwrap().remove(0).result.unwrap();
+		let mut res = ds.execute("SELECT * FROM person", &ses, None).await.unwrap();
+		assert_eq!(res.remove(0).result.unwrap(), Value::parse("[]"));
+		txs.commit(&ds, &id).await.unwrap();
+		let mut res = ds.execute("SELECT * FROM person", &ses, None).await.unwrap();
+		assert_eq!(res.remove(0).result.unwrap(), Value::parse("[{ id: person:one }]"));
+		// Cancelled changes are discarded
+		let id = txs.begin(&ds).await.unwrap();
+		let sql = syn::parse("CREATE person:two").unwrap();
+		txs.process(&ds, &id, sql, &ses, None).await.unwrap().remove(0).result.unwrap();
+		txs.cancel(&id).await.unwrap();
+		let mut res = ds.execute("SELECT * FROM person", &ses, None).await.unwrap();
+		assert_eq!(res.remove(0).result.unwrap(), Value::parse("[{ id: person:one }]"));
+		// Finished transactions can not be used again
+		assert!(matches!(txs.commit(&ds, &id).await, Err(Error::TxNotFound(_))));
+	}
+
+	#[tokio::test]
+	async fn failed_statement_cancels() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let txs = Transactions::new(Duration::from_secs(60));
+		let id = txs.begin(&ds).await.unwrap();
+		let sql = syn::parse("CREATE person:one; CREATE person:one; CREATE person:two").unwrap();
+		let res = txs.process(&ds, &id, sql, &ses, None).await.unwrap();
+		assert!(res[0].result.is_ok());
+		assert!(res[1].result.is_err());
+		assert!(matches!(res[2].result, Err(Error::QueryNotExecuted)));
+		assert!(matches!(txs.commit(&ds, &id).await, Err(Error::TxNotFound(_))));
+		let mut res = ds.execute("SELECT * FROM person", &ses, None).await.unwrap();
+		assert_eq!(res.remove(0).result.unwrap(), Value::parse("[]"));
+	}
+
+	#[tokio::test]
+	async fn expired_transaction() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let txs = Transactions::new(Duration::from_millis(10));
+		let id = txs.begin(&ds).await.unwrap();
+		tokio::time::sleep(Duration::from_millis(20)).await;
+		let sql = syn::parse("CREATE person:one").unwrap();
+		let res = txs.process(&ds, &id, sql, &ses, None).await;
+		assert!(matches!(res, Err(Error::TxTimedout(_))));
+		let id = txs.begin(&ds).await.unwrap();
+		tokio::time::sleep(Duration::from_millis(20)).await;
+		txs.expire().await;
+		assert!(matches!(txs.cancel(&id).await, Err(Error::TxNotFound(_))));
+		// Using a transaction resets the timeout
+		let txs = Transactions::new(Duration::from_millis(50));
+		let id = txs.begin(&ds).await.unwrap();
+		for _ in 0..4 {
+			tokio::time::sleep(Duration::from_millis(20)).await;
+			let sql = syn::parse("SELECT * FROM person").unwrap();
+			txs.process(&ds, &id, sql, &ses, None).await.unwrap();
+		}
+		txs.commit(&ds, &id).await.unwrap();
+	}
+}
@@ -9,7 +9,7 @@ use crate::sql::statements::{
 	AlterStatement, BreakStatement, ContinueStatement, CreateStatement, DefineStatement,
 	DeleteStatement, ForeachStatement, IfelseStatement, InsertStatement, OutputStatement,
 	RelateStatement, RemoveStatement, SelectStatement, SetStatement, ThrowStatement,
-	UpdateStatement, UpsertStatement,
+	TrycatchStatement, UpdateStatement, UpsertStatement, WhileStatement,
 };
 use crate::sql::value::Value;
 use reblessive::tree::Stk;
@@ -80,9 +80,15 @@ impl Block {
 				Entry::Foreach(v) => {
 					v.compute(stk, &ctx, opt, doc).await?;
 				}
+				Entry::While(v) => {
+					v.compute(stk, &ctx, opt, doc).await?;
+				}
 				Entry::Ifelse(v) => {
 					v.compute(stk, &ctx, opt, doc).await?;
 				}
+				Entry::Trycatch(v) => {
+					v.compute(stk, &ctx, opt, doc).await?;
+				}
 				Entry::Select(v) => {
 					v.compute(stk, &ctx, opt, doc).await?;
 				}
@@ -186,7 +192,7 @@ impl InfoStructure for Block {
 	}
 }
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -213,6 +219,10 @@ pub enum Entry {
 	Upsert(UpsertStatement),
 	#[revision(start = 4)]
 	Alter(AlterStatement),
+	#[revision(start = 5)]
+	Trycatch(TrycatchStatement),
+	#[revision(start = 6)]
+	While(WhileStatement),
 }
 
 impl PartialOrd for Entry {
@@ -245,6 +255,8 @@ impl Entry {
 			Self::Continue(v) => v.writeable(),
 			Self::Foreach(v) => v.writeable(),
 			Self::Alter(v) => v.writeable(),
+			Self::Trycatch(v) => v.writeable(),
+			Self::While(v) => v.writeable(),
 		}
 	}
 }
@@ -271,6 +283,8 @@ impl Display for Entry {
 			Self::Continue(v) => write!(f, "{v}"),
 			Self::Foreach(v) => write!(f, "{v}"),
 			Self::Alter(v) => write!(f, "{v}"),
+			Self::Trycatch(v) => write!(f, "{v}"),
+			Self::While(v) => write!(f, "{v}"),
 		}
 	}
 }
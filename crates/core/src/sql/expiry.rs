@@ -0,0 +1,48 @@
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{Datetime, Duration, Idiom, Value};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[serde(rename = "$surrealdb::private::sql::Expiry")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum Expiry {
+	/// Records expire once this long has passed since they were last written
+	Duration(Duration),
+	/// Records expire at the datetime stored in this field
+	Field(Idiom),
+}
+
+impl Expiry {
+	/// Computes when a record expires, if at all, as it is being written
+	pub(crate) fn expires_at(&self, doc: &Value) -> Option<Datetime> {
+		match self {
+			Expiry::Duration(v) => Some(*v + Datetime::default()),
+			Expiry::Field(v) => match doc.pick(v) {
+				Value::Datetime(v) => Some(v),
+				_ => None,
+			},
+		}
+	}
+}
+
+impl Display for Expiry {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Expiry::Duration(v) => write!(f, "EXPIRE {v}"),
+			Expiry::Field(v) => write!(f, "EXPIRE {v}"),
+		}
+	}
+}
+
+impl InfoStructure for Expiry {
+	fn structure(self) -> Value {
+		match self {
+			Expiry::Duration(v) => v.structure(),
+			Expiry::Field(v) => v.structure(),
+		}
+	}
+}
@@ -3,7 +3,7 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::{fmt::Fmt, Idiom, Part, Value};
+use crate::sql::{fmt::Fmt, Idiom, Part, Value, Window};
 use crate::syn;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -54,6 +54,23 @@ impl Fields {
 			_ => None,
 		}
 	}
+	/// Get all window function fields, with the name of each output field
+	pub(crate) fn windows(&self) -> impl Iterator<Item = (Cow<'_, Idiom>, &Window)> {
+		self.0.iter().filter_map(|v| match v {
+			Field::Window {
+				expr,
+				alias,
+			} => Some((
+				alias.as_ref().map(Cow::Borrowed).unwrap_or_else(|| Cow::Owned(expr.to_idiom())),
+				expr.as_ref(),
+			)),
+			_ => None,
+		})
+	}
+	/// Check if any of the fields is a window function
+	pub(crate) fn has_windows(&self) -> bool {
+		self.windows().next().is_some()
+	}
 	/// Check if the fields are only about counting
 	pub(crate) fn is_count_all_only(&self) -> bool {
 		let mut is_count_only = false;
@@ -270,13 +287,30 @@ impl Fields {
 						}
 					}
 				}
+				Field::Window {
+					expr,
+					alias,
+				} => {
+					// The window is computed once all of the documents are known
+					let x = expr.compute(stk, ctx, opt, doc).await?;
+					// Check if this is a single VALUE field expression
+					if self.single().is_some() {
+						out = x;
+					} else {
+						let name = alias
+							.as_ref()
+							.map(Cow::Borrowed)
+							.unwrap_or_else(|| Cow::Owned(expr.to_idiom()));
+						out.set(stk, ctx, opt, name.as_ref(), x).await?;
+					}
+				}
 			}
 		}
 		Ok(out)
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -290,6 +324,13 @@ pub enum Field {
 		/// The `quality` in `SELECT rating AS quality FROM ...`
 		alias: Option<Idiom>,
 	},
+	/// The 'window::row_number() OVER (ORDER BY ts)' in `SELECT window::row_number() OVER (ORDER BY ts) FROM ...`
+	#[revision(start = 2)]
+	Window {
+		expr: Box<Window>,
+		/// The `num` in `SELECT window::row_number() OVER (ORDER BY ts) AS num FROM ...`
+		alias: Option<Idiom>,
+	},
 }
 
 impl Display for Field {
@@ -308,6 +349,18 @@ impl Display for Field {
 					Ok(())
 				}
 			}
+			Self::Window {
+				expr,
+				alias,
+			} => {
+				Display::fmt(expr, f)?;
+				if let Some(alias) = alias {
+					f.write_str(" AS ")?;
+					Display::fmt(alias, f)
+				} else {
+					Ok(())
+				}
+			}
 		}
 	}
 }
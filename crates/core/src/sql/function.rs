@@ -3,7 +3,7 @@ use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::fnc;
-use crate::iam::Action;
+use crate::iam::{Action, ResourceKind};
 use crate::sql::fmt::Fmt;
 use crate::sql::idiom::Idiom;
 use crate::sql::script::Script;
@@ -49,6 +49,7 @@ impl Function {
 	}
 }
 
+#[derive(Clone, Copy)]
 pub(crate) enum OptimisedAggregate {
 	None,
 	Count,
@@ -100,6 +101,7 @@ impl Function {
 			Self::Custom(_, _) => true,
 			Self::Script(_, _) => true,
 			Self::Normal(f, _) if f == "api::invoke" => true,
+			Self::Normal(f, _) if f == "sequence::nextval" => true,
 			_ => self.args().iter().any(Value::writeable),
 		}
 	}
@@ -138,6 +140,28 @@ impl Function {
 		}
 	}
 
+	/// Check if this function always returns the same result for the same arguments
+	pub(crate) fn is_deterministic(&self) -> bool {
+		match self {
+			Self::Normal(f, a) => {
+				let deterministic = match f.split("::").next() {
+					Some("rand" | "http" | "session" | "sequence" | "search" | "sleep" | "api") => {
+						false
+					}
+					// These functions read other records
+					_ if f == "record::exists" || f == "record::refs" => false,
+					// These functions return a new salted hash each time
+					_ if f.starts_with("crypto::") && f.ends_with("::generate") => false,
+					// Time functions without arguments use the current time
+					Some("time") => !a.is_empty(),
+					_ => f != "array::shuffle",
+				};
+				deterministic && a.iter().all(Value::is_deterministic)
+			}
+			_ => false,
+		}
+	}
+
 	/// Check if this function is a closure function
 	pub fn is_inline(&self) -> bool {
 		matches!(self, Self::Anonymous(_, _, _))
@@ -146,11 +170,17 @@ impl Function {
 	/// Check if this function is a rolling function
 	pub fn is_rolling(&self) -> bool {
 		match self {
+			Self::Normal(f, _) if f == "array::distinct" => true,
+			Self::Normal(f, _) if f == "array::group" => true,
 			Self::Normal(f, _) if f == "count" => true,
+			Self::Normal(f, _) if f == fnc::count::DISTINCT_FUNCTION => true,
 			Self::Normal(f, _) if f == "math::max" => true,
 			Self::Normal(f, _) if f == "math::mean" => true,
 			Self::Normal(f, _) if f == "math::min" => true,
+			Self::Normal(f, _) if f == "math::percentile" => true,
+			Self::Normal(f, _) if f == "math::stddev" => true,
 			Self::Normal(f, _) if f == "math::sum" => true,
+			Self::Normal(f, _) if f == "math::variance" => true,
 			Self::Normal(f, _) if f == "time::max" => true,
 			Self::Normal(f, _) if f == "time::min" => true,
 			_ => false,
@@ -165,6 +195,7 @@ impl Function {
 			Self::Normal(f, _) if f == "array::group" => true,
 			Self::Normal(f, _) if f == "array::last" => true,
 			Self::Normal(f, _) if f == "count" => true,
+			Self::Normal(f, _) if f == fnc::count::DISTINCT_FUNCTION => true,
 			Self::Normal(f, _) if f == "math::bottom" => true,
 			Self::Normal(f, _) if f == "math::interquartile" => true,
 			Self::Normal(f, _) if f == "math::max" => true,
@@ -283,7 +314,7 @@ impl Function {
 				let (ns, db) = opt.ns_db()?;
 				let val = ctx.tx().get_db_function(ns, db, s).await?;
 				// Check permissions
-				if opt.check_perms(Action::View)? {
+				if opt.check_perms(Action::View, ResourceKind::Function, s)? {
 					match &val.permissions {
 						Permission::Full => (),
 						Permission::None => {
@@ -386,6 +417,9 @@ impl Function {
 impl fmt::Display for Function {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		match self {
+			Self::Normal(s, e) if s == fnc::count::DISTINCT_FUNCTION => {
+				write!(f, "count(DISTINCT {})", Fmt::comma_separated(e))
+			}
 			Self::Normal(s, e) => write!(f, "{s}({})", Fmt::comma_separated(e)),
 			Self::Custom(s, e) => write!(f, "fn::{s}({})", Fmt::comma_separated(e)),
 			Self::Script(s, e) => write!(f, "function({}) {{{s}}}", Fmt::comma_separated(e)),
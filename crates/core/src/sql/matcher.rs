@@ -0,0 +1,173 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::escape::EscapeKey;
+use crate::sql::fmt::{is_pretty, pretty_indent, Fmt, Pretty};
+use crate::sql::{Idiom, Kind, Part, Value};
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter, Write};
+
+pub(crate) const TOKEN: &str = "$surrealdb::private::sql::Match";
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[serde(rename = "$surrealdb::private::sql::Match")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Match {
+	/// The value which is matched against each pattern
+	pub value: Value,
+	/// The patterns, in order, each followed by the value it results in
+	pub arms: Vec<MatchArm>,
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct MatchArm {
+	pub pattern: Pattern,
+	pub then: Value,
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum Pattern {
+	/// The `_` pattern, which matches any value
+	#[default]
+	Any,
+	/// A `<int>` pattern, which matches any value of the type
+	Kind(Kind),
+	/// A `{ status: "x" }` pattern, which matches an object with the fields
+	Object(Vec<(String, Pattern)>),
+	/// A pattern which matches an equal value, or a value within a range
+	Value(Value),
+}
+
+impl Match {
+	/// Check if we require a writeable transaction
+	pub(crate) fn writeable(&self) -> bool {
+		self.value.writeable()
+			|| self.arms.iter().any(|arm| arm.pattern.writeable() || arm.then.writeable())
+	}
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		let val = self.value.compute(stk, ctx, opt, doc).await?;
+		for arm in self.arms.iter() {
+			if arm.pattern.matches(stk, ctx, opt, doc, &val).await? {
+				return arm.then.compute_unbordered(stk, ctx, opt, doc).await;
+			}
+		}
+		Ok(Value::None)
+	}
+}
+
+impl Pattern {
+	/// Convert a parsed value into a pattern, treating `_` as any value
+	pub(crate) fn from_value(v: Value) -> Self {
+		match v {
+			Value::Idiom(Idiom(ref p)) if matches!(p.as_slice(), [Part::Field(f)] if f.0 == "_") => {
+				Pattern::Any
+			}
+			Value::Table(ref t) if t.0 == "_" => Pattern::Any,
+			v => Pattern::Value(v),
+		}
+	}
+	/// Check if we require a writeable transaction
+	pub(crate) fn writeable(&self) -> bool {
+		match self {
+			Pattern::Object(v) => v.iter().any(|(_, p)| p.writeable()),
+			Pattern::Value(v) => v.writeable(),
+			_ => false,
+		}
+	}
+	/// Check if a computed value matches this pattern
+	pub(crate) async fn matches(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+		val: &Value,
+	) -> Result<bool, Error> {
+		match self {
+			Pattern::Any => Ok(true),
+			Pattern::Kind(kind) => Ok(val.clone().coerce_to(kind).is_ok()),
+			Pattern::Object(fields) => {
+				let Value::Object(obj) = val else {
+					return Ok(false);
+				};
+				for (key, pattern) in fields.iter() {
+					let v = obj.get(key).cloned().unwrap_or_default();
+					if !stk.run(|stk| pattern.matches(stk, ctx, opt, doc, &v)).await? {
+						return Ok(false);
+					}
+				}
+				Ok(true)
+			}
+			Pattern::Value(v) => match v.compute(stk, ctx, opt, doc).await? {
+				r @ Value::Range(_) => Ok(r.contains(val)),
+				v => Ok(v.equal(val)),
+			},
+		}
+	}
+}
+
+impl Display for Match {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		let mut f = Pretty::from(f);
+		write!(f, "MATCH {} ", self.value)?;
+		if is_pretty() {
+			f.write_char('{')?;
+		} else {
+			f.write_str("{ ")?;
+		}
+		if !self.arms.is_empty() {
+			let indent = pretty_indent();
+			write!(f, "{}", Fmt::pretty_comma_separated(self.arms.iter()))?;
+			drop(indent);
+		}
+		if is_pretty() {
+			f.write_char('}')
+		} else {
+			f.write_str(" }")
+		}
+	}
+}
+
+impl Display for MatchArm {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "{} => {}", self.pattern, self.then)
+	}
+}
+
+impl Display for Pattern {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Pattern::Any => f.write_char('_'),
+			Pattern::Kind(v) => write!(f, "<{v}>"),
+			Pattern::Object(v) => write!(
+				f,
+				"{{ {} }}",
+				Fmt::comma_separated(v.iter().map(|args| Fmt::new(args, |(k, p), f| write!(
+					f,
+					"{}: {}",
+					EscapeKey(k),
+					p
+				))))
+			),
+			Pattern::Value(v) => write!(f, "{v}"),
+		}
+	}
+}
@@ -22,6 +22,7 @@ pub(crate) mod dir;
 pub(crate) mod duration;
 pub(crate) mod edges;
 pub(crate) mod escape;
+pub(crate) mod expiry;
 pub(crate) mod explain;
 pub(crate) mod expression;
 pub(crate) mod fetch;
@@ -39,6 +40,7 @@ pub(crate) mod idiom;
 pub(crate) mod kind;
 pub(crate) mod language;
 pub(crate) mod limit;
+pub(crate) mod matcher;
 pub(crate) mod mock;
 pub(crate) mod model;
 pub(crate) mod number;
@@ -72,6 +74,7 @@ pub(crate) mod uuid;
 pub(crate) mod value;
 pub(crate) mod version;
 pub(crate) mod view;
+pub(crate) mod window;
 pub(crate) mod with;
 
 pub mod index;
@@ -99,6 +102,7 @@ pub use self::datetime::Datetime;
 pub use self::dir::Dir;
 pub use self::duration::Duration;
 pub use self::edges::Edges;
+pub use self::expiry::Expiry;
 pub use self::explain::Explain;
 pub use self::expression::Expression;
 pub use self::fetch::Fetch;
@@ -121,6 +125,9 @@ pub use self::index::Index;
 pub use self::kind::Kind;
 pub use self::kind::Literal;
 pub use self::limit::Limit;
+pub use self::matcher::Match;
+pub use self::matcher::MatchArm;
+pub use self::matcher::Pattern;
 pub use self::mock::Mock;
 pub use self::model::Model;
 pub use self::number::Number;
@@ -158,6 +165,7 @@ pub use self::value::Value;
 pub use self::value::Values;
 pub use self::version::Version;
 pub use self::view::View;
+pub use self::window::Window;
 pub use self::with::With;
 
 // module reexporting parsing function to prevent a breaking change.
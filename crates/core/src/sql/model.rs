@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 
 #[cfg(feature = "ml")]
-use crate::iam::Action;
+use crate::iam::{Action, ResourceKind};
 #[cfg(feature = "ml")]
 use crate::sql::Permission;
 #[cfg(feature = "ml")]
@@ -75,7 +75,7 @@ impl Model {
 		let (ns, db) = opt.ns_db()?;
 		let path = format!("ml/{}/{}/{}-{}-{}.surml", ns, db, self.name, self.version, val.hash);
 		// Check permissions
-		if opt.check_perms(Action::View)? {
+		if opt.check_perms(Action::View, ResourceKind::Model, &self.name)? {
 			match &val.permissions {
 				Permission::Full => (),
 				Permission::None => {
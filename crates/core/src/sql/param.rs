@@ -3,7 +3,7 @@ use crate::{
 	dbs::Options,
 	doc::CursorDoc,
 	err::Error,
-	iam::Action,
+	iam::{Action, ResourceKind},
 	sql::{ident::Ident, value::Value, Permission},
 };
 use reblessive::tree::Stk;
@@ -79,7 +79,7 @@ impl Param {
 						// The param has been set globally
 						Ok(val) => {
 							// Check permissions
-							if opt.check_perms(Action::View)? {
+							if opt.check_perms(Action::View, ResourceKind::Parameter, v)? {
 								match &val.permissions {
 									Permission::Full => (),
 									Permission::None => {
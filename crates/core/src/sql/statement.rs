@@ -10,9 +10,10 @@ use crate::sql::{
 		AlterStatement, AnalyzeStatement, BeginStatement, BreakStatement, CancelStatement,
 		CommitStatement, ContinueStatement, CreateStatement, DefineStatement, DeleteStatement,
 		ForeachStatement, IfelseStatement, InfoStatement, InsertStatement, KillStatement,
-		LiveStatement, OptionStatement, OutputStatement, RelateStatement, RemoveStatement,
-		SelectStatement, SetStatement, ShowStatement, SleepStatement, ThrowStatement,
-		UpdateStatement, UpsertStatement, UseStatement,
+		LiveStatement, OptionStatement, OutputStatement, RelateStatement, ReleaseStatement,
+		RemoveStatement, RollbackStatement, SavepointStatement, SelectStatement, SetStatement,
+		ShowStatement, SleepStatement, ThrowStatement, TruncateStatement, TrycatchStatement,
+		UpdateStatement, UpsertStatement, UseStatement, WhileStatement,
 	},
 	value::Value,
 };
@@ -55,7 +56,7 @@ impl Display for Statements {
 	}
 }
 
-#[revisioned(revision = 5)]
+#[revisioned(revision = 9)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -96,6 +97,18 @@ pub enum Statement {
 	// TODO(gguillemas): Document once bearer access is no longer experimental.
 	#[revision(start = 5)]
 	Access(AccessStatement),
+	#[revision(start = 6)]
+	Trycatch(TrycatchStatement),
+	#[revision(start = 7)]
+	Savepoint(SavepointStatement),
+	#[revision(start = 7)]
+	Rollback(RollbackStatement),
+	#[revision(start = 7)]
+	Release(ReleaseStatement),
+	#[revision(start = 8)]
+	While(WhileStatement),
+	#[revision(start = 9)]
+	Truncate(TruncateStatement),
 }
 
 impl Statement {
@@ -127,6 +140,9 @@ impl Statement {
 			Self::Show(_) => false,
 			Self::Sleep(_) => false,
 			Self::Throw(_) => false,
+			Self::Truncate(_) => true,
+			Self::Trycatch(v) => v.writeable(),
+			Self::While(v) => v.writeable(),
 			Self::Upsert(v) => v.writeable(),
 			Self::Update(v) => v.writeable(),
 			Self::Use(_) => false,
@@ -183,6 +199,9 @@ impl Statement {
 			Self::Show(v) => v.compute(ctx, opt, doc).await,
 			Self::Sleep(v) => v.compute(ctx, opt, doc).await,
 			Self::Throw(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Truncate(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Trycatch(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::While(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Update(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Upsert(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Value(v) => {
@@ -221,12 +240,18 @@ impl Display for Statement {
 			Self::Output(v) => write!(Pretty::from(f), "{v}"),
 			Self::Rebuild(v) => write!(Pretty::from(f), "{v}"),
 			Self::Relate(v) => write!(Pretty::from(f), "{v}"),
+			Self::Release(v) => write!(Pretty::from(f), "{v}"),
 			Self::Remove(v) => write!(Pretty::from(f), "{v}"),
+			Self::Rollback(v) => write!(Pretty::from(f), "{v}"),
+			Self::Savepoint(v) => write!(Pretty::from(f), "{v}"),
 			Self::Select(v) => write!(Pretty::from(f), "{v}"),
 			Self::Set(v) => write!(Pretty::from(f), "{v}"),
 			Self::Show(v) => write!(Pretty::from(f), "{v}"),
 			Self::Sleep(v) => write!(Pretty::from(f), "{v}"),
 			Self::Throw(v) => write!(Pretty::from(f), "{v}"),
+			Self::Truncate(v) => write!(Pretty::from(f), "{v}"),
+			Self::Trycatch(v) => write!(Pretty::from(f), "{v}"),
+			Self::While(v) => write!(Pretty::from(f), "{v}"),
 			Self::Update(v) => write!(Pretty::from(f), "{v}"),
 			Self::Upsert(v) => write!(Pretty::from(f), "{v}"),
 			Self::Use(v) => write!(Pretty::from(f), "{v}"),
@@ -0,0 +1,64 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::{Base, Ident, Value};
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct AlterAccessStatement {
+	pub name: Ident,
+	pub base: Base,
+	pub if_exists: bool,
+	pub unlock: bool,
+}
+
+impl AlterAccessStatement {
+	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Access, &self.base)?;
+		// Record access methods can only be defined on databases
+		let Base::Db = self.base else {
+			return Err(Error::InvalidLevel(self.base.to_string()));
+		};
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Check if the access method exists
+		let (ns, db) = opt.ns_db()?;
+		let ac = match txn.get_db_access(ns, db, &self.name).await {
+			Ok(ac) => ac,
+			Err(Error::AccessDbNotFound {
+				..
+			}) if self.if_exists => return Ok(Value::None),
+			Err(v) => return Err(v),
+		};
+		// Clear the failed authentication attempts of all subjects
+		if self.unlock {
+			let beg = crate::key::database::access::lo::prefix(ns, db, &ac.name)?;
+			let end = crate::key::database::access::lo::suffix(ns, db, &ac.name)?;
+			txn.delr(beg..end).await?;
+		}
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for AlterAccessStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ALTER ACCESS")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", self.name, self.base)?;
+		if self.unlock {
+			write!(f, " UNLOCK")?
+		}
+		Ok(())
+	}
+}
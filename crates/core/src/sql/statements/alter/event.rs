@@ -0,0 +1,90 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::statements::DefineEventStatement;
+use crate::sql::{Base, Ident, Strand, Value, Values};
+
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::ops::Deref;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct AlterEventStatement {
+	pub name: Ident,
+	pub what: Ident,
+	pub if_exists: bool,
+	pub when: Option<Value>,
+	pub then: Option<Values>,
+	pub comment: Option<Option<Strand>>,
+}
+
+impl AlterEventStatement {
+	pub(crate) async fn compute(
+		&self,
+		_stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Event, &Base::Db)?;
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Get the event definition
+		let mut de = match txn.get_tb_event(ns, db, &self.what, &self.name).await {
+			Ok(ev) => ev.deref().clone(),
+			Err(Error::EvNotFound {
+				..
+			}) if self.if_exists => return Ok(Value::None),
+			Err(v) => return Err(v),
+		};
+		// Process the statement
+		if let Some(ref when) = &self.when {
+			de.when = when.clone();
+		}
+		if let Some(ref then) = &self.then {
+			de.then = then.clone();
+		}
+		if let Some(ref comment) = &self.comment {
+			de.comment.clone_from(comment);
+		}
+		// Define the event again, so that the table caches are updated
+		DefineEventStatement {
+			overwrite: true,
+			..de
+		}
+		.compute(ctx, opt, doc)
+		.await
+	}
+}
+
+impl Display for AlterEventStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ALTER EVENT")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", self.name, self.what)?;
+		if let Some(ref v) = self.when {
+			write!(f, " WHEN {v}")?
+		}
+		if let Some(ref v) = self.then {
+			write!(f, " THEN {v}")?
+		}
+		match &self.comment {
+			Some(Some(v)) => write!(f, " COMMENT {v}")?,
+			Some(None) => write!(f, " DROP COMMENT")?,
+			None => {}
+		}
+		Ok(())
+	}
+}
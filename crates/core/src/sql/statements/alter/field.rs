@@ -0,0 +1,294 @@
+use super::rename::{refresh, rename_idiom, rename_path};
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::key::r#ref::Ref;
+use crate::kvs::KeyDecode;
+use crate::sql::fmt::{is_pretty, pretty_indent};
+use crate::sql::reference::Reference;
+use crate::sql::statements::{DefineFieldStatement, DefineIndexStatement, RemoveIndexStatement};
+use crate::sql::{Base, Ident, Idiom, Idioms, Kind, Permissions, Strand, Value};
+
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Write};
+use std::ops::Deref;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct AlterFieldStatement {
+	pub name: Idiom,
+	pub what: Ident,
+	pub if_exists: bool,
+	pub flex: Option<bool>,
+	pub kind: Option<Option<Kind>>,
+	pub readonly: Option<bool>,
+	pub value: Option<Option<Value>>,
+	pub assert: Option<Option<Value>>,
+	pub default: Option<Option<Value>>,
+	pub default_always: bool,
+	pub permissions: Option<Permissions>,
+	pub comment: Option<Option<Strand>>,
+	pub reference: Option<Option<Reference>>,
+	pub rename: Option<Idiom>,
+}
+
+impl AlterFieldStatement {
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Field, &Base::Db)?;
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Get the field definition
+		let mut df = match txn.get_tb_field(ns, db, &self.what, &self.name.to_string()).await {
+			Ok(fd) => fd.deref().clone(),
+			Err(Error::FdNotFound {
+				..
+			}) if self.if_exists => return Ok(Value::None),
+			Err(v) => return Err(v),
+		};
+		// Process the statement
+		if let Some(flex) = self.flex {
+			df.flex = flex;
+		}
+		if let Some(ref kind) = &self.kind {
+			df.kind.clone_from(kind);
+		}
+		if let Some(readonly) = self.readonly {
+			df.readonly = readonly;
+		}
+		if let Some(ref value) = &self.value {
+			df.value.clone_from(value);
+		}
+		if let Some(ref assert) = &self.assert {
+			df.assert.clone_from(assert);
+		}
+		if let Some(ref default) = &self.default {
+			df.default.clone_from(default);
+			df.default_always = self.default_always;
+		}
+		if let Some(ref permissions) = &self.permissions {
+			df.permissions = permissions.clone();
+		}
+		if let Some(ref comment) = &self.comment {
+			df.comment.clone_from(comment);
+		}
+		if let Some(ref reference) = &self.reference {
+			df.reference.clone_from(reference);
+		}
+		// Define the field again, so that the altered
+		// definition is checked, and any sub fields and
+		// table caches are updated.
+		DefineFieldStatement {
+			overwrite: true,
+			..df
+		}
+		.compute(ctx, opt, doc)
+		.await?;
+		// Rename the field and move its data
+		if let Some(ref rename) = &self.rename {
+			self.rename(stk, ctx, opt, rename).await?;
+		}
+		// Ok all good
+		Ok(Value::None)
+	}
+
+	/// Renames the field, along with any nested fields, moving the
+	/// values on each record and rebuilding the indexes which use it.
+	async fn rename(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		rename: &Idiom,
+	) -> Result<(), Error> {
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Get the old and new field names
+		let (from, to) = (&self.name, rename);
+		let tb = self.what.as_str();
+		// Check the new name is not already taken
+		if txn.get_tb_field(ns, db, tb, &to.to_string()).await.is_ok() {
+			return Err(Error::FdAlreadyExists {
+				name: to.to_string(),
+			});
+		}
+		// Move the field definitions, including any nested fields
+		for fd in txn.all_tb_fields(ns, db, tb, None).await?.iter() {
+			if let Some(name) = rename_idiom(&fd.name, from, to) {
+				let old = fd.name.to_string();
+				txn.del(crate::key::table::fd::new(ns, db, tb, &old)).await?;
+				let fd = DefineFieldStatement {
+					name,
+					..fd.clone()
+				};
+				let new = fd.name.to_string();
+				let key = crate::key::table::fd::new(ns, db, tb, &new);
+				txn.set(key, revision::to_vec(&fd)?, None).await?;
+			}
+		}
+		// Move the field values on each record
+		let beg = crate::key::thing::prefix(ns, db, tb)?;
+		let end = crate::key::thing::suffix(ns, db, tb)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for (k, v) in batch.result {
+				let mut val: Value = revision::from_slice(&v)?;
+				let field = val.pick(from);
+				if !field.is_none() {
+					val.cut(from);
+					val.put(to, field);
+					txn.set(k, revision::to_vec(&val)?, None).await?;
+				}
+			}
+			// Pause execution
+			yield_now!();
+		}
+		// Move the references made from the field
+		let (from_path, to_path) = (from.to_string(), to.to_string());
+		for dt in txn.all_tb(ns, db, None).await?.iter() {
+			let beg = crate::key::r#ref::tbprefix(ns, db, &dt.name)?;
+			let end = crate::key::r#ref::tbsuffix(ns, db, &dt.name)?;
+			let mut next = Some(beg..end);
+			while let Some(rng) = next {
+				let batch = txn.batch_keys(rng, *NORMAL_FETCH_SIZE, None).await?;
+				next = batch.next;
+				for k in batch.result {
+					let r = Ref::decode(&k)?;
+					if r.ft != tb {
+						continue;
+					}
+					if let Some(ff) = rename_path(r.ff, &from_path, &to_path) {
+						let key = crate::key::r#ref::new(ns, db, r.tb, &r.id, tb, &ff, &r.fk);
+						txn.set(key, vec![], None).await?;
+						txn.del(k).await?;
+					}
+				}
+			}
+		}
+		// Refresh the table cache
+		let key = crate::key::database::tb::new(ns, db, tb);
+		let dt = txn.get_tb(ns, db, tb).await?;
+		txn.set(key, revision::to_vec(&refresh(dt.as_ref().clone()))?, None).await?;
+		// Clear the cache
+		if let Some(cache) = ctx.get_cache() {
+			cache.clear_tb(ns, db, tb);
+		}
+		// Clear the cache
+		txn.clear();
+		// Rebuild the indexes which use the field
+		for ix in txn.all_tb_indexes(ns, db, tb).await?.iter() {
+			let rename = |cols: &Idioms| -> Vec<Idiom> {
+				cols.iter()
+					.map(|col| rename_idiom(col, from, to).unwrap_or_else(|| col.clone()))
+					.collect()
+			};
+			let cols = rename(&ix.cols);
+			let include = rename(&ix.include);
+			if cols != ix.cols.0 || include != ix.include.0 {
+				RemoveIndexStatement {
+					name: ix.name.clone(),
+					what: ix.what.clone(),
+					if_exists: false,
+				}
+				.compute(ctx, opt)
+				.await?;
+				DefineIndexStatement {
+					cols: Idioms(cols),
+					include: Idioms(include),
+					..ix.clone()
+				}
+				.compute(stk, ctx, opt, None)
+				.await?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl Display for AlterFieldStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ALTER FIELD")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", self.name, self.what)?;
+		match self.flex {
+			Some(true) => write!(f, " FLEXIBLE")?,
+			Some(false) => write!(f, " DROP FLEXIBLE")?,
+			None => {}
+		}
+		match &self.kind {
+			Some(Some(v)) => write!(f, " TYPE {v}")?,
+			Some(None) => write!(f, " DROP TYPE")?,
+			None => {}
+		}
+		match &self.default {
+			Some(Some(v)) => {
+				write!(f, " DEFAULT")?;
+				if self.default_always {
+					write!(f, " ALWAYS")?
+				}
+				write!(f, " {v}")?
+			}
+			Some(None) => write!(f, " DROP DEFAULT")?,
+			None => {}
+		}
+		match self.readonly {
+			Some(true) => write!(f, " READONLY")?,
+			Some(false) => write!(f, " DROP READONLY")?,
+			None => {}
+		}
+		match &self.value {
+			Some(Some(v)) => write!(f, " VALUE {v}")?,
+			Some(None) => write!(f, " DROP VALUE")?,
+			None => {}
+		}
+		match &self.assert {
+			Some(Some(v)) => write!(f, " ASSERT {v}")?,
+			Some(None) => write!(f, " DROP ASSERT")?,
+			None => {}
+		}
+		match &self.reference {
+			Some(Some(v)) => write!(f, " REFERENCE {v}")?,
+			Some(None) => write!(f, " DROP REFERENCE")?,
+			None => {}
+		}
+		match &self.comment {
+			Some(Some(v)) => write!(f, " COMMENT {v}")?,
+			Some(None) => write!(f, " DROP COMMENT")?,
+			None => {}
+		}
+		if let Some(rename) = &self.rename {
+			write!(f, " RENAME TO {rename}")?
+		}
+		if let Some(permissions) = &self.permissions {
+			let _indent = if is_pretty() {
+				Some(pretty_indent())
+			} else {
+				f.write_char(' ')?;
+				None
+			};
+			write!(f, "{permissions:#}")?;
+		}
+		Ok(())
+	}
+}
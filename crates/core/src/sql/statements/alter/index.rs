@@ -0,0 +1,216 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::kvs::KeyEncode;
+use crate::sql::statements::DefineTableStatement;
+use crate::sql::{Base, Ident, Index, Strand, Value};
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::ops::Deref;
+use uuid::Uuid;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct AlterIndexStatement {
+	pub name: Ident,
+	pub what: Ident,
+	pub if_exists: bool,
+	pub rename: Option<Ident>,
+	pub comment: Option<Option<Strand>>,
+	pub doc_ids_cache: Option<u32>,
+	pub doc_lengths_cache: Option<u32>,
+	pub postings_cache: Option<u32>,
+	pub terms_cache: Option<u32>,
+	pub mtree_cache: Option<u32>,
+}
+
+impl AlterIndexStatement {
+	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Index, &Base::Db)?;
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Get the index definition
+		let mut ix = match txn.get_tb_index(ns, db, &self.what, &self.name).await {
+			Ok(ix) => ix.deref().clone(),
+			Err(Error::IxNotFound {
+				..
+			}) if self.if_exists => return Ok(Value::None),
+			Err(v) => return Err(v),
+		};
+		// Process the statement
+		if let Some(ref comment) = &self.comment {
+			ix.comment.clone_from(comment);
+		}
+		self.set_caches(&mut ix.index)?;
+		// Are we renaming the index?
+		if let Some(ref rename) = &self.rename {
+			// Check the new name is not already taken
+			if txn.get_tb_index(ns, db, &self.what, rename).await.is_ok() {
+				return Err(Error::IxAlreadyExists {
+					name: rename.to_string(),
+				});
+			}
+			// An index which is still being built can not be moved
+			#[cfg(not(target_family = "wasm"))]
+			if let Some(ib) = ctx.get_index_builder() {
+				if !ib.get_status(ns, db, &ix).await.is_ready() {
+					return Err(Error::IndexAlreadyBuilding {
+						name: self.name.to_string(),
+					});
+				}
+			}
+			// Clear the index store cache of the old name
+			#[cfg(not(target_family = "wasm"))]
+			ctx.get_index_stores()
+				.index_removed(ctx.get_index_builder(), &txn, ns, db, &self.what, &self.name)
+				.await?;
+			#[cfg(target_family = "wasm")]
+			ctx.get_index_stores().index_removed(&txn, ns, db, &self.what, &self.name).await?;
+			// The index data does not depend on the name of the index, so its
+			// keys are moved under the new name instead of building it again
+			let from = crate::key::index::all::new(ns, db, &self.what, &self.name).encode()?;
+			let to = crate::key::index::all::new(ns, db, &self.what, rename).encode()?;
+			let mut end = from.clone();
+			end.push(0xff);
+			let mut next = Some(from.clone()..end);
+			while let Some(rng) = next {
+				let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+				next = batch.next;
+				for (k, v) in batch.result {
+					let mut key = to.clone();
+					key.extend_from_slice(&k[from.len()..]);
+					txn.set(key, v, None).await?;
+				}
+				// Pause execution
+				yield_now!();
+			}
+			txn.delp(crate::key::index::all::new(ns, db, &self.what, &self.name)).await?;
+			// Remove the definition under the old name
+			txn.del(crate::key::table::ix::new(ns, db, &self.what, &self.name)).await?;
+			ix.name = rename.clone();
+		}
+		// Set the index definition
+		let key = crate::key::table::ix::new(ns, db, &self.what, &ix.name);
+		txn.set(key, revision::to_vec(&ix)?, None).await?;
+		// Refresh the table cache for indexes
+		let key = crate::key::database::tb::new(ns, db, &self.what);
+		let tb = txn.get_tb(ns, db, &self.what).await?;
+		txn.set(
+			key,
+			revision::to_vec(&DefineTableStatement {
+				cache_indexes_ts: Uuid::now_v7(),
+				..tb.as_ref().clone()
+			})?,
+			None,
+		)
+		.await?;
+		// Clear the cache
+		if let Some(cache) = ctx.get_cache() {
+			cache.clear_tb(ns, db, &self.what);
+		}
+		// Clear the cache
+		txn.clear();
+		// Ok all good
+		Ok(Value::None)
+	}
+
+	/// Apply the cache sizes, checking that the index type has them
+	fn set_caches(&self, index: &mut Index) -> Result<(), Error> {
+		let unsupported = |cache: &str| Error::IxCacheNotSupported {
+			name: self.name.to_string(),
+			cache: cache.to_owned(),
+		};
+		match index {
+			Index::Search(p) => {
+				if self.mtree_cache.is_some() {
+					return Err(unsupported("MTREE_CACHE"));
+				}
+				if let Some(v) = self.doc_ids_cache {
+					p.doc_ids_cache = v;
+				}
+				if let Some(v) = self.doc_lengths_cache {
+					p.doc_lengths_cache = v;
+				}
+				if let Some(v) = self.postings_cache {
+					p.postings_cache = v;
+				}
+				if let Some(v) = self.terms_cache {
+					p.terms_cache = v;
+				}
+			}
+			Index::MTree(p) => {
+				if self.doc_lengths_cache.is_some() {
+					return Err(unsupported("DOC_LENGTHS_CACHE"));
+				}
+				if self.postings_cache.is_some() {
+					return Err(unsupported("POSTINGS_CACHE"));
+				}
+				if self.terms_cache.is_some() {
+					return Err(unsupported("TERMS_CACHE"));
+				}
+				if let Some(v) = self.doc_ids_cache {
+					p.doc_ids_cache = v;
+				}
+				if let Some(v) = self.mtree_cache {
+					p.mtree_cache = v;
+				}
+			}
+			_ => {
+				let caches = [
+					(self.doc_ids_cache, "DOC_IDS_CACHE"),
+					(self.doc_lengths_cache, "DOC_LENGTHS_CACHE"),
+					(self.postings_cache, "POSTINGS_CACHE"),
+					(self.terms_cache, "TERMS_CACHE"),
+					(self.mtree_cache, "MTREE_CACHE"),
+				];
+				if let Some((_, cache)) = caches.iter().find(|(v, _)| v.is_some()) {
+					return Err(unsupported(cache));
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+impl Display for AlterIndexStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ALTER INDEX")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", self.name, self.what)?;
+		if let Some(ref v) = self.rename {
+			write!(f, " RENAME TO {v}")?
+		}
+		if let Some(v) = self.doc_ids_cache {
+			write!(f, " DOC_IDS_CACHE {v}")?
+		}
+		if let Some(v) = self.doc_lengths_cache {
+			write!(f, " DOC_LENGTHS_CACHE {v}")?
+		}
+		if let Some(v) = self.postings_cache {
+			write!(f, " POSTINGS_CACHE {v}")?
+		}
+		if let Some(v) = self.terms_cache {
+			write!(f, " TERMS_CACHE {v}")?
+		}
+		if let Some(v) = self.mtree_cache {
+			write!(f, " MTREE_CACHE {v}")?
+		}
+		match &self.comment {
+			Some(Some(v)) => write!(f, " COMMENT {v}")?,
+			Some(None) => write!(f, " DROP COMMENT")?,
+			None => {}
+		}
+		Ok(())
+	}
+}
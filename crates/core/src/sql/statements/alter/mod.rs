@@ -1,6 +1,17 @@
+mod access;
+mod event;
+mod field;
+mod index;
+mod rename;
 mod table;
+mod user;
 
+pub use access::AlterAccessStatement;
+pub use event::AlterEventStatement;
+pub use field::AlterFieldStatement;
+pub use index::AlterIndexStatement;
 pub use table::AlterTableStatement;
+pub use user::AlterUserStatement;
 
 use crate::ctx::Context;
 use crate::dbs::Options;
@@ -13,12 +24,22 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub enum AlterStatement {
 	Table(AlterTableStatement),
+	#[revision(start = 2)]
+	Field(AlterFieldStatement),
+	#[revision(start = 2)]
+	Index(AlterIndexStatement),
+	#[revision(start = 2)]
+	Event(AlterEventStatement),
+	#[revision(start = 3)]
+	User(AlterUserStatement),
+	#[revision(start = 4)]
+	Access(AlterAccessStatement),
 }
 
 impl AlterStatement {
@@ -36,6 +57,11 @@ impl AlterStatement {
 	) -> Result<Value, Error> {
 		match self {
 			Self::Table(ref v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Field(ref v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Index(ref v) => v.compute(ctx, opt).await,
+			Self::Event(ref v) => v.compute(stk, ctx, opt, doc).await,
+			Self::User(ref v) => v.compute(ctx, opt).await,
+			Self::Access(ref v) => v.compute(ctx, opt).await,
 		}
 	}
 }
@@ -44,6 +70,11 @@ impl Display for AlterStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		match self {
 			Self::Table(v) => Display::fmt(v, f),
+			Self::Field(v) => Display::fmt(v, f),
+			Self::Index(v) => Display::fmt(v, f),
+			Self::Event(v) => Display::fmt(v, f),
+			Self::User(v) => Display::fmt(v, f),
+			Self::Access(v) => Display::fmt(v, f),
 		}
 	}
 }
@@ -61,6 +92,6 @@ mod tests {
 			..Default::default()
 		});
 		let enc: Vec<u8> = revision::to_vec(&stm).unwrap();
-		assert_eq!(16, enc.len());
+		assert_eq!(17, enc.len());
 	}
 }
@@ -0,0 +1,88 @@
+//! Helpers for rewriting stored data when a table or a field is renamed
+use crate::sql::statements::DefineTableStatement;
+use crate::sql::{Id, Idiom, Kind, Table, TableType, Value};
+
+use uuid::Uuid;
+
+/// Renames any record links to the table `from`, returning whether the value changed
+pub(super) fn rename_links(v: &mut Value, from: &str, to: &str) -> bool {
+	match v {
+		Value::Thing(t) => {
+			let changed = rename_id(&mut t.id, from, to);
+			if t.tb == from {
+				to.clone_into(&mut t.tb);
+				return true;
+			}
+			changed
+		}
+		Value::Array(v) => v.0.iter_mut().fold(false, |c, v| rename_links(v, from, to) | c),
+		Value::Object(v) => v.0.values_mut().fold(false, |c, v| rename_links(v, from, to) | c),
+		_ => false,
+	}
+}
+
+/// Renames any record links to the table `from` within a record id, returning whether the id changed
+pub(super) fn rename_id(id: &mut Id, from: &str, to: &str) -> bool {
+	match id {
+		Id::Array(v) => v.0.iter_mut().fold(false, |c, v| rename_links(v, from, to) | c),
+		Id::Object(v) => v.0.values_mut().fold(false, |c, v| rename_links(v, from, to) | c),
+		_ => false,
+	}
+}
+
+/// Renames any record types for the table `from`, returning whether the kind changed
+pub(super) fn rename_kind(k: &mut Kind, from: &str, to: &str) -> bool {
+	match k {
+		Kind::Record(v) => v.iter_mut().fold(false, |c, t| rename_table(t, from, to) | c),
+		Kind::Option(k) | Kind::Set(k, _) | Kind::Array(k, _) => rename_kind(k, from, to),
+		Kind::Either(v) => v.iter_mut().fold(false, |c, k| rename_kind(k, from, to) | c),
+		Kind::References(Some(t), _) => rename_table(t, from, to),
+		_ => false,
+	}
+}
+
+/// Renames the table `from` in the table type of a relation table
+pub(super) fn rename_table_type(kind: &mut TableType, from: &str, to: &str) -> bool {
+	match kind {
+		TableType::Relation(rel) => [&mut rel.from, &mut rel.to]
+			.into_iter()
+			.flatten()
+			.fold(false, |c, k| rename_kind(k, from, to) | c),
+		_ => false,
+	}
+}
+
+/// Renames a table name, returning whether the name changed
+pub(super) fn rename_table(t: &mut Table, from: &str, to: &str) -> bool {
+	if t.0 == from {
+		to.clone_into(&mut t.0);
+		return true;
+	}
+	false
+}
+
+/// Moves an idiom which is, or is nested within, the field `from` to the field `to`
+pub(super) fn rename_idiom(idiom: &Idiom, from: &Idiom, to: &Idiom) -> Option<Idiom> {
+	idiom.0.strip_prefix(from.0.as_slice()).map(|rest| Idiom([to.0.as_slice(), rest].concat()))
+}
+
+/// Moves a field path, as stored in a reference key, from the field `from` to the field `to`
+pub(super) fn rename_path(path: &str, from: &str, to: &str) -> Option<String> {
+	match path.strip_prefix(from) {
+		Some(rest) if rest.is_empty() || rest.starts_with(['.', '[']) => {
+			Some(format!("{to}{rest}"))
+		}
+		_ => None,
+	}
+}
+
+/// Refreshes the cache timestamps of a table, so that its cached definitions are reloaded
+pub(super) fn refresh(tb: DefineTableStatement) -> DefineTableStatement {
+	DefineTableStatement {
+		cache_fields_ts: Uuid::now_v7(),
+		cache_events_ts: Uuid::now_v7(),
+		cache_tables_ts: Uuid::now_v7(),
+		cache_indexes_ts: Uuid::now_v7(),
+		..tb
+	}
+}
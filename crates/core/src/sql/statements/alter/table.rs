@@ -1,10 +1,24 @@
+use super::rename::{
+	refresh, rename_id, rename_kind, rename_links, rename_table, rename_table_type,
+};
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
+use crate::key::graph::Graph;
+use crate::key::r#ref::Ref;
+use crate::key::table::et::Et;
+use crate::key::table::ex::Ex;
+use crate::key::thing::Thing;
+use crate::kvs::KeyDecode;
 use crate::sql::fmt::{is_pretty, pretty_indent};
-use crate::sql::statements::DefineTableStatement;
+use crate::sql::paths::ID;
+use crate::sql::statements::rebuild::RebuildIndexStatement;
+use crate::sql::statements::{
+	DefineEventStatement, DefineFieldStatement, DefineIndexStatement, DefineTableStatement,
+};
 use crate::sql::{changefeed::ChangeFeed, Base, Ident, Permissions, Strand, Value};
 use crate::sql::{Kind, TableType};
 
@@ -14,7 +28,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Write};
 use std::ops::Deref;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -27,12 +41,14 @@ pub struct AlterTableStatement {
 	pub changefeed: Option<Option<ChangeFeed>>,
 	pub comment: Option<Option<Strand>>,
 	pub kind: Option<TableType>,
+	#[revision(start = 2)]
+	pub rename: Option<Ident>,
 }
 
 impl AlterTableStatement {
 	pub(crate) async fn compute(
 		&self,
-		_stk: &mut Stk,
+		stk: &mut Stk,
 		ctx: &Context,
 		opt: &Options,
 		_doc: Option<&CursorDoc>,
@@ -76,6 +92,10 @@ impl AlterTableStatement {
 		if matches!(self.kind, Some(TableType::Relation(_))) {
 			DefineTableStatement::add_in_out_fields(&txn, ns, db, &mut dt).await?;
 		}
+		// Rename the table and all of its data
+		if let Some(ref rename) = &self.rename {
+			return self.rename(stk, ctx, opt, dt, rename).await;
+		}
 		// Set the table definition
 		txn.set(key, revision::to_vec(&dt)?, None).await?;
 		// Record definition change
@@ -87,6 +107,342 @@ impl AlterTableStatement {
 		// Ok all good
 		Ok(Value::None)
 	}
+
+	/// Renames the table, along with its definitions and records, and
+	/// rewrites the record links, edges, and references from other tables.
+	/// The ids of records in other tables are left as is. Tables whose
+	/// changes are recorded in a change feed can not be renamed.
+	async fn rename(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		dt: DefineTableStatement,
+		rename: &Ident,
+	) -> Result<Value, Error> {
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Get the old and new table names
+		let (from, to) = (self.name.as_str(), rename.as_str());
+		// Check the new name is not already taken
+		if txn.get_tb(ns, db, to).await.is_ok() {
+			return Err(Error::TbAlreadyExists {
+				name: to.to_owned(),
+			});
+		}
+		// Check the table changes are not recorded in a change feed
+		if dt.changefeed.is_some() || txn.get_db(ns, db).await?.changefeed.is_some() {
+			return Err(Error::TbRenameChangefeed {
+				name: from.to_owned(),
+			});
+		}
+		// Remove the index stores
+		#[cfg(not(target_family = "wasm"))]
+		ctx.get_index_stores().table_removed(ctx.get_index_builder(), &txn, ns, db, from).await?;
+		#[cfg(target_family = "wasm")]
+		ctx.get_index_stores().table_removed(&txn, ns, db, from).await?;
+		// Fetch the definitions before anything is changed
+		let tbs = txn.all_tb(ns, db, None).await?;
+		let fds = txn.all_tb_fields(ns, db, from, None).await?;
+		let evs = txn.all_tb_events(ns, db, from).await?;
+		let ixs = txn.all_tb_indexes(ns, db, from).await?;
+		// Set the table definition under the new name
+		let mut dt = refresh(DefineTableStatement {
+			name: rename.clone(),
+			..dt
+		});
+		rename_table_type(&mut dt.kind, from, to);
+		txn.del(crate::key::database::tb::new(ns, db, from)).await?;
+		let key = crate::key::database::tb::new(ns, db, to);
+		txn.set(key, revision::to_vec(&dt)?, None).await?;
+		// Move the field definitions
+		for fd in fds.iter() {
+			let mut fd = DefineFieldStatement {
+				what: rename.clone(),
+				..fd.clone()
+			};
+			if let Some(kind) = &mut fd.kind {
+				rename_kind(kind, from, to);
+			}
+			let fd_name = fd.name.to_string();
+			let key = crate::key::table::fd::new(ns, db, to, &fd_name);
+			txn.set(key, revision::to_vec(&fd)?, None).await?;
+		}
+		// Move the event definitions
+		for ev in evs.iter() {
+			let ev = DefineEventStatement {
+				what: rename.clone(),
+				..ev.clone()
+			};
+			let key = crate::key::table::ev::new(ns, db, to, &ev.name);
+			txn.set(key, revision::to_vec(&ev)?, None).await?;
+		}
+		// Move the records
+		let beg = crate::key::thing::prefix(ns, db, from)?;
+		let end = crate::key::thing::suffix(ns, db, from)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for (k, v) in batch.result {
+				let mut id = Thing::decode(&k)?.id;
+				rename_id(&mut id, from, to);
+				let mut val: Value = revision::from_slice(&v)?;
+				rename_links(&mut val, from, to);
+				let key = crate::key::thing::new(ns, db, to, &id);
+				txn.set(key, revision::to_vec(&val)?, None).await?;
+			}
+			// Pause execution
+			yield_now!();
+		}
+		// Move the record expiry times
+		let beg = crate::key::table::ex::prefix(ns, db, from)?;
+		let end = crate::key::table::ex::suffix(ns, db, from)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for (k, v) in batch.result {
+				let mut id = Ex::decode(&k)?.id;
+				rename_id(&mut id, from, to);
+				txn.set(crate::key::table::ex::new(ns, db, to, &id), v, None).await?;
+			}
+			// Pause execution
+			yield_now!();
+		}
+		// Move the record expiry order
+		let beg = crate::key::table::et::prefix(ns, db, from)?;
+		let end = crate::key::table::et::suffix(ns, db, from)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for k in batch.result {
+				let mut et = Et::decode(&k)?;
+				rename_id(&mut et.id, from, to);
+				txn.set(Et::new(ns, db, to, et.ts, et.id), vec![], None).await?;
+			}
+			// Pause execution
+			yield_now!();
+		}
+		// Move the graph edge pointers
+		let beg = crate::key::graph::tbprefix(ns, db, from)?;
+		let end = crate::key::graph::tbsuffix(ns, db, from)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for k in batch.result {
+				let mut g = Graph::decode(&k)?;
+				rename_id(&mut g.id, from, to);
+				let ft = if g.ft == from {
+					rename_id(&mut g.fk, from, to);
+					to
+				} else {
+					g.ft
+				};
+				let key = Graph::new_from_id(ns, db, to, g.id, g.eg, ft, g.fk);
+				txn.set(key, vec![], None).await?;
+			}
+			// Pause execution
+			yield_now!();
+		}
+		// Move the references to the records
+		let beg = crate::key::r#ref::tbprefix(ns, db, from)?;
+		let end = crate::key::r#ref::tbsuffix(ns, db, from)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for k in batch.result {
+				let mut r = Ref::decode(&k)?;
+				rename_id(&mut r.id, from, to);
+				let ft = if r.ft == from {
+					rename_id(&mut r.fk, from, to);
+					to
+				} else {
+					r.ft
+				};
+				let key = crate::key::r#ref::new(ns, db, to, &r.id, ft, r.ff, &r.fk);
+				txn.set(key, vec![], None).await?;
+			}
+			// Pause execution
+			yield_now!();
+		}
+		// Remove the old table data, including any index data
+		txn.delp(crate::key::table::all::new(ns, db, from)).await?;
+		// Rewrite the other tables in the database
+		for tb in tbs.iter().filter(|tb| tb.name.as_str() != from) {
+			self.rename_in(stk, ctx, opt, &dt, tb).await?;
+		}
+		// Clear the cache
+		if let Some(cache) = ctx.get_cache() {
+			cache.clear_tb(ns, db, from);
+			cache.clear_tb(ns, db, to);
+		}
+		// Clear the cache
+		txn.clear();
+		// Rebuild the indexes on the renamed table
+		for ix in ixs.iter() {
+			DefineIndexStatement {
+				what: rename.clone(),
+				..ix.clone()
+			}
+			.compute(stk, ctx, opt, None)
+			.await?;
+		}
+		// Ok all good
+		Ok(Value::None)
+	}
+
+	/// Rewrites any links to the renamed table `dt` within another table
+	async fn rename_in(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		dt: &DefineTableStatement,
+		tb: &DefineTableStatement,
+	) -> Result<(), Error> {
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Get the old and new table names
+		let (from, to, name) = (self.name.as_str(), dt.name.as_str(), tb.name.as_str());
+		let mut def = tb.clone();
+		let mut changed = rename_table_type(&mut def.kind, from, to);
+		// Is this table a view on the renamed table?
+		if let Some(view) = &mut def.view {
+			changed |= view.what.0.iter_mut().fold(false, |c, t| rename_table(t, from, to) | c);
+		}
+		// Is the renamed table a view on this table?
+		if dt.view.as_ref().is_some_and(|v| v.what.0.iter().any(|t| t.as_str() == name)) {
+			txn.del(crate::key::table::ft::new(ns, db, name, from)).await?;
+			let key = crate::key::table::ft::new(ns, db, name, to);
+			txn.set(key, revision::to_vec(dt)?, None).await?;
+			changed = true;
+		}
+		// Rewrite any record types for the renamed table
+		for fd in txn.all_tb_fields(ns, db, name, None).await?.iter() {
+			let mut fd = fd.clone();
+			if fd.kind.as_mut().is_some_and(|k| rename_kind(k, from, to)) {
+				let fd_name = fd.name.to_string();
+				let key = crate::key::table::fd::new(ns, db, name, &fd_name);
+				txn.set(key, revision::to_vec(&fd)?, None).await?;
+				changed = true;
+			}
+		}
+		// Set the table definition
+		if changed {
+			let def = refresh(def);
+			let key = crate::key::database::tb::new(ns, db, name);
+			txn.set(key, revision::to_vec(&def)?, None).await?;
+			// Record definition change
+			if def.changefeed.is_some() {
+				txn.lock().await.record_table_change(ns, db, name, &def);
+			}
+			// Save the view config under the renamed table
+			if def.view.as_ref().is_some_and(|v| v.what.0.iter().any(|t| t.as_str() == to)) {
+				let key = crate::key::table::ft::new(ns, db, to, name);
+				txn.set(key, revision::to_vec(&def)?, None).await?;
+			}
+		}
+		// Rewrite the record links, and find the indexes over any rewritten values
+		let ixs = txn.all_tb_indexes(ns, db, name).await?;
+		let mut affected = vec![false; ixs.len()];
+		let beg = crate::key::thing::prefix(ns, db, name)?;
+		let end = crate::key::thing::suffix(ns, db, name)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for (k, v) in batch.result {
+				let mut val: Value = revision::from_slice(&v)?;
+				for (ix, a) in ixs.iter().zip(affected.iter_mut()).filter(|(_, a)| !**a) {
+					*a = ix.cols.iter().any(|c| rename_links(&mut val.pick(c), from, to));
+				}
+				let rid = val.pick(&*ID);
+				let prev = tb.changefeed.is_some().then(|| val.clone());
+				if rename_links(&mut val, from, to) {
+					// The record id is not changed
+					val.put(&*ID, rid.clone());
+					txn.set(k, revision::to_vec(&val)?, None).await?;
+					// Record the change in the change feed
+					if let (Some(cf), Some(prev), Value::Thing(rid)) = (&tb.changefeed, prev, rid) {
+						let (prev, val) = (prev.into(), val.into());
+						txn.lock().await.record_change(
+							ns,
+							db,
+							name,
+							&rid,
+							prev,
+							val,
+							cf.store_diff,
+						);
+					}
+				}
+			}
+			// Pause execution
+			yield_now!();
+		}
+		// Rewrite the graph edge pointers
+		let beg = crate::key::graph::tbprefix(ns, db, name)?;
+		let end = crate::key::graph::tbsuffix(ns, db, name)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for k in batch.result {
+				let mut g = Graph::decode(&k)?;
+				if g.ft == from {
+					rename_id(&mut g.fk, from, to);
+					let key = Graph::new_from_id(ns, db, name, g.id, g.eg, to, g.fk);
+					txn.set(key, vec![], None).await?;
+					txn.del(k).await?;
+				}
+			}
+			// Pause execution
+			yield_now!();
+		}
+		// Rewrite the references from the renamed table
+		let beg = crate::key::r#ref::tbprefix(ns, db, name)?;
+		let end = crate::key::r#ref::tbsuffix(ns, db, name)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for k in batch.result {
+				let mut r = Ref::decode(&k)?;
+				if r.ft == from {
+					rename_id(&mut r.fk, from, to);
+					let key = crate::key::r#ref::new(ns, db, name, &r.id, to, r.ff, &r.fk);
+					txn.set(key, vec![], None).await?;
+					txn.del(k).await?;
+				}
+			}
+			// Pause execution
+			yield_now!();
+		}
+		// Clear the cache
+		if let Some(cache) = ctx.get_cache() {
+			cache.clear_tb(ns, db, name);
+		}
+		// Rebuild the indexes over any rewritten values
+		txn.clear();
+		for (ix, _) in ixs.iter().zip(affected).filter(|(_, a)| *a) {
+			RebuildIndexStatement {
+				name: ix.name.clone(),
+				what: ix.what.clone(),
+				if_exists: false,
+			}
+			.compute(stk, ctx, opt, None)
+			.await?;
+		}
+		Ok(())
+	}
 }
 
 impl Display for AlterTableStatement {
@@ -140,6 +496,9 @@ impl Display for AlterTableStatement {
 		if let Some(changefeed) = &self.changefeed {
 			write!(f, " CHANGEFEED {}", changefeed.map_or("NONE".into(), |v| v.to_string()))?
 		}
+		if let Some(rename) = &self.rename {
+			write!(f, " RENAME TO {rename}")?;
+		}
 		let _indent = if is_pretty() {
 			Some(pretty_indent())
 		} else {
@@ -0,0 +1,85 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::kvs::KeyEncode;
+use crate::sql::{Base, Ident, Value};
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct AlterUserStatement {
+	pub name: Ident,
+	pub base: Base,
+	pub if_exists: bool,
+	pub unlock: bool,
+}
+
+impl AlterUserStatement {
+	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Get the failed authentication state key for the user
+		let res = match self.base {
+			Base::Root => txn
+				.get_root_user(&self.name)
+				.await
+				.map(|us| crate::key::root::lo::new(&us.name).encode()),
+			Base::Ns => {
+				let ns = opt.ns()?;
+				txn.get_ns_user(ns, &self.name)
+					.await
+					.map(|us| crate::key::namespace::lo::new(ns, &us.name).encode())
+			}
+			Base::Db => {
+				let (ns, db) = opt.ns_db()?;
+				txn.get_db_user(ns, db, &self.name)
+					.await
+					.map(|us| crate::key::database::lo::new(ns, db, &us.name).encode())
+			}
+			_ => return Err(Error::InvalidLevel(self.base.to_string())),
+		};
+		let key = match res {
+			Ok(key) => key?,
+			Err(
+				Error::UserRootNotFound {
+					..
+				}
+				| Error::UserNsNotFound {
+					..
+				}
+				| Error::UserDbNotFound {
+					..
+				},
+			) if self.if_exists => return Ok(Value::None),
+			Err(v) => return Err(v),
+		};
+		// Clear any failed authentication attempts
+		if self.unlock {
+			txn.del(key).await?;
+		}
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for AlterUserStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ALTER USER")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", self.name, self.base)?;
+		if self.unlock {
+			write!(f, " UNLOCK")?
+		}
+		Ok(())
+	}
+}
@@ -6,7 +6,7 @@ use crate::iam::{Action, ResourceKind};
 use crate::sql::statements::info::InfoStructure;
 use crate::sql::statements::DefineTableStatement;
 use crate::sql::statements::UpdateStatement;
-use crate::sql::{Base, Ident, Idioms, Index, Output, Part, Strand, Value, Values};
+use crate::sql::{Base, Cond, Ident, Idioms, Index, Output, Part, Strand, Value, Values};
 
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -15,7 +15,7 @@ use std::fmt::{self, Display};
 use std::sync::Arc;
 use uuid::Uuid;
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -31,6 +31,12 @@ pub struct DefineIndexStatement {
 	pub overwrite: bool,
 	#[revision(start = 4)]
 	pub concurrently: bool,
+	/// Only records which match this condition are indexed
+	#[revision(start = 5)]
+	pub cond: Option<Cond>,
+	/// Additional fields which are stored in the index entries
+	#[revision(start = 6)]
+	pub include: Idioms,
 }
 
 impl DefineIndexStatement {
@@ -44,6 +50,21 @@ impl DefineIndexStatement {
 	) -> Result<Value, Error> {
 		// Allowed to run?
 		opt.is_allowed(Action::Edit, ResourceKind::Index, &Base::Db)?;
+		// Check that the indexed expressions are deterministic
+		for idiom in self.cols.iter() {
+			if !Value::Idiom(idiom.clone()).is_deterministic() {
+				return Err(Error::IxNotDeterministic {
+					name: self.name.to_string(),
+					value: idiom.to_string(),
+				});
+			}
+		}
+		// Only standard and unique indexes can include additional fields
+		if !self.include.is_empty() && !matches!(self.index, Index::Idx | Index::Uniq) {
+			return Err(Error::IxIncludeNotSupported {
+				name: self.name.to_string(),
+			});
+		}
 		// Get the NS and DB
 		let (ns, db) = opt.ns_db()?;
 		// Fetch the transaction
@@ -71,7 +92,7 @@ impl DefineIndexStatement {
 				// Are we SchemaFull?
 				if tb.full {
 					// Check that the fields exists
-					for idiom in self.cols.iter() {
+					for idiom in self.cols.iter().chain(self.include.iter()) {
 						let Some(Part::Field(first)) = idiom.0.first() else {
 							continue;
 						};
@@ -173,9 +194,15 @@ impl Display for DefineIndexStatement {
 			write!(f, " OVERWRITE")?
 		}
 		write!(f, " {} ON {} FIELDS {}", self.name, self.what, self.cols)?;
+		if !self.include.is_empty() {
+			write!(f, " INCLUDE {}", self.include)?;
+		}
 		if Index::Idx != self.index {
 			write!(f, " {}", self.index)?;
 		}
+		if let Some(ref v) = self.cond {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -192,7 +219,9 @@ impl InfoStructure for DefineIndexStatement {
 			"name".to_string() => self.name.structure(),
 			"what".to_string() => self.what.structure(),
 			"cols".to_string() => self.cols.structure(),
+			"include".to_string(), if !self.include.is_empty() => self.include.structure(),
 			"index".to_string() => self.index.structure(),
+			"cond".to_string(), if let Some(v) = self.cond => v.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
 	}
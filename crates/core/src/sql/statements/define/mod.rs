@@ -11,6 +11,8 @@ mod index;
 mod model;
 mod namespace;
 mod param;
+mod role;
+mod sequence;
 mod table;
 mod user;
 
@@ -26,6 +28,8 @@ pub use index::DefineIndexStatement;
 pub use model::DefineModelStatement;
 pub use namespace::DefineNamespaceStatement;
 pub use param::DefineParamStatement;
+pub use role::DefineRoleStatement;
+pub use sequence::DefineSequenceStatement;
 pub use table::DefineTableStatement;
 pub use user::DefineUserStatement;
 
@@ -47,7 +51,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -80,6 +84,10 @@ pub enum DefineStatement {
 	Config(DefineConfigStatement),
 	#[revision(start = 3)]
 	Api(DefineApiStatement),
+	#[revision(start = 4)]
+	Sequence(DefineSequenceStatement),
+	#[revision(start = 5)]
+	Role(DefineRoleStatement),
 }
 
 // Revision implementations
@@ -127,6 +135,8 @@ impl DefineStatement {
 			Self::Access(ref v) => v.compute(ctx, opt, doc).await,
 			Self::Config(ref v) => v.compute(ctx, opt, doc).await,
 			Self::Api(ref v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Sequence(ref v) => v.compute(ctx, opt, doc).await,
+			Self::Role(ref v) => v.compute(ctx, opt, doc).await,
 		}
 	}
 }
@@ -148,6 +158,8 @@ impl Display for DefineStatement {
 			Self::Access(v) => Display::fmt(v, f),
 			Self::Config(v) => Display::fmt(v, f),
 			Self::Api(v) => Display::fmt(v, f),
+			Self::Sequence(v) => Display::fmt(v, f),
+			Self::Role(v) => Display::fmt(v, f),
 		}
 	}
 }
@@ -0,0 +1,201 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::policies::compile_role;
+use crate::iam::{Action, ResourceKind, Role};
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{Base, Ident, Strand, Value};
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct DefineRoleStatement {
+	pub name: Ident,
+	pub base: Base,
+	pub action: Action,
+	pub resource: ResourceKind,
+	pub when: Option<Value>,
+	pub comment: Option<Strand>,
+	pub if_not_exists: bool,
+	pub overwrite: bool,
+}
+
+impl DefineRoleStatement {
+	/// Returns the resource kind with the given name, as used in `PERMIT ... ON <resource>`
+	pub(crate) fn resource_from_name(name: &str) -> Option<ResourceKind> {
+		match name.to_ascii_uppercase().as_str() {
+			"ANY" => Some(ResourceKind::Any),
+			"NAMESPACE" => Some(ResourceKind::Namespace),
+			"DATABASE" => Some(ResourceKind::Database),
+			"TABLE" => Some(ResourceKind::Table),
+			"RECORD" => Some(ResourceKind::Record),
+			"FUNCTION" => Some(ResourceKind::Function),
+			"ANALYZER" => Some(ResourceKind::Analyzer),
+			"PARAM" => Some(ResourceKind::Parameter),
+			"MODEL" => Some(ResourceKind::Model),
+			"EVENT" => Some(ResourceKind::Event),
+			"FIELD" => Some(ResourceKind::Field),
+			"INDEX" => Some(ResourceKind::Index),
+			"ACCESS" => Some(ResourceKind::Access),
+			"API" => Some(ResourceKind::Api),
+			"SEQUENCE" => Some(ResourceKind::Sequence),
+			"USER" => Some(ResourceKind::Actor),
+			_ => None,
+		}
+	}
+
+	/// Returns the name of the resource kind, as used in `PERMIT ... ON <resource>`
+	fn resource_name(&self) -> String {
+		match self.resource {
+			ResourceKind::Parameter => "PARAM".to_owned(),
+			ResourceKind::Actor => "USER".to_owned(),
+			ref v => v.to_string().to_uppercase(),
+		}
+	}
+
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+		// Built-in role names would shadow the custom role
+		if Role::from_str(&self.name).is_ok() {
+			return Err(Error::RoleBuiltin {
+				name: self.name.to_string(),
+			});
+		}
+		// Ensure the role can be compiled into a policy
+		compile_role(self, self.name.to_string()).map_err(|message| {
+			Error::RoleInvalidCondition {
+				name: self.name.to_string(),
+				message,
+			}
+		})?;
+		// Check the statement type
+		match self.base {
+			Base::Ns => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				// Check if the definition exists
+				if txn.get_ns_role(opt.ns()?, &self.name).await.is_ok() {
+					if self.if_not_exists {
+						return Ok(Value::None);
+					} else if !self.overwrite {
+						return Err(Error::RoleNsAlreadyExists {
+							name: self.name.to_string(),
+							ns: opt.ns()?.into(),
+						});
+					}
+				}
+				// Process the statement
+				let key = crate::key::namespace::ro::new(opt.ns()?, &self.name);
+				txn.get_or_add_ns(opt.ns()?, opt.strict).await?;
+				txn.set(
+					key,
+					revision::to_vec(&DefineRoleStatement {
+						// Don't persist the `IF NOT EXISTS` clause to schema
+						if_not_exists: false,
+						overwrite: false,
+						..self.clone()
+					})?,
+					None,
+				)
+				.await?;
+				// Clear the cache
+				txn.clear();
+				// Ok all good
+				Ok(Value::None)
+			}
+			Base::Db => {
+				// Fetch the transaction
+				let txn = ctx.tx();
+				// Check if the definition exists
+				let (ns, db) = opt.ns_db()?;
+				if txn.get_db_role(ns, db, &self.name).await.is_ok() {
+					if self.if_not_exists {
+						return Ok(Value::None);
+					} else if !self.overwrite {
+						return Err(Error::RoleDbAlreadyExists {
+							name: self.name.to_string(),
+							ns: ns.into(),
+							db: db.into(),
+						});
+					}
+				}
+				// Process the statement
+				let key = crate::key::database::ro::new(ns, db, &self.name);
+				txn.get_or_add_ns(ns, opt.strict).await?;
+				txn.get_or_add_db(ns, db, opt.strict).await?;
+				txn.set(
+					key,
+					revision::to_vec(&DefineRoleStatement {
+						// Don't persist the `IF NOT EXISTS` clause to schema
+						if_not_exists: false,
+						overwrite: false,
+						..self.clone()
+					})?,
+					None,
+				)
+				.await?;
+				// Clear the cache
+				txn.clear();
+				// Ok all good
+				Ok(Value::None)
+			}
+			// Other levels are not supported
+			_ => Err(Error::InvalidLevel(self.base.to_string())),
+		}
+	}
+}
+
+impl Display for DefineRoleStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE ROLE")?;
+		if self.if_not_exists {
+			write!(f, " IF NOT EXISTS")?
+		}
+		if self.overwrite {
+			write!(f, " OVERWRITE")?
+		}
+		write!(
+			f,
+			" {} ON {} PERMIT {} ON {}",
+			self.name,
+			self.base,
+			self.action.to_string().to_uppercase(),
+			self.resource_name()
+		)?;
+		if let Some(ref v) = self.when {
+			write!(f, " WHEN {v}")?
+		}
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {v}")?
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for DefineRoleStatement {
+	fn structure(self) -> Value {
+		let resource = self.resource_name();
+		Value::from(map! {
+			"name".to_string() => self.name.structure(),
+			"base".to_string() => self.base.structure(),
+			"action".to_string() => self.action.to_string().to_uppercase().into(),
+			"resource".to_string() => resource.into(),
+			"when".to_string(), if let Some(v) = self.when => v.structure(),
+			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
@@ -0,0 +1,109 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{Base, Ident, Value};
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use uuid::Uuid;
+
+/// Defines a sequence, which hands out unique numbers such as invoice ids.
+///
+/// With `BATCH 1` the sequence is gap-free, and its values increase
+/// monotonically across all nodes, as each value is taken within the
+/// transaction which uses it. With a larger `BATCH`, each node reserves
+/// that many values at once, so that the sequence does not become a hot
+/// key, at the cost of skipping any values which are not used.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct DefineSequenceStatement {
+	pub name: Ident,
+	pub if_not_exists: bool,
+	pub overwrite: bool,
+	/// How many values each node reserves at once, or 1 for a gap-free sequence
+	pub batch: u32,
+	/// The first value of the sequence
+	pub start: i64,
+	/// The last time that this sequence was defined
+	pub cache_batches_ts: Uuid,
+}
+
+impl DefineSequenceStatement {
+	/// The number of values reserved at once when no `BATCH` is specified
+	pub const DEFAULT_BATCH: u32 = 1000;
+
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		ctx: &Context,
+		opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Sequence, &Base::Db)?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Check if the definition exists
+		let (ns, db) = opt.ns_db()?;
+		if txn.get_db_sequence(ns, db, &self.name).await.is_ok() {
+			if self.if_not_exists {
+				return Ok(Value::None);
+			} else if !self.overwrite {
+				return Err(Error::SqAlreadyExists {
+					name: self.name.to_string(),
+				});
+			}
+		}
+		// Process the statement
+		let key = crate::key::database::sq::new(ns, db, &self.name);
+		txn.get_or_add_ns(ns, opt.strict).await?;
+		txn.get_or_add_db(ns, db, opt.strict).await?;
+		txn.set(
+			key,
+			revision::to_vec(&DefineSequenceStatement {
+				// Don't persist the `IF NOT EXISTS` clause to schema
+				if_not_exists: false,
+				overwrite: false,
+				// Discard the values already reserved by each node
+				cache_batches_ts: Uuid::now_v7(),
+				..self.clone()
+			})?,
+			None,
+		)
+		.await?;
+		// Clear the cache
+		txn.clear();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for DefineSequenceStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE SEQUENCE")?;
+		if self.if_not_exists {
+			write!(f, " IF NOT EXISTS")?
+		}
+		if self.overwrite {
+			write!(f, " OVERWRITE")?
+		}
+		write!(f, " {} BATCH {} START {}", self.name, self.batch, self.start)?;
+		Ok(())
+	}
+}
+
+impl InfoStructure for DefineSequenceStatement {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name".to_string() => self.name.structure(),
+			"batch".to_string() => Value::from(self.batch),
+			"start".to_string() => Value::from(self.start),
+		})
+	}
+}
@@ -1,16 +1,17 @@
 use super::DefineFieldStatement;
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::{Force, Options};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
-use crate::kvs::Transaction;
+use crate::kvs::{KeyDecode, Transaction};
 use crate::sql::fmt::{is_pretty, pretty_indent};
 use crate::sql::paths::{IN, OUT};
 use crate::sql::statements::info::InfoStructure;
 use crate::sql::{
-	changefeed::ChangeFeed, statements::UpdateStatement, Base, Ident, Output, Permissions, Strand,
-	Value, Values, View,
+	changefeed::ChangeFeed, statements::UpdateStatement, Base, Expiry, Ident, Output, Permissions,
+	Strand, Value, Values, View,
 };
 use crate::sql::{Idiom, Kind, TableType};
 
@@ -22,7 +23,7 @@ use std::fmt::{self, Display, Write};
 use std::sync::Arc;
 use uuid::Uuid;
 
-#[revisioned(revision = 6)]
+#[revisioned(revision = 7)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -57,6 +58,9 @@ pub struct DefineTableStatement {
 	/// The last time that a LIVE query was added to this table
 	#[revision(start = 5, end = 6, convert_fn = "convert_cache_ts")]
 	pub cache_lives_ts: Uuid,
+	/// When records in this table expire
+	#[revision(start = 7)]
+	pub expire: Option<Expiry>,
 }
 
 impl DefineTableStatement {
@@ -74,7 +78,8 @@ impl DefineTableStatement {
 		// Fetch the transaction
 		let txn = ctx.tx();
 		// Check if the definition exists
-		if txn.get_tb(ns, db, &self.name).await.is_ok() {
+		let prev = txn.get_tb(ns, db, &self.name).await.ok();
+		if prev.is_some() {
 			if self.if_not_exists {
 				return Ok(Value::None);
 			} else if !self.overwrite {
@@ -112,6 +117,10 @@ impl DefineTableStatement {
 		if dt.changefeed.is_some() {
 			txn.lock().await.record_table_change(ns, db, &self.name, &dt);
 		}
+		// Update the record expiry times
+		if prev.as_ref().and_then(|tb| tb.expire.as_ref()) != dt.expire.as_ref() {
+			Self::refresh_expiry(&txn, ns, db, &dt).await?;
+		}
 		// Check if table is a view
 		if let Some(view) = &self.view {
 			// Force queries to run
@@ -179,6 +188,46 @@ impl DefineTableStatement {
 	pub fn allows_normal(&self) -> bool {
 		matches!(self.kind, TableType::Normal | TableType::Any)
 	}
+	/// Used to update the expiry times of existing table records
+	/// when the `EXPIRE` clause of the table is added or changed
+	pub async fn refresh_expiry(
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+		tb: &DefineTableStatement,
+	) -> Result<(), Error> {
+		// Remove the existing expiry times
+		let beg = crate::key::table::ex::prefix(ns, db, &tb.name)?;
+		let end = crate::key::table::ex::suffix(ns, db, &tb.name)?;
+		txn.delr(beg..end).await?;
+		let beg = crate::key::table::et::prefix(ns, db, &tb.name)?;
+		let end = crate::key::table::et::suffix(ns, db, &tb.name)?;
+		txn.delr(beg..end).await?;
+		// Set the expiry time of each existing record
+		if let Some(expire) = &tb.expire {
+			let beg = crate::key::thing::prefix(ns, db, &tb.name)?;
+			let end = crate::key::thing::suffix(ns, db, &tb.name)?;
+			let mut next = Some(beg..end);
+			while let Some(rng) = next {
+				let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+				next = batch.next;
+				for (k, v) in batch.result {
+					let val: Value = revision::from_slice(&v)?;
+					if let Some(at) = expire.expires_at(&val) {
+						let id = crate::key::thing::Thing::decode(&k)?.id;
+						let key = crate::key::table::ex::new(ns, db, &tb.name, &id);
+						txn.set(key, revision::to_vec(&at)?, None).await?;
+						let key = crate::key::table::et::new(ns, db, &tb.name, &at, &id);
+						txn.set(key, vec![], None).await?;
+					}
+				}
+				// Pause execution
+				yield_now!();
+			}
+		}
+		// Everything ok
+		Ok(())
+	}
 	/// Used to add relational fields to existing table records
 	pub async fn add_in_out_fields(
 		txn: &Transaction,
@@ -283,6 +332,9 @@ impl Display for DefineTableStatement {
 		if let Some(ref v) = self.changefeed {
 			write!(f, " {v}")?;
 		}
+		if let Some(ref v) = self.expire {
+			write!(f, " {v}")?;
+		}
 		let _indent = if is_pretty() {
 			Some(pretty_indent())
 		} else {
@@ -303,6 +355,7 @@ impl InfoStructure for DefineTableStatement {
 			"kind".to_string() => self.kind.structure(),
 			"view".to_string(), if let Some(v) = self.view => v.structure(),
 			"changefeed".to_string(), if let Some(v) = self.changefeed => v.structure(),
+			"expire".to_string(), if let Some(v) = self.expire => v.structure(),
 			"permissions".to_string() => self.permissions.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
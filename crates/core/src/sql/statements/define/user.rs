@@ -1,23 +1,27 @@
+use crate::cnf::{PASSWORD_HISTORY, PASSWORD_MIN_CHARACTER_CLASSES, PASSWORD_MIN_LENGTH};
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::iam::{Action, ResourceKind};
+use crate::iam::check::resolve_roles;
+use crate::iam::{Action, Level, ResourceKind, Role};
 use crate::sql::statements::info::InfoStructure;
 use crate::sql::{
 	escape::QuoteStr, fmt::Fmt, user::UserDuration, Base, Duration, Ident, Strand, Value,
 };
 use argon2::{
-	password_hash::{PasswordHasher, SaltString},
+	password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
 	Argon2,
 };
 
 use rand::{distributions::Alphanumeric, rngs::OsRng, Rng};
-use revision::revisioned;
+use revision::{revisioned, Revisioned};
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -34,6 +38,13 @@ pub struct DefineUserStatement {
 	pub if_not_exists: bool,
 	#[revision(start = 4)]
 	pub overwrite: bool,
+	/// The hashes of the previously used passwords, most recent first
+	#[revision(start = 5)]
+	pub history: Vec<String>,
+	/// The plaintext password, which is only kept until the statement is computed
+	#[revision(start = 5)]
+	#[serde(skip)]
+	pub(crate) password: Plaintext,
 }
 
 impl From<(Base, &str, &str, &str)> for DefineUserStatement {
@@ -55,6 +66,7 @@ impl From<(Base, &str, &str, &str)> for DefineUserStatement {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
+			..Default::default()
 		}
 	}
 }
@@ -84,11 +96,14 @@ impl DefineUserStatement {
 		self.hash = Argon2::default()
 			.hash_password(password.as_bytes(), &SaltString::generate(&mut OsRng))
 			.unwrap()
-			.to_string()
+			.to_string();
+		// Keep the password, so that the password policy can be checked
+		self.password = Plaintext(Some(password.to_owned()));
 	}
 
 	pub(crate) fn set_passhash(&mut self, passhash: String) {
 		self.hash = passhash;
+		self.password = Plaintext::default();
 	}
 
 	pub(crate) fn set_token_duration(&mut self, duration: Option<Duration>) {
@@ -99,6 +114,36 @@ impl DefineUserStatement {
 		self.duration.session = duration;
 	}
 
+	/// Computes the password history to store when redefining an existing user.
+	///
+	/// Returns an error if the new password matches one of the recently used passwords.
+	fn history(&self, user: Option<&DefineUserStatement>) -> Result<Vec<String>, Error> {
+		// Check if password history is enabled
+		let limit = *PASSWORD_HISTORY;
+		if limit == 0 {
+			return Ok(Vec::new());
+		}
+		// A new user has no previous passwords
+		let Some(user) = user else {
+			return Ok(Vec::new());
+		};
+		// The recently used passwords, including the current password
+		let recent = || std::iter::once(&user.hash).chain(user.history.iter()).take(limit);
+		// Ensure that the new password has not been used recently
+		if let Some(password) = self.password.0.as_deref() {
+			if recent().any(|hash| verify_hash(password, hash)) {
+				return Err(Error::PasswordPolicy(format!(
+					"the password can not be one of the last {limit} passwords"
+				)));
+			}
+		}
+		// Keep the previous passwords if the password is unchanged
+		if self.hash == user.hash {
+			return Ok(user.history.iter().take(limit - 1).cloned().collect());
+		}
+		Ok(recent().take(limit - 1).cloned().collect())
+	}
+
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(
 		&self,
@@ -108,13 +153,25 @@ impl DefineUserStatement {
 	) -> Result<Value, Error> {
 		// Allowed to run?
 		opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+		// Ensure that the password satisfies the password policy
+		if let Some(password) = self.password.0.as_deref() {
+			check_password_policy(password)?;
+		}
+		// Ensure that any custom roles are defined
+		let level = match self.base {
+			Base::Ns => Level::from((opt.ns()?,)),
+			Base::Db => Level::from(opt.ns_db()?),
+			_ => Level::Root,
+		};
+		resolve_roles(&ctx.tx(), &self.roles, &level).await?;
 		// Check the statement type
 		match self.base {
 			Base::Root => {
 				// Fetch the transaction
 				let txn = ctx.tx();
 				// Check if the definition exists
-				if txn.get_root_user(&self.name).await.is_ok() {
+				let user = txn.get_root_user(&self.name).await.ok();
+				if user.is_some() {
 					if self.if_not_exists {
 						return Ok(Value::None);
 					} else if !self.overwrite {
@@ -128,6 +185,10 @@ impl DefineUserStatement {
 				txn.set(
 					key,
 					revision::to_vec(&DefineUserStatement {
+						// Keep track of previously used passwords
+						history: self.history(user.as_deref())?,
+						// Don't persist the plaintext password
+						password: Plaintext::default(),
 						// Don't persist the `IF NOT EXISTS` clause to schema
 						if_not_exists: false,
 						overwrite: false,
@@ -145,7 +206,8 @@ impl DefineUserStatement {
 				// Fetch the transaction
 				let txn = ctx.tx();
 				// Check if the definition exists
-				if txn.get_ns_user(opt.ns()?, &self.name).await.is_ok() {
+				let user = txn.get_ns_user(opt.ns()?, &self.name).await.ok();
+				if user.is_some() {
 					if self.if_not_exists {
 						return Ok(Value::None);
 					} else if !self.overwrite {
@@ -161,6 +223,10 @@ impl DefineUserStatement {
 				txn.set(
 					key,
 					revision::to_vec(&DefineUserStatement {
+						// Keep track of previously used passwords
+						history: self.history(user.as_deref())?,
+						// Don't persist the plaintext password
+						password: Plaintext::default(),
 						// Don't persist the `IF NOT EXISTS` clause to schema
 						if_not_exists: false,
 						overwrite: false,
@@ -179,7 +245,8 @@ impl DefineUserStatement {
 				let txn = ctx.tx();
 				// Check if the definition exists
 				let (ns, db) = opt.ns_db()?;
-				if txn.get_db_user(ns, db, &self.name).await.is_ok() {
+				let user = txn.get_db_user(ns, db, &self.name).await.ok();
+				if user.is_some() {
 					if self.if_not_exists {
 						return Ok(Value::None);
 					} else if !self.overwrite {
@@ -197,6 +264,10 @@ impl DefineUserStatement {
 				txn.set(
 					key,
 					revision::to_vec(&DefineUserStatement {
+						// Keep track of previously used passwords
+						history: self.history(user.as_deref())?,
+						// Don't persist the plaintext password
+						password: Plaintext::default(),
 						// Don't persist the `IF NOT EXISTS` clause to schema
 						if_not_exists: false,
 						overwrite: false,
@@ -216,6 +287,86 @@ impl DefineUserStatement {
 	}
 }
 
+/// Ensures that a plaintext password satisfies the configured password policy
+fn check_password_policy(password: &str) -> Result<(), Error> {
+	// Check the minimum password length
+	let length = *PASSWORD_MIN_LENGTH;
+	if password.chars().count() < length {
+		return Err(Error::PasswordPolicy(format!(
+			"the password must be at least {length} characters long"
+		)));
+	}
+	// Check the number of character classes
+	let classes = [
+		password.chars().any(|c| c.is_lowercase()),
+		password.chars().any(|c| c.is_uppercase()),
+		password.chars().any(|c| c.is_numeric()),
+		password.chars().any(|c| !c.is_alphanumeric()),
+	];
+	let count = *PASSWORD_MIN_CHARACTER_CLASSES;
+	if classes.iter().filter(|v| **v).count() < count {
+		return Err(Error::PasswordPolicy(format!(
+			"the password must contain at least {count} of lowercase letters, uppercase letters, digits, and symbols"
+		)));
+	}
+	Ok(())
+}
+
+/// Checks if a plaintext password matches a password hash
+fn verify_hash(password: &str, hash: &str) -> bool {
+	PasswordHash::new(hash)
+		.is_ok_and(|hash| Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())
+}
+
+/// A plaintext password, which is never displayed, compared, or serialized
+#[derive(Clone, Default)]
+pub(crate) struct Plaintext(Option<String>);
+
+impl fmt::Debug for Plaintext {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("Plaintext(..)")
+	}
+}
+
+impl PartialEq for Plaintext {
+	fn eq(&self, _: &Self) -> bool {
+		true
+	}
+}
+
+impl Eq for Plaintext {}
+
+impl PartialOrd for Plaintext {
+	fn partial_cmp(&self, _: &Self) -> Option<std::cmp::Ordering> {
+		Some(std::cmp::Ordering::Equal)
+	}
+}
+
+impl Hash for Plaintext {
+	fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+impl Revisioned for Plaintext {
+	fn revision() -> u16 {
+		1
+	}
+
+	fn serialize_revisioned<W: std::io::Write>(&self, _: &mut W) -> Result<(), revision::Error> {
+		Ok(())
+	}
+
+	fn deserialize_revisioned<R: std::io::Read>(_: &mut R) -> Result<Self, revision::Error> {
+		Ok(Self::default())
+	}
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Plaintext {
+	fn arbitrary(_: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+		Ok(Self::default())
+	}
+}
+
 impl Display for DefineUserStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "DEFINE USER")?;
@@ -232,7 +383,15 @@ impl Display for DefineUserStatement {
 			self.base,
 			QuoteStr(&self.hash),
 			Fmt::comma_separated(
-				&self.roles.iter().map(|r| r.to_string().to_uppercase()).collect::<Vec<String>>()
+				&self
+					.roles
+					.iter()
+					.map(|r| match Role::from_str(r) {
+						// Built-in roles are displayed in uppercase
+						Ok(_) => r.to_string().to_uppercase(),
+						Err(_) => r.to_string(),
+					})
+					.collect::<Vec<String>>()
 			),
 		)?;
 		// Always print relevant durations so defaults can be changed in the future
@@ -95,7 +95,9 @@ impl ForeachStatement {
 					Entry::Break(v) => v.compute(&ctx, opt, doc).await,
 					Entry::Continue(v) => v.compute(&ctx, opt, doc).await,
 					Entry::Foreach(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+					Entry::While(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
 					Entry::Ifelse(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+					Entry::Trycatch(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
 					Entry::Select(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
 					Entry::Create(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
 					Entry::Upsert(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
@@ -19,15 +19,21 @@ pub(crate) mod option;
 pub(crate) mod output;
 pub(crate) mod rebuild;
 pub(crate) mod relate;
+pub(crate) mod release;
 pub(crate) mod remove;
+pub(crate) mod rollback;
+pub(crate) mod savepoint;
 pub(crate) mod select;
 pub(crate) mod set;
 pub(crate) mod show;
 pub(crate) mod sleep;
 pub(crate) mod throw;
+pub(crate) mod truncate;
+pub(crate) mod trycatch;
 pub(crate) mod update;
 pub(crate) mod upsert;
 pub(crate) mod r#use;
+pub(crate) mod r#while;
 
 pub use self::access::{AccessGrant, AccessStatement};
 pub use self::analyze::AnalyzeStatement;
@@ -47,29 +53,38 @@ pub use self::output::OutputStatement;
 pub use self::r#break::BreakStatement;
 pub use self::r#continue::ContinueStatement;
 pub use self::r#use::UseStatement;
+pub use self::r#while::WhileStatement;
 pub use self::relate::RelateStatement;
-pub use self::select::SelectStatement;
+pub use self::release::ReleaseStatement;
+pub use self::rollback::RollbackStatement;
+pub use self::savepoint::SavepointStatement;
+pub use self::select::{SelectStatement, SetOperation, SetOperator};
 pub use self::set::SetStatement;
 pub use self::show::ShowStatement;
 pub use self::sleep::SleepStatement;
 pub use self::throw::ThrowStatement;
+pub use self::truncate::TruncateStatement;
+pub use self::trycatch::TrycatchStatement;
 pub use self::update::UpdateStatement;
 pub use self::upsert::UpsertStatement;
 
-pub use self::alter::{AlterStatement, AlterTableStatement};
+pub use self::alter::{
+	AlterAccessStatement, AlterEventStatement, AlterFieldStatement, AlterIndexStatement,
+	AlterStatement, AlterTableStatement, AlterUserStatement,
+};
 
 pub use self::define::{
 	DefineAccessStatement, DefineAnalyzerStatement, DefineApiStatement, DefineDatabaseStatement,
 	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
-	DefineModelStatement, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
-	DefineTableStatement, DefineUserStatement,
+	DefineModelStatement, DefineNamespaceStatement, DefineParamStatement, DefineRoleStatement,
+	DefineSequenceStatement, DefineStatement, DefineTableStatement, DefineUserStatement,
 };
 
 pub use self::remove::{
 	RemoveAccessStatement, RemoveAnalyzerStatement, RemoveDatabaseStatement, RemoveEventStatement,
 	RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement, RemoveModelStatement,
-	RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement, RemoveTableStatement,
-	RemoveUserStatement,
+	RemoveNamespaceStatement, RemoveParamStatement, RemoveRoleStatement, RemoveSequenceStatement,
+	RemoveStatement, RemoveTableStatement, RemoveUserStatement,
 };
 
 pub use self::define::FindApi;
@@ -0,0 +1,19 @@
+use crate::sql::Ident;
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct ReleaseStatement {
+	pub name: Ident,
+}
+
+impl fmt::Display for ReleaseStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "RELEASE SAVEPOINT {}", self.name)
+	}
+}
@@ -8,6 +8,8 @@ mod index;
 mod model;
 mod namespace;
 mod param;
+mod role;
+mod sequence;
 mod table;
 mod user;
 
@@ -21,6 +23,8 @@ pub use index::RemoveIndexStatement;
 pub use model::RemoveModelStatement;
 pub use namespace::RemoveNamespaceStatement;
 pub use param::RemoveParamStatement;
+pub use role::RemoveRoleStatement;
+pub use sequence::RemoveSequenceStatement;
 pub use table::RemoveTableStatement;
 pub use user::RemoveUserStatement;
 
@@ -34,7 +38,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -51,6 +55,10 @@ pub enum RemoveStatement {
 	Index(RemoveIndexStatement),
 	User(RemoveUserStatement),
 	Model(RemoveModelStatement),
+	#[revision(start = 2)]
+	Sequence(RemoveSequenceStatement),
+	#[revision(start = 3)]
+	Role(RemoveRoleStatement),
 }
 
 impl RemoveStatement {
@@ -78,6 +86,8 @@ impl RemoveStatement {
 			Self::Analyzer(ref v) => v.compute(ctx, opt).await,
 			Self::User(ref v) => v.compute(ctx, opt).await,
 			Self::Model(ref v) => v.compute(ctx, opt).await,
+			Self::Sequence(ref v) => v.compute(ctx, opt).await,
+			Self::Role(ref v) => v.compute(ctx, opt).await,
 		}
 	}
 }
@@ -97,6 +107,8 @@ impl Display for RemoveStatement {
 			Self::Analyzer(v) => Display::fmt(v, f),
 			Self::User(v) => Display::fmt(v, f),
 			Self::Model(v) => Display::fmt(v, f),
+			Self::Sequence(v) => Display::fmt(v, f),
+			Self::Role(v) => Display::fmt(v, f),
 		}
 	}
 }
@@ -0,0 +1,104 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::statements::DefineUserStatement;
+use crate::sql::{Base, Ident, Value};
+
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct RemoveRoleStatement {
+	pub name: Ident,
+	pub base: Base,
+	pub if_exists: bool,
+}
+
+impl RemoveRoleStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(&self, ctx: &Context, opt: &Options) -> Result<Value, Error> {
+		let future = async {
+			// Allowed to run?
+			opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+			// Check the statement type
+			match self.base {
+				Base::Ns => {
+					// Get the transaction
+					let txn = ctx.tx();
+					// Get the definition
+					let ro = txn.get_ns_role(opt.ns()?, &self.name).await?;
+					// Check the role is not assigned to any user
+					self.check_users(&txn.all_ns_users(opt.ns()?).await?)?;
+					for db in txn.all_db(opt.ns()?).await?.iter() {
+						self.check_users(&txn.all_db_users(opt.ns()?, &db.name).await?)?;
+					}
+					// Delete the definition
+					let key = crate::key::namespace::ro::new(opt.ns()?, &ro.name);
+					txn.del(key).await?;
+					// Clear the cache
+					txn.clear();
+					// Ok all good
+					Ok(Value::None)
+				}
+				Base::Db => {
+					// Get the transaction
+					let txn = ctx.tx();
+					// Get the definition
+					let (ns, db) = opt.ns_db()?;
+					let ro = txn.get_db_role(ns, db, &self.name).await?;
+					// Check the role is not assigned to any user
+					self.check_users(&txn.all_db_users(ns, db).await?)?;
+					// Delete the definition
+					let key = crate::key::database::ro::new(ns, db, &ro.name);
+					txn.del(key).await?;
+					// Clear the cache
+					txn.clear();
+					// Ok all good
+					Ok(Value::None)
+				}
+				_ => Err(Error::InvalidLevel(self.base.to_string())),
+			}
+		}
+		.await;
+		match future {
+			Err(e) if self.if_exists => match e {
+				Error::RoleNsNotFound {
+					..
+				} => Ok(Value::None),
+				Error::RoleDbNotFound {
+					..
+				} => Ok(Value::None),
+				e => Err(e),
+			},
+			v => v,
+		}
+	}
+
+	/// Ensures that none of the users are assigned this role, as
+	/// those users would otherwise no longer be able to sign in.
+	fn check_users(&self, users: &[DefineUserStatement]) -> Result<(), Error> {
+		match users.iter().find(|u| u.roles.contains(&self.name)) {
+			Some(user) => Err(Error::RoleInUse {
+				name: self.name.to_string(),
+				user: user.name.to_string(),
+			}),
+			None => Ok(()),
+		}
+	}
+}
+
+impl Display for RemoveRoleStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "REMOVE ROLE")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {}", self.name, self.base)?;
+		Ok(())
+	}
+}
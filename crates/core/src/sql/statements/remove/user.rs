@@ -35,6 +35,9 @@ impl RemoveUserStatement {
 					// Process the statement
 					let key = crate::key::root::us::new(&us.name);
 					txn.del(key).await?;
+					// Delete any failed authentication state
+					let key = crate::key::root::lo::new(&us.name);
+					txn.del(key).await?;
 					// Clear the cache
 					txn.clear();
 					// Ok all good
@@ -48,6 +51,9 @@ impl RemoveUserStatement {
 					// Delete the definition
 					let key = crate::key::namespace::us::new(opt.ns()?, &us.name);
 					txn.del(key).await?;
+					// Delete any failed authentication state
+					let key = crate::key::namespace::lo::new(opt.ns()?, &us.name);
+					txn.del(key).await?;
 					// Clear the cache
 					txn.clear();
 					// Ok all good
@@ -62,6 +68,9 @@ impl RemoveUserStatement {
 					// Delete the definition
 					let key = crate::key::database::us::new(ns, db, &us.name);
 					txn.del(key).await?;
+					// Delete any failed authentication state
+					let key = crate::key::database::lo::new(ns, db, &us.name);
+					txn.del(key).await?;
 					// Clear the cache
 					txn.clear();
 					// Ok all good
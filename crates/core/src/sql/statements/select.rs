@@ -1,5 +1,5 @@
 use crate::ctx::Context;
-use crate::dbs::{Iterator, Options, Statement};
+use crate::dbs::{Iterator, Options, SetResults, Statement};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::idx::planner::{QueryPlanner, RecordStrategy, StatementContext};
@@ -15,7 +15,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::sync::Arc;
 
-#[revisioned(revision = 4)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -44,6 +44,49 @@ pub struct SelectStatement {
 	pub explain: Option<Explain>,
 	#[revision(start = 3)]
 	pub tempfiles: bool,
+	/// Whether duplicate values are removed from the output.
+	#[revision(start = 5)]
+	pub distinct: bool,
+	/// The UNION, INTERSECT and EXCEPT parts, applied from left to right.
+	#[revision(start = 5)]
+	pub combine: Vec<SetOperation>,
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum SetOperator {
+	Union,
+	UnionAll,
+	Intersect,
+	Except,
+}
+
+impl fmt::Display for SetOperator {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Union => f.write_str("UNION"),
+			Self::UnionAll => f.write_str("UNION ALL"),
+			Self::Intersect => f.write_str("INTERSECT"),
+			Self::Except => f.write_str("EXCEPT"),
+		}
+	}
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct SetOperation {
+	pub operator: SetOperator,
+	pub select: SelectStatement,
+}
+
+impl fmt::Display for SetOperation {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} {}", self.operator, self.select)
+	}
 }
 
 impl SelectStatement {
@@ -85,12 +128,19 @@ impl SelectStatement {
 				expr,
 				..
 			} => expr.writeable(),
+			Field::Window {
+				expr,
+				..
+			} => expr.writeable(),
 		}) {
 			return true;
 		}
 		if self.what.iter().any(|v| v.writeable()) {
 			return true;
 		}
+		if self.combine.iter().any(|v| v.select.writeable()) {
+			return true;
+		}
 		self.cond.as_deref().is_some_and(Value::writeable)
 	}
 
@@ -102,6 +152,104 @@ impl SelectStatement {
 		opt: &Options,
 		doc: Option<&CursorDoc>,
 	) -> Result<Value, Error> {
+		// Without set operators, the statement is processed as is
+		if self.combine.is_empty() {
+			let res = self.iterate(stk, ctx, opt, doc).await?.take().await?;
+			return self.output(res.into());
+		}
+		// The TIMEOUT clause applies to the combined statement
+		let stm = Statement::from(self);
+		let ctx = stm.setup_timeout(ctx)?;
+		// The ORDER BY, LIMIT, START and FETCH clauses apply to the combined results
+		let mut res = self.operand().iterate(stk, &ctx, opt, doc).await?;
+		// Combine the results with any other statements
+		for v in self.combine.iter() {
+			// The PARALLEL and TEMPFILES clauses apply to every operand
+			let select = SelectStatement {
+				parallel: self.parallel,
+				tempfiles: self.tempfiles,
+				..v.select.clone()
+			};
+			let other = stk.run(|stk| select.iterate(stk, &ctx, opt, doc)).await?;
+			res = res
+				.combine(
+					#[cfg(storage)]
+					&ctx,
+					#[cfg(storage)]
+					self.tempfiles,
+					v.operator,
+					other,
+				)
+				.await?;
+		}
+		// Order and page the combined results
+		let start = match &self.start {
+			Some(v) => Some(v.process(stk, &ctx, opt, doc).await?),
+			None => None,
+		};
+		let limit = match &self.limit {
+			Some(v) => Some(v.process(stk, &ctx, opt, doc).await?),
+			None => None,
+		};
+		let values = res.output(stk, &ctx, opt, &stm, start, limit).await?;
+		// Catch statement timeout
+		if ctx.is_timedout() {
+			return Err(Error::QueryTimedout);
+		}
+		self.output(values.into())
+	}
+
+	/// Outputs the results, checking any ONLY clause
+	fn output(&self, res: Value) -> Result<Value, Error> {
+		match res {
+			// This is a single record result
+			Value::Array(mut a) if self.only => match a.len() {
+				// There were no results
+				0 => Ok(Value::None),
+				// There was exactly one result
+				1 => Ok(a.remove(0)),
+				// There were no results
+				_ => Err(Error::SingleOnlyOutput),
+			},
+			// This is standard query result
+			v => Ok(v),
+		}
+	}
+
+	/// The first SELECT of this statement, without the set operators and the
+	/// clauses which apply to the combined results
+	fn operand(&self) -> SelectStatement {
+		SelectStatement {
+			expr: self.expr.clone(),
+			omit: self.omit.clone(),
+			only: self.only,
+			what: self.what.clone(),
+			with: self.with.clone(),
+			cond: self.cond.clone(),
+			split: self.split.clone(),
+			group: self.group.clone(),
+			order: None,
+			limit: None,
+			start: None,
+			fetch: None,
+			version: self.version.clone(),
+			timeout: None,
+			parallel: self.parallel,
+			tempfiles: self.tempfiles,
+			explain: None,
+			distinct: self.distinct,
+			combine: Vec::new(),
+		}
+	}
+
+	/// Process the SELECT part of this statement, without any set operators
+	async fn iterate(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<SetResults, Error> {
 		// Valid options?
 		opt.valid_for_db()?;
 		// Assign the statement
@@ -133,31 +281,23 @@ impl SelectStatement {
 		// Attach the query planner to the context
 		let ctx = stm.setup_query_planner(planner, ctx);
 		// Process the statement
-		let res = i.output(stk, &ctx, &opt, &stm, RecordStrategy::KeysAndValues).await?;
+		let res = i.output_set(stk, &ctx, &opt, &stm, RecordStrategy::KeysAndValues).await?;
 		// Catch statement timeout
 		if ctx.is_timedout() {
 			return Err(Error::QueryTimedout);
 		}
 		// Output the results
-		match res {
-			// This is a single record result
-			Value::Array(mut a) if self.only => match a.len() {
-				// There were no results
-				0 => Ok(Value::None),
-				// There was exactly one result
-				1 => Ok(a.remove(0)),
-				// There were no results
-				_ => Err(Error::SingleOnlyOutput),
-			},
-			// This is standard query result
-			v => Ok(v),
-		}
+		Ok(res)
 	}
 }
 
 impl fmt::Display for SelectStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "SELECT {}", self.expr)?;
+		f.write_str("SELECT")?;
+		if self.distinct {
+			f.write_str(" DISTINCT")?
+		}
+		write!(f, " {}", self.expr)?;
 		if let Some(ref v) = self.omit {
 			write!(f, " OMIT {v}")?
 		}
@@ -178,6 +318,15 @@ impl fmt::Display for SelectStatement {
 		if let Some(ref v) = self.group {
 			write!(f, " {v}")?
 		}
+		// The trailing clauses of a combined statement follow the set operators
+		if !self.combine.is_empty() {
+			if let Some(ref v) = self.version {
+				write!(f, " {v}")?
+			}
+			for v in self.combine.iter() {
+				write!(f, " {v}")?
+			}
+		}
 		if let Some(ref v) = self.order {
 			write!(f, " {v}")?
 		}
@@ -190,8 +339,10 @@ impl fmt::Display for SelectStatement {
 		if let Some(ref v) = self.fetch {
 			write!(f, " {v}")?
 		}
-		if let Some(ref v) = self.version {
-			write!(f, " {v}")?
+		if self.combine.is_empty() {
+			if let Some(ref v) = self.version {
+				write!(f, " {v}")?
+			}
 		}
 		if let Some(ref v) = self.timeout {
 			write!(f, " {v}")?
@@ -199,6 +350,9 @@ impl fmt::Display for SelectStatement {
 		if self.parallel {
 			f.write_str(" PARALLEL")?
 		}
+		if self.tempfiles {
+			f.write_str(" TEMPFILES")?
+		}
 		if let Some(ref v) = self.explain {
 			write!(f, " {v}")?
 		}
@@ -0,0 +1,207 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::ctx::{Context, MutableContext};
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::key::thing;
+use crate::kvs::KeyDecode;
+use crate::sql::{Base, Ident, Thing, Value};
+
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct TruncateStatement {
+	pub name: Ident,
+	pub if_exists: bool,
+	/// Whether the table events are skipped for the removed records
+	pub skip_events: bool,
+}
+
+impl TruncateStatement {
+	/// Process this type returning a computed simple Value.
+	///
+	/// The records, along with their expiry times and index entries, are
+	/// removed with range deletes, while the table, field, index and event
+	/// definitions are kept. Tables with foreign tables, live queries,
+	/// references or graph edges are rejected, as the removed records would
+	/// otherwise have to be processed one at a time.
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		_doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Db)?;
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the defined table
+		let tb = match txn.get_tb(ns, db, &self.name).await {
+			Ok(tb) => tb,
+			Err(Error::TbNotFound {
+				..
+			}) if self.if_exists => return Ok(Value::None),
+			Err(v) => return Err(v),
+		};
+		// Check the records are not used elsewhere
+		self.check_dependents(ctx, opt).await?;
+		// Process the table events for each record
+		if !self.skip_events && !opt.import {
+			self.process_events(stk, ctx, opt).await?;
+		}
+		// Remove the index stores
+		#[cfg(not(target_family = "wasm"))]
+		ctx.get_index_stores()
+			.table_removed(ctx.get_index_builder(), &txn, ns, db, &self.name)
+			.await?;
+		#[cfg(target_family = "wasm")]
+		ctx.get_index_stores().table_removed(&txn, ns, db, &self.name).await?;
+		// Remove the records
+		let beg = thing::prefix(ns, db, &self.name)?;
+		let end = thing::suffix(ns, db, &self.name)?;
+		txn.delr(beg..end).await?;
+		// Remove the record expiry times
+		let beg = crate::key::table::ex::prefix(ns, db, &self.name)?;
+		let end = crate::key::table::ex::suffix(ns, db, &self.name)?;
+		txn.delr(beg..end).await?;
+		let beg = crate::key::table::et::prefix(ns, db, &self.name)?;
+		let end = crate::key::table::et::suffix(ns, db, &self.name)?;
+		txn.delr(beg..end).await?;
+		// Remove the index data
+		let beg = crate::key::index::all::tbprefix(ns, db, &self.name)?;
+		let end = crate::key::index::all::tbsuffix(ns, db, &self.name)?;
+		txn.delr(beg..end).await?;
+		// Record the truncation in the change feed
+		let dbv = txn.get_db(ns, db).await?;
+		if dbv.changefeed.is_some() || tb.changefeed.is_some() {
+			txn.lock().await.record_table_truncate(ns, db, &self.name);
+		}
+		// Clear the cache
+		if let Some(cache) = ctx.get_cache() {
+			cache.clear_tb(ns, db, &self.name);
+		}
+		// Clear the cache
+		txn.clear();
+		// Ok all good
+		Ok(Value::None)
+	}
+
+	/// Checks that no other data depends on the records of the table
+	async fn check_dependents(&self, ctx: &Context, opt: &Options) -> Result<(), Error> {
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// The error for the reason the table can not be truncated
+		let fail = |reason: &str| Error::TbNotTruncatable {
+			name: self.name.to_string(),
+			reason: reason.to_owned(),
+		};
+		// Foreign tables are computed from the records
+		if !txn.all_tb_views(ns, db, &self.name).await?.is_empty() {
+			return Err(fail("foreign tables"));
+		}
+		// Live queries are notified of removed records
+		if !txn.all_tb_lives(ns, db, &self.name).await?.is_empty() {
+			return Err(fail("live queries"));
+		}
+		// References to other records are stored with the referenced record
+		let fds = txn.all_tb_fields(ns, db, &self.name, None).await?;
+		if fds.iter().any(|fd| fd.reference.is_some()) {
+			return Err(fail("reference fields"));
+		}
+		// References from other records
+		let beg = crate::key::r#ref::tbprefix(ns, db, &self.name)?;
+		let end = crate::key::r#ref::tbsuffix(ns, db, &self.name)?;
+		if !txn.keys(beg..end, 1, None).await?.is_empty() {
+			return Err(fail("records which are referenced"));
+		}
+		// Graph edges are stored with the records on both sides of the edge
+		let beg = crate::key::graph::tbprefix(ns, db, &self.name)?;
+		let end = crate::key::graph::tbsuffix(ns, db, &self.name)?;
+		if !txn.keys(beg..end, 1, None).await?.is_empty() {
+			return Err(fail("graph edges"));
+		}
+		Ok(())
+	}
+
+	/// Runs the table events for each record, as though it were deleted
+	async fn process_events(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+	) -> Result<(), Error> {
+		// Get the NS and DB
+		let (ns, db) = opt.ns_db()?;
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the table events
+		let evs = txn.all_tb_events(ns, db, &self.name).await?;
+		if evs.is_empty() {
+			return Ok(());
+		}
+		// Don't run permissions
+		let opt = &opt.new_with_perms(false);
+		// Loop over the records
+		let beg = thing::prefix(ns, db, &self.name)?;
+		let end = thing::suffix(ns, db, &self.name)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = txn.batch_keys_vals(rng, *NORMAL_FETCH_SIZE, None).await?;
+			next = batch.next;
+			for (k, v) in batch.result {
+				let key = thing::Thing::decode(&k)?;
+				let rid = Arc::new(Thing::from((key.tb, key.id)));
+				let val: Arc<Value> = Arc::new(revision::from_slice(&v)?);
+				let doc = CursorDoc::new(Some(rid), None, val.clone());
+				for ev in evs.iter() {
+					// Configure the context
+					let mut ctx = MutableContext::new(ctx);
+					ctx.add_value("event", Value::from("DELETE").into());
+					ctx.add_value("value", val.clone());
+					ctx.add_value("after", Value::None.into());
+					ctx.add_value("before", val.clone());
+					// Freeze the context
+					let ctx = ctx.freeze();
+					// Process conditional clause
+					let res = ev.when.compute(stk, &ctx, opt, Some(&doc)).await?;
+					// Execute event if value is truthy
+					if res.is_truthy() {
+						for v in ev.then.iter() {
+							v.compute(stk, &ctx, opt, Some(&doc)).await?;
+						}
+					}
+				}
+			}
+			// Pause execution
+			yield_now!();
+		}
+		Ok(())
+	}
+}
+
+impl Display for TruncateStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "TRUNCATE TABLE")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {}", self.name)?;
+		if self.skip_events {
+			write!(f, " WITH NOEVENTS")?
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,101 @@
+use crate::ctx::{Context, MutableContext};
+use crate::dbs::{NotificationSavePoint, Options};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::{Block, Param, Value};
+
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct TrycatchStatement {
+	/// The block which is attempted
+	pub block: Block,
+	/// The parameter which any caught error is assigned to
+	pub param: Option<Param>,
+	/// The block which is run if an error is caught
+	pub catch: Block,
+}
+
+impl TrycatchStatement {
+	/// Check if we require a writeable transaction
+	pub(crate) fn writeable(&self) -> bool {
+		self.block.writeable() || self.catch.writeable()
+	}
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		// Create a save point so we can roll back any changes made in the block
+		ctx.tx().lock().await.new_save_point().await;
+		// Buffer any notifications, so they are only sent if the block succeeds
+		let buffer = opt.sender.clone().map(NotificationSavePoint::new);
+		let block_opt;
+		let block_opt = match &buffer {
+			Some(buffer) => {
+				block_opt = opt.new_with_sender(buffer.sender());
+				&block_opt
+			}
+			None => opt,
+		};
+		// Process the block
+		match stk.run(|stk| self.block.compute(stk, ctx, block_opt, doc)).await {
+			Err(e) if e.is_catchable() => {
+				// We roll back any change following the save point
+				ctx.tx().lock().await.rollback_to_save_point().await?;
+				if let Some(buffer) = buffer {
+					buffer.rollback();
+				}
+				// Clear the cache, as cached entries may have been rolled back
+				ctx.tx().clear();
+				// Expose the caught error to the catch block
+				let mut ctx = MutableContext::new(ctx);
+				if let Some(param) = &self.param {
+					ctx.add_value(param.0.to_raw(), Self::error(e).into());
+				}
+				let ctx = ctx.freeze();
+				// Process the catch block
+				stk.run(|stk| self.catch.compute(stk, &ctx, opt, doc)).await
+			}
+			res => {
+				// The block did not fail, so we can release the save point
+				ctx.tx().lock().await.release_last_save_point().await?;
+				if let Some(buffer) = buffer {
+					buffer.release();
+				}
+				res
+			}
+		}
+	}
+	/// Converts a caught error into a structured object
+	fn error(e: Error) -> Value {
+		let kind = e.kind();
+		let message = match e {
+			Error::Thrown(message) => message,
+			e => e.to_string(),
+		};
+		Value::from(map! {
+			"kind".to_string() => Value::from(kind),
+			"message".to_string() => Value::from(message),
+		})
+	}
+}
+
+impl Display for TrycatchStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "TRY {} CATCH", self.block)?;
+		if let Some(ref param) = self.param {
+			write!(f, " ({param})")?
+		}
+		write!(f, " {}", self.catch)
+	}
+}
@@ -0,0 +1,116 @@
+use crate::cnf::MAX_LOOP_ITERATIONS;
+use crate::ctx::{Context, MutableContext};
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::{block::Entry, Block, Value};
+
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct WhileStatement {
+	/// The condition which is checked before each iteration
+	pub cond: Value,
+	/// The block which is run while the condition is truthy. Any
+	/// parameters which it sets are kept for the next iteration.
+	pub block: Block,
+}
+
+impl WhileStatement {
+	/// Check if we require a writeable transaction
+	pub(crate) fn writeable(&self) -> bool {
+		self.cond.writeable() || self.block.writeable()
+	}
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value, Error> {
+		let mut iterations = 0;
+		// Duplicate context, which is kept between
+		// iterations so that the block can change the
+		// parameters which the condition depends on
+		let mut ctx = MutableContext::new(ctx).freeze();
+		// Loop while the condition holds
+		'main: loop {
+			// Check if the query has been cancelled or timed out
+			if let Some(reason) = ctx.done(true) {
+				return Err(reason.into());
+			}
+			// Check the loop condition
+			if !stk.run(|stk| self.cond.compute(stk, &ctx, opt, doc)).await?.is_truthy() {
+				break;
+			}
+			// Check the iteration limit
+			iterations += 1;
+			if iterations > *MAX_LOOP_ITERATIONS {
+				return Err(Error::LoopIterationsExceeded {
+					limit: *MAX_LOOP_ITERATIONS,
+				});
+			}
+			// Loop over the code block statements
+			for v in self.block.iter() {
+				// Compute each block entry
+				let res = match v {
+					Entry::Set(v) => {
+						let val = stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await?;
+						let mut c = MutableContext::unfreeze(ctx)?;
+						c.add_value(v.name.to_owned(), val.into());
+						ctx = c.freeze();
+						Ok(Value::None)
+					}
+					Entry::Value(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+					Entry::Break(v) => v.compute(&ctx, opt, doc).await,
+					Entry::Continue(v) => v.compute(&ctx, opt, doc).await,
+					Entry::Foreach(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+					Entry::While(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+					Entry::Ifelse(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+					Entry::Trycatch(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+					Entry::Select(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+					Entry::Create(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+					Entry::Upsert(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+					Entry::Update(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+					Entry::Delete(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+					Entry::Relate(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+					Entry::Insert(v) => stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await,
+					Entry::Define(v) => v.compute(stk, &ctx, opt, doc).await,
+					Entry::Alter(v) => v.compute(stk, &ctx, opt, doc).await,
+					Entry::Rebuild(v) => v.compute(stk, &ctx, opt, doc).await,
+					Entry::Remove(v) => v.compute(&ctx, opt, doc).await,
+					Entry::Output(v) => {
+						return stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await;
+					}
+					Entry::Throw(v) => {
+						return stk.run(|stk| v.compute(stk, &ctx, opt, doc)).await;
+					}
+				};
+				// Catch any special errors
+				match res {
+					Err(Error::Continue) => break,
+					Err(Error::Break) => break 'main,
+					Err(err) => return Err(err),
+					_ => (),
+				};
+			}
+			// Cooperitively yield if the task has been running for too long.
+			yield_now!();
+		}
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for WhileStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "WHILE {} {}", self.cond, self.block)
+	}
+}
@@ -69,6 +69,7 @@ impl Value {
 			Value::Model(v) => serializer.serialize(v).map_err(Into::into),
 			Value::Closure(v) => serializer.serialize(v).map_err(Into::into),
 			Value::Refs(_) => Ok(Content::Seq(vec![])),
+			Value::Match(v) => serializer.serialize(v).map_err(Into::into),
 		}
 	}
 }
@@ -11,6 +11,7 @@ use crate::sql::range::OldRange;
 use crate::sql::reference::Refs;
 use crate::sql::statements::info::InfoStructure;
 use crate::sql::Closure;
+use crate::sql::Match;
 use crate::sql::{
 	array::Uniq,
 	fmt::{Fmt, Pretty},
@@ -84,7 +85,7 @@ impl From<&Tables> for Values {
 	}
 }
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[serde(rename = "$surrealdb::private::sql::Value")]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -139,6 +140,8 @@ pub enum Value {
 	Model(Box<Model>),
 	Closure(Box<Closure>),
 	Refs(Refs),
+	#[revision(start = 3)]
+	Match(Box<Match>),
 	// Add new variants here
 }
 
@@ -184,6 +187,12 @@ impl From<Closure> for Value {
 	}
 }
 
+impl From<Match> for Value {
+	fn from(v: Match) -> Self {
+		Value::Match(Box::new(v))
+	}
+}
+
 impl From<Param> for Value {
 	fn from(v: Param) -> Self {
 		Value::Param(v)
@@ -2643,6 +2652,53 @@ impl Value {
 		}
 	}
 
+	/// Checks whether this value always computes to the same result for the same document
+	pub(crate) fn is_deterministic(&self) -> bool {
+		match self {
+			Value::Array(v) => v.iter().all(Value::is_deterministic),
+			Value::Object(v) => v.values().all(Value::is_deterministic),
+			Value::Thing(v) => !matches!(v.id, Id::Generate(_)),
+			Value::Cast(v) => v.1.is_deterministic(),
+			Value::Function(v) => v.is_deterministic(),
+			Value::Expression(v) => match v.as_ref() {
+				Expression::Unary {
+					v,
+					..
+				} => v.is_deterministic(),
+				Expression::Binary {
+					l,
+					r,
+					..
+				} => l.is_deterministic() && r.is_deterministic(),
+			},
+			Value::Subquery(v) => match v.as_ref() {
+				Subquery::Value(v) => v.is_deterministic(),
+				_ => false,
+			},
+			Value::Idiom(v) => v.iter().all(|p| match p {
+				Part::Start(v) | Part::Where(v) | Part::Value(v) => v.is_deterministic(),
+				Part::Method(m, a) => {
+					!matches!(m.as_str(), "shuffle" | "exists" | "refs")
+						&& a.iter().all(Value::is_deterministic)
+				}
+				Part::Graph(_) | Part::Recurse(..) | Part::RepeatRecurse => false,
+				_ => true,
+			}),
+			Value::Param(_)
+			| Value::Mock(_)
+			| Value::Block(_)
+			| Value::Range(_)
+			| Value::Edges(_)
+			| Value::Future(_)
+			| Value::Query(_)
+			| Value::Model(_)
+			| Value::Closure(_)
+			| Value::Refs(_)
+			| Value::Match(_) => false,
+			_ => true,
+		}
+	}
+
 	// -----------------------------------
 	// Value operations
 	// -----------------------------------
@@ -2942,6 +2998,7 @@ impl fmt::Display for Value {
 			Value::Uuid(v) => write!(f, "{v}"),
 			Value::Closure(v) => write!(f, "{v}"),
 			Value::Refs(v) => write!(f, "{v}"),
+			Value::Match(v) => write!(f, "{v}"),
 		}
 	}
 }
@@ -2980,6 +3037,7 @@ impl Value {
 			Value::Model(m) => m.args.iter().any(Value::writeable),
 			Value::Subquery(v) => v.writeable(),
 			Value::Expression(v) => v.writeable(),
+			Value::Match(v) => v.writeable(),
 			_ => false,
 		}
 	}
@@ -3025,6 +3083,7 @@ impl Value {
 			Value::Subquery(v) => stk.run(|stk| v.compute(stk, ctx, opt, doc)).await,
 			Value::Expression(v) => stk.run(|stk| v.compute(stk, ctx, opt, doc)).await,
 			Value::Refs(v) => v.compute(ctx, opt, doc).await,
+			Value::Match(v) => stk.run(|stk| v.compute(stk, ctx, opt, doc)).await,
 			_ => Ok(self.to_owned()),
 		}
 	}
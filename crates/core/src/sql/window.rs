@@ -0,0 +1,98 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::fmt::Fmt;
+use crate::sql::order::OrderList;
+use crate::sql::{Array, Function, Idiom, Value};
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The functions which can only be computed over a window of rows
+pub(crate) const WINDOW_FUNCTIONS: [&str; 4] =
+	["window::lag", "window::lead", "window::rank", "window::row_number"];
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Window {
+	/// The `math::sum(amount)` in `math::sum(amount) OVER (...)`
+	pub func: Function,
+	/// The `account` in `OVER (PARTITION BY account)`
+	pub partition: Vec<Value>,
+	/// The `ts` in `OVER (ORDER BY ts)`
+	pub order: Option<OrderList>,
+}
+
+impl Window {
+	/// Convert the window function to a field name
+	pub(crate) fn to_idiom(&self) -> Idiom {
+		self.func.to_idiom()
+	}
+
+	/// Check if this window computes an aggregate function
+	pub(crate) fn is_aggregate(&self) -> bool {
+		self.func.is_aggregate()
+	}
+
+	/// Check if we require a writeable transaction
+	pub(crate) fn writeable(&self) -> bool {
+		self.func.writeable() || self.partition.iter().any(Value::writeable)
+	}
+
+	/// Computes the values which the window function needs from a single
+	/// document. The window function itself is computed once all of the
+	/// documents are known, and this value is returned as `[partition, order,
+	/// arguments]` until then.
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context,
+		opt: &Options,
+		doc: &CursorDoc,
+	) -> Result<Value, Error> {
+		// Compute the partition of this document
+		let mut partition = Array::with_capacity(self.partition.len());
+		for v in self.partition.iter() {
+			partition.push(stk.run(|stk| v.compute(stk, ctx, opt, Some(doc))).await?);
+		}
+		// Compute the position of this document within the partition
+		let mut order = Array::new();
+		if let Some(orders) = &self.order {
+			for o in orders.iter() {
+				let v = Value::Idiom(o.value.clone());
+				order.push(stk.run(|stk| v.compute(stk, ctx, opt, Some(doc))).await?);
+			}
+		}
+		// Compute the arguments of the function
+		let mut args = Array::with_capacity(self.func.args().len());
+		if self.is_aggregate() && self.func.args().is_empty() {
+			// If no function arguments, then compute the result
+			args.push(stk.run(|stk| self.func.compute(stk, ctx, opt, Some(doc))).await?);
+		} else {
+			for v in self.func.args() {
+				args.push(stk.run(|stk| v.compute(stk, ctx, opt, Some(doc))).await?);
+			}
+		}
+		Ok(Value::Array(Array(vec![partition.into(), order.into(), args.into()])))
+	}
+}
+
+impl fmt::Display for Window {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} OVER (", self.func)?;
+		if !self.partition.is_empty() {
+			write!(f, "PARTITION BY {}", Fmt::comma_separated(&self.partition))?;
+			if self.order.is_some() {
+				f.write_str(" ")?;
+			}
+		}
+		if let Some(ref v) = self.order {
+			write!(f, "ORDER BY {v}")?;
+		}
+		f.write_str(")")
+	}
+}
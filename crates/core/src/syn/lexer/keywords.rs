@@ -26,15 +26,21 @@ pub static RESERVED_KEYWORD: phf::Set<UniCase<&'static str>> = phf_set! {
 	UniCase::ascii("REBUILD"),
 	UniCase::ascii("RETURN"),
 	UniCase::ascii("RELATE"),
+	UniCase::ascii("RELEASE"),
 	UniCase::ascii("REMOVE"),
+	UniCase::ascii("ROLLBACK"),
+	UniCase::ascii("SAVEPOINT"),
 	UniCase::ascii("SELECT"),
 	UniCase::ascii("LET"),
 	UniCase::ascii("SHOW"),
 	UniCase::ascii("SLEEP"),
 	UniCase::ascii("THROW"),
+	UniCase::ascii("TRUNCATE"),
+	UniCase::ascii("TRY"),
 	UniCase::ascii("UPDATE"),
 	UniCase::ascii("UPSERT"),
 	UniCase::ascii("USE"),
+	UniCase::ascii("WHILE"),
 	UniCase::ascii("DIFF"),
 	UniCase::ascii("RAND"),
 	UniCase::ascii("NONE"),
@@ -74,6 +80,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("AT") => TokenKind::Keyword(Keyword::At),
 	UniCase::ascii("AUTHENTICATE") => TokenKind::Keyword(Keyword::Authenticate),
 	UniCase::ascii("AUTO") => TokenKind::Keyword(Keyword::Auto),
+	UniCase::ascii("BATCH") => TokenKind::Keyword(Keyword::Batch),
 	UniCase::ascii("BEARER") => TokenKind::Keyword(Keyword::Bearer),
 	UniCase::ascii("BEFORE") => TokenKind::Keyword(Keyword::Before),
 	UniCase::ascii("BEGIN") => TokenKind::Keyword(Keyword::Begin),
@@ -87,6 +94,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("CHANGEFEED") => TokenKind::Keyword(Keyword::ChangeFeed),
 	UniCase::ascii("CHANGES") => TokenKind::Keyword(Keyword::Changes),
 	UniCase::ascii("CAPACITY") => TokenKind::Keyword(Keyword::Capacity),
+	UniCase::ascii("CATCH") => TokenKind::Keyword(Keyword::Catch),
 	UniCase::ascii("CLASS") => TokenKind::Keyword(Keyword::Class),
 	UniCase::ascii("COMMENT") => TokenKind::Keyword(Keyword::Comment),
 	UniCase::ascii("COMMIT") => TokenKind::Keyword(Keyword::Commit),
@@ -106,6 +114,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("DIMENSION") => TokenKind::Keyword(Keyword::Dimension),
 	UniCase::ascii("DISTANCE") => TokenKind::Keyword(Keyword::Distance),
 	UniCase::ascii("DIST") => TokenKind::Keyword(Keyword::Distance),
+	UniCase::ascii("DISTINCT") => TokenKind::Keyword(Keyword::Distinct),
 	UniCase::ascii("DOC_IDS_CACHE") => TokenKind::Keyword(Keyword::DocIdsCache),
 	UniCase::ascii("DOC_IDS_ORDER") => TokenKind::Keyword(Keyword::DocIdsOrder),
 	UniCase::ascii("DOC_LENGTHS_CACHE") => TokenKind::Keyword(Keyword::DocLengthsCache),
@@ -118,8 +127,10 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("ELSE") => TokenKind::Keyword(Keyword::Else),
 	UniCase::ascii("END") => TokenKind::Keyword(Keyword::End),
 	UniCase::ascii("ENFORCED") => TokenKind::Keyword(Keyword::Enforced),
+	UniCase::ascii("EXCEPT") => TokenKind::Keyword(Keyword::Except),
 	UniCase::ascii("EXCLUDE") => TokenKind::Keyword(Keyword::Exclude),
 	UniCase::ascii("EXISTS") => TokenKind::Keyword(Keyword::Exists),
+	UniCase::ascii("EXPIRE") => TokenKind::Keyword(Keyword::Expire),
 	UniCase::ascii("EXPIRED") => TokenKind::Keyword(Keyword::Expired),
 	UniCase::ascii("EXPLAIN") => TokenKind::Keyword(Keyword::Explain),
 	UniCase::ascii("EXPUNGE") => TokenKind::Keyword(Keyword::Expunge),
@@ -149,6 +160,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("INDEX") => TokenKind::Keyword(Keyword::Index),
 	UniCase::ascii("INFO") => TokenKind::Keyword(Keyword::Info),
 	UniCase::ascii("INSERT") => TokenKind::Keyword(Keyword::Insert),
+	UniCase::ascii("INTERSECT") => TokenKind::Keyword(Keyword::Intersect),
 	UniCase::ascii("INTO") => TokenKind::Keyword(Keyword::Into),
 	UniCase::ascii("IF") => TokenKind::Keyword(Keyword::If),
 	UniCase::ascii("IS") => TokenKind::Keyword(Keyword::Is),
@@ -166,6 +178,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("M") => TokenKind::Keyword(Keyword::M),
 	UniCase::ascii("M0") => TokenKind::Keyword(Keyword::M0),
 	UniCase::ascii("MAPPER") => TokenKind::Keyword(Keyword::Mapper),
+	UniCase::ascii("MATCH") => TokenKind::Keyword(Keyword::Match),
 	UniCase::ascii("MIDDLEWARE") => TokenKind::Keyword(Keyword::Middleware),
 	UniCase::ascii("ML") => TokenKind::Keyword(Keyword::ML),
 	UniCase::ascii("MERGE") => TokenKind::Keyword(Keyword::Merge),
@@ -176,6 +189,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("NS") => TokenKind::Keyword(Keyword::Namespace),
 	UniCase::ascii("NGRAM") => TokenKind::Keyword(Keyword::Ngram),
 	UniCase::ascii("NO") => TokenKind::Keyword(Keyword::No),
+	UniCase::ascii("NOEVENTS") => TokenKind::Keyword(Keyword::NoEvents),
 	UniCase::ascii("NOINDEX") => TokenKind::Keyword(Keyword::NoIndex),
 	UniCase::ascii("NONE") => TokenKind::Keyword(Keyword::None),
 	UniCase::ascii("NULL") => TokenKind::Keyword(Keyword::Null),
@@ -186,13 +200,16 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("OPTION") => TokenKind::Keyword(Keyword::Option),
 	UniCase::ascii("ORDER") => TokenKind::Keyword(Keyword::Order),
 	UniCase::ascii("ORIGINAL") => TokenKind::Keyword(Keyword::Original),
+	UniCase::ascii("OVER") => TokenKind::Keyword(Keyword::Over),
 	UniCase::ascii("OVERWRITE") => TokenKind::Keyword(Keyword::Overwrite),
 	UniCase::ascii("PARALLEL") => TokenKind::Keyword(Keyword::Parallel),
 	UniCase::ascii("PARAM") => TokenKind::Keyword(Keyword::Param),
+	UniCase::ascii("PARTITION") => TokenKind::Keyword(Keyword::Partition),
 	UniCase::ascii("PASSHASH") => TokenKind::Keyword(Keyword::Passhash),
 	UniCase::ascii("PASSWORD") => TokenKind::Keyword(Keyword::Password),
 	UniCase::ascii("PATCH") => TokenKind::Keyword(Keyword::Patch),
 	UniCase::ascii("PERMISSIONS") => TokenKind::Keyword(Keyword::Permissions),
+	UniCase::ascii("PERMIT") => TokenKind::Keyword(Keyword::Permit),
 	UniCase::ascii("POSTINGS_CACHE") => TokenKind::Keyword(Keyword::PostingsCache),
 	UniCase::ascii("POSTINGS_ORDER") => TokenKind::Keyword(Keyword::PostingsOrder),
 	UniCase::ascii("PUNCT") => TokenKind::Keyword(Keyword::Punct),
@@ -202,17 +219,22 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("REJECT") => TokenKind::Keyword(Keyword::Reject),
 	UniCase::ascii("RELATE") => TokenKind::Keyword(Keyword::Relate),
 	UniCase::ascii("RELATION") => TokenKind::Keyword(Keyword::Relation),
+	UniCase::ascii("RELEASE") => TokenKind::Keyword(Keyword::Release),
 	UniCase::ascii("REBUILD") => TokenKind::Keyword(Keyword::Rebuild),
 	UniCase::ascii("REFERENCE") => TokenKind::Keyword(Keyword::Reference),
 	UniCase::ascii("REFRESH") => TokenKind::Keyword(Keyword::Refresh),
 	UniCase::ascii("REMOVE") => TokenKind::Keyword(Keyword::Remove),
+	UniCase::ascii("RENAME") => TokenKind::Keyword(Keyword::Rename),
 	UniCase::ascii("REPLACE") => TokenKind::Keyword(Keyword::Replace),
 	UniCase::ascii("RETURN") => TokenKind::Keyword(Keyword::Return),
 	UniCase::ascii("REVOKE") => TokenKind::Keyword(Keyword::Revoke),
 	UniCase::ascii("REVOKED") => TokenKind::Keyword(Keyword::Revoked),
+	UniCase::ascii("ROLE") => TokenKind::Keyword(Keyword::Role),
 	UniCase::ascii("ROLES") => TokenKind::Keyword(Keyword::Roles),
+	UniCase::ascii("ROLLBACK") => TokenKind::Keyword(Keyword::Rollback),
 	UniCase::ascii("ROOT") => TokenKind::Keyword(Keyword::Root),
 	UniCase::ascii("KV") => TokenKind::Keyword(Keyword::Root),
+	UniCase::ascii("SAVEPOINT") => TokenKind::Keyword(Keyword::Savepoint),
 	UniCase::ascii("SCHEMAFULL") => TokenKind::Keyword(Keyword::Schemafull),
 	UniCase::ascii("SCHEMAFUL") => TokenKind::Keyword(Keyword::Schemafull),
 	UniCase::ascii("SCHEMALESS") => TokenKind::Keyword(Keyword::Schemaless),
@@ -220,6 +242,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("SC") => TokenKind::Keyword(Keyword::Scope),
 	UniCase::ascii("SEARCH") => TokenKind::Keyword(Keyword::Search),
 	UniCase::ascii("SELECT") => TokenKind::Keyword(Keyword::Select),
+	UniCase::ascii("SEQUENCE") => TokenKind::Keyword(Keyword::Sequence),
 	UniCase::ascii("SESSION") => TokenKind::Keyword(Keyword::Session),
 	UniCase::ascii("SET") => TokenKind::Keyword(Keyword::Set),
 	UniCase::ascii("SHOW") => TokenKind::Keyword(Keyword::Show),
@@ -244,9 +267,13 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("TOKENIZERS") => TokenKind::Keyword(Keyword::Tokenizers),
 	UniCase::ascii("TOKEN") => TokenKind::Keyword(Keyword::Token),
 	UniCase::ascii("TRANSACTION") => TokenKind::Keyword(Keyword::Transaction),
+	UniCase::ascii("TRUNCATE") => TokenKind::Keyword(Keyword::Truncate),
+	UniCase::ascii("TRY") => TokenKind::Keyword(Keyword::Try),
 	UniCase::ascii("true") => TokenKind::Keyword(Keyword::True),
 	UniCase::ascii("TYPE") => TokenKind::Keyword(Keyword::Type),
+	UniCase::ascii("UNION") => TokenKind::Keyword(Keyword::Union),
 	UniCase::ascii("UNIQUE") => TokenKind::Keyword(Keyword::Unique),
+	UniCase::ascii("UNLOCK") => TokenKind::Keyword(Keyword::Unlock),
 	UniCase::ascii("UNSET") => TokenKind::Keyword(Keyword::Unset),
 	UniCase::ascii("UPDATE") => TokenKind::Keyword(Keyword::Update),
 	UniCase::ascii("UPSERT") => TokenKind::Keyword(Keyword::Upsert),
@@ -260,6 +287,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("VS") => TokenKind::Keyword(Keyword::Vs),
 	UniCase::ascii("WHEN") => TokenKind::Keyword(Keyword::When),
 	UniCase::ascii("WHERE") => TokenKind::Keyword(Keyword::Where),
+	UniCase::ascii("WHILE") => TokenKind::Keyword(Keyword::While),
 	UniCase::ascii("WITH") => TokenKind::Keyword(Keyword::With),
 	UniCase::ascii("ALLINSIDE") => TokenKind::Keyword(Keyword::AllInside),
 	UniCase::ascii("ANDKW") => TokenKind::Keyword(Keyword::AndKw),
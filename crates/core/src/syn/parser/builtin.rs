@@ -1,5 +1,6 @@
 use super::{ParseResult, Parser};
 use crate::{
+	fnc::count,
 	sql::{Constant, Function, Value},
 	syn::{
 		error::{bail, MessageKind},
@@ -228,6 +229,9 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("search::highlight") => PathKind::Function,
 		UniCase::ascii("search::offsets") => PathKind::Function,
 		//
+		UniCase::ascii("sequence::currval") => PathKind::Function,
+		UniCase::ascii("sequence::nextval") => PathKind::Function,
+		//
 		UniCase::ascii("session::ac") => PathKind::Function,
 		UniCase::ascii("session::db") => PathKind::Function,
 		UniCase::ascii("session::id") => PathKind::Function,
@@ -401,6 +405,10 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("vector::similarity::jaccard") => PathKind::Function,
 		UniCase::ascii("vector::similarity::pearson") => PathKind::Function,
 		UniCase::ascii("vector::similarity::spearman") => PathKind::Function,
+		UniCase::ascii("window::lag") => PathKind::Function,
+		UniCase::ascii("window::lead") => PathKind::Function,
+		UniCase::ascii("window::rank") => PathKind::Function,
+		UniCase::ascii("window::row_number") => PathKind::Function,
 		// constants
 		UniCase::ascii("math::E") => PathKind::Constant(Constant::MathE),
 		UniCase::ascii("math::FRAC_1_PI") => PathKind::Constant(Constant::MathFrac1Pi),
@@ -556,6 +564,16 @@ impl Parser<'_> {
 		name: String,
 	) -> ParseResult<Function> {
 		let start = expected!(self, t!("(")).span;
+		// `count(DISTINCT value)` counts the distinct values which are not empty
+		if name == "count"
+			&& self.peek_kind() == t!("DISTINCT")
+			&& !matches!(self.peek1().kind, t!(")") | t!(","))
+		{
+			self.pop_peek();
+			let arg = stk.run(|ctx| self.parse_value_inherit(ctx)).await?;
+			self.expect_closing_delimiter(t!(")"), start)?;
+			return Ok(Function::Normal(count::DISTINCT_FUNCTION.to_owned(), vec![arg]));
+		}
 		let mut args = Vec::new();
 		loop {
 			if self.eat(t!(")")) {
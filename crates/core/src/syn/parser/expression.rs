@@ -68,6 +68,14 @@ impl Parser<'_> {
 		self.pratt_parse_expr(ctx, BindingPower::Base).await
 	}
 
+	/// Parsers a value which binds more strongly than an equality operator, so that it can be
+	/// followed by a `=`, as in the `=>` of a match arm.
+	///
+	/// Inherits how loose identifiers are parsed from it's caller.
+	pub(super) async fn parse_value_relation(&mut self, ctx: &mut Stk) -> ParseResult<Value> {
+		self.pratt_parse_expr(ctx, BindingPower::Equality).await
+	}
+
 	/// Parse a assigner operator.
 	pub(super) fn parse_assigner(&mut self) -> ParseResult<Operator> {
 		let token = self.next();
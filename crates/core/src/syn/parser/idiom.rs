@@ -30,36 +30,29 @@ impl Parser<'_> {
 	/// # Parser State
 	/// Expects the next tokens to be of a field set.
 	pub(super) async fn parse_fields(&mut self, ctx: &mut Stk) -> ParseResult<Fields> {
+		self.parse_field_list(ctx, false).await
+	}
+
+	/// Parse fields of a select statement, which can include window functions:
+	/// `foo, window::row_number() OVER (ORDER BY foo)` in `SELECT foo, window::row_number() OVER (ORDER BY foo) FROM baz`.
+	///
+	/// # Parser State
+	/// Expects the next tokens to be of a field set.
+	pub(super) async fn parse_select_fields(&mut self, ctx: &mut Stk) -> ParseResult<Fields> {
+		self.parse_field_list(ctx, true).await
+	}
+
+	async fn parse_field_list(&mut self, ctx: &mut Stk, windows: bool) -> ParseResult<Fields> {
 		if self.eat(t!("VALUE")) {
-			let expr = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
-			let alias = if self.eat(t!("AS")) {
-				Some(self.parse_plain_idiom(ctx).await?)
-			} else {
-				None
-			};
-			Ok(Fields(
-				vec![Field::Single {
-					expr,
-					alias,
-				}],
-				true,
-			))
+			let field = self.parse_field(ctx, windows).await?;
+			Ok(Fields(vec![field], true))
 		} else {
 			let mut fields = Vec::new();
 			loop {
 				let field = if self.eat(t!("*")) {
 					Field::All
 				} else {
-					let expr = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
-					let alias = if self.eat(t!("AS")) {
-						Some(self.parse_plain_idiom(ctx).await?)
-					} else {
-						None
-					};
-					Field::Single {
-						expr,
-						alias,
-					}
+					self.parse_field(ctx, windows).await?
 				};
 				fields.push(field);
 				if !self.eat(t!(",")) {
@@ -70,6 +63,34 @@ impl Parser<'_> {
 		}
 	}
 
+	/// Parse a single field with an optional alias: `foo AS bar`.
+	async fn parse_field(&mut self, ctx: &mut Stk, windows: bool) -> ParseResult<Field> {
+		let before = self.peek().span;
+		let expr = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
+		let expr_span = before.covers(self.last_span());
+		if windows && self.eat(t!("OVER")) {
+			let window = self.parse_window(ctx, expr, expr_span).await?;
+			let alias = self.try_parse_alias(ctx).await?;
+			return Ok(Field::Window {
+				expr: Box::new(window),
+				alias,
+			});
+		}
+		let alias = self.try_parse_alias(ctx).await?;
+		Ok(Field::Single {
+			expr,
+			alias,
+		})
+	}
+
+	async fn try_parse_alias(&mut self, ctx: &mut Stk) -> ParseResult<Option<Idiom>> {
+		if self.eat(t!("AS")) {
+			Ok(Some(self.parse_plain_idiom(ctx).await?))
+		} else {
+			Ok(None)
+		}
+	}
+
 	/// Parses a list of idioms separated by a `,`
 	pub(super) async fn parse_idiom_list(&mut self, ctx: &mut Stk) -> ParseResult<Vec<Idiom>> {
 		let mut res = vec![self.parse_plain_idiom(ctx).await?];
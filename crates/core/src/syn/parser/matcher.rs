@@ -0,0 +1,89 @@
+use reblessive::Stk;
+
+use crate::{
+	sql::{Match, MatchArm, Pattern},
+	syn::{
+		parser::mac::{expected, expected_whitespace},
+		token::t,
+	},
+};
+
+use super::{ParseResult, Parser};
+
+impl Parser<'_> {
+	/// Returns if a `MATCH` keyword starts a match expression, instead of being an identifier.
+	pub(super) fn peek_starts_match(&mut self) -> bool {
+		let kind = self.peek1().kind;
+		Self::kind_starts_expression(kind)
+			&& !Self::kind_is_keyword_like(kind)
+			&& !matches!(
+				kind,
+				t!("<") | t!("-") | t!("+") | t!("/") | t!("|") | t!("||") | t!("..") | t!("->")
+			)
+	}
+
+	/// Parse a match expression
+	///
+	/// # Parser State
+	/// Expects the `MATCH` keyword to already be eaten.
+	pub(super) async fn parse_match(&mut self, ctx: &mut Stk) -> ParseResult<Match> {
+		let value = ctx.run(|ctx| self.parse_value_inherit(ctx)).await?;
+		let start = expected!(self, t!("{")).span;
+		let mut arms = Vec::new();
+		loop {
+			if self.eat(t!("}")) {
+				break;
+			}
+			let pattern = ctx.run(|ctx| self.parse_pattern(ctx)).await?;
+			expected!(self, t!("="));
+			expected_whitespace!(self, t!(">"));
+			let then = ctx.run(|ctx| self.parse_value_inherit(ctx)).await?;
+			arms.push(MatchArm {
+				pattern,
+				then,
+			});
+			if !self.eat(t!(",")) {
+				self.expect_closing_delimiter(t!("}"), start)?;
+				break;
+			}
+		}
+		Ok(Match {
+			value,
+			arms,
+		})
+	}
+
+	/// Parse the pattern of a match arm
+	async fn parse_pattern(&mut self, ctx: &mut Stk) -> ParseResult<Pattern> {
+		let token = self.peek();
+		match token.kind {
+			t!("<") => {
+				self.pop_peek();
+				let kind = ctx.run(|ctx| self.parse_kind(ctx, token.span)).await?;
+				Ok(Pattern::Kind(kind))
+			}
+			t!("{") => {
+				self.pop_peek();
+				let mut fields = Vec::new();
+				loop {
+					if self.eat(t!("}")) {
+						break;
+					}
+					let key = self.parse_object_key()?;
+					expected!(self, t!(":"));
+					let pattern = ctx.run(|ctx| self.parse_pattern(ctx)).await?;
+					fields.push((key, pattern));
+					if !self.eat(t!(",")) {
+						self.expect_closing_delimiter(t!("}"), token.span)?;
+						break;
+					}
+				}
+				Ok(Pattern::Object(fields))
+			}
+			_ => {
+				let value = ctx.run(|ctx| self.parse_value_relation(ctx)).await?;
+				Ok(Pattern::from_value(value))
+			}
+		}
+	}
+}
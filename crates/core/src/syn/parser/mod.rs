@@ -74,6 +74,7 @@ mod idiom;
 mod json;
 mod kind;
 pub(crate) mod mac;
+mod matcher;
 mod object;
 mod prime;
 mod stmt;
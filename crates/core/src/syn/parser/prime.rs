@@ -54,6 +54,11 @@ impl Parser<'_> {
 				let stmt = ctx.run(|ctx| self.parse_if_stmt(ctx)).await?;
 				Ok(Value::Subquery(Box::new(Subquery::Ifelse(stmt))))
 			}
+			t!("MATCH") if self.peek_starts_match() => {
+				self.pop_peek();
+				let value = ctx.run(|ctx| self.parse_match(ctx)).await?;
+				Ok(Value::Match(Box::new(value)))
+			}
 			t!("(") => {
 				let token = self.pop_peek();
 				let value = self
@@ -291,6 +296,11 @@ impl Parser<'_> {
 					Value::Subquery(Box::new(Subquery::Ifelse(stmt)))
 				})
 			}
+			t!("MATCH") if self.peek_starts_match() => {
+				self.pop_peek();
+				let value = ctx.run(|ctx| self.parse_match(ctx)).await?;
+				Value::Match(Box::new(value))
+			}
 			t!("(") => {
 				self.pop_peek();
 				let value = self.parse_inner_subquery_or_coordinate(ctx, token.span).await?;
@@ -2,10 +2,14 @@ use reblessive::Stk;
 
 use crate::{
 	sql::{
-		statements::{AlterStatement, AlterTableStatement},
-		TableType,
+		statements::{
+			AlterAccessStatement, AlterEventStatement, AlterFieldStatement, AlterIndexStatement,
+			AlterStatement, AlterTableStatement, AlterUserStatement,
+		},
+		TableType, Value, Values,
 	},
 	syn::{
+		error::bail,
 		parser::{
 			mac::{expected, unexpected},
 			ParseResult, Parser,
@@ -19,6 +23,11 @@ impl Parser<'_> {
 		let next = self.next();
 		match next.kind {
 			t!("TABLE") => self.parse_alter_table(ctx).await.map(AlterStatement::Table),
+			t!("FIELD") => self.parse_alter_field(ctx).await.map(AlterStatement::Field),
+			t!("INDEX") => self.parse_alter_index().map(AlterStatement::Index),
+			t!("EVENT") => self.parse_alter_event(ctx).await.map(AlterStatement::Event),
+			t!("USER") => self.parse_alter_user().map(AlterStatement::User),
+			t!("ACCESS") => self.parse_alter_access().map(AlterStatement::Access),
 			_ => unexpected!(self, next, "a alter statement keyword"),
 		}
 	}
@@ -97,10 +106,280 @@ impl Parser<'_> {
 						res.changefeed = Some(Some(self.parse_changefeed()?));
 					}
 				}
+				t!("RENAME") => {
+					self.pop_peek();
+					expected!(self, t!("TO"));
+					res.rename = Some(self.next_token_value()?);
+				}
 				_ => break,
 			}
 		}
 
+		Ok(res)
+	}
+	pub(crate) async fn parse_alter_field(
+		&mut self,
+		ctx: &mut Stk,
+	) -> ParseResult<AlterFieldStatement> {
+		let if_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name = self.parse_local_idiom(ctx).await?;
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let what = self.next_token_value()?;
+		let mut res = AlterFieldStatement {
+			name,
+			what,
+			if_exists,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("DROP") => {
+					self.pop_peek();
+					let next = self.next();
+					match next.kind {
+						t!("FLEXIBLE") => res.flex = Some(false),
+						t!("TYPE") => res.kind = Some(None),
+						t!("READONLY") => res.readonly = Some(false),
+						t!("VALUE") => res.value = Some(None),
+						t!("ASSERT") => res.assert = Some(None),
+						t!("DEFAULT") => {
+							res.default = Some(None);
+							res.default_always = false;
+						}
+						t!("COMMENT") => res.comment = Some(None),
+						t!("REFERENCE") => res.reference = Some(None),
+						_ => unexpected!(
+							self,
+							next,
+							"`FLEXIBLE`, `TYPE`, `READONLY`, `VALUE`, `ASSERT`, `DEFAULT`, `COMMENT` or `REFERENCE`"
+						),
+					}
+				}
+				// FLEX, FLEXI and FLEXIBLE are all the same token type.
+				t!("FLEXIBLE") => {
+					self.pop_peek();
+					res.flex = Some(true);
+				}
+				t!("TYPE") => {
+					self.pop_peek();
+					res.kind = Some(Some(ctx.run(|ctx| self.parse_inner_kind(ctx)).await?));
+				}
+				t!("READONLY") => {
+					self.pop_peek();
+					res.readonly = Some(true);
+				}
+				t!("VALUE") => {
+					self.pop_peek();
+					res.value = Some(Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?));
+				}
+				t!("ASSERT") => {
+					self.pop_peek();
+					res.assert = Some(Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?));
+				}
+				t!("DEFAULT") => {
+					self.pop_peek();
+					res.default_always = self.eat(t!("ALWAYS"));
+					res.default = Some(Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?));
+				}
+				t!("PERMISSIONS") => {
+					self.pop_peek();
+					res.permissions = Some(ctx.run(|ctx| self.parse_permission(ctx, true)).await?);
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(Some(self.next_token_value()?));
+				}
+				t!("REFERENCE") => {
+					if !self.settings.references_enabled {
+						bail!(
+							"Experimental capability `record_references` is not enabled",
+							@self.last_span() => "Use of `REFERENCE` keyword is still experimental"
+						)
+					}
+
+					self.pop_peek();
+					res.reference = Some(Some(self.parse_reference(ctx).await?));
+				}
+				t!("RENAME") => {
+					self.pop_peek();
+					expected!(self, t!("TO"));
+					res.rename = Some(self.parse_local_idiom(ctx).await?);
+				}
+				_ => break,
+			}
+		}
+
+		Ok(res)
+	}
+
+	pub(crate) fn parse_alter_index(&mut self) -> ParseResult<AlterIndexStatement> {
+		let if_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let what = self.next_token_value()?;
+		let mut res = AlterIndexStatement {
+			name,
+			what,
+			if_exists,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("DROP") => {
+					self.pop_peek();
+					expected!(self, t!("COMMENT"));
+					res.comment = Some(None);
+				}
+				t!("RENAME") => {
+					self.pop_peek();
+					expected!(self, t!("TO"));
+					res.rename = Some(self.next_token_value()?);
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(Some(self.next_token_value()?));
+				}
+				t!("DOC_IDS_CACHE") => {
+					self.pop_peek();
+					res.doc_ids_cache = Some(self.next_token_value()?);
+				}
+				t!("DOC_LENGTHS_CACHE") => {
+					self.pop_peek();
+					res.doc_lengths_cache = Some(self.next_token_value()?);
+				}
+				t!("POSTINGS_CACHE") => {
+					self.pop_peek();
+					res.postings_cache = Some(self.next_token_value()?);
+				}
+				t!("TERMS_CACHE") => {
+					self.pop_peek();
+					res.terms_cache = Some(self.next_token_value()?);
+				}
+				t!("MTREE_CACHE") => {
+					self.pop_peek();
+					res.mtree_cache = Some(self.next_token_value()?);
+				}
+				_ => break,
+			}
+		}
+
+		Ok(res)
+	}
+
+	pub(crate) async fn parse_alter_event(
+		&mut self,
+		ctx: &mut Stk,
+	) -> ParseResult<AlterEventStatement> {
+		let if_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let what = self.next_token_value()?;
+		let mut res = AlterEventStatement {
+			name,
+			what,
+			if_exists,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("DROP") => {
+					self.pop_peek();
+					let next = self.next();
+					match next.kind {
+						// Without a condition an event always runs
+						t!("WHEN") => res.when = Some(Value::Bool(true)),
+						t!("COMMENT") => res.comment = Some(None),
+						_ => unexpected!(self, next, "`WHEN` or `COMMENT`"),
+					}
+				}
+				t!("WHEN") => {
+					self.pop_peek();
+					res.when = Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?);
+				}
+				t!("THEN") => {
+					self.pop_peek();
+					let mut then = Values(vec![ctx.run(|ctx| self.parse_value_field(ctx)).await?]);
+					while self.eat(t!(",")) {
+						then.0.push(ctx.run(|ctx| self.parse_value_field(ctx)).await?)
+					}
+					res.then = Some(then);
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(Some(self.next_token_value()?));
+				}
+				_ => break,
+			}
+		}
+
+		Ok(res)
+	}
+
+	pub(crate) fn parse_alter_user(&mut self) -> ParseResult<AlterUserStatement> {
+		let if_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		let base = self.parse_base(false)?;
+		let mut res = AlterUserStatement {
+			name,
+			base,
+			if_exists,
+			..Default::default()
+		};
+
+		while self.eat(t!("UNLOCK")) {
+			res.unlock = true;
+		}
+
+		Ok(res)
+	}
+	pub(crate) fn parse_alter_access(&mut self) -> ParseResult<AlterAccessStatement> {
+		let if_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		let base = self.parse_base(false)?;
+		let mut res = AlterAccessStatement {
+			name,
+			base,
+			if_exists,
+			..Default::default()
+		};
+
+		while self.eat(t!("UNLOCK")) {
+			res.unlock = true;
+		}
+
 		Ok(res)
 	}
 }
@@ -2,6 +2,7 @@ use reblessive::Stk;
 
 use crate::api::method::Method;
 use crate::api::middleware::RequestMiddleware;
+use crate::iam::Action;
 use crate::sql::access_type::JwtAccessVerify;
 use crate::sql::index::HnswParams;
 use crate::sql::statements::define::config::api::ApiConfig;
@@ -21,12 +22,13 @@ use crate::{
 			define::config::graphql, DefineAccessStatement, DefineAnalyzerStatement,
 			DefineDatabaseStatement, DefineEventStatement, DefineFieldStatement,
 			DefineFunctionStatement, DefineIndexStatement, DefineNamespaceStatement,
-			DefineParamStatement, DefineStatement, DefineTableStatement, DefineUserStatement,
+			DefineParamStatement, DefineRoleStatement, DefineSequenceStatement, DefineStatement,
+			DefineTableStatement, DefineUserStatement,
 		},
 		table_type,
 		tokenizer::Tokenizer,
-		user, AccessType, Ident, Idioms, Index, Kind, Param, Permissions, Scoring, Strand,
-		TableType, Values,
+		user, AccessType, Cond, Ident, Idiom, Idioms, Index, Kind, Number, Param, Part,
+		Permissions, Scoring, Strand, TableType, Values,
 	},
 	syn::{
 		parser::{
@@ -48,9 +50,11 @@ impl Parser<'_> {
 			t!("DATABASE") => self.parse_define_database().map(DefineStatement::Database),
 			t!("FUNCTION") => self.parse_define_function(ctx).await.map(DefineStatement::Function),
 			t!("USER") => self.parse_define_user().map(DefineStatement::User),
+			t!("ROLE") => self.parse_define_role(ctx).await.map(DefineStatement::Role),
 			t!("TOKEN") => self.parse_define_token().map(DefineStatement::Access),
 			t!("SCOPE") => self.parse_define_scope(ctx).await.map(DefineStatement::Access),
 			t!("PARAM") => self.parse_define_param(ctx).await.map(DefineStatement::Param),
+			t!("SEQUENCE") => self.parse_define_sequence().map(DefineStatement::Sequence),
 			t!("TABLE") => self.parse_define_table(ctx).await.map(DefineStatement::Table),
 			t!("API") => self.parse_define_api(ctx).await.map(DefineStatement::Api),
 			t!("EVENT") => {
@@ -245,15 +249,8 @@ impl Parser<'_> {
 					self.pop_peek();
 					let mut roles = Vec::new();
 					loop {
-						let token = self.peek();
+						// Custom roles are checked when the user is defined
 						let role = self.next_token_value::<Ident>()?;
-						// NOTE(gguillemas): This hardcoded list is a temporary fix in order
-						// to avoid making breaking changes to the DefineUserStatement structure
-						// while still providing parsing feedback to users referencing unexistent roles.
-						// This list should be removed once arbitrary roles can be defined by users.
-						if !matches!(role.to_lowercase().as_str(), "viewer" | "editor" | "owner") {
-							unexpected!(self, token, "an existent role");
-						}
 						roles.push(role);
 
 						if !self.eat(t!(",")) {
@@ -300,6 +297,65 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	pub async fn parse_define_role(&mut self, ctx: &mut Stk) -> ParseResult<DefineRoleStatement> {
+		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			(true, false)
+		} else if self.eat(t!("OVERWRITE")) {
+			(false, true)
+		} else {
+			(false, false)
+		};
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		let token = self.peek();
+		let base = match self.parse_base(false)? {
+			Base::Root => unexpected!(self, token, "'NAMESPACE' or 'DATABASE'"),
+			base => base,
+		};
+		expected!(self, t!("PERMIT"));
+		let token = self.peek();
+		let action = match self.next_token_value::<Ident>()?.to_ascii_uppercase().as_str() {
+			"VIEW" => Action::View,
+			"EDIT" => Action::Edit,
+			_ => unexpected!(self, token, "'VIEW' or 'EDIT'"),
+		};
+		expected!(self, t!("ON"));
+		let token = self.peek();
+		let resource =
+			match DefineRoleStatement::resource_from_name(&self.next_token_value::<Ident>()?) {
+				Some(resource) => resource,
+				None => unexpected!(self, token, "a resource type"),
+			};
+
+		let mut res = DefineRoleStatement {
+			name,
+			base,
+			action,
+			resource,
+			if_not_exists,
+			overwrite,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("WHEN") => {
+					self.pop_peek();
+					res.when = Some(ctx.run(|ctx| self.parse_value_field(ctx)).await?);
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				_ => break,
+			}
+		}
+
+		Ok(res)
+	}
+
 	pub async fn parse_define_access(
 		&mut self,
 		stk: &mut Stk,
@@ -704,6 +760,53 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	pub fn parse_define_sequence(&mut self) -> ParseResult<DefineSequenceStatement> {
+		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			(true, false)
+		} else if self.eat(t!("OVERWRITE")) {
+			(false, true)
+		} else {
+			(false, false)
+		};
+		let name = self.next_token_value()?;
+
+		let mut res = DefineSequenceStatement {
+			name,
+			if_not_exists,
+			overwrite,
+			batch: DefineSequenceStatement::DEFAULT_BATCH,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("BATCH") => {
+					self.pop_peek();
+					let token = self.peek();
+					res.batch = self.next_token_value()?;
+					if res.batch == 0 {
+						bail!(
+							"Invalid sequence batch size",
+							@token.span => "The batch size must be at least 1"
+						);
+					}
+				}
+				t!("START") => {
+					self.pop_peek();
+					let token = self.peek();
+					res.start = match self.next_token_value::<Number>()? {
+						Number::Int(v) => v,
+						_ => bail!("Unexpected token", @token.span => "Expected an integer"),
+					};
+				}
+				_ => break,
+			}
+		}
+		Ok(res)
+	}
+
 	pub async fn parse_define_table(&mut self, ctx: &mut Stk) -> ParseResult<DefineTableStatement> {
 		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
 			expected!(self, t!("NOT"));
@@ -773,6 +876,10 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = Some(self.parse_changefeed()?);
 				}
+				t!("EXPIRE") => {
+					self.pop_peek();
+					res.expire = Some(self.parse_expiry(ctx).await?);
+				}
 				t!("AS") => {
 					self.pop_peek();
 					let peek = self.peek();
@@ -1014,6 +1121,23 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses an indexed column, which is either a local idiom, or an
+	/// expression, such as a function call, which is computed on each record.
+	async fn parse_index_col(&mut self, ctx: &mut Stk) -> ParseResult<Idiom> {
+		let is_expression = match self.peek_kind() {
+			t!("(") | t!("fn") => true,
+			x if Self::kind_is_identifier(x) => matches!(self.peek1().kind, t!("::") | t!("(")),
+			_ => false,
+		};
+		if !is_expression {
+			return self.parse_local_idiom(ctx).await;
+		}
+		match ctx.run(|ctx| self.parse_value_field(ctx)).await? {
+			Value::Idiom(v) => Ok(v),
+			v => Ok(Idiom(vec![Part::Start(v)])),
+		}
+	}
+
 	pub async fn parse_define_index(&mut self, ctx: &mut Stk) -> ParseResult<DefineIndexStatement> {
 		let (if_not_exists, overwrite) = if self.eat(t!("IF")) {
 			expected!(self, t!("NOT"));
@@ -1043,9 +1167,16 @@ impl Parser<'_> {
 				// COLUMNS and FIELDS are the same tokenkind
 				t!("FIELDS") => {
 					self.pop_peek();
-					res.cols = Idioms(vec![self.parse_local_idiom(ctx).await?]);
+					res.cols = Idioms(vec![self.parse_index_col(ctx).await?]);
+					while self.eat(t!(",")) {
+						res.cols.0.push(self.parse_index_col(ctx).await?);
+					}
+				}
+				t!("INCLUDE") => {
+					self.pop_peek();
+					res.include = Idioms(vec![self.parse_local_idiom(ctx).await?]);
 					while self.eat(t!(",")) {
-						res.cols.0.push(self.parse_local_idiom(ctx).await?);
+						res.include.0.push(self.parse_local_idiom(ctx).await?);
 					}
 				}
 				t!("UNIQUE") => {
@@ -1262,6 +1393,11 @@ impl Parser<'_> {
 						keep_pruned_connections,
 					));
 				}
+				t!("WHERE") => {
+					self.pop_peek();
+					let cond = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
+					res.cond = Some(Cond(cond));
+				}
 				t!("CONCURRENTLY") => {
 					self.pop_peek();
 					res.concurrently = true;
@@ -1435,34 +1571,46 @@ impl Parser<'_> {
 					self.pop_peek();
 
 					let mut middleware: Vec<(String, Vec<Value>)> = Vec::new();
-					// let mut parsed_custom = false;
+					let mut parsed_custom = false;
 
 					loop {
-						let mut name = match self.peek_kind() {
+						let (mut name, builtin) = match self.peek_kind() {
 							t!("API") => {
-								// if parsed_custom {
-								// 	bail!("Cannot specify builtin middlewares after custom middlewares");
-								// }
+								if parsed_custom {
+									bail!("Cannot specify builtin middlewares after custom middlewares");
+								}
 
 								self.pop_peek();
 								expected!(self, t!("::"));
-								"api::".to_string()
+								("api::".to_string(), true)
 							}
 							t!("fn") => {
-								bail!("Custom middlewares are not yet supported")
+								self.pop_peek();
+								expected!(self, t!("::"));
+								parsed_custom = true;
+								("fn::".to_string(), false)
 							}
 							_ => {
 								break;
 							}
 						};
 
+						// Builtin middleware names are case-insensitive
 						let part = self.next_token_value::<Ident>()?;
-						name.push_str(part.0.to_lowercase().as_str());
+						if builtin {
+							name.push_str(part.0.to_lowercase().as_str());
+						} else {
+							name.push_str(part.0.as_str());
+						}
 
 						while self.eat(t!("::")) {
 							let part = self.next_token_value::<Ident>()?;
 							name.push_str("::");
-							name.push_str(part.0.to_lowercase().as_str());
+							if builtin {
+								name.push_str(part.0.to_lowercase().as_str());
+							} else {
+								name.push_str(part.0.as_str());
+							}
 						}
 
 						expected!(self, t!("("));
@@ -9,7 +9,8 @@ use crate::sql::statements::{
 		AccessStatement, AccessStatementGrant, AccessStatementPurge, AccessStatementRevoke,
 		AccessStatementShow, Subject,
 	},
-	KillStatement, LiveStatement, OptionStatement, SetStatement, ThrowStatement,
+	KillStatement, LiveStatement, OptionStatement, SetStatement, ThrowStatement, TruncateStatement,
+	TrycatchStatement, WhileStatement,
 };
 use crate::sql::{Duration, Fields, Ident, Param};
 use crate::syn::lexer::compound;
@@ -20,7 +21,7 @@ use crate::{
 		statements::{
 			analyze::AnalyzeStatement, BeginStatement, BreakStatement, CancelStatement,
 			CommitStatement, ContinueStatement, ForeachStatement, InfoStatement, OutputStatement,
-			UseStatement,
+			ReleaseStatement, RollbackStatement, SavepointStatement, UseStatement,
 		},
 		Expression, Operator, Statement, Statements, Value,
 	},
@@ -178,10 +179,22 @@ impl Parser<'_> {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_relate_stmt(ctx)).await.map(Statement::Relate)
 			}
+			t!("RELEASE") => {
+				self.pop_peek();
+				self.parse_release().map(Statement::Release)
+			}
 			t!("REMOVE") => {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_remove_stmt(ctx)).await.map(Statement::Remove)
 			}
+			t!("ROLLBACK") => {
+				self.pop_peek();
+				self.parse_rollback().map(Statement::Rollback)
+			}
+			t!("SAVEPOINT") => {
+				self.pop_peek();
+				self.parse_savepoint().map(Statement::Savepoint)
+			}
 			t!("SELECT") => {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_select_stmt(ctx)).await.map(Statement::Select)
@@ -202,6 +215,14 @@ impl Parser<'_> {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_throw_stmt(ctx)).await.map(Statement::Throw)
 			}
+			t!("TRUNCATE") => {
+				self.pop_peek();
+				self.parse_truncate_stmt().map(Statement::Truncate)
+			}
+			t!("TRY") => {
+				self.pop_peek();
+				ctx.run(|ctx| self.parse_try_stmt(ctx)).await.map(Statement::Trycatch)
+			}
 			t!("UPDATE") => {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_update_stmt(ctx)).await.map(Statement::Update)
@@ -214,6 +235,10 @@ impl Parser<'_> {
 				self.pop_peek();
 				self.parse_use_stmt().map(Statement::Use)
 			}
+			t!("WHILE") => {
+				self.pop_peek();
+				ctx.run(|ctx| self.parse_while_stmt(ctx)).await.map(Statement::While)
+			}
 			_ => {
 				// TODO: Provide information about keywords.
 				let value = ctx.run(|ctx| self.parse_value_field(ctx)).await?;
@@ -295,6 +320,10 @@ impl Parser<'_> {
 				self.pop_peek();
 				self.parse_throw_stmt(ctx).await.map(Entry::Throw)
 			}
+			t!("TRY") => {
+				self.pop_peek();
+				self.parse_try_stmt(ctx).await.map(Entry::Trycatch)
+			}
 			t!("UPDATE") => {
 				self.pop_peek();
 				self.parse_update_stmt(ctx).await.map(Entry::Update)
@@ -303,6 +332,10 @@ impl Parser<'_> {
 				self.pop_peek();
 				self.parse_upsert_stmt(ctx).await.map(Entry::Upsert)
 			}
+			t!("WHILE") => {
+				self.pop_peek();
+				self.parse_while_stmt(ctx).await.map(Entry::While)
+			}
 			_ => {
 				// TODO: Provide information about keywords.
 				let v = ctx.run(|ctx| self.parse_value_inherit(ctx)).await?;
@@ -529,6 +562,39 @@ impl Parser<'_> {
 		Ok(CommitStatement)
 	}
 
+	/// Parsers a savepoint statement.
+	///
+	/// # Parser State
+	/// Expects `SAVEPOINT` to already be consumed.
+	fn parse_savepoint(&mut self) -> ParseResult<SavepointStatement> {
+		Ok(SavepointStatement {
+			name: self.next_token_value()?,
+		})
+	}
+
+	/// Parsers a rollback statement.
+	///
+	/// # Parser State
+	/// Expects `ROLLBACK` to already be consumed.
+	fn parse_rollback(&mut self) -> ParseResult<RollbackStatement> {
+		expected!(self, t!("TO"));
+		self.eat(t!("SAVEPOINT"));
+		Ok(RollbackStatement {
+			name: self.next_token_value()?,
+		})
+	}
+
+	/// Parsers a release statement.
+	///
+	/// # Parser State
+	/// Expects `RELEASE` to already be consumed.
+	fn parse_release(&mut self) -> ParseResult<ReleaseStatement> {
+		self.eat(t!("SAVEPOINT"));
+		Ok(ReleaseStatement {
+			name: self.next_token_value()?,
+		})
+	}
+
 	/// Parsers a USE statement.
 	///
 	/// # Parser State
@@ -817,4 +883,69 @@ impl Parser<'_> {
 			error,
 		})
 	}
+
+	/// Parsers a TRUNCATE statement
+	///
+	/// # Parser State
+	/// Expects `TRUNCATE` to already be consumed.
+	pub(super) fn parse_truncate_stmt(&mut self) -> ParseResult<TruncateStatement> {
+		expected!(self, t!("TABLE"));
+		let if_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name = self.next_token_value()?;
+		let skip_events = if self.eat(t!("WITH")) {
+			expected!(self, t!("NOEVENTS"));
+			true
+		} else {
+			false
+		};
+		Ok(TruncateStatement {
+			name,
+			if_exists,
+			skip_events,
+		})
+	}
+
+	/// Parsers a TRY statement
+	///
+	/// # Parser State
+	/// Expects `TRY` to already be consumed.
+	pub(super) async fn parse_try_stmt(&mut self, ctx: &mut Stk) -> ParseResult<TrycatchStatement> {
+		let span = expected!(self, t!("{")).span;
+		let block = self.parse_block(ctx, span).await?;
+		expected!(self, t!("CATCH"));
+		let param = if self.eat(t!("(")) {
+			let start = self.last_span();
+			let param = self.next_token_value()?;
+			self.expect_closing_delimiter(t!(")"), start)?;
+			Some(param)
+		} else {
+			None
+		};
+		let span = expected!(self, t!("{")).span;
+		let catch = self.parse_block(ctx, span).await?;
+		Ok(TrycatchStatement {
+			block,
+			param,
+			catch,
+		})
+	}
+
+	/// Parsers a WHILE statement.
+	///
+	/// # Parser State
+	/// Expects `WHILE` to already be consumed.
+	pub(super) async fn parse_while_stmt(&mut self, stk: &mut Stk) -> ParseResult<WhileStatement> {
+		let cond = stk.run(|stk| self.parse_value_inherit(stk)).await?;
+		let span = expected!(self, t!("{")).span;
+		let block = self.parse_block(stk, span).await?;
+		Ok(WhileStatement {
+			cond,
+			block,
+		})
+	}
 }
@@ -9,15 +9,15 @@ use crate::{
 	sql::{
 		changefeed::ChangeFeed,
 		index::{Distance, VectorType},
-		Base, Cond, Data, Duration, Fetchs, Field, Fields, Group, Groups, Ident, Idiom, Output,
-		Permission, Permissions, Tables, Timeout, Value, View,
+		Base, Cond, Data, Duration, Expiry, Fetchs, Field, Fields, Group, Groups, Ident, Idiom,
+		Output, Permission, Permissions, Tables, Timeout, Value, View,
 	},
 	syn::{
 		parser::{
 			mac::{expected, unexpected},
 			ParseResult, Parser,
 		},
-		token::{t, DistanceKind, Span, TokenKind, VectorTypeKind},
+		token::{t, DistanceKind, Glued, Span, TokenKind, VectorTypeKind},
 	},
 };
 
@@ -169,12 +169,23 @@ impl Parser<'_> {
 	) -> ParseResult<&'a Field> {
 		let mut found = None;
 		for field in fields.iter() {
-			let Field::Single {
-				expr,
-				alias,
-			} = field
-			else {
-				unreachable!()
+			let (expr, alias) = match field {
+				Field::Single {
+					expr,
+					alias,
+				} => (expr, alias),
+				// A window function is found by the name of its output field
+				Field::Window {
+					expr,
+					alias,
+				} => {
+					if alias.as_ref().map_or_else(|| *idiom == expr.to_idiom(), |x| x == idiom) {
+						found = Some(field);
+						break;
+					}
+					continue;
+				}
+				Field::All => unreachable!(),
 			};
 
 			if let Some(alias) = alias {
@@ -426,6 +437,19 @@ impl Parser<'_> {
 		})
 	}
 
+	/// Parses a table record expiry
+	///
+	/// # Parser State
+	/// Expects the parser to have already eating the `EXPIRE` keyword
+	pub async fn parse_expiry(&mut self, ctx: &mut Stk) -> ParseResult<Expiry> {
+		match self.peek_kind() {
+			TokenKind::Digits | TokenKind::Glued(Glued::Duration) => {
+				Ok(Expiry::Duration(self.next_token_value()?))
+			}
+			_ => Ok(Expiry::Field(self.parse_local_idiom(ctx).await?)),
+		}
+	}
+
 	/// Parses a reference
 	///
 	/// # Parser State
@@ -2,11 +2,15 @@ use reblessive::Stk;
 
 use crate::{
 	sql::{
+		function::OptimisedAggregate,
 		order::{OrderList, Ordering},
-		statements::SelectStatement,
-		Field, Fields, Idioms, Limit, Order, Split, Splits, Start, Values, Version,
+		statements::{SelectStatement, SetOperation, SetOperator},
+		window::WINDOW_FUNCTIONS,
+		Field, Fields, Function, Idioms, Limit, Order, Split, Splits, Start, Value, Values,
+		Version, Window,
 	},
 	syn::{
+		error::bail,
 		parser::{mac::expected, ParseResult, Parser},
 		token::{t, Span},
 	},
@@ -19,8 +23,85 @@ impl Parser<'_> {
 		&mut self,
 		stk: &mut Stk,
 	) -> ParseResult<SelectStatement> {
+		let (mut stmt, fields_span) = self.parse_select_operand(stk, true).await?;
+		// Set operators are applied from left to right
+		loop {
+			let span = self.peek().span;
+			let operator = match self.peek_kind() {
+				t!("UNION") => {
+					self.pop_peek();
+					if self.eat(t!("ALL")) {
+						SetOperator::UnionAll
+					} else {
+						SetOperator::Union
+					}
+				}
+				t!("INTERSECT") => {
+					self.pop_peek();
+					SetOperator::Intersect
+				}
+				t!("EXCEPT") => {
+					self.pop_peek();
+					SetOperator::Except
+				}
+				_ => break,
+			};
+			if stmt.combine.is_empty()
+				&& (stmt.order.is_some()
+					|| stmt.limit.is_some()
+					|| stmt.start.is_some()
+					|| stmt.fetch.is_some()
+					|| stmt.timeout.is_some()
+					|| stmt.parallel
+					|| stmt.tempfiles
+					|| stmt.explain.is_some())
+			{
+				bail!("ORDER BY, LIMIT, START, FETCH, TIMEOUT, PARALLEL, TEMPFILES and EXPLAIN must follow the last SELECT of a {operator}",
+					@span => "these clauses apply to the combined result");
+			}
+			expected!(self, t!("SELECT"));
+			let (select, _) = stk.run(|ctx| self.parse_select_operand(ctx, false)).await?;
+			stmt.combine.push(SetOperation {
+				operator,
+				select,
+			});
+		}
+		// The trailing clauses apply to the combined result
+		if !stmt.combine.is_empty() {
+			stmt.order = self.try_parse_orders(stk, &stmt.expr, fields_span).await?;
+			(stmt.limit, stmt.start) = self.try_parse_limit_start(stk).await?;
+			stmt.fetch = self.try_parse_fetch(stk).await?;
+			stmt.timeout = self.try_parse_timeout()?;
+			stmt.parallel = self.eat(t!("PARALLEL"));
+			stmt.tempfiles = self.eat(t!("TEMPFILES"));
+			if self.peek_kind() == t!("EXPLAIN") {
+				bail!("EXPLAIN can not be used with set operators", @self.peek().span);
+			}
+		}
+		Ok(stmt)
+	}
+
+	/// Parses a single SELECT statement, without any set operators. The ORDER BY,
+	/// LIMIT, START, FETCH, TIMEOUT, PARALLEL, TEMPFILES and EXPLAIN clauses are
+	/// only parsed if `trailing` is set, as they apply to the combined result of
+	/// any set operators.
+	async fn parse_select_operand(
+		&mut self,
+		stk: &mut Stk,
+		trailing: bool,
+	) -> ParseResult<(SelectStatement, Span)> {
+		// `SELECT DISTINCT` unless `distinct` is the name of a selected field
+		let distinct = self.peek_kind() == t!("DISTINCT")
+			&& !matches!(
+				self.peek1().kind,
+				t!("FROM") | t!(",") | t!(".") | t!("[") | t!("AS") | t!("OMIT")
+			);
+		if distinct {
+			self.pop_peek();
+		}
+
 		let before = self.peek().span;
-		let expr = self.parse_fields(stk).await?;
+		let expr = self.parse_select_fields(stk).await?;
 		let fields_span = before.covers(self.last_span());
 
 		let omit = if self.eat(t!("OMIT")) {
@@ -43,16 +124,28 @@ impl Parser<'_> {
 		let cond = self.try_parse_condition(stk).await?;
 		let split = self.try_parse_split(stk, &expr, fields_span).await?;
 		let group = self.try_parse_group(stk, &expr, fields_span).await?;
+		if group.is_some() && expr.has_windows() {
+			bail!("Window functions can not be used in a grouped selection", @fields_span);
+		}
+		if !trailing {
+			let version = self.try_parse_version(stk).await?;
+			let stmt = SelectStatement {
+				expr,
+				omit,
+				only,
+				what,
+				with,
+				cond,
+				split,
+				group,
+				version,
+				distinct,
+				..Default::default()
+			};
+			return Ok((stmt, fields_span));
+		}
 		let order = self.try_parse_orders(stk, &expr, fields_span).await?;
-		let (limit, start) = if let t!("START") = self.peek_kind() {
-			let start = self.try_parse_start(stk).await?;
-			let limit = self.try_parse_limit(stk).await?;
-			(limit, start)
-		} else {
-			let limit = self.try_parse_limit(stk).await?;
-			let start = self.try_parse_start(stk).await?;
-			(limit, start)
-		};
+		let (limit, start) = self.try_parse_limit_start(stk).await?;
 		let fetch = self.try_parse_fetch(stk).await?;
 		let version = self.try_parse_version(stk).await?;
 		let timeout = self.try_parse_timeout()?;
@@ -60,7 +153,7 @@ impl Parser<'_> {
 		let tempfiles = self.eat(t!("TEMPFILES"));
 		let explain = self.try_parse_explain()?;
 
-		Ok(SelectStatement {
+		let stmt = SelectStatement {
 			expr,
 			omit,
 			only,
@@ -78,7 +171,26 @@ impl Parser<'_> {
 			parallel,
 			tempfiles,
 			explain,
-		})
+			distinct,
+			combine: Vec::new(),
+		};
+		Ok((stmt, fields_span))
+	}
+
+	/// Parses the LIMIT and START clauses, in either order
+	async fn try_parse_limit_start(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<(Option<Limit>, Option<Start>)> {
+		if let t!("START") = self.peek_kind() {
+			let start = self.try_parse_start(stk).await?;
+			let limit = self.try_parse_limit(stk).await?;
+			Ok((limit, start))
+		} else {
+			let limit = self.try_parse_limit(stk).await?;
+			let start = self.try_parse_start(stk).await?;
+			Ok((limit, start))
+		}
 	}
 
 	pub(crate) async fn try_parse_split(
@@ -163,7 +275,7 @@ impl Parser<'_> {
 		Ok(Some(Ordering::Order(OrderList(orders))))
 	}
 
-	async fn parse_order(&mut self, ctx: &mut Stk) -> ParseResult<Order> {
+	pub(crate) async fn parse_order(&mut self, ctx: &mut Stk) -> ParseResult<Order> {
 		let start = self.parse_basic_idiom(ctx).await?;
 		let collate = self.eat(t!("COLLATE"));
 		let numeric = self.eat(t!("NUMERIC"));
@@ -186,6 +298,66 @@ impl Parser<'_> {
 		})
 	}
 
+	/// Parses the window of a window function: `OVER (PARTITION BY foo ORDER BY bar)`.
+	///
+	/// # Parser State
+	/// Expects the `OVER` keyword to already be eaten.
+	pub(crate) async fn parse_window(
+		&mut self,
+		stk: &mut Stk,
+		expr: Value,
+		expr_span: Span,
+	) -> ParseResult<Window> {
+		let Value::Function(func) = expr else {
+			bail!("Expected a window function or an aggregate function before `OVER`", @expr_span);
+		};
+		match func.as_ref() {
+			Function::Normal(name, args) if WINDOW_FUNCTIONS.contains(&name.as_str()) => {
+				match (name.as_str(), args.len()) {
+					("window::row_number" | "window::rank", 0)
+					| ("window::lag" | "window::lead", 1..=3) => {}
+					_ => bail!("Invalid arguments for window function `{name}`", @expr_span),
+				}
+			}
+			f if f.is_aggregate() => {}
+			_ => {
+				bail!("Expected a window function or an aggregate function before `OVER`", @expr_span)
+			}
+		}
+		let start = expected!(self, t!("(")).span;
+		let mut partition = Vec::new();
+		if self.eat(t!("PARTITION")) {
+			self.eat(t!("BY"));
+			partition.push(stk.run(|ctx| self.parse_value_field(ctx)).await?);
+			while self.eat(t!(",")) {
+				partition.push(stk.run(|ctx| self.parse_value_field(ctx)).await?);
+			}
+		}
+		let order = if self.eat(t!("ORDER")) {
+			self.eat(t!("BY"));
+			let mut orders = vec![self.parse_order(stk).await?];
+			while self.eat(t!(",")) {
+				orders.push(self.parse_order(stk).await?);
+			}
+			Some(OrderList(orders))
+		} else {
+			None
+		};
+		self.expect_closing_delimiter(t!(")"), start)?;
+		// Aggregates which can not be updated row by row are only computed over a whole partition
+		if order.is_some()
+			&& func.is_aggregate()
+			&& matches!(func.get_optimised_aggregate(), OptimisedAggregate::None)
+		{
+			bail!("Aggregate function `{}` can not be used in a window with an ORDER BY clause", func.name().unwrap_or_default(), @expr_span);
+		}
+		Ok(Window {
+			func: *func,
+			partition,
+			order,
+		})
+	}
+
 	pub(crate) async fn try_parse_limit(&mut self, ctx: &mut Stk) -> ParseResult<Option<Limit>> {
 		if !self.eat(t!("LIMIT")) {
 			return Ok(None);
@@ -1,4 +1,5 @@
 use crate::{
+	iam::{Action, ResourceKind},
 	sql::{
 		access::AccessDuration,
 		access_type::{
@@ -19,26 +20,30 @@ use crate::{
 			analyze::AnalyzeStatement,
 			show::{ShowSince, ShowStatement},
 			sleep::SleepStatement,
-			AccessStatement, BeginStatement, BreakStatement, CancelStatement, CommitStatement,
-			ContinueStatement, CreateStatement, DefineAccessStatement, DefineAnalyzerStatement,
+			AccessStatement, AlterAccessStatement, AlterEventStatement, AlterFieldStatement,
+			AlterIndexStatement, AlterStatement, AlterTableStatement, AlterUserStatement,
+			BeginStatement, BreakStatement, CancelStatement, CommitStatement, ContinueStatement,
+			CreateStatement, DefineAccessStatement, DefineAnalyzerStatement,
 			DefineDatabaseStatement, DefineEventStatement, DefineFieldStatement,
 			DefineFunctionStatement, DefineIndexStatement, DefineNamespaceStatement,
-			DefineParamStatement, DefineStatement, DefineTableStatement, DeleteStatement,
-			ForeachStatement, IfelseStatement, InfoStatement, InsertStatement, KillStatement,
-			OptionStatement, OutputStatement, RelateStatement, RemoveAccessStatement,
-			RemoveAnalyzerStatement, RemoveDatabaseStatement, RemoveEventStatement,
-			RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement,
-			RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement, RemoveTableStatement,
-			RemoveUserStatement, SelectStatement, SetStatement, ThrowStatement, UpdateStatement,
-			UpsertStatement, UseStatement,
+			DefineParamStatement, DefineRoleStatement, DefineSequenceStatement, DefineStatement,
+			DefineTableStatement, DeleteStatement, ForeachStatement, IfelseStatement,
+			InfoStatement, InsertStatement, KillStatement, OptionStatement, OutputStatement,
+			RelateStatement, ReleaseStatement, RemoveAccessStatement, RemoveAnalyzerStatement,
+			RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement,
+			RemoveFunctionStatement, RemoveIndexStatement, RemoveNamespaceStatement,
+			RemoveParamStatement, RemoveSequenceStatement, RemoveStatement, RemoveTableStatement,
+			RemoveUserStatement, RollbackStatement, SavepointStatement, SelectStatement,
+			SetOperator, SetStatement, ThrowStatement, TruncateStatement, TrycatchStatement,
+			UpdateStatement, UpsertStatement, UseStatement, WhileStatement,
 		},
 		tokenizer::Tokenizer,
 		user::UserDuration,
 		Algorithm, Array, Base, Block, Cond, Data, Datetime, Dir, Duration, Edges, Explain,
-		Expression, Fetch, Fetchs, Field, Fields, Future, Graph, Group, Groups, Id, Ident, Idiom,
-		Idioms, Index, Kind, Limit, Number, Object, Operator, Order, Output, Param, Part,
-		Permission, Permissions, Scoring, Split, Splits, Start, Statement, Strand, Subquery, Table,
-		TableType, Tables, Thing, Timeout, Uuid, Value, Values, Version, With,
+		Expression, Fetch, Fetchs, Field, Fields, Function, Future, Graph, Group, Groups, Id,
+		Ident, Idiom, Idioms, Index, Kind, Limit, Number, Object, Operator, Order, Output, Param,
+		Part, Permission, Permissions, Scoring, Split, Splits, Start, Statement, Strand, Subquery,
+		Table, TableType, Tables, Thing, Timeout, Uuid, Value, Values, Version, Window, With,
 	},
 	syn::parser::{
 		mac::{test_parse, test_parse_with_settings},
@@ -90,6 +95,39 @@ pub fn parse_commit() {
 	assert_eq!(res, Statement::Commit(CommitStatement));
 }
 
+#[test]
+pub fn parse_savepoint() {
+	let res = test_parse!(parse_stmt, r#"SAVEPOINT import"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Savepoint(SavepointStatement {
+			name: Ident("import".to_owned()),
+		})
+	);
+	let res = test_parse!(parse_stmt, r#"ROLLBACK TO import"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Rollback(RollbackStatement {
+			name: Ident("import".to_owned()),
+		})
+	);
+	let res = test_parse!(parse_stmt, r#"ROLLBACK TO SAVEPOINT import"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Rollback(RollbackStatement {
+			name: Ident("import".to_owned()),
+		})
+	);
+	let res = test_parse!(parse_stmt, r#"RELEASE SAVEPOINT import"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Release(ReleaseStatement {
+			name: Ident("import".to_owned()),
+		})
+	);
+	test_parse!(parse_stmt, r#"ROLLBACK import"#).unwrap_err();
+}
+
 #[test]
 pub fn parse_continue() {
 	let res = test_parse!(parse_stmt, r#"CONTINUE"#).unwrap();
@@ -363,32 +401,55 @@ fn parse_define_user() {
 			res
 		);
 	}
-	// With nonexistent role.
-	{
-		let res = test_parse!(
-			parse_stmt,
-			r#"DEFINE USER user ON ROOT COMMENT 'test' PASSHASH 'hunter2' ROLES foo"#
-		);
-		assert!(
-			res.is_err(),
-			"Unexpected successful parsing of user with nonexistent role: {:?}",
-			res
-		);
-	}
-	// With existent and nonexistent roles.
+	// With custom roles, which are checked when the user is defined.
 	{
 		let res = test_parse!(
 			parse_stmt,
 			r#"DEFINE USER user ON ROOT COMMENT 'test' PASSHASH 'hunter2' ROLES Viewer, foo"#
-		);
-		assert!(
-			res.is_err(),
-			"Unexpected successful parsing of user with nonexistent role: {:?}",
-			res
-		);
+		)
+		.unwrap();
+		let Statement::Define(DefineStatement::User(stmt)) = res else {
+			panic!()
+		};
+		assert_eq!(stmt.roles, vec![Ident::from("Viewer"), Ident::from("foo")]);
 	}
 }
 
+#[test]
+fn parse_define_role() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE ROLE records ON DATABASE PERMIT EDIT ON RECORD WHEN $resource.db != 'audit' COMMENT 'test'"#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Role(DefineRoleStatement {
+			name: Ident("records".to_string()),
+			base: Base::Db,
+			action: Action::Edit,
+			resource: ResourceKind::Record,
+			when: Some(Value::Expression(Box::new(Expression::Binary {
+				l: Value::Idiom(Idiom(vec![
+					Part::Start(Value::Param(Param(Ident("resource".to_string())))),
+					Part::Field(Ident("db".to_string())),
+				])),
+				o: Operator::NotEqual,
+				r: Value::Strand(Strand("audit".to_string())),
+			}))),
+			comment: Some(Strand("test".to_string())),
+			if_not_exists: false,
+			overwrite: false,
+		}))
+	);
+	// Roles can not be defined on the root level.
+	let res = test_parse!(parse_stmt, r#"DEFINE ROLE records ON ROOT PERMIT VIEW ON ANY"#);
+	assert!(res.is_err(), "Unexpected successful parsing of root role: {:?}", res);
+	// Roles require a known resource type.
+	let res = test_parse!(parse_stmt, r#"DEFINE ROLE records ON NAMESPACE PERMIT VIEW ON foo"#);
+	assert!(res.is_err(), "Unexpected successful parsing of unknown resource: {:?}", res);
+}
+
 // TODO(gguillemas): This test is kept in 2.0.0 for backward compatibility. Drop in 3.0.0.
 #[test]
 fn parse_define_token() {
@@ -1813,6 +1874,38 @@ fn parse_define_param() {
 	);
 }
 
+#[test]
+fn parse_define_sequence() {
+	let res = test_parse!(parse_stmt, r#"DEFINE SEQUENCE invoice BATCH 50 START -100"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Sequence(DefineSequenceStatement {
+			name: Ident("invoice".to_string()),
+			if_not_exists: false,
+			overwrite: false,
+			batch: 50,
+			start: -100,
+			cache_batches_ts: uuid::Uuid::default(),
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, r#"DEFINE SEQUENCE IF NOT EXISTS ticket"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Sequence(DefineSequenceStatement {
+			name: Ident("ticket".to_string()),
+			if_not_exists: true,
+			overwrite: false,
+			batch: DefineSequenceStatement::DEFAULT_BATCH,
+			start: 0,
+			cache_batches_ts: uuid::Uuid::default(),
+		}))
+	);
+
+	test_parse!(parse_stmt, r#"DEFINE SEQUENCE ticket BATCH 0"#).unwrap_err();
+	test_parse!(parse_stmt, r#"DEFINE SEQUENCE ticket START 1.5"#).unwrap_err();
+}
+
 #[test]
 fn parse_define_table() {
 	let res =
@@ -1862,6 +1955,7 @@ fn parse_define_table() {
 			cache_events_ts: uuid::Uuid::default(),
 			cache_tables_ts: uuid::Uuid::default(),
 			cache_indexes_ts: uuid::Uuid::default(),
+			expire: None,
 		}))
 	);
 }
@@ -1886,6 +1980,137 @@ fn parse_define_event() {
 	)
 }
 
+#[test]
+fn parse_alter_field() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"ALTER FIELD IF EXISTS foo ON TABLE bar TYPE number DROP DEFAULT DROP READONLY COMMENT "test""#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Alter(AlterStatement::Field(AlterFieldStatement {
+			name: Idiom(vec![Part::Field(Ident("foo".to_owned()))]),
+			what: Ident("bar".to_owned()),
+			if_exists: true,
+			kind: Some(Some(Kind::Number)),
+			readonly: Some(false),
+			default: Some(None),
+			comment: Some(Some(Strand("test".to_owned()))),
+			..Default::default()
+		}))
+	);
+	assert_eq!(
+		res.to_string(),
+		"ALTER FIELD IF EXISTS foo ON bar TYPE number DROP DEFAULT DROP READONLY COMMENT 'test'"
+	);
+
+	test_parse!(parse_stmt, r#"ALTER FIELD foo ON bar DROP PERMISSIONS"#).unwrap_err();
+
+	let res = test_parse!(parse_stmt, r#"ALTER FIELD foo.bar ON baz RENAME TO qux"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Alter(AlterStatement::Field(AlterFieldStatement {
+			name: Idiom(vec![
+				Part::Field(Ident("foo".to_owned())),
+				Part::Field(Ident("bar".to_owned())),
+			]),
+			what: Ident("baz".to_owned()),
+			rename: Some(Idiom(vec![Part::Field(Ident("qux".to_owned()))])),
+			..Default::default()
+		}))
+	);
+}
+
+#[test]
+fn parse_alter_table() {
+	let res = test_parse!(parse_stmt, r#"ALTER TABLE IF EXISTS foo RENAME TO bar"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Alter(AlterStatement::Table(AlterTableStatement {
+			name: Ident("foo".to_owned()),
+			if_exists: true,
+			rename: Some(Ident("bar".to_owned())),
+			..Default::default()
+		}))
+	);
+}
+
+#[test]
+fn parse_alter_index() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"ALTER INDEX foo ON bar RENAME TO baz DOC_IDS_CACHE 10 MTREE_CACHE 20 DROP COMMENT"#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Alter(AlterStatement::Index(AlterIndexStatement {
+			name: Ident("foo".to_owned()),
+			what: Ident("bar".to_owned()),
+			rename: Some(Ident("baz".to_owned())),
+			doc_ids_cache: Some(10),
+			mtree_cache: Some(20),
+			comment: Some(None),
+			..Default::default()
+		}))
+	);
+	assert_eq!(
+		res.to_string(),
+		"ALTER INDEX foo ON bar RENAME TO baz DOC_IDS_CACHE 10 MTREE_CACHE 20 DROP COMMENT"
+	);
+}
+
+#[test]
+fn parse_alter_event() {
+	let res =
+		test_parse!(parse_stmt, r#"ALTER EVENT foo ON bar THEN null, none DROP WHEN"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Alter(AlterStatement::Event(AlterEventStatement {
+			name: Ident("foo".to_owned()),
+			what: Ident("bar".to_owned()),
+			when: Some(Value::Bool(true)),
+			then: Some(Values(vec![Value::Null, Value::None])),
+			..Default::default()
+		}))
+	);
+
+	test_parse!(parse_stmt, r#"ALTER EVENT foo ON bar DROP THEN"#).unwrap_err();
+}
+
+#[test]
+fn parse_alter_user() {
+	let res = test_parse!(parse_stmt, r#"ALTER USER IF EXISTS foo ON DATABASE UNLOCK"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Alter(AlterStatement::User(AlterUserStatement {
+			name: Ident("foo".to_owned()),
+			base: Base::Db,
+			if_exists: true,
+			unlock: true,
+		}))
+	);
+	assert_eq!(res.to_string(), "ALTER USER IF EXISTS foo ON DATABASE UNLOCK");
+
+	test_parse!(parse_stmt, r#"ALTER USER foo UNLOCK"#).unwrap_err();
+}
+
+#[test]
+fn parse_alter_access() {
+	let res = test_parse!(parse_stmt, r#"ALTER ACCESS IF EXISTS foo ON DATABASE UNLOCK"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Alter(AlterStatement::Access(AlterAccessStatement {
+			name: Ident("foo".to_owned()),
+			base: Base::Db,
+			if_exists: true,
+			unlock: true,
+		}))
+	);
+	assert_eq!(res.to_string(), "ALTER ACCESS IF EXISTS foo ON DATABASE UNLOCK");
+}
+
 #[test]
 fn parse_define_field() {
 	// General
@@ -2009,7 +2234,9 @@ fn parse_define_index() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
-			concurrently: false
+			concurrently: false,
+			cond: None,
+			include: Idioms(vec![]),
 		}))
 	);
 
@@ -2026,10 +2253,56 @@ fn parse_define_index() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
-			concurrently: false
+			concurrently: false,
+			cond: None,
+			include: Idioms(vec![]),
 		}))
 	);
 
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE INDEX index ON TABLE person FIELDS a UNIQUE WHERE b IS NONE"#
+	)
+	.unwrap();
+	assert_eq!(res.to_string(), "DEFINE INDEX index ON person FIELDS a UNIQUE WHERE b = NONE");
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE INDEX index ON TABLE person FIELDS string::lowercase(a), (b * c), d.e"#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Index(ix)) = &res else {
+		panic!("expected a DEFINE INDEX statement");
+	};
+	assert!(matches!(ix.cols.0[0].0.as_slice(), [Part::Start(Value::Function(_))]));
+	assert!(matches!(ix.cols.0[1].0.as_slice(), [Part::Start(Value::Subquery(_))]));
+	assert_eq!(
+		ix.cols.0[2],
+		Idiom(vec![Part::Field(Ident("d".to_owned())), Part::Field(Ident("e".to_owned()))])
+	);
+	assert_eq!(
+		res.to_string(),
+		"DEFINE INDEX index ON person FIELDS string::lowercase(a), (b * c), d.e"
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE INDEX index ON TABLE person FIELDS a UNIQUE INCLUDE b, c.d"#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Index(ix)) = &res else {
+		panic!("expected a DEFINE INDEX statement");
+	};
+	assert_eq!(ix.index, Index::Uniq);
+	assert_eq!(
+		ix.include,
+		Idioms(vec![
+			Idiom(vec![Part::Field(Ident("b".to_owned()))]),
+			Idiom(vec![Part::Field(Ident("c".to_owned())), Part::Field(Ident("d".to_owned()))]),
+		])
+	);
+	assert_eq!(res.to_string(), "DEFINE INDEX index ON person FIELDS a INCLUDE b, c.d UNIQUE");
+
 	let res =
 		test_parse!(parse_stmt, r#"DEFINE INDEX index ON TABLE table FIELDS a MTREE DIMENSION 4 DISTANCE MINKOWSKI 5 CAPACITY 6 TYPE I16 DOC_IDS_ORDER 7 DOC_IDS_CACHE 8 MTREE_CACHE 9"#).unwrap();
 
@@ -2051,7 +2324,9 @@ fn parse_define_index() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
-			concurrently: false
+			concurrently: false,
+			cond: None,
+			include: Idioms(vec![]),
 		}))
 	);
 
@@ -2078,7 +2353,9 @@ fn parse_define_index() {
 			comment: None,
 			if_not_exists: false,
 			overwrite: false,
-			concurrently: false
+			concurrently: false,
+			cond: None,
+			include: Idioms(vec![]),
 		}))
 	);
 }
@@ -2360,10 +2637,147 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
 			parallel: false,
 			tempfiles: false,
 			explain: Some(Explain(true)),
+			distinct: false,
+			combine: Vec::new(),
 		}),
 	);
 }
 
+#[test]
+fn parse_select_set_operators() {
+	let sql =
+		"SELECT DISTINCT name FROM a UNION ALL SELECT name FROM b EXCEPT SELECT distinct FROM c";
+	let res = test_parse!(parse_stmt, sql).unwrap();
+	let Statement::Select(res) = res else {
+		panic!("expected a SELECT statement")
+	};
+	assert!(res.distinct);
+	assert_eq!(res.combine.len(), 2);
+	assert_eq!(res.combine[0].operator, SetOperator::UnionAll);
+	assert!(!res.combine[0].select.distinct);
+	assert_eq!(res.combine[1].operator, SetOperator::Except);
+	assert!(!res.combine[1].select.distinct);
+	assert_eq!(
+		res.combine[1].select.expr,
+		Fields(
+			vec![Field::Single {
+				expr: Value::Idiom(Idiom(vec![Part::Field(Ident("distinct".to_owned()))])),
+				alias: None,
+			}],
+			false,
+		)
+	);
+	assert_eq!(res.to_string(), sql);
+	// The new keywords are not reserved, so existing names are neither rejected nor escaped
+	for sql in [
+		"SELECT union, intersect, except, distinct FROM union, intersect, except, distinct",
+		"CREATE union:1 SET intersect = true, except = distinct",
+		"DEFINE TABLE except TYPE ANY SCHEMALESS PERMISSIONS NONE",
+	] {
+		let res = test_parse!(parse_stmt, sql).unwrap();
+		assert_eq!(res.to_string(), sql);
+	}
+}
+
+#[test]
+fn parse_select_set_operators_paging() {
+	let sql = "SELECT name FROM a UNION SELECT name FROM b ORDER BY name LIMIT 2 START 1";
+	let res = test_parse!(parse_stmt, sql).unwrap();
+	let Statement::Select(res) = res else {
+		panic!("expected a SELECT statement")
+	};
+	assert!(res.order.is_some());
+	assert!(res.limit.is_some());
+	assert!(res.start.is_some());
+	assert!(res.combine[0].select.order.is_none());
+	assert!(res.combine[0].select.limit.is_none());
+	assert!(res.combine[0].select.start.is_none());
+	assert_eq!(
+		res.to_string(),
+		"SELECT name FROM a UNION SELECT name FROM b ORDER BY name\n LIMIT 2 START 1"
+	);
+	// The clauses can only follow the last SELECT
+	test_parse!(parse_stmt, "SELECT name FROM a LIMIT 1 UNION SELECT name FROM b").unwrap_err();
+}
+
+#[test]
+fn parse_select_set_operators_trailing_clauses() {
+	let sql = "SELECT name, friend FROM a UNION SELECT name, friend FROM b LIMIT 5 FETCH friend TIMEOUT 1s PARALLEL TEMPFILES";
+	let res = test_parse!(parse_stmt, sql).unwrap();
+	let Statement::Select(res) = res else {
+		panic!("expected a SELECT statement")
+	};
+	assert!(res.limit.is_some());
+	assert!(res.fetch.is_some());
+	assert!(res.timeout.is_some());
+	assert!(res.parallel);
+	assert!(res.tempfiles);
+	assert!(res.combine[0].select.fetch.is_none());
+	assert!(res.combine[0].select.timeout.is_none());
+	assert!(!res.combine[0].select.parallel);
+	assert!(!res.combine[0].select.tempfiles);
+	assert_eq!(res.to_string(), sql);
+	// The output can be parsed again
+	let out = res.to_string();
+	let again = test_parse!(parse_stmt, &out).unwrap();
+	assert_eq!(again, Statement::Select(res));
+	// The clauses can only follow the last SELECT
+	for sql in [
+		"SELECT name FROM a FETCH name UNION SELECT name FROM b",
+		"SELECT name FROM a TIMEOUT 1s UNION SELECT name FROM b",
+		"SELECT name FROM a UNION SELECT name FROM b LIMIT 5 EXPLAIN",
+	] {
+		test_parse!(parse_stmt, sql).unwrap_err();
+	}
+}
+
+#[test]
+fn parse_select_window_functions() {
+	let sql = "SELECT math::sum(amount) OVER (PARTITION BY account ORDER BY ts) AS total, window::row_number() OVER (ORDER BY ts DESC) FROM payment";
+	let res = test_parse!(parse_stmt, sql).unwrap();
+	let Statement::Select(res) = res else {
+		panic!("expected a SELECT statement")
+	};
+	assert_eq!(
+		res.expr.0[1],
+		Field::Window {
+			expr: Box::new(Window {
+				func: Function::Normal("window::row_number".to_owned(), vec![]),
+				partition: vec![],
+				order: Some(OrderList(vec![Order {
+					value: Idiom(vec![Part::Field(Ident("ts".to_owned()))]),
+					collate: false,
+					numeric: false,
+					direction: false,
+				}])),
+			}),
+			alias: None,
+		}
+	);
+	assert_eq!(res.to_string(), sql);
+	// Only window functions and aggregate functions can be computed over a window
+	test_parse!(parse_stmt, "SELECT string::len(name) OVER () FROM person").unwrap_err();
+	test_parse!(parse_stmt, "SELECT row_number(1) OVER () FROM person").unwrap_err();
+	// Window functions are not computed for grouped or live selections
+	test_parse!(parse_stmt, "SELECT count() OVER () FROM person GROUP ALL").unwrap_err();
+	test_parse!(parse_stmt, "LIVE SELECT window::rank() OVER () FROM person").unwrap_err();
+	// Aggregates which can not be updated row by row are not computed over an ordered window
+	test_parse!(parse_stmt, "SELECT math::median(age) OVER (ORDER BY age) FROM person")
+		.unwrap_err();
+	test_parse!(parse_stmt, "SELECT math::median(age) OVER (PARTITION BY name) FROM person")
+		.unwrap();
+	// The new keywords are not reserved, so existing names are neither rejected nor escaped
+	for sql in [
+		"SELECT over, partition FROM over, partition",
+		"SELECT math::sum(over) OVER (PARTITION BY partition ORDER BY over) FROM partition",
+		"CREATE over:1 SET partition = true",
+		"DEFINE TABLE partition TYPE ANY SCHEMALESS PERMISSIONS NONE",
+	] {
+		let res = test_parse!(parse_stmt, sql).unwrap();
+		assert_eq!(res.to_string(), sql);
+	}
+}
+
 #[test]
 fn parse_let() {
 	let res = test_parse!(parse_stmt, r#"LET $param = 1"#).unwrap();
@@ -2502,6 +2916,62 @@ fn parse_throw() {
 	assert_eq!(res, expect)
 }
 
+#[test]
+fn parse_try() {
+	let res = test_parse!(parse_stmt, r"TRY { THROW 1s } CATCH ($err) { $err }").unwrap();
+
+	let expect = Statement::Trycatch(TrycatchStatement {
+		block: Block(vec![Entry::Throw(ThrowStatement {
+			error: Value::Duration(Duration(std::time::Duration::from_secs(1))),
+		})]),
+		param: Some(Param(Ident("err".to_owned()))),
+		catch: Block(vec![Entry::Value(Value::Param(Param(Ident("err".to_owned()))))]),
+	});
+	assert_eq!(res, expect);
+
+	let res = test_parse!(parse_stmt, r"TRY {} CATCH {}").unwrap();
+	assert_eq!(res.to_string(), "TRY {} CATCH {}");
+}
+
+#[test]
+fn parse_truncate() {
+	let res = test_parse!(parse_stmt, r"TRUNCATE TABLE IF EXISTS foo WITH NOEVENTS").unwrap();
+
+	let expect = Statement::Truncate(TruncateStatement {
+		name: Ident("foo".to_owned()),
+		if_exists: true,
+		skip_events: true,
+	});
+	assert_eq!(res, expect);
+
+	let res = test_parse!(parse_stmt, r"TRUNCATE TABLE foo").unwrap();
+	assert_eq!(res.to_string(), "TRUNCATE TABLE foo");
+}
+
+#[test]
+fn parse_while() {
+	let res = test_parse!(parse_stmt, r"WHILE $node { LET $node = $node.next; BREAK }").unwrap();
+
+	let expect = Statement::While(WhileStatement {
+		cond: Value::Param(Param(Ident("node".to_owned()))),
+		block: Block(vec![
+			Entry::Set(SetStatement {
+				name: "node".to_owned(),
+				what: Value::Idiom(Idiom(vec![
+					Part::Start(Value::Param(Param(Ident("node".to_owned())))),
+					Part::Field(Ident("next".to_owned())),
+				])),
+				kind: None,
+			}),
+			Entry::Break(BreakStatement),
+		]),
+	});
+	assert_eq!(res, expect);
+
+	let res = test_parse!(parse_stmt, r"WHILE true {}").unwrap();
+	assert_eq!(res.to_string(), "WHILE true {}");
+}
+
 #[test]
 fn parse_insert() {
 	let res = test_parse!(
@@ -2601,7 +3071,9 @@ fn parse_insert_select() {
 					timeout: None,
 					parallel: false,
 					explain: None,
-					tempfiles: false
+					tempfiles: false,
+					distinct: false,
+					combine: Vec::new(),
 				}
 			)))),
 			ignore: true,
@@ -2796,6 +3268,15 @@ fn parse_remove() {
 		}))
 	);
 
+	let res = test_parse!(parse_stmt, r#"REMOVE SEQUENCE IF EXISTS foo"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Sequence(RemoveSequenceStatement {
+			name: Ident("foo".to_owned()),
+			if_exists: true,
+		}))
+	);
+
 	let res = test_parse!(parse_stmt, r#"REMOVE TABLE foo"#).unwrap();
 	assert_eq!(
 		res,
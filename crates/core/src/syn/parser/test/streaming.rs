@@ -296,6 +296,7 @@ fn statements() -> Vec<Statement> {
 			cache_events_ts: uuid::Uuid::default(),
 			cache_tables_ts: uuid::Uuid::default(),
 			cache_indexes_ts: uuid::Uuid::default(),
+			expire: None,
 		})),
 		Statement::Define(DefineStatement::Event(DefineEventStatement {
 			name: Ident("event".to_owned()),
@@ -362,6 +363,8 @@ fn statements() -> Vec<Statement> {
 			if_not_exists: false,
 			overwrite: false,
 			concurrently: false,
+			cond: None,
+			include: Idioms(vec![]),
 		})),
 		Statement::Define(DefineStatement::Index(DefineIndexStatement {
 			name: Ident("index".to_owned()),
@@ -372,6 +375,8 @@ fn statements() -> Vec<Statement> {
 			if_not_exists: false,
 			overwrite: false,
 			concurrently: false,
+			cond: None,
+			include: Idioms(vec![]),
 		})),
 		Statement::Define(DefineStatement::Index(DefineIndexStatement {
 			name: Ident("index".to_owned()),
@@ -390,6 +395,8 @@ fn statements() -> Vec<Statement> {
 			if_not_exists: false,
 			overwrite: false,
 			concurrently: false,
+			cond: None,
+			include: Idioms(vec![]),
 		})),
 		Statement::Define(DefineStatement::Analyzer(DefineAnalyzerStatement {
 			name: Ident("ana".to_owned()),
@@ -540,6 +547,8 @@ fn statements() -> Vec<Statement> {
 			parallel: false,
 			tempfiles: false,
 			explain: Some(Explain(true)),
+			distinct: false,
+			combine: Vec::new(),
 		}),
 		Statement::Set(SetStatement {
 			name: "param".to_owned(),
@@ -5,8 +5,9 @@ use rust_decimal::Decimal;
 
 use crate::{
 	sql::{
-		Array, Constant, Expression, Geometry, Id, Ident, Idiom, Number, Object, Operator, Part,
-		Query, Statement, Statements, Strand, Thing, Value,
+		Array, Constant, Expression, Function, Geometry, Id, Ident, Idiom, Kind, Match, MatchArm,
+		Number, Object, Operator, Part, Pattern, Query, Statement, Statements, Strand, Thing,
+		Value,
 	},
 	syn::parser::{mac::test_parse, Parser, ParserSettings},
 };
@@ -259,6 +260,76 @@ fn number_method() {
 	assert_eq!(res, expected);
 }
 
+#[test]
+fn count_distinct() {
+	let res = test_parse!(parse_value_field, "count(DISTINCT owner)").unwrap();
+	let expected = Value::Function(Box::new(Function::Normal(
+		crate::fnc::count::DISTINCT_FUNCTION.to_string(),
+		vec![Value::Idiom(Idiom(vec![Part::Field(Ident("owner".to_string()))]))],
+	)));
+	assert_eq!(res, expected);
+	assert_eq!(res.to_string(), "count(DISTINCT owner)");
+	// The function behind `count(DISTINCT value)` can not be called by name
+	test_parse!(parse_value_field, "count::distinct(owner)").unwrap_err();
+
+	let res = test_parse!(parse_value_field, "count(distinct)").unwrap();
+	let expected = Value::Function(Box::new(Function::Normal(
+		"count".to_string(),
+		vec![Value::Idiom(Idiom(vec![Part::Field(Ident("distinct".to_string()))]))],
+	)));
+	assert_eq!(res, expected);
+}
+
+#[test]
+fn match_expression() {
+	let res = test_parse!(
+		parse_value_field,
+		r#"MATCH $x { <int> => "int", 1..10 => "range", { status: "x", age: _ } => "obj", _ => "other", }"#
+	)
+	.unwrap();
+	let Value::Match(m) = &res else {
+		panic!("not the right value type");
+	};
+	assert_eq!(m.value, Value::Param("x".into()));
+	assert_eq!(
+		m.arms[0],
+		MatchArm {
+			pattern: Pattern::Kind(Kind::Int),
+			then: Value::Strand("int".into()),
+		}
+	);
+	assert!(matches!(m.arms[1].pattern, Pattern::Value(Value::Range(_))));
+	assert_eq!(
+		m.arms[2].pattern,
+		Pattern::Object(vec![
+			("status".to_string(), Pattern::Value(Value::Strand("x".into()))),
+			("age".to_string(), Pattern::Any),
+		])
+	);
+	assert_eq!(m.arms[3].pattern, Pattern::Any);
+	assert_eq!(
+		res.to_string(),
+		"MATCH $x { <int> => 'int', 1..10 => 'range', { status: 'x', age: _ } => 'obj', _ => 'other' }"
+	);
+
+	let res = test_parse!(parse_value_field, "MATCH $x {}").unwrap();
+	assert_eq!(
+		res,
+		Value::Match(Box::new(Match {
+			value: Value::Param("x".into()),
+			arms: Vec::new(),
+		}))
+	);
+
+	// The keyword can still be used as a field name
+	let res = test_parse!(parse_value_field, "match.a").unwrap();
+	let expected = Value::Idiom(Idiom(vec![
+		Part::Field(Ident("match".to_string())),
+		Part::Field(Ident("a".to_string())),
+	]));
+	assert_eq!(res, expected);
+}
+
 #[test]
 fn datetime_error() {
 	test_parse!(parse_value_field, r#" d"2001-01-01T01:01:01.9999999999" "#).unwrap_err();
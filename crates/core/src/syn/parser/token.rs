@@ -25,14 +25,18 @@ impl Parser<'_> {
 				| t!("REBUILD")
 				| t!("RETURN")
 				| t!("RELATE")
+				| t!("RELEASE")
 				| t!("REMOVE")
+				| t!("ROLLBACK")
+				| t!("SAVEPOINT")
 				| t!("SELECT")
 				| t!("LET") | t!("SHOW")
 				| t!("SLEEP")
 				| t!("THROW")
-				| t!("UPDATE")
+				| t!("TRUNCATE")
+				| t!("TRY") | t!("UPDATE")
 				| t!("UPSERT")
-				| t!("USE")
+				| t!("USE") | t!("WHILE")
 		)
 	}
 
@@ -128,10 +132,14 @@ impl Parser<'_> {
 				| t!("FOR") | t!("INFO")
 				| t!("KILL") | t!("LIVE")
 				| t!("OPTION")
-				| t!("LET") | t!("SHOW")
-				| t!("SLEEP")
+				| t!("LET") | t!("RELEASE")
+				| t!("ROLLBACK")
+				| t!("SAVEPOINT")
+				| t!("SHOW") | t!("SLEEP")
 				| t!("THROW")
-				| t!("USE")
+				| t!("TRUNCATE")
+				| t!("TRY") | t!("USE")
+				| t!("WHILE")
 		)
 	}
 }
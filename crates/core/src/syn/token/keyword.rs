@@ -1,7 +1,7 @@
 macro_rules! keyword {
 	($($name:ident => $value:tt),* $(,)?) => {
 
-		#[repr(u8)]
+		#[repr(u16)]
 		#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
 		#[non_exhaustive] pub enum Keyword{
 			$($name,)*
@@ -40,6 +40,7 @@ keyword! {
 	At => "AT",
 	Authenticate => "AUTHENTICATE",
 	Auto => "AUTO",
+	Batch => "BATCH",
 	Bearer => "BEARER",
 	Before => "BEFORE",
 	Begin => "BEGIN",
@@ -54,6 +55,7 @@ keyword! {
 	ChangeFeed => "CHANGEFEED",
 	Changes => "CHANGES",
 	Capacity => "CAPACITY",
+	Catch => "CATCH",
 	Class => "CLASS",
 	Comment => "COMMENT",
 	Commit => "COMMIT",
@@ -70,6 +72,7 @@ keyword! {
 	Diff => "DIFF",
 	Dimension => "DIMENSION",
 	Distance => "DISTANCE",
+	Distinct => "DISTINCT",
 	DocIdsCache => "DOC_IDS_CACHE",
 	DocIdsOrder => "DOC_IDS_ORDER",
 	DocLengthsCache => "DOC_LENGTHS_CACHE",
@@ -82,8 +85,10 @@ keyword! {
 	Else => "ELSE",
 	End => "END",
 	Enforced => "ENFORCED",
+	Except => "EXCEPT",
 	Exclude => "EXCLUDE",
 	Exists => "EXISTS",
+	Expire => "EXPIRE",
 	Expired => "EXPIRED",
 	Explain => "EXPLAIN",
 	Expunge => "EXPUNGE",
@@ -110,6 +115,7 @@ keyword! {
 	Index => "INDEX",
 	Info => "INFO",
 	Insert => "INSERT",
+	Intersect => "INTERSECT",
 	Into => "INTO",
 	If => "IF",
 	Is => "IS",
@@ -127,6 +133,7 @@ keyword! {
 	M => "M",
 	M0 => "M0",
 	Mapper => "MAPPER",
+	Match => "MATCH",
 	Middleware => "MIDDLEWARE",
 	Merge => "MERGE",
 	Model => "MODEL",
@@ -135,6 +142,7 @@ keyword! {
 	Namespace => "NAMESPACE",
 	Ngram => "NGRAM",
 	No => "NO",
+	NoEvents => "NOEVENTS",
 	NoIndex => "NOINDEX",
 	None => "NONE",
 	Null => "NULL",
@@ -145,13 +153,16 @@ keyword! {
 	Option => "OPTION",
 	Order => "ORDER",
 	Original => "ORIGINAL",
+	Over => "OVER",
 	Overwrite => "OVERWRITE",
 	Parallel => "PARALLEL",
 	Param => "PARAM",
+	Partition => "PARTITION",
 	Passhash => "PASSHASH",
 	Password => "PASSWORD",
 	Patch => "PATCH",
 	Permissions => "PERMISSIONS",
+	Permit => "PERMIT",
 	PostingsCache => "POSTINGS_CACHE",
 	PostingsOrder => "POSTINGS_ORDER",
 	Punct => "PUNCT",
@@ -163,18 +174,24 @@ keyword! {
 	Refresh => "REFRESH",
 	Relate => "RELATE",
 	Relation => "RELATION",
+	Release => "RELEASE",
 	Remove => "REMOVE",
+	Rename => "RENAME",
 	Replace => "REPLACE",
 	Return => "RETURN",
 	Revoke => "REVOKE",
 	Revoked => "REVOKED",
+	Role => "ROLE",
 	Roles => "ROLES",
+	Rollback => "ROLLBACK",
 	Root => "ROOT",
+	Savepoint => "SAVEPOINT",
 	Schemafull => "SCHEMAFULL",
 	Schemaless => "SCHEMALESS",
 	Scope => "SCOPE",
 	Search => "SEARCH",
 	Select => "SELECT",
+	Sequence => "SEQUENCE",
 	Session => "SESSION",
 	Set => "SET",
 	Show => "SHOW",
@@ -198,9 +215,13 @@ keyword! {
 	Token => "TOKEN",
 	To => "TO",
 	Transaction => "TRANSACTION",
+	Truncate => "TRUNCATE",
+	Try => "TRY",
 	True => "true",
 	Type => "TYPE",
+	Union => "UNION",
 	Unique => "UNIQUE",
+	Unlock => "UNLOCK",
 	Unset => "UNSET",
 	Update => "UPDATE",
 	Upsert => "UPSERT",
@@ -214,6 +235,7 @@ keyword! {
 	Vs => "VS",
 	When => "WHEN",
 	Where => "WHERE",
+	While => "WHILE",
 	With => "WITH",
 	AllInside => "ALLINSIDE",
 	AndKw => "ANDKW",
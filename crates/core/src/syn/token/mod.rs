@@ -401,8 +401,10 @@ impl fmt::Display for TokenKind {
 	}
 }
 
-/// An assertion statically checking that the size of Tokenkind remains two bytes
-const _TOKEN_KIND_SIZE_ASSERT: [(); 2] = [(); std::mem::size_of::<TokenKind>()];
+/// An assertion statically checking that the size of Tokenkind remains four bytes.
+///
+/// There are more than 256 keywords, so keywords need a two byte discriminant.
+const _TOKEN_KIND_SIZE_ASSERT: [(); 4] = [(); std::mem::size_of::<TokenKind>()];
 
 impl TokenKind {
 	pub fn has_data(&self) -> bool {
@@ -8,6 +8,7 @@ use std::borrow::Cow;
 use std::io::Read;
 use std::path::PathBuf;
 use surrealdb_core::kvs::export::Config as DbExportConfig;
+use surrealdb_core::kvs::import::Config as DbImportConfig;
 use surrealdb_core::sql::{Array as CoreArray, Object as CoreObject, Query, Value as CoreValue};
 use uuid::Uuid;
 
@@ -94,6 +95,7 @@ pub(crate) enum Command {
 	},
 	ImportFile {
 		path: PathBuf,
+		config: Option<DbImportConfig>,
 	},
 	ImportMl {
 		path: PathBuf,
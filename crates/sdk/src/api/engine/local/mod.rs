@@ -985,6 +985,7 @@ async fn router(
 		#[cfg(not(target_family = "wasm"))]
 		Command::ImportFile {
 			path,
+			config,
 		} => {
 			let mut file = match OpenOptions::new().read(true).open(&path).await {
 				Ok(path) => path,
@@ -1021,9 +1022,19 @@ async fn router(
 				}
 			});
 
-			let responses = kvs
-				.execute_import(&*session.read().await, Some(vars.read().await.clone()), stream)
-				.await?;
+			let responses = match config {
+				Some(config) => {
+					kvs.import_with_config(&*session.read().await, config, stream).await?
+				}
+				None => {
+					kvs.execute_import(
+						&*session.read().await,
+						Some(vars.read().await.clone()),
+						stream,
+					)
+					.await?
+				}
+			};
 
 			for response in responses {
 				response.result?;
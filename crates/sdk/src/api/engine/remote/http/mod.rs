@@ -515,13 +515,24 @@ async fn router(
 		#[cfg(not(target_family = "wasm"))]
 		Command::ImportFile {
 			path,
+			config,
 		} => {
 			let req_path = base_url.join("import")?;
-			let request = client
+			let mut request = client
 				.post(req_path)
 				.headers(headers.clone())
 				.auth(auth)
 				.header(CONTENT_TYPE, "application/octet-stream");
+			if let Some(config) = config {
+				request = request.query(&[("format", config.format.to_string())]);
+				if let Some(table) = config.table {
+					request = request.query(&[("table", table)]);
+				}
+				if let Some(columns) = config.columns {
+					let columns: Vec<String> = columns.iter().map(ToString::to_string).collect();
+					request = request.query(&[("columns", columns.join(","))]);
+				}
+			}
 			import(request, path).await?;
 			Ok(DbResponse::Other(CoreValue::None))
 		}
@@ -8,3 +8,5 @@ pub static DB: HeaderName = HeaderName::from_static("surreal-db");
 pub static AUTH_NS: HeaderName = HeaderName::from_static("surreal-auth-ns");
 pub static AUTH_DB: HeaderName = HeaderName::from_static("surreal-auth-db");
 pub static VERSION: HeaderName = HeaderName::from_static("surreal-version");
+pub static VERSIONSTAMP: HeaderName = HeaderName::from_static("surreal-versionstamp");
+pub static DEFINITIONS: HeaderName = HeaderName::from_static("surreal-definitions");
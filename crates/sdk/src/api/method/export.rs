@@ -20,7 +20,7 @@ use std::path::PathBuf;
 use std::pin::Pin;
 use std::task::Context;
 use std::task::Poll;
-use surrealdb_core::kvs::export::{Config as DbExportConfig, TableConfig};
+use surrealdb_core::kvs::export::{Column, Config as DbExportConfig, Format, TableConfig};
 
 /// A database export future
 #[derive(Debug)]
@@ -95,6 +95,14 @@ where
 		self
 	}
 
+	/// Whether to export sequences from the database
+	pub fn sequences(mut self, sequences: bool) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.sequences = sequences;
+		}
+		self
+	}
+
 	/// Whether to export functions from the database
 	pub fn functions(mut self, functions: bool) -> Self {
 		if let Some(cfg) = self.db_config.as_mut() {
@@ -149,6 +157,31 @@ where
 		}
 		self
 	}
+
+	/// The format of the export
+	///
+	/// CSV and NDJSON exports contain the records of a single table:
+	/// ```
+	/// # let db = surrealdb::Surreal::<surrealdb::engine::any::Any>::init();
+	/// # let target = ();
+	/// use surrealdb::kvs::export::Format;
+	///
+	/// db.export(target).with_config().format(Format::Csv).tables(vec!["person"]);
+	/// ```
+	pub fn format(mut self, format: Format) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.format = format;
+		}
+		self
+	}
+
+	/// Which record fields to export in a CSV or NDJSON export, and the columns they map onto
+	pub fn columns(mut self, columns: Vec<Column>) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.columns = Some(columns);
+		}
+		self
+	}
 }
 
 impl<C, R, T> Export<'_, C, R, T>
@@ -4,6 +4,7 @@ use crate::api::Connection;
 use crate::api::Error;
 use crate::api::ExtraFeatures;
 use crate::api::Result;
+use crate::method::ImportConfig as Config;
 use crate::method::Model;
 use crate::method::OnceLockExt;
 use crate::Surreal;
@@ -11,6 +12,7 @@ use std::borrow::Cow;
 use std::future::IntoFuture;
 use std::marker::PhantomData;
 use std::path::PathBuf;
+use surrealdb_core::kvs::import::{Column, Config as DbImportConfig, Format};
 
 /// An database import future
 #[derive(Debug)]
@@ -19,6 +21,7 @@ pub struct Import<'r, C: Connection, T = ()> {
 	pub(super) client: Cow<'r, Surreal<C>>,
 	pub(super) file: PathBuf,
 	pub(super) is_ml: bool,
+	pub(super) db_config: Option<DbImportConfig>,
 	pub(super) import_type: PhantomData<T>,
 }
 
@@ -32,9 +35,67 @@ where
 			client: self.client,
 			file: self.file,
 			is_ml: true,
+			db_config: self.db_config,
 			import_type: PhantomData,
 		}
 	}
+
+	/// Configure the import options
+	pub fn with_config(self) -> Import<'r, C, Config> {
+		Import {
+			client: self.client,
+			file: self.file,
+			is_ml: self.is_ml,
+			// Use default configuration options
+			db_config: Some(Default::default()),
+			import_type: PhantomData,
+		}
+	}
+}
+
+impl<C> Import<'_, C, Config>
+where
+	C: Connection,
+{
+	/// The format of the file to import
+	///
+	/// CSV and NDJSON files are imported as records into a single table:
+	/// ```
+	/// # let db = surrealdb::Surreal::<surrealdb::engine::any::Any>::init();
+	/// use surrealdb::kvs::import::Format;
+	///
+	/// db.import("people.csv").with_config().format(Format::Csv).table("person");
+	/// ```
+	pub fn format(mut self, format: Format) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.format = format;
+		}
+		self
+	}
+
+	/// The table to import CSV or NDJSON records into
+	pub fn table(mut self, table: impl Into<String>) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.table = Some(table.into());
+		}
+		self
+	}
+
+	/// Which columns to import, and the record fields they map onto
+	///
+	/// ```
+	/// # let db = surrealdb::Surreal::<surrealdb::engine::any::Any>::init();
+	/// use surrealdb::kvs::import::Format;
+	///
+	/// let columns = vec!["id".parse().unwrap(), "city=address.city".parse().unwrap()];
+	/// db.import("people.csv").with_config().format(Format::Csv).table("person").columns(columns);
+	/// ```
+	pub fn columns(mut self, columns: Vec<Column>) -> Self {
+		if let Some(cfg) = self.db_config.as_mut() {
+			cfg.columns = Some(columns);
+		}
+		self
+	}
 }
 
 impl<C, T> Import<'_, C, T>
@@ -75,6 +136,7 @@ where
 			router
 				.execute_unit(Command::ImportFile {
 					path: self.file,
+					config: self.db_config,
 				})
 				.await
 		})
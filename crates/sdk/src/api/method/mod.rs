@@ -118,6 +118,9 @@ pub struct Model;
 /// Marker type for configured exports
 pub struct ExportConfig;
 
+/// Marker type for configured imports
+pub struct ImportConfig;
+
 /// Live query marker type
 pub struct Live;
 
@@ -1429,6 +1432,7 @@ where
 			client: Cow::Borrowed(self),
 			file: file.as_ref().to_owned(),
 			is_ml: false,
+			db_config: None,
 			import_type: PhantomData,
 		}
 	}
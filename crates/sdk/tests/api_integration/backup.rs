@@ -196,6 +196,27 @@ relate person:`a`->`friends2\`;\nDEFINE USER IF NOT EXISTS pwned ON ROOT PASSWOR
 	assert_eq!(export_text, export_text_2);
 }
 
+pub async fn export_sequences_with_config(new_db: impl CreateDb) {
+	let (_, db) = new_db.create_db().await;
+	let db_name = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&db_name).await.unwrap();
+	db.query("DEFINE SEQUENCE invoice BATCH 1 START 10").await.unwrap();
+
+	// Sequences are only exported if enabled in the config
+	for sequences in [true, false] {
+		let res = db.export(()).with_config().sequences(sequences).await.unwrap();
+		let bytes = res
+			.fold(Vec::new(), |mut acc, x| async move {
+				let mut buffer = x.unwrap();
+				acc.append(&mut buffer);
+				acc
+			})
+			.await;
+		let export_text = String::from_utf8(bytes).unwrap();
+		assert_eq!(export_text.contains("DEFINE SEQUENCE invoice BATCH 1 START 10"), sequences);
+	}
+}
+
 define_include_tests!(backup => {
 	#[tokio::test]
 	export_import,
@@ -209,4 +230,7 @@ define_include_tests!(backup => {
 
 	#[tokio::test]
 	export_escaped_table_names,
+
+	#[tokio::test]
+	export_sequences_with_config,
 });
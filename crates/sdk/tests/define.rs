@@ -76,6 +76,7 @@ async fn define_statement_database() -> Result<(), Error> {
 		"{
 			accesses: {},
 			databases: { test: 'DEFINE DATABASE test' },
+			roles: {},
 			users: {},
 		}",
 	);
@@ -349,6 +350,8 @@ async fn define_statement_analyzer() -> Result<(), Error> {
 			},
 			models: {},
 			params: {},
+			roles: {},
+			sequences: {},
 			tables: {},
 			users: {},
 		}"#,
@@ -356,6 +359,100 @@ async fn define_statement_analyzer() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn define_statement_sequence() -> Result<(), Error> {
+	let sql = r#"
+		DEFINE SEQUENCE invoice BATCH 2 START 100;
+		DEFINE SEQUENCE invoice;
+		INFO FOR DB;
+		RETURN sequence::currval('invoice');
+		BEGIN;
+		[sequence::nextval('invoice'), sequence::nextval('invoice'), sequence::nextval('invoice')];
+		sequence::currval('invoice');
+		COMMIT;
+		RETURN sequence::currval('invoice');
+	"#;
+	let mut t = Test::new(sql).await?;
+	t.expect_size(7)?;
+	t.skip_ok(1)?;
+	t.expect_error("The sequence 'invoice' already exists")?;
+	t.expect_val(
+		r#"{
+			accesses: {},
+			analyzers: {},
+			apis: {},
+			configs: {},
+			functions: {},
+			models: {},
+			params: {},
+			roles: {},
+			sequences: {
+				invoice: 'DEFINE SEQUENCE invoice BATCH 2 START 100'
+			},
+			tables: {},
+			users: {},
+		}"#,
+	)?;
+	t.expect_val("NONE")?;
+	t.expect_val("[100, 101, 102]")?;
+	t.expect_val("102")?;
+	t.expect_val("NONE")?;
+	// The values reserved before the restart are not handed out again
+	let sql = r#"
+		RETURN sequence::nextval('invoice');
+		DEFINE SEQUENCE OVERWRITE invoice BATCH 2 START 0;
+		RETURN sequence::nextval('invoice');
+		DEFINE SEQUENCE OVERWRITE invoice BATCH 2 START 200;
+		RETURN sequence::nextval('invoice');
+		REMOVE SEQUENCE invoice;
+		RETURN sequence::nextval('invoice');
+	"#;
+	let mut t = t.restart(sql).await?;
+	t.expect_size(7)?;
+	t.expect_val("104")?;
+	t.skip_ok(1)?;
+	// Redefining the sequence keeps the values already reserved
+	t.expect_val("106")?;
+	t.skip_ok(1)?;
+	t.expect_val("200")?;
+	t.skip_ok(1)?;
+	t.expect_error("The sequence 'invoice' does not exist")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_statement_sequence_gap_free() -> Result<(), Error> {
+	// A sequence with a batch of one takes each value within its transaction
+	let sql = r#"
+		DEFINE SEQUENCE invoice BATCH 1 START 1;
+		BEGIN;
+		sequence::nextval('invoice');
+		CANCEL;
+		RETURN sequence::nextval('invoice');
+		BEGIN;
+		sequence::nextval('invoice');
+		THROW 'failed';
+		COMMIT;
+		RETURN sequence::nextval('invoice');
+	"#;
+	let mut t = Test::new(sql).await?;
+	t.expect_size(6)?;
+	t.skip_ok(1)?;
+	t.expect_error("The query was not executed due to a cancelled transaction")?;
+	// The value taken in the cancelled transaction is handed out again
+	t.expect_val("1")?;
+	t.expect_error("The query was not executed due to a failed transaction")?;
+	t.expect_error("An error occurred: failed")?;
+	// The value taken in the failed transaction is handed out again
+	t.expect_val("2")?;
+	// No values are skipped after a restart
+	let sql = "RETURN sequence::nextval('invoice');";
+	let mut t = t.restart(sql).await?;
+	t.expect_size(1)?;
+	t.expect_val("3")?;
+	Ok(())
+}
+
 #[tokio::test]
 async fn define_statement_search_index() -> Result<(), Error> {
 	let sql = r#"
@@ -631,8 +728,10 @@ async fn permissions_checks_define_db() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-		vec!["{ accesses: {  }, databases: { DB: 'DEFINE DATABASE DB' }, users: {  } }"],
-		vec!["{ accesses: {  }, databases: {  }, users: {  } }"],
+		vec![
+			"{ accesses: {  }, databases: { DB: 'DEFINE DATABASE DB' }, roles: {  }, users: {  } }",
+		],
+		vec!["{ accesses: {  }, databases: {  }, roles: {  }, users: {  } }"],
 	];
 
 	let test_cases = [
@@ -673,8 +772,8 @@ async fn permissions_checks_define_function() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: { greet: \"DEFINE FUNCTION fn::greet() { RETURN 'Hello'; } PERMISSIONS FULL\" }, models: {  }, params: {  }, tables: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: { greet: \"DEFINE FUNCTION fn::greet() { RETURN 'Hello'; } PERMISSIONS FULL\" }, models: {  }, params: {  }, roles: {  }, sequences: {  }, tables: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, sequences: {  }, tables: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -715,8 +814,8 @@ async fn permissions_checks_define_analyzer() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: { analyzer: 'DEFINE ANALYZER analyzer TOKENIZERS BLANK' }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, analyzers: { analyzer: 'DEFINE ANALYZER analyzer TOKENIZERS BLANK' }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, sequences: {  }, tables: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, sequences: {  }, tables: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -798,8 +897,8 @@ async fn permissions_checks_define_access_ns() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: { access: \"DEFINE ACCESS access ON NAMESPACE TYPE JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE\" }, databases: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, databases: {  }, users: {  } }"]
+        vec!["{ accesses: { access: \"DEFINE ACCESS access ON NAMESPACE TYPE JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE\" }, databases: {  }, roles: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, databases: {  }, roles: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -840,8 +939,8 @@ async fn permissions_checks_define_access_db() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: { access: \"DEFINE ACCESS access ON DATABASE TYPE JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE\" }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: { access: \"DEFINE ACCESS access ON DATABASE TYPE JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 1h, FOR SESSION NONE\" }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, sequences: {  }, tables: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, sequences: {  }, tables: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -923,8 +1022,8 @@ async fn permissions_checks_define_user_ns() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, databases: {  }, users: { user: \"DEFINE USER user ON NAMESPACE PASSHASH 'secret' ROLES VIEWER DURATION FOR TOKEN 15m, FOR SESSION 6h\" } }"],
-		vec!["{ accesses: {  }, databases: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, databases: {  }, roles: {  }, users: { user: \"DEFINE USER user ON NAMESPACE PASSHASH 'secret' ROLES VIEWER DURATION FOR TOKEN 15m, FOR SESSION 6h\" } }"],
+		vec!["{ accesses: {  }, databases: {  }, roles: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -965,8 +1064,8 @@ async fn permissions_checks_define_user_db() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: { user: \"DEFINE USER user ON DATABASE PASSHASH 'secret' ROLES VIEWER DURATION FOR TOKEN 15m, FOR SESSION 6h\" } }"],
-        vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, sequences: {  }, tables: {  }, users: { user: \"DEFINE USER user ON DATABASE PASSHASH 'secret' ROLES VIEWER DURATION FOR TOKEN 15m, FOR SESSION 6h\" } }"],
+        vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, sequences: {  }, tables: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1007,8 +1106,8 @@ async fn permissions_checks_define_access_record() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: { account: \"DEFINE ACCESS account ON DATABASE TYPE RECORD WITH JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 15m, FOR SESSION 12h\" }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: { account: \"DEFINE ACCESS account ON DATABASE TYPE RECORD WITH JWT ALGORITHM HS512 KEY '[REDACTED]' WITH ISSUER KEY '[REDACTED]' DURATION FOR TOKEN 15m, FOR SESSION 12h\" }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, sequences: {  }, tables: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, sequences: {  }, tables: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1049,8 +1148,8 @@ async fn permissions_checks_define_param() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: { param: \"DEFINE PARAM $param VALUE 'foo' PERMISSIONS FULL\" }, tables: {  }, users: {  } }"],
-		vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: { param: \"DEFINE PARAM $param VALUE 'foo' PERMISSIONS FULL\" }, roles: {}, sequences: {}, tables: {  }, users: {  } }"],
+		vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, sequences: {  }, tables: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1088,8 +1187,8 @@ async fn permissions_checks_define_table() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: { TB: 'DEFINE TABLE TB TYPE ANY SCHEMALESS PERMISSIONS NONE' }, users: {  } }"],
-        vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, tables: {  }, users: {  } }"]
+        vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, sequences: {  }, tables: { TB: 'DEFINE TABLE TB TYPE ANY SCHEMALESS PERMISSIONS NONE' }, users: {  } }"],
+        vec!["{ accesses: {  }, analyzers: {  }, apis: {  }, configs: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, sequences: {  }, tables: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -424,3 +424,65 @@ async fn field_definition_array_any() -> Result<(), Error> {
 	)?;
 	Ok(())
 }
+
+#[tokio::test]
+async fn field_definition_value_match() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE ticket;
+		DEFINE FIELD priority ON ticket VALUE MATCH severity {
+			<string> => 'unknown',
+			0..3 => 'low',
+			3..6 => 'high',
+			_ => 'none',
+		};
+		DEFINE FIELD state ON ticket VALUE MATCH meta {
+			{ status: 'open', owner: <string> } => 'assigned',
+			{ status: 'open' } => 'unassigned',
+			_ => 'closed',
+		};
+		CREATE ticket:1 SET severity = 1, meta = { status: 'open', owner: 'tobie' };
+		CREATE ticket:2 SET severity = 4, meta = { status: 'open' };
+		CREATE ticket:3 SET severity = 'x', meta = { status: 'done' };
+		CREATE ticket:4 SET severity = 9;
+		RETURN MATCH 'a' { 'b' => 1 };
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(3)?;
+	t.expect_val(
+		"[{
+			id: ticket:1,
+			meta: { owner: 'tobie', status: 'open' },
+			priority: 'low',
+			severity: 1,
+			state: 'assigned'
+		}]",
+	)?;
+	t.expect_val(
+		"[{
+			id: ticket:2,
+			meta: { status: 'open' },
+			priority: 'high',
+			severity: 4,
+			state: 'unassigned'
+		}]",
+	)?;
+	t.expect_val(
+		"[{
+			id: ticket:3,
+			meta: { status: 'done' },
+			priority: 'unknown',
+			severity: 'x',
+			state: 'closed'
+		}]",
+	)?;
+	t.expect_val(
+		"[{
+			id: ticket:4,
+			priority: 'none',
+			severity: 9,
+			state: 'closed'
+		}]",
+	)?;
+	t.expect_val("NONE")?;
+	Ok(())
+}
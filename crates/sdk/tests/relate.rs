@@ -287,6 +287,8 @@ async fn relate_enforced() -> Result<(), Error> {
 	functions: {},
 	models: {},
 	params: {},
+	roles: {},
+	sequences: {},
 	tables: {
 		a: 'DEFINE TABLE a TYPE ANY SCHEMALESS PERMISSIONS NONE',
 		edge: 'DEFINE TABLE edge TYPE RELATION ENFORCED SCHEMALESS PERMISSIONS NONE'
@@ -1475,3 +1475,191 @@ async fn select_from_none() -> Result<(), Error> {
 	}
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_distinct() -> Result<(), Error> {
+	let sql = "
+		INSERT INTO person [
+			{ id: 1, name: 'Tobie', city: 'London' },
+			{ id: 2, name: 'Jaime', city: 'London' },
+			{ id: 3, name: 'Tobie', city: 'Paris' },
+		] RETURN NONE;
+		SELECT DISTINCT name FROM person ORDER BY name;
+		SELECT DISTINCT VALUE city FROM person;
+		SELECT DISTINCT VALUE city FROM person LIMIT 1;
+		SELECT DISTINCT VALUE city FROM person START 1;
+		SELECT DISTINCT tags FROM [{ tags: ['a', 'b'] }, { tags: ['b', 'c'] }] SPLIT tags;
+		SELECT distinct FROM { distinct: true };
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(1)?;
+	t.expect_val("[{ name: 'Jaime' }, { name: 'Tobie' }]")?;
+	t.expect_val("['London', 'Paris']")?;
+	t.expect_val("['London']")?;
+	t.expect_val("['Paris']")?;
+	t.expect_val("[{ tags: 'a' }, { tags: 'b' }, { tags: 'c' }]")?;
+	t.expect_val("[{ distinct: true }]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_set_operators() -> Result<(), Error> {
+	let sql = "
+		INSERT INTO person [
+			{ id: 1, name: 'Tobie' },
+			{ id: 2, name: 'Jaime' },
+			{ id: 3, name: 'Tobie' },
+		] RETURN NONE;
+		INSERT INTO staff [
+			{ id: 1, name: 'Jaime' },
+			{ id: 2, name: 'Tamara' },
+		] RETURN NONE;
+		SELECT VALUE name FROM person UNION SELECT VALUE name FROM staff;
+		SELECT VALUE name FROM person UNION ALL SELECT VALUE name FROM staff;
+		SELECT VALUE name FROM person INTERSECT SELECT VALUE name FROM staff;
+		SELECT VALUE name FROM person EXCEPT SELECT VALUE name FROM staff;
+		SELECT VALUE name FROM person UNION ALL SELECT VALUE name FROM staff EXCEPT SELECT VALUE name FROM person:1;
+		SELECT VALUE name FROM ONLY person:2 UNION SELECT VALUE name FROM staff:1 LIMIT 1;
+		SELECT VALUE name FROM person UNION SELECT VALUE name FROM staff ORDER BY name LIMIT 2;
+		SELECT name FROM person UNION ALL SELECT name FROM staff ORDER BY name DESC START 3;
+		SELECT id FROM person:2 UNION SELECT id FROM staff:1 LIMIT 5 FETCH id TIMEOUT 1s PARALLEL;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(2)?;
+	t.expect_val("['Tobie', 'Jaime', 'Tamara']")?;
+	t.expect_val("['Tobie', 'Jaime', 'Tobie', 'Jaime', 'Tamara']")?;
+	t.expect_val("['Jaime']")?;
+	t.expect_val("['Tobie']")?;
+	t.expect_val("['Jaime', 'Tamara']")?;
+	t.expect_val("'Jaime'")?;
+	t.expect_val("['Jaime', 'Tamara']")?;
+	t.expect_val("[{ name: 'Jaime' }, { name: 'Jaime' }]")?;
+	t.expect_val(
+		"[{ id: { id: person:2, name: 'Jaime' } }, { id: { id: staff:1, name: 'Jaime' } }]",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_distinct_and_set_operators_with_tempfiles() -> Result<(), Error> {
+	let dir = temp_dir::TempDir::new().unwrap();
+	let ds = new_ds().await?.with_temporary_directory(Some(dir.path().to_path_buf()));
+	let sql = "
+		INSERT INTO person [
+			{ id: 1, name: 'Tobie' },
+			{ id: 2, name: 'Jaime' },
+			{ id: 3, name: 'Tobie' },
+		] RETURN NONE;
+		INSERT INTO staff [
+			{ id: 1, name: 'Jaime' },
+			{ id: 2, name: 'Tamara' },
+		] RETURN NONE;
+		SELECT DISTINCT VALUE name FROM person TEMPFILES;
+		SELECT DISTINCT name FROM person ORDER BY name DESC TEMPFILES;
+		SELECT VALUE name FROM person UNION SELECT VALUE name FROM staff TEMPFILES;
+		SELECT VALUE name FROM person INTERSECT SELECT VALUE name FROM staff TEMPFILES;
+		SELECT VALUE name FROM person EXCEPT SELECT VALUE name FROM staff TEMPFILES;
+		SELECT DISTINCT VALUE name FROM person UNION ALL SELECT VALUE name FROM staff TEMPFILES;
+		SELECT VALUE name FROM person UNION ALL SELECT VALUE name FROM staff ORDER BY name LIMIT 3 START 1 TEMPFILES;
+		SELECT name FROM person UNION SELECT name FROM staff ORDER BY name DESC LIMIT 2 TEMPFILES;
+	";
+	let mut t = Test::new_ds(ds, sql).await?;
+	t.skip_ok(2)?;
+	t.expect_val("['Tobie', 'Jaime']")?;
+	t.expect_val("[{ name: 'Tobie' }, { name: 'Jaime' }]")?;
+	t.expect_val("['Tobie', 'Jaime', 'Tamara']")?;
+	t.expect_val("['Jaime']")?;
+	t.expect_val("['Tobie']")?;
+	t.expect_val("['Tobie', 'Jaime', 'Jaime', 'Tamara']")?;
+	t.expect_val("['Jaime', 'Tamara', 'Tobie']")?;
+	t.expect_val("[{ name: 'Tobie' }, { name: 'Tamara' }]")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_window_functions() -> Result<(), Error> {
+	let sql = "
+		INSERT INTO payment [
+			{ id: 1, account: 'a', ts: 1, amount: 10 },
+			{ id: 2, account: 'b', ts: 1, amount: 5 },
+			{ id: 3, account: 'a', ts: 2, amount: 20 },
+			{ id: 4, account: 'a', ts: 2, amount: 30 },
+			{ id: 5, account: 'b', ts: 3, amount: 15 },
+		] RETURN NONE;
+		SELECT id, math::sum(amount) OVER (PARTITION BY account ORDER BY ts) AS total FROM payment;
+		SELECT id, window::row_number() OVER (PARTITION BY account ORDER BY ts) AS num, window::rank() OVER (ORDER BY ts) AS rank FROM payment;
+		SELECT id, window::lag(amount, 1, 0) OVER (PARTITION BY account ORDER BY id) AS prev, window::lead(amount, 2, -1) OVER (PARTITION BY account ORDER BY id) AS next FROM payment;
+		SELECT VALUE count() OVER () FROM payment;
+		SELECT id, window::row_number() OVER (ORDER BY amount DESC) AS num FROM payment ORDER BY num LIMIT 2;
+		SELECT DISTINCT account, count() OVER (PARTITION BY account) AS count FROM payment;
+		SELECT VALUE math::median(amount) OVER (PARTITION BY account) FROM payment;
+		SELECT window::rank() FROM payment;
+	";
+	let mut t = Test::new(sql).await?;
+	t.skip_ok(1)?;
+	t.expect_val(
+		"[
+			{ id: payment:1, total: 10 },
+			{ id: payment:2, total: 5 },
+			{ id: payment:3, total: 60 },
+			{ id: payment:4, total: 60 },
+			{ id: payment:5, total: 20 },
+		]",
+	)?;
+	t.expect_val(
+		"[
+			{ id: payment:1, num: 1, rank: 1 },
+			{ id: payment:2, num: 1, rank: 1 },
+			{ id: payment:3, num: 2, rank: 3 },
+			{ id: payment:4, num: 3, rank: 3 },
+			{ id: payment:5, num: 2, rank: 5 },
+		]",
+	)?;
+	t.expect_val(
+		"[
+			{ id: payment:1, prev: 0, next: 30 },
+			{ id: payment:2, prev: 0, next: -1 },
+			{ id: payment:3, prev: 10, next: -1 },
+			{ id: payment:4, prev: 20, next: -1 },
+			{ id: payment:5, prev: 5, next: -1 },
+		]",
+	)?;
+	t.expect_val("[5, 5, 5, 5, 5]")?;
+	t.expect_val("[{ id: payment:4, num: 1 }, { id: payment:3, num: 2 }]")?;
+	t.expect_val("[{ account: 'a', count: 3 }, { account: 'b', count: 2 }]")?;
+	t.expect_val("[20, 10, 20, 20, 10]")?;
+	t.expect_error("There was a problem running the window::rank() function. This function can only be used with an OVER clause in a SELECT statement")?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_window_functions_with_tempfiles() -> Result<(), Error> {
+	let dir = temp_dir::TempDir::new().unwrap();
+	let ds = new_ds().await?.with_temporary_directory(Some(dir.path().to_path_buf()));
+	let sql = "
+		INSERT INTO payment [
+			{ id: 1, account: 'a', ts: 1, amount: 10 },
+			{ id: 2, account: 'b', ts: 1, amount: 5 },
+			{ id: 3, account: 'a', ts: 2, amount: 20 },
+			{ id: 4, account: 'a', ts: 2, amount: 30 },
+			{ id: 5, account: 'b', ts: 3, amount: 15 },
+		] RETURN NONE;
+		SELECT id, math::sum(amount) OVER (PARTITION BY account ORDER BY ts) AS total, window::row_number() OVER (ORDER BY amount DESC) AS num FROM payment TEMPFILES;
+		SELECT id, window::lag(amount, 1, 0) OVER (PARTITION BY account ORDER BY id) AS prev FROM payment ORDER BY prev DESC LIMIT 2 TEMPFILES;
+		SELECT DISTINCT VALUE count() OVER (PARTITION BY account) FROM payment TEMPFILES;
+	";
+	let mut t = Test::new_ds(ds, sql).await?;
+	t.skip_ok(1)?;
+	t.expect_val(
+		"[
+			{ id: payment:1, total: 10, num: 4 },
+			{ id: payment:2, total: 5, num: 5 },
+			{ id: payment:3, total: 60, num: 2 },
+			{ id: payment:4, total: 60, num: 1 },
+			{ id: payment:5, total: 20, num: 3 },
+		]",
+	)?;
+	t.expect_val("[{ id: payment:4, prev: 20 }, { id: payment:3, prev: 10 }]")?;
+	t.expect_val("[3, 2]")?;
+	Ok(())
+}
@@ -248,6 +248,7 @@ async fn loose_mode_all_ok() -> Result<(), Error> {
 		"{
 			accesses: {},
 			databases: { test: 'DEFINE DATABASE test' },
+			roles: {},
 			users: {},
 		}",
 	);
@@ -263,6 +264,8 @@ async fn loose_mode_all_ok() -> Result<(), Error> {
 			functions: {},
 			models: {},
 			params: {},
+			roles: {},
+			sequences: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY SCHEMALESS PERMISSIONS NONE' },
 			users: {},
 		}",
@@ -328,3 +328,300 @@ async fn define_foreign_table_with_no_cond_and_group_max() -> Result<(), Error>
 async fn define_foreign_table_with_no_cond_and_group_sum() -> Result<(), Error> {
 	define_foreign_table_group(false, "math::sum(value)").await
 }
+
+#[tokio::test]
+async fn define_foreign_table_group_distribution() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE wallet;
+		DEFINE TABLE wallet_agr AS SELECT
+			math::variance(value) AS variance,
+			math::stddev(value) AS stddev,
+			math::percentile(value, 50) AS median,
+			array::distinct(value) AS values,
+			array::group(tags) AS tags,
+			count(DISTINCT owner) AS owners,
+			day
+		FROM wallet GROUP BY day;
+		CREATE wallet:1 SET value = 2, day = 1, tags = ['a', 'b'], owner = 'x' RETURN NONE;
+		CREATE wallet:2 SET value = 4, day = 1, tags = ['b'], owner = 'y' RETURN NONE;
+		CREATE wallet:3 SET value = 4, day = 1, tags = ['c'], owner = 'x' RETURN NONE;
+		CREATE wallet:4 SET value = 6, day = 1, tags = [], owner = NONE RETURN NONE;
+		// 0
+		SELECT math::variance(value) AS variance, math::stddev(value) AS stddev FROM wallet GROUP ALL;
+		SELECT variance, stddev FROM wallet_agr;
+		SELECT array::sort(values) AS values, array::sort(tags) AS tags, owners, math::abs(median - 4) < 0.04 AS median FROM wallet_agr;
+		// 1
+		UPDATE wallet:2 SET value = 8, tags = ['d'], owner = 'x' RETURN NONE;
+		SELECT math::variance(value) AS variance, math::stddev(value) AS stddev FROM wallet GROUP ALL;
+		SELECT variance, stddev FROM wallet_agr;
+		SELECT array::sort(values) AS values, array::sort(tags) AS tags, owners, math::abs(median - 5) < 0.05 AS median FROM wallet_agr;
+		// 2
+		DELETE wallet:1;
+		SELECT math::variance(value) AS variance, math::stddev(value) AS stddev FROM wallet GROUP ALL;
+		SELECT variance, stddev FROM wallet_agr;
+		SELECT array::sort(values) AS values, array::sort(tags) AS tags, owners, math::abs(median - 6) < 0.06 AS median FROM wallet_agr;
+		// 3
+		DELETE wallet:2, wallet:3, wallet:4;
+		SELECT * FROM wallet_agr;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 19);
+	//
+	skip_ok(res, 6)?;
+	//
+	let expected = [
+		"[{ median: true, owners: 2, tags: ['a', 'b', 'c'], values: [2, 4, 6] }]",
+		"[{ median: true, owners: 1, tags: ['a', 'b', 'c', 'd'], values: [2, 4, 6, 8] }]",
+		"[{ median: true, owners: 1, tags: ['c', 'd'], values: [4, 6, 8] }]",
+	];
+	for (i, val) in expected.into_iter().enumerate() {
+		// Skip the UPDATE or DELETE statement
+		if i > 0 {
+			skip_ok(res, 1)?;
+		}
+		// Get the computed result
+		let comp = res.remove(0).result?;
+		// Get the projected result
+		let proj = res.remove(0).result?;
+		// Check they are similar
+		assert_eq!(format!("{proj:#}"), format!("{comp:#}"), "#{i}");
+		// Check the other aggregates
+		let tmp = res.remove(0).result?;
+		assert_eq!(tmp, Value::parse(val), "#{i}");
+	}
+	//
+	skip_ok(res, 1)?;
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[]"));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_table_with_expiry() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE session EXPIRE expires;
+		INFO FOR DB;
+		CREATE session:one SET expires = time::now() - 1h;
+		CREATE session:two SET expires = time::now() + 1h;
+		CREATE session:three;
+		SELECT id FROM session;
+		SELECT id FROM session:one;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	let tmp = res.remove(0).result;
+	tmp.unwrap();
+	//
+	let tmp = res.remove(0).result?;
+	let tb = tmp.pick(&["tables".into(), "session".into()]);
+	assert_eq!(
+		tb,
+		Value::from("DEFINE TABLE session TYPE ANY SCHEMALESS EXPIRE expires PERMISSIONS NONE")
+	);
+	//
+	skip_ok(res, 3)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: session:three }, { id: session:two }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	// Remove the expired records
+	dbs.expiry_process().await?;
+	//
+	let sql = "
+		DEFINE TABLE OVERWRITE session;
+		SELECT id FROM session;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	//
+	let tmp = res.remove(0).result;
+	tmp.unwrap();
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: session:three }, { id: session:two }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_table_with_expiry_hides_expired_records() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE session EXPIRE expires;
+		CREATE session:one SET expires = time::now() - 1h;
+		CREATE session:two SET expires = time::now() + 1h;
+		RELATE user:tobie->owns->session:one;
+		RELATE user:tobie->owns->session:two;
+		CREATE login:one SET session = session:one;
+		SELECT count() FROM session GROUP ALL;
+		SELECT count() FROM session:one..=two GROUP ALL;
+		SELECT id FROM session START 1;
+		RETURN user:tobie->owns->session;
+		SELECT session.expires AS expires FROM ONLY login:one;
+		SELECT session FROM ONLY login:one FETCH session;
+		RETURN record::exists(session:one);
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 13);
+	//
+	skip_ok(res, 6)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 1 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 1 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[session:two]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("{ expires: NONE }");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("{ session: NONE }");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("false");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_table_with_expiry_relate_expired_record() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE session EXPIRE expires;
+		DEFINE TABLE owns TYPE RELATION ENFORCED;
+		CREATE user:tobie;
+		CREATE session:one SET expires = time::now() - 1h;
+		CREATE session:two SET expires = time::now() + 1h;
+		RELATE user:tobie->owns->session:one;
+		RELATE user:tobie->owns->session:two RETURN VALUE out;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	skip_ok(res, 5)?;
+	//
+	let tmp = res.remove(0).result.unwrap_err();
+	assert!(matches!(tmp, Error::IdNotFound { .. }));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[session:two]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_table_with_expiry_create_expired_record() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE session EXPIRE expires;
+		DEFINE INDEX token ON session FIELDS token UNIQUE;
+		CREATE session:one SET token = 'one', expires = time::now() - 1h;
+		CREATE session:one SET token = 'two', expires = time::now() + 1h RETURN id, token;
+		SELECT id, token FROM session;
+		SELECT id FROM session WHERE token = 'one';
+		UPSERT session:two SET expires = time::now() - 1h;
+		UPSERT session:two SET token = 'three' RETURN id, token, expires;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	skip_ok(res, 3)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: session:one, token: 'two' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: session:one, token: 'two' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	skip_ok(res, 1)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: session:two, token: 'three', expires: NONE }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_table_with_expiry_on_existing_records() -> Result<(), Error> {
+	let sql = "
+		CREATE session:one SET expires = time::now() - 1h;
+		CREATE session:two SET expires = time::now() + 1h;
+		DEFINE TABLE OVERWRITE session EXPIRE expires;
+		SELECT id FROM session;
+		DEFINE TABLE OVERWRITE session;
+		SELECT id FROM session;
+		DEFINE TABLE OVERWRITE session EXPIRE expires;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	skip_ok(res, 3)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: session:two }]");
+	assert_eq!(tmp, val);
+	//
+	skip_ok(res, 1)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: session:one }, { id: session:two }]");
+	assert_eq!(tmp, val);
+	//
+	skip_ok(res, 1)?;
+	// Remove the expired records
+	dbs.expiry_process().await?;
+	//
+	let sql = "
+		DEFINE TABLE OVERWRITE session;
+		SELECT id FROM session;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	//
+	skip_ok(res, 1)?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: session:two }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
@@ -1,7 +1,11 @@
+mod parse;
+use parse::Parse;
 mod helpers;
 use helpers::new_ds;
+use std::time::Duration;
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
+use surrealdb::sql::Value;
 
 #[tokio::test]
 async fn throw_basic() -> Result<(), Error> {
@@ -69,3 +73,160 @@ async fn throw_value() -> Result<(), Error> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn try_catch_thrown() -> Result<(), Error> {
+	let sql = "
+		TRY { THROW 'there was an error'; } CATCH ($err) { RETURN $err; };
+		TRY { RETURN 'no error'; } CATCH ($err) { RETURN $err; };
+		TRY { THROW 'there was an error'; } CATCH { RETURN 'caught'; };
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("{ kind: 'Thrown', message: 'there was an error' }");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("'no error'");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("'caught'");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn try_catch_rollback() -> Result<(), Error> {
+	let sql = "
+		CREATE person:one;
+		TRY {
+			CREATE person:two;
+			CREATE person:one;
+		} CATCH ($err) {
+			CREATE person:three;
+			RETURN $err.kind;
+		};
+		SELECT id FROM person;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0).result;
+	tmp.unwrap();
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("'RecordExists'");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:one }, { id: person:three }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn try_catch_function_and_event() -> Result<(), Error> {
+	let sql = "
+		DEFINE FUNCTION fn::create($id: string) {
+			TRY {
+				CREATE type::thing('person', $id);
+				THROW 'cannot create ' + $id;
+			} CATCH ($err) {
+				RETURN $err.message;
+			};
+		};
+		DEFINE EVENT audit ON person THEN {
+			TRY {
+				CREATE audit:one;
+				CREATE audit:one;
+			} CATCH ($err) {
+				CREATE log SET kind = $err.kind;
+			};
+		};
+		RETURN fn::create('one');
+		SELECT id FROM person;
+		CREATE person:two RETURN NONE;
+		SELECT id FROM audit;
+		SELECT kind FROM log;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	let tmp = res.remove(0).result;
+	tmp.unwrap();
+	//
+	let tmp = res.remove(0).result;
+	tmp.unwrap();
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("'cannot create one'");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	tmp.unwrap();
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ kind: 'RecordExists' }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn try_catch_rollback_changes_and_notifications() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let sql = "
+		DEFINE TABLE person CHANGEFEED 1h;
+		LIVE SELECT * FROM person;
+		TRY {
+			CREATE person:one;
+			THROW 'there was an error';
+		} CATCH {
+			CREATE person:two;
+		};
+		SHOW CHANGES FOR TABLE person SINCE 0;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(0).result;
+	tmp.unwrap();
+	//
+	let tmp = res.remove(0).result;
+	tmp.unwrap();
+	//
+	let tmp = res.remove(0).result;
+	tmp.unwrap();
+	// The rolled back record is not in the change feed
+	let tmp = res.remove(0).result?.to_string();
+	assert!(tmp.contains("person:two"), "{tmp}");
+	assert!(!tmp.contains("person:one"), "{tmp}");
+	// The rolled back record is not sent to live queries
+	let notifications = dbs.notifications().expect("expected notifications");
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(notification.record, Value::parse("person:two"));
+	let next = tokio::time::timeout(Duration::from_millis(100), notifications.recv()).await;
+	assert!(next.is_err(), "unexpected notification: {next:?}");
+	//
+	Ok(())
+}
@@ -56,3 +56,22 @@ async fn query_timeout() -> Result<(), Error> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn while_timeout() -> Result<(), Error> {
+	let sql = "
+		WHILE true {
+			FOR $i in 0..1000000 {}
+		}
+	";
+	let ds = new_ds().await?.with_query_timeout(Some(Duration::from_millis(500)));
+	let session = Session::owner();
+	let before = Instant::now();
+	let mut res = ds.execute(sql, &session, None).await.unwrap();
+	if before.elapsed() > Duration::from_millis(7050) {
+		panic!("Query did not properly timeout");
+	}
+	res.pop().unwrap().result.unwrap_err();
+
+	Ok(())
+}
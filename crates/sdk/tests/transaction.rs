@@ -2,6 +2,7 @@ mod parse;
 use parse::Parse;
 mod helpers;
 use helpers::new_ds;
+use std::time::Duration;
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
 use surrealdb::sql::Value;
@@ -200,3 +201,161 @@ async fn transaction_with_throw_and_return() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn transaction_with_savepoint() -> Result<(), Error> {
+	let sql = "
+		BEGIN;
+		CREATE person:tobie;
+		SAVEPOINT import;
+		CREATE person:jaime;
+		ROLLBACK TO SAVEPOINT import;
+		CREATE person:jaime SET name = 'Jaime';
+		RELEASE SAVEPOINT import;
+		COMMIT;
+		SELECT * FROM person;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:tobie }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp,
+		Err(Error::QueryRolledBack { name }) if name == "import"
+	));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:jaime, name: 'Jaime' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:jaime, name: 'Jaime' }, { id: person:tobie }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn transaction_with_failure_and_savepoint() -> Result<(), Error> {
+	let sql = "
+		BEGIN;
+		CREATE person:tobie;
+		SAVEPOINT import;
+		CREATE person:jaime;
+		CREATE person:tobie;
+		CREATE person:jaime;
+		ROLLBACK TO import;
+		CREATE person:tamara;
+		COMMIT;
+		SELECT * FROM person;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:tobie }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::QueryRolledBack { .. })));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::RecordExists { .. })));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::QueryNotExecuted)));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:tamara }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:tamara }, { id: person:tobie }]");
+	assert_eq!(tmp, val);
+	//
+	let sql = "
+		BEGIN;
+		CREATE person:jaime;
+		SAVEPOINT import;
+		CREATE person:tobie;
+		COMMIT;
+		SELECT * FROM person:jaime;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::QueryNotExecuted)));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::QueryNotExecuted)));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::RecordExists { .. })));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::Array(Default::default()));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn transaction_rollback_to_savepoint_changes_and_notifications() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person CHANGEFEED 1h;
+		LIVE SELECT * FROM person;
+		BEGIN;
+		CREATE person:tobie;
+		SAVEPOINT import;
+		CREATE person:jaime;
+		SAVEPOINT nested;
+		CREATE person:tamara;
+		ROLLBACK TO import;
+		CREATE person:jaime SET name = 'Jaime';
+		COMMIT;
+		SHOW CHANGES FOR TABLE person SINCE 0;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 10);
+	// The rolled back records are not in the change feed
+	let tmp = res.pop().unwrap().result?.to_string();
+	assert!(tmp.contains("name: 'Jaime'"), "{tmp}");
+	assert!(tmp.contains("person:tobie"), "{tmp}");
+	assert!(!tmp.contains("person:tamara"), "{tmp}");
+	assert!(!tmp.contains("id: person:jaime }"), "{tmp}");
+	// The rolled back records are not sent to live queries
+	let notifications = dbs.notifications().expect("expected notifications");
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(notification.result, Value::parse("{ id: person:tobie }"));
+	let notification = notifications.recv().await.unwrap();
+	assert_eq!(notification.result, Value::parse("{ id: person:jaime, name: 'Jaime' }"));
+	let next = tokio::time::timeout(Duration::from_millis(100), notifications.recv()).await;
+	assert!(next.is_err(), "unexpected notification: {next:?}");
+	//
+	Ok(())
+}
@@ -0,0 +1,94 @@
+use crate::err::Error;
+use clap::Args;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::PathBuf;
+use surrealdb::engine::any::IntoEndpoint;
+use surrealdb::kvs::backup::Manifest;
+use surrealdb::kvs::Datastore;
+
+#[derive(Args, Debug)]
+pub struct BackupCommandArguments {
+	#[arg(help = "Database path to backup from, or the backup file to restore from")]
+	#[arg(index = 1)]
+	from: String,
+	#[arg(help = "Backup file to write to, or the database path to restore into")]
+	#[arg(index = 2)]
+	into: String,
+	#[arg(help = "Path to a previous backup file, to only backup the changes since that backup")]
+	#[arg(long = "since")]
+	#[arg(value_parser = super::validator::file_exists)]
+	since: Option<PathBuf>,
+	#[arg(help = "Encryption key to use for on-disk encryption")]
+	#[arg(env = "SURREAL_KEY", short = 'k', long = "key")]
+	#[arg(value_parser = super::validator::key_valid)]
+	key: Option<String>,
+}
+
+pub async fn init(
+	BackupCommandArguments {
+		from,
+		into,
+		since,
+		key,
+	}: BackupCommandArguments,
+) -> Result<(), Error> {
+	// Work out the direction of the backup
+	match (super::validator::path_valid(&from), super::validator::path_valid(&into)) {
+		// Backup a datastore into a file
+		(Ok(path), Err(_)) => backup(clean(path)?, into.into(), since, key).await,
+		// Restore a file into a datastore
+		(Err(_), Ok(path)) if since.is_none() => restore(from.into(), clean(path)?, key).await,
+		// Incremental backups are restored like any other backup
+		(Err(_), Ok(_)) => {
+			Err(Error::Other("The --since argument can only be used when taking a backup".into()))
+		}
+		// Exactly one side needs to be a datastore
+		_ => Err(Error::Other("Provide a database path and a backup file path".into())),
+	}
+}
+
+async fn backup(
+	path: String,
+	file: PathBuf,
+	since: Option<PathBuf>,
+	key: Option<String>,
+) -> Result<(), Error> {
+	// Read the manifest of the previous backup
+	let since = match since {
+		Some(since) => Some(Manifest::read(&mut BufReader::new(File::open(since)?))?),
+		None => None,
+	};
+	// Parse and setup the desired kv datastore
+	let dbs = Datastore::new(&path).await?.with_encryption_key(key).await?;
+	// Write the backup to the file
+	let manifest = dbs.backup(since.as_ref(), BufWriter::new(File::create(&file)?)).await?;
+	// Log success
+	match manifest.is_incremental() {
+		true => info!("The incremental backup was written successfully to {}", file.display()),
+		false => info!("The backup was written successfully to {}", file.display()),
+	}
+	// All ok
+	Ok(())
+}
+
+async fn restore(file: PathBuf, path: String, key: Option<String>) -> Result<(), Error> {
+	// Parse and setup the desired kv datastore
+	let dbs = Datastore::new(&path).await?.with_encryption_key(key).await?;
+	// Restore the backup from the file
+	dbs.restore(BufReader::new(File::open(&file)?)).await?;
+	// Log success
+	info!("The backup was restored successfully from {}", file.display());
+	// All ok
+	Ok(())
+}
+
+fn clean(path: String) -> Result<String, Error> {
+	// Clean the path
+	let endpoint = path.into_endpoint()?;
+	Ok(if endpoint.path.is_empty() {
+		endpoint.url.to_string()
+	} else {
+		endpoint.path
+	})
+}
@@ -4,7 +4,7 @@ use crate::err::Error;
 use clap::Args;
 use futures_util::StreamExt;
 use surrealdb::engine::any::{connect, IntoEndpoint};
-use surrealdb::kvs::export::TableConfig;
+use surrealdb::kvs::export::{Column, Format, TableConfig};
 use surrealdb::method::{Export, ExportConfig};
 use surrealdb::Connection;
 use tokio::io::{self, AsyncWriteExt};
@@ -32,6 +32,9 @@ struct ExportConfigArguments {
 	/// Whether params should be exported
 	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
 	params: Option<bool>,
+	/// Whether sequences should be exported
+	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
+	sequences: Option<bool>,
 	/// Whether functions should be exported
 	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
 	functions: Option<bool>,
@@ -47,11 +50,19 @@ struct ExportConfigArguments {
 	/// Whether records should be exported
 	#[arg(long, num_args = 0..=1, default_missing_value = "true")]
 	records: Option<bool>,
+	/// The format of the export, with CSV and NDJSON exporting the records of a single table
+	#[arg(long, default_value = "surrealql")]
+	format: Format,
+	/// The fields to export as CSV or NDJSON, optionally naming their columns as column=field
+	#[arg(long, value_parser = super::validator::columns)]
+	columns: Option<::std::vec::Vec<Column>>,
 }
 
 #[derive(Args, Debug)]
 pub struct ExportCommandArguments {
-	#[arg(help = "Path to the SurrealQL file to export. Use dash - to write into stdout.")]
+	#[arg(
+		help = "Path to the SurrealQL, CSV or NDJSON file to export. Use dash - to write into stdout."
+	)]
 	#[arg(default_value = "-")]
 	#[arg(index = 1)]
 	file: String,
@@ -119,6 +130,8 @@ pub async fn init(
 
 	// Use the specified namespace / database
 	client.use_ns(namespace).use_db(database).await?;
+	// Keep the format for logging
+	let format = config.format;
 
 	// Export the data from the database
 	debug!("Exporting data from the database");
@@ -134,7 +147,11 @@ pub async fn init(
 	} else {
 		apply_config(config, client.export(file)).await?;
 	}
-	info!("The SurrealQL file was exported successfully");
+	match format {
+		Format::Surrealql => info!("The SurrealQL file was exported successfully"),
+		Format::Csv => info!("The CSV file was exported successfully"),
+		Format::Ndjson => info!("The NDJSON file was exported successfully"),
+	}
 	// Everything OK
 	Ok(())
 }
@@ -150,6 +167,7 @@ fn apply_config<C: Connection, R>(
 			.users(false)
 			.accesses(false)
 			.params(false)
+			.sequences(false)
 			.functions(false)
 			.analyzers(false)
 			.tables(false)
@@ -169,6 +187,10 @@ fn apply_config<C: Connection, R>(
 		export = export.params(value);
 	}
 
+	if let Some(value) = config.sequences {
+		export = export.sequences(value);
+	}
+
 	if let Some(value) = config.functions {
 		export = export.functions(value);
 	}
@@ -189,5 +211,11 @@ fn apply_config<C: Connection, R>(
 		export = export.records(value);
 	}
 
+	export = export.format(config.format);
+
+	if let Some(columns) = config.columns {
+		export = export.columns(columns);
+	}
+
 	export
 }
@@ -10,11 +10,16 @@ pub struct FixCommandArguments {
 	#[arg(default_value = "memory")]
 	#[arg(value_parser = super::validator::path_valid)]
 	path: String,
+	#[arg(help = "Encryption key to use for on-disk encryption")]
+	#[arg(env = "SURREAL_KEY", short = 'k', long = "key")]
+	#[arg(value_parser = super::validator::key_valid)]
+	key: Option<String>,
 }
 
 pub async fn init(
 	FixCommandArguments {
 		path,
+		key,
 	}: FixCommandArguments,
 ) -> Result<(), Error> {
 	// Clean the path
@@ -25,7 +30,7 @@ pub async fn init(
 		endpoint.path
 	};
 	// Fix the datastore, if applicable
-	dbs::fix(path).await?;
+	dbs::fix(path, key).await?;
 	// All ok
 	Ok(())
 }
@@ -3,6 +3,7 @@ use crate::cli::abstraction::{AuthArguments, DatabaseSelectionArguments};
 use crate::err::Error;
 use clap::Args;
 use surrealdb::engine::any::{connect, IntoEndpoint};
+use surrealdb::kvs::import::{Column, Format};
 use surrealdb::opt::{capabilities::Capabilities, Config};
 
 #[derive(Args, Debug)]
@@ -16,9 +17,18 @@ pub struct DatabaseConnectionArguments {
 
 #[derive(Args, Debug)]
 pub struct ImportCommandArguments {
-	#[arg(help = "Path to the SurrealQL file to import")]
+	#[arg(help = "Path to the SurrealQL, CSV or NDJSON file to import")]
 	#[arg(index = 1)]
 	file: String,
+	/// The format of the file to import
+	#[arg(long, default_value = "surrealql")]
+	format: Format,
+	/// The table to import CSV or NDJSON records into
+	#[arg(long, required_if_eq_any = [("format", "csv"), ("format", "ndjson")])]
+	table: Option<String>,
+	/// The columns to import, optionally mapping them onto other fields as column=field
+	#[arg(long, requires = "table", value_parser = super::validator::columns)]
+	columns: Option<::std::vec::Vec<Column>>,
 	#[command(flatten)]
 	conn: DatabaseConnectionArguments,
 	#[command(flatten)]
@@ -30,6 +40,9 @@ pub struct ImportCommandArguments {
 pub async fn init(
 	ImportCommandArguments {
 		file,
+		format,
+		table,
+		columns,
 		conn: DatabaseConnectionArguments {
 			endpoint,
 		},
@@ -83,10 +96,27 @@ pub async fn init(
 	// Use the specified namespace / database
 	client.use_ns(namespace).use_db(database).await?;
 	// Import the data into the database
-	client.import(file).await.inspect_err(|_| {
+	let import = match format {
+		Format::Surrealql => client.import(file).await,
+		format => {
+			let mut import = client.import(file).with_config().format(format);
+			if let Some(table) = table {
+				import = import.table(table);
+			}
+			if let Some(columns) = columns {
+				import = import.columns(columns);
+			}
+			import.await
+		}
+	};
+	import.inspect_err(|_| {
 		error!("Surreal import failed, import might only be partially completed or have failed entirely.")
 	})?;
-	info!("The SurrealQL file was imported successfully");
+	match format {
+		Format::Surrealql => info!("The SurrealQL file was imported successfully"),
+		Format::Csv => info!("The CSV file was imported successfully"),
+		Format::Ndjson => info!("The NDJSON file was imported successfully"),
+	}
 	// All ok
 	Ok(())
 }
@@ -1,10 +1,12 @@
 pub(crate) mod abstraction;
+mod backup;
 mod config;
 mod export;
 mod fix;
 mod import;
 mod isready;
 mod ml;
+mod rekey;
 mod sql;
 mod start;
 #[cfg(test)]
@@ -22,6 +24,7 @@ use crate::cli::version_client::VersionClient;
 use crate::cnf::DEBUG_BUILD_WARNING;
 use crate::cnf::{LOGO, PKG_VERSION};
 use crate::env::RELEASE;
+use backup::BackupCommandArguments;
 use clap::{Parser, Subcommand};
 pub use config::CF;
 use export::ExportCommandArguments;
@@ -29,6 +32,7 @@ use fix::FixCommandArguments;
 use import::ImportCommandArguments;
 use isready::IsReadyCommandArguments;
 use ml::MlCommand;
+use rekey::RekeyCommandArguments;
 use semver::Version;
 use sql::SqlCommandArguments;
 use start::StartCommandArguments;
@@ -76,10 +80,8 @@ struct Cli {
 enum Commands {
 	#[command(about = "Start the database server")]
 	Start(StartCommandArguments),
-	/* Not implemented yet
 	#[command(about = "Backup data to or from an existing database")]
 	Backup(BackupCommandArguments),
-	*/
 	#[command(about = "Import a SurrealQL script into an existing database")]
 	Import(ImportCommandArguments),
 	#[command(about = "Export an existing database as a SurrealQL script")]
@@ -101,6 +103,8 @@ enum Commands {
 	Validate(ValidateCommandArguments),
 	#[command(about = "Fix database storage issues")]
 	Fix(FixCommandArguments),
+	#[command(about = "Rewrite stored data with a new on-disk encryption key")]
+	Rekey(RekeyCommandArguments),
 }
 
 pub async fn init() -> ExitCode {
@@ -144,6 +148,7 @@ pub async fn init() -> ExitCode {
 	// After version warning we can run the respective command
 	let output = match args.command {
 		Commands::Start(args) => start::init(args).await,
+		Commands::Backup(args) => backup::init(args).await,
 		Commands::Import(args) => import::init(args).await,
 		Commands::Export(args) => export::init(args).await,
 		Commands::Version(args) => version::init(args).await,
@@ -153,6 +158,7 @@ pub async fn init() -> ExitCode {
 		Commands::IsReady(args) => isready::init(args).await,
 		Commands::Validate(args) => validate::init(args).await,
 		Commands::Fix(args) => fix::init(args).await,
+		Commands::Rekey(args) => rekey::init(args).await,
 	};
 	// Save the flamegraph and profile
 	#[cfg(feature = "performance-profiler")]
@@ -0,0 +1,41 @@
+use crate::dbs;
+use crate::err::Error;
+use clap::Args;
+use surrealdb::engine::any::IntoEndpoint;
+
+#[derive(Args, Debug)]
+pub struct RekeyCommandArguments {
+	#[arg(help = "Database path used for storing data")]
+	#[arg(env = "SURREAL_PATH", index = 1)]
+	#[arg(default_value = "memory")]
+	#[arg(value_parser = super::validator::path_valid)]
+	path: String,
+	#[arg(help = "Encryption key to rewrite the stored data with, or none to remove encryption")]
+	#[arg(env = "SURREAL_KEY", short = 'k', long = "key")]
+	#[arg(value_parser = super::validator::key_valid)]
+	key: Option<String>,
+	#[arg(help = "Encryption key the stored data is currently encrypted with, if any")]
+	#[arg(env = "SURREAL_PREVIOUS_KEY", long = "previous-key")]
+	#[arg(value_parser = super::validator::key_valid)]
+	previous_key: Option<String>,
+}
+
+pub async fn init(
+	RekeyCommandArguments {
+		path,
+		key,
+		previous_key,
+	}: RekeyCommandArguments,
+) -> Result<(), Error> {
+	// Clean the path
+	let endpoint = path.into_endpoint()?;
+	let path = if endpoint.path.is_empty() {
+		endpoint.url.to_string()
+	} else {
+		endpoint.path
+	};
+	// Rewrite the stored data with the new key
+	dbs::rekey(path, key, previous_key).await?;
+	// All ok
+	Ok(())
+}
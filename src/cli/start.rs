@@ -4,6 +4,7 @@ use crate::dbs;
 use crate::dbs::StartCommandDbsOptions;
 use crate::env;
 use crate::err::Error;
+use crate::net::replica::ReplicaOptions;
 use crate::net::{self, client_ip::ClientIp};
 use clap::Args;
 use std::net::SocketAddr;
@@ -26,11 +27,6 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_NO_BANNER", long)]
 	#[arg(default_value_t = false)]
 	no_banner: bool,
-	#[arg(help = "Encryption key to use for on-disk encryption")]
-	#[arg(env = "SURREAL_KEY", short = 'k', long = "key")]
-	#[arg(value_parser = super::validator::key_valid)]
-	#[arg(hide = true)] // Not currently in use
-	key: Option<String>,
 	//
 	// Tasks
 	//
@@ -101,6 +97,12 @@ pub struct StartCommandArguments {
 	#[command(next_help_heading = "HTTP server")]
 	#[command(flatten)]
 	web: Option<StartCommandWebTlsOptions>,
+	//
+	// Replication
+	//
+	#[command(next_help_heading = "Replication")]
+	#[command(flatten)]
+	replica: Option<StartCommandReplicaOptions>,
 	#[arg(help = "The method of detecting the client's IP address")]
 	#[arg(env = "SURREAL_CLIENT_IP", long)]
 	#[arg(default_value = "socket", value_enum)]
@@ -146,6 +148,30 @@ struct StartCommandWebTlsOptions {
 	web_key: Option<PathBuf>,
 }
 
+#[derive(Args, Debug)]
+#[group(requires_all = ["replica_of", "replica_ns", "replica_db"], multiple = true)]
+struct StartCommandReplicaOptions {
+	#[arg(help = "The endpoint of a primary server to follow as a read replica")]
+	#[arg(env = "SURREAL_REPLICA_OF", long = "replica-of", value_parser = super::validator::endpoint_valid)]
+	replica_of: Option<String>,
+	#[arg(help = "The namespace to replicate from the primary server")]
+	#[arg(env = "SURREAL_REPLICA_NS", long = "replica-ns")]
+	replica_ns: Option<String>,
+	#[arg(help = "The database to replicate from the primary server")]
+	#[arg(env = "SURREAL_REPLICA_DB", long = "replica-db")]
+	replica_db: Option<String>,
+	#[arg(help = "The username used to authenticate with the primary server")]
+	#[arg(env = "SURREAL_REPLICA_USER", long = "replica-user", requires = "replica_pass")]
+	replica_user: Option<String>,
+	#[arg(help = "The password used to authenticate with the primary server")]
+	#[arg(env = "SURREAL_REPLICA_PASS", long = "replica-pass", requires = "replica_user")]
+	replica_pass: Option<String>,
+	#[arg(help = "The interval at which to poll the primary server for changes")]
+	#[arg(env = "SURREAL_REPLICA_INTERVAL", long = "replica-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "1s")]
+	replica_interval: Duration,
+}
+
 pub async fn init(
 	StartCommandArguments {
 		path,
@@ -155,6 +181,7 @@ pub async fn init(
 		listen_addresses,
 		dbs,
 		web,
+		replica,
 		node_membership_refresh_interval,
 		node_membership_check_interval,
 		node_membership_cleanup_interval,
@@ -205,16 +232,45 @@ pub async fn init(
 	env::init().await?;
 	// Create a token to cancel tasks
 	let canceller = CancellationToken::new();
+	// A read replica only applies the changes from the primary server
+	let replicating = replica.as_ref().is_some_and(|v| v.replica_of.is_some());
 	// Start the datastore
-	let datastore = Arc::new(dbs::init(dbs).await?);
+	let datastore = Arc::new(dbs::init(dbs).await?.with_read_only(replicating));
 	// Start the node agent
 	let nodetasks = tasks::init(datastore.clone(), canceller.clone(), &CF.get().unwrap().engine);
+	// Start following the primary server
+	let replica = match replica {
+		Some(StartCommandReplicaOptions {
+			replica_of: Some(endpoint),
+			replica_ns: Some(namespace),
+			replica_db: Some(database),
+			replica_user: username,
+			replica_pass: password,
+			replica_interval: interval,
+		}) => Some(net::replica::init(
+			datastore.clone(),
+			canceller.clone(),
+			ReplicaOptions {
+				endpoint,
+				namespace,
+				database,
+				username,
+				password,
+				interval,
+			},
+		)?),
+		_ => None,
+	};
 	// Start the web server
 	net::init(datastore.clone(), canceller.clone()).await?;
 	// Shutdown and stop closed tasks
 	canceller.cancel();
 	// Wait for background tasks to finish
 	nodetasks.resolve().await?;
+	// Wait for replication to finish
+	if let Some(replica) = replica {
+		let _ = replica.await;
+	}
 	// Shutdown the datastore
 	datastore.shutdown().await?;
 	// All ok
@@ -9,7 +9,7 @@ use surrealdb::dbs::capabilities::{
 	ArbitraryQueryTarget, ExperimentalTarget, FuncTarget, MethodTarget, NetTarget, RouteTarget,
 	Targets,
 };
-use surrealdb::kvs::export::TableConfig;
+use surrealdb::kvs::export::{Column, TableConfig};
 
 pub(crate) mod parser;
 
@@ -81,11 +81,11 @@ pub(crate) fn endpoint_valid(v: &str) -> Result<String, String> {
 }
 
 pub(crate) fn key_valid(v: &str) -> Result<String, String> {
-	match v.len() {
-		16 => Ok(v.to_string()),
-		24 => Ok(v.to_string()),
-		32 => Ok(v.to_string()),
-		_ => Err(String::from("Ensure your database encryption key is 16, 24, or 32 bytes long")),
+	match surrealdb::kvs::decode_encryption_key(v) {
+		Ok(_) => Ok(v.to_string()),
+		Err(_) => Err(String::from(
+			"Ensure your database encryption key is 32 random bytes, encoded as hex or base64",
+		)),
 	}
 }
 
@@ -191,6 +191,14 @@ pub(crate) fn export_tables(value: &str) -> Result<TableConfig, String> {
 	Ok(TableConfig::Some(value.split(",").filter(|s| !s.is_empty()).map(str::to_string).collect()))
 }
 
+pub(crate) fn columns(value: &str) -> Result<Vec<Column>, String> {
+	value
+		.split(",")
+		.filter(|s| !s.is_empty())
+		.map(|s| Column::from_str(s).map_err(|e| e.to_string()))
+		.collect()
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
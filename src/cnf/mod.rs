@@ -87,6 +87,10 @@ pub static WEBSOCKET_RESPONSE_FLUSH_PERIOD: LazyLock<u64> =
 pub static WEBSOCKET_RESPONSE_CHANNEL_SIZE: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_WEBSOCKET_RESPONSE_CHANNEL_SIZE", usize, 100);
 
+/// How long an RPC transaction can remain idle before it is cancelled (defaults to 30 seconds).
+pub static WEBSOCKET_TRANSACTION_TIMEOUT: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_WEBSOCKET_TRANSACTION_TIMEOUT", u64, 30);
+
 /// The number of runtime worker threads to start (defaults to the number of CPU cores, minimum 4)
 pub static RUNTIME_WORKER_THREADS: LazyLock<usize> =
 	lazy_env_parse_or_else!("SURREAL_RUNTIME_WORKER_THREADS", usize, |_| {
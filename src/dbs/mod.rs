@@ -27,6 +27,10 @@ pub struct StartCommandDbsOptions {
 	#[arg(env = "SURREAL_TRANSACTION_TIMEOUT", long)]
 	#[arg(value_parser = super::cli::validator::duration)]
 	transaction_timeout: Option<Duration>,
+	#[arg(help = "Encryption key to use for on-disk encryption")]
+	#[arg(env = "SURREAL_KEY", short = 'k', long = "key")]
+	#[arg(value_parser = super::cli::validator::key_valid)]
+	encryption_key: Option<String>,
 	#[arg(help = "Whether to allow unauthenticated access", help_heading = "Authentication")]
 	#[arg(env = "SURREAL_UNAUTHENTICATED", long = "unauthenticated")]
 	#[arg(default_value_t = false)]
@@ -468,6 +472,7 @@ pub async fn init(
 		strict_mode,
 		query_timeout,
 		transaction_timeout,
+		encryption_key,
 		unauthenticated,
 		capabilities,
 		temporary_directory,
@@ -486,6 +491,10 @@ pub async fn init(
 	if let Some(v) = transaction_timeout {
 		debug!("Maximum transaction processing timeout is {v:?}");
 	}
+	// Log whether on-disk encryption is enabled
+	if encryption_key.is_some() {
+		debug!("Database on-disk encryption is enabled");
+	}
 	// Log whether authentication is disabled
 	if unauthenticated {
 		warn!("❌🔒 IMPORTANT: Authentication is disabled. This is not recommended for production use. 🔒❌");
@@ -507,7 +516,9 @@ pub async fn init(
 		.with_transaction_timeout(transaction_timeout)
 		.with_auth_enabled(!unauthenticated)
 		.with_temporary_directory(temporary_directory)
-		.with_capabilities(capabilities);
+		.with_capabilities(capabilities)
+		.with_encryption_key(encryption_key)
+		.await?;
 	// Ensure the storage version is up-to-date to prevent corruption
 	dbs.check_version().await?;
 	// Import file at start, if provided
@@ -526,9 +537,9 @@ pub async fn init(
 	Ok(dbs)
 }
 
-pub async fn fix(path: String) -> Result<(), Error> {
+pub async fn fix(path: String, key: Option<String>) -> Result<(), Error> {
 	// Parse and setup the desired kv datastore
-	let dbs = Arc::new(Datastore::new(&path).await?);
+	let dbs = Arc::new(Datastore::new(&path).await?.with_encryption_key(key).await?);
 	// Ensure the storage version is up-to-date to prevent corruption
 	let version = dbs.get_version().await?;
 	// Apply fixes
@@ -539,6 +550,21 @@ pub async fn fix(path: String) -> Result<(), Error> {
 	Ok(())
 }
 
+pub async fn rekey(
+	path: String,
+	key: Option<String>,
+	previous_key: Option<String>,
+) -> Result<(), Error> {
+	// Open the datastore with the key the stored data is encrypted with
+	let dbs = Datastore::new(&path).await?.with_encryption_key(previous_key).await?;
+	// Rewrite the stored values with the new key
+	let count = dbs.rewrite_encryption(key).await?;
+	// Log success
+	info!("Rewrote {count} stored values with the new encryption key");
+	// All ok
+	Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 	use std::str::FromStr;
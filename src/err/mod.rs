@@ -227,6 +227,15 @@ impl IntoResponse for Error {
 					information: None,
 				}),
 			),
+			err @ Error::Db(SurrealError::Db(SurrealDbError::ChangefeedExpired { .. })) => (
+				StatusCode::GONE,
+				Json(Message {
+					code: StatusCode::GONE.as_u16(),
+					details: Some("Changes no longer available".to_string()),
+					description: Some("The requested changes have been removed from the change feed. Request a new snapshot instead.".to_string()),
+					information: Some(err.to_string()),
+				}),
+			),
 			Error::InvalidType => (
 				StatusCode::UNSUPPORTED_MEDIA_TYPE,
 				Json(Message {
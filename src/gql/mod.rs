@@ -8,16 +8,17 @@ use std::{
 };
 
 use async_graphql::{
-	http::{create_multipart_mixed_stream, is_accept_multipart_mixed},
+	http::{create_multipart_mixed_stream, is_accept_multipart_mixed, ALL_WEBSOCKET_PROTOCOLS},
 	Executor, ParseRequestError,
 };
 use async_graphql_axum::{
-	rejection::GraphQLRejection, GraphQLBatchRequest, GraphQLRequest, GraphQLResponse,
+	rejection::GraphQLRejection, GraphQLBatchRequest, GraphQLProtocol, GraphQLRequest,
+	GraphQLResponse, GraphQLWebSocket,
 };
 use axum::{
 	body::{Body, HttpBody},
-	extract::FromRequest,
-	http::{Request as HttpRequest, Response as HttpResponse},
+	extract::{FromRequest, FromRequestParts, WebSocketUpgrade},
+	http::{Method, Request as HttpRequest, Response as HttpResponse},
 	response::IntoResponse,
 	BoxError,
 };
@@ -27,6 +28,7 @@ use surrealdb::dbs::capabilities::RouteTarget;
 use surrealdb::dbs::Session;
 use surrealdb::gql::cache::{Invalidator, SchemaCache};
 use surrealdb::gql::error::resolver_error;
+use surrealdb::gql::Subscriptions;
 use surrealdb::kvs::Datastore;
 use tower_service::Service;
 
@@ -41,10 +43,10 @@ pub struct GraphQL<I: Invalidator> {
 
 impl<I: Invalidator> GraphQL<I> {
 	/// Create a GraphQL handler.
-	pub fn new(invalidator: I, datastore: Arc<Datastore>) -> Self {
+	pub fn new(invalidator: I, datastore: Arc<Datastore>, subscriptions: Subscriptions) -> Self {
 		let _ = invalidator;
 		GraphQL {
-			cache: SchemaCache::new(datastore),
+			cache: SchemaCache::with_subscriptions(datastore, subscriptions),
 			// datastore,
 		}
 	}
@@ -107,6 +109,25 @@ where
 					return Ok(to_rejection(e).into_response());
 				}
 			};
+
+			// Serve subscriptions over a WebSocket connection
+			if req.method() == Method::GET {
+				let (mut parts, _body) = req.into_parts();
+				let protocol = match GraphQLProtocol::from_request_parts(&mut parts, &()).await {
+					Ok(protocol) => protocol,
+					Err(err) => return Ok(err.into_response()),
+				};
+				let upgrade = match WebSocketUpgrade::from_request_parts(&mut parts, &()).await {
+					Ok(upgrade) => upgrade,
+					Err(err) => return Ok(err.into_response()),
+				};
+				return Ok(upgrade
+					.protocols(ALL_WEBSOCKET_PROTOCOLS)
+					.on_upgrade(move |stream| {
+						GraphQLWebSocket::new(stream, executor, protocol).serve()
+					})
+					.into_response());
+			}
 			let is_accept_multipart_mixed = req
 				.headers()
 				.get("accept")
@@ -14,7 +14,8 @@ use axum::response::IntoResponse;
 use axum::routing::any;
 use axum::Extension;
 use axum::Router;
-use http::header::CONTENT_TYPE;
+use http::header::{ACCESS_CONTROL_REQUEST_METHOD, CONTENT_TYPE};
+use http::{Extensions, StatusCode, Version};
 use surrealdb::dbs::capabilities::ExperimentalTarget;
 use surrealdb::dbs::capabilities::RouteTarget;
 use surrealdb::dbs::Session;
@@ -32,8 +33,14 @@ use surrealdb_core::api::{
 	body::ApiBody, invocation::ApiInvocation, method::Method as ApiMethod,
 	response::ResponseInstruction,
 };
+use tower_http::compression::CompressionLayer;
 use tower_http::limit::RequestBodyLimitLayer;
 
+/// Marks whether a response should be compressed, as
+/// configured by the `api::res::compress` middleware
+#[derive(Clone, Copy)]
+struct Compress(bool);
+
 pub(super) fn router<S>() -> Router<S>
 where
 	S: Clone + Send + Sync + 'static,
@@ -42,6 +49,11 @@ where
 		.route("/api/:ns/:db/*path", any(handler))
 		.route_layer(DefaultBodyLimit::disable())
 		.layer(RequestBodyLimitLayer::new(*HTTP_MAX_API_BODY_SIZE))
+		.layer(CompressionLayer::new().compress_when(compress))
+}
+
+fn compress(_: StatusCode, _: Version, _: &HeaderMap, extensions: &Extensions) -> bool {
+	extensions.get::<Compress>().is_some_and(|v| v.0)
 }
 
 async fn handler(
@@ -68,6 +80,47 @@ async fn handler(
 		return Err(Error::ForbiddenRoute(RouteTarget::Api.to_string()));
 	}
 
+	// Answer CORS preflight requests using the policy of the requested method
+	if method == Method::OPTIONS {
+		let Some(method) = headers
+			.get(ACCESS_CONTROL_REQUEST_METHOD)
+			.and_then(|v| v.to_str().ok())
+			.and_then(|v| ApiMethod::try_from(&Value::from(v)).ok())
+		else {
+			return Err(Error::NotFound(url));
+		};
+
+		let tx = Arc::new(
+			ds.transaction(TransactionType::Read, LockType::Optimistic)
+				.await
+				.map_err(Error::from)?,
+		);
+		let apis = tx.all_db_apis(&ns, &db).await.map_err(Error::from)?;
+		let segments: Vec<&str> = path.split('/').filter(|x| !x.is_empty()).collect();
+
+		let res = if let Some((api, params)) = apis.as_ref().find_api(segments, method) {
+			let invocation = ApiInvocation {
+				params,
+				method,
+				headers,
+				query: query.inner,
+			};
+
+			invocation.preflight_with_transaction(tx.clone(), ds.clone(), &session, api).await
+		} else {
+			Ok(None)
+		};
+
+		// Cancel the transaction
+		tx.cancel().await.map_err(Error::from)?;
+
+		return match res {
+			Ok(Some(res)) => Ok((res.status, res.headers, Extension(Compress(false)), Vec::new())),
+			Err(e) => Err(Error::from(e)),
+			_ => Err(Error::NotFound(url)),
+		};
+	}
+
 	let method = match method {
 		Method::DELETE => ApiMethod::Delete,
 		Method::GET => ApiMethod::Get,
@@ -78,9 +131,12 @@ async fn handler(
 		_ => return Err(Error::NotFound(url)),
 	};
 
-	let tx = Arc::new(
-		ds.transaction(TransactionType::Write, LockType::Optimistic).await.map_err(Error::from)?,
-	);
+	// Data can not be written on a read only replica
+	let tt = match ds.is_read_only() {
+		true => TransactionType::Read,
+		false => TransactionType::Write,
+	};
+	let tx = Arc::new(ds.transaction(tt, LockType::Optimistic).await.map_err(Error::from)?);
 	let apis = tx.all_db_apis(&ns, &db).await.map_err(Error::from)?;
 	let segments: Vec<&str> = path.split('/').filter(|x| !x.is_empty()).collect();
 
@@ -112,7 +168,10 @@ async fn handler(
 		};
 
 	// Commit the transaction
-	tx.commit().await.map_err(Error::from)?;
+	match ds.is_read_only() {
+		true => tx.cancel().await.map_err(Error::from)?,
+		false => tx.commit().await.map_err(Error::from)?,
+	}
 
 	let res_body: Vec<u8> = if let Some(body) = res.body {
 		match res_instruction {
@@ -175,5 +234,5 @@ async fn handler(
 		Vec::new()
 	};
 
-	Ok((res.status, res.headers, res_body))
+	Ok((res.status, res.headers, Extension(Compress(res.compress)), res_body))
 }
@@ -65,7 +65,7 @@ async fn handle_inner(
 	// Ensure a NS and DB are set
 	let (nsv, dbv) = check_ns_db(&session)?;
 	// Check the permissions level
-	db.check(&session, View, Any.on_db(&nsv, &dbv))?;
+	db.check_with_roles(&session, View, Any.on_db(&nsv, &dbv)).await?;
 	// Create a new bounded channel
 	let (snd, rcv) = surrealdb::channel::bounded(1);
 	// Start the export task
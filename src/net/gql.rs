@@ -2,16 +2,18 @@ use axum::Router;
 use std::sync::Arc;
 
 use axum::routing::post_service;
+use surrealdb::gql::Subscriptions;
 
 use surrealdb::gql::cache::Pessimistic;
 use surrealdb::kvs::Datastore;
 
 use crate::gql::GraphQL;
 
-pub(super) async fn router<S>(ds: Arc<Datastore>) -> Router<S>
+pub(super) async fn router<S>(ds: Arc<Datastore>, subscriptions: Subscriptions) -> Router<S>
 where
 	S: Clone + Send + Sync + 'static,
 {
-	let service = GraphQL::new(Pessimistic, ds);
-	Router::new().route("/graphql", post_service(service))
+	let service = GraphQL::new(Pessimistic, ds, subscriptions);
+	// Subscriptions are served over a WebSocket upgrade
+	Router::new().route("/graphql", post_service(service.clone()).get_service(service))
 }
@@ -4,6 +4,7 @@ use crate::cnf::HTTP_MAX_IMPORT_BODY_SIZE;
 use crate::err::Error;
 use crate::net::output;
 use axum::extract::DefaultBodyLimit;
+use axum::extract::Query;
 use axum::extract::Request;
 use axum::response::IntoResponse;
 use axum::routing::post;
@@ -11,12 +12,38 @@ use axum::Extension;
 use axum::Router;
 use axum_extra::TypedHeader;
 use futures::TryStreamExt;
+use serde::Deserialize;
 use surrealdb::dbs::capabilities::RouteTarget;
 use surrealdb::dbs::Session;
 use surrealdb::iam::Action::Edit;
 use surrealdb::iam::ResourceKind::Any;
+use surrealdb::kvs::import;
 use tower_http::limit::RequestBodyLimitLayer;
 
+#[derive(Default, Deserialize, Debug, Clone)]
+struct QueryOptions {
+	pub format: Option<String>,
+	pub table: Option<String>,
+	pub columns: Option<String>,
+}
+
+impl TryFrom<QueryOptions> for import::Config {
+	type Error = Error;
+	fn try_from(query: QueryOptions) -> Result<Self, Self::Error> {
+		Ok(import::Config {
+			format: match query.format {
+				Some(v) => v.parse()?,
+				None => Default::default(),
+			},
+			table: query.table,
+			columns: match query.columns {
+				Some(v) => Some(v.split(',').map(str::parse).collect::<Result<_, _>>()?),
+				None => None,
+			},
+		})
+	}
+}
+
 pub(super) fn router<S>() -> Router<S>
 where
 	S: Clone + Send + Sync + 'static,
@@ -31,6 +58,7 @@ async fn handler(
 	Extension(state): Extension<AppState>,
 	Extension(session): Extension<Session>,
 	accept: Option<TypedHeader<Accept>>,
+	Query(query): Query<QueryOptions>,
 	request: Request,
 ) -> Result<impl IntoResponse, impl IntoResponse> {
 	// Get the datastore reference
@@ -41,7 +69,9 @@ async fn handler(
 		return Err(Error::ForbiddenRoute(RouteTarget::Import.to_string()));
 	}
 	// Check the permissions level
-	db.check(&session, Edit, Any.on_level(session.au.level().to_owned()))?;
+	db.check_with_roles(&session, Edit, Any.on_level(session.au.level().to_owned())).await?;
+	// Parse the import format and options
+	let cfg = import::Config::try_from(query)?;
 
 	let body_stream = request
 		.into_body()
@@ -49,7 +79,7 @@ async fn handler(
 		.map_err(|e| surrealdb_core::err::Error::QueryStream(e.to_string()));
 
 	// Execute the sql query in the database
-	match db.import_stream(&session, body_stream).await {
+	match db.import_with_config(&session, cfg, body_stream).await {
 		Ok(res) => {
 			match accept.as_deref() {
 				// Simple serialization
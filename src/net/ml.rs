@@ -64,7 +64,7 @@ async fn import(
 	// Ensure a NS and DB are set
 	let (nsv, dbv) = check_ns_db(&session)?;
 	// Check the permissions level
-	db.check(&session, Edit, Model.on_db(&nsv, &dbv))?;
+	db.check_with_roles(&session, Edit, Model.on_db(&nsv, &dbv)).await?;
 	// Create a new buffer
 	let mut buffer = Vec::new();
 	// Load all the uploaded file chunks
@@ -122,7 +122,7 @@ async fn export(
 	// Ensure a NS and DB are set
 	let (nsv, dbv) = check_ns_db(&session)?;
 	// Check the permissions level
-	db.check(&session, View, Model.on_db(&nsv, &dbv))?;
+	db.check_with_roles(&session, View, Model.on_db(&nsv, &dbv)).await?;
 	// Start a new readonly transaction
 	let tx = db.transaction(Read, Optimistic).await?;
 	// Attempt to get the model definition
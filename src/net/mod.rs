@@ -12,6 +12,7 @@ mod key;
 mod ml;
 pub(crate) mod output;
 mod params;
+pub(crate) mod replica;
 mod rpc;
 mod signals;
 mod signin;
@@ -149,23 +150,23 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<(), Error
 		.layer(SetSensitiveResponseHeadersLayer::from_shared(headers))
 		.layer(AsyncRequireAuthorizationLayer::new(auth::SurrealAuth))
 		.layer(headers::add_server_header(!opt.no_identification_headers))
-		.layer(headers::add_version_header(!opt.no_identification_headers))
-		// Apply CORS headers to relevant responses
-		.layer(
-			CorsLayer::new()
-				.allow_methods([
-					http::Method::GET,
-					http::Method::PUT,
-					http::Method::POST,
-					http::Method::PATCH,
-					http::Method::DELETE,
-					http::Method::OPTIONS,
-				])
-				.allow_headers(allow_header)
-				// allow requests from any origin
-				.allow_origin(Any)
-				.max_age(Duration::from_secs(86400)),
-		);
+		.layer(headers::add_version_header(!opt.no_identification_headers));
+
+	// Apply CORS headers to relevant responses. API routes
+	// are excluded, as they configure their own CORS policy
+	let cors = CorsLayer::new()
+		.allow_methods([
+			http::Method::GET,
+			http::Method::PUT,
+			http::Method::POST,
+			http::Method::PATCH,
+			http::Method::DELETE,
+			http::Method::OPTIONS,
+		])
+		.allow_headers(allow_header)
+		// allow requests from any origin
+		.allow_origin(Any)
+		.max_age(Duration::from_secs(86400));
 
 	let axum_app = Router::<Arc<RpcState>>::new()
 		// Redirect until we provide a UI
@@ -181,14 +182,15 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<(), Error
 		.merge(signin::router())
 		.merge(signup::router())
 		.merge(key::router())
-		.merge(ml::router())
-		.merge(api::router());
+		.merge(ml::router());
+
+	let rpc_state = Arc::new(RpcState::new());
 
 	let axum_app = if ds.get_capabilities().allows_experimental(&ExperimentalTarget::GraphQL) {
 		#[cfg(surrealdb_unstable)]
 		{
 			warn!("❌🔒IMPORTANT: GraphQL is a pre-release feature with known security flaws. This is not recommended for production use.🔒❌");
-			axum_app.merge(gql::router(ds.clone()).await)
+			axum_app.merge(gql::router(ds.clone(), rpc_state.gql_subscriptions.clone()).await)
 		}
 		#[cfg(not(surrealdb_unstable))]
 		{
@@ -199,13 +201,11 @@ pub async fn init(ds: Arc<Datastore>, ct: CancellationToken) -> Result<(), Error
 		axum_app
 	};
 
-	let axum_app = axum_app.layer(service);
+	let axum_app = axum_app.layer(cors).merge(api::router()).layer(service);
 
 	// Get a new server handler
 	let handle = Handle::new();
 
-	let rpc_state = Arc::new(RpcState::new());
-
 	// Setup the graceful shutdown handler
 	let shutdown_handler = graceful_shutdown(rpc_state.clone(), ct.clone(), handle.clone());
 
@@ -0,0 +1,170 @@
+use crate::err::Error;
+use futures::TryStreamExt;
+use reqwest::header::HeaderName;
+use reqwest::Client;
+use reqwest::StatusCode;
+use std::sync::Arc;
+use std::time::Duration;
+use surrealdb::dbs::Session;
+use surrealdb::headers::{DB, DEFINITIONS, NS, VERSIONSTAMP};
+use surrealdb::kvs::Datastore;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+const LOG: &str = "surrealdb::net::replica";
+
+#[derive(Clone, Debug)]
+pub struct ReplicaOptions {
+	/// The endpoint of the primary server
+	pub endpoint: String,
+	/// The namespace which should be replicated
+	pub namespace: String,
+	/// The database which should be replicated
+	pub database: String,
+	/// The username used to authenticate with the primary
+	pub username: Option<String>,
+	/// The password used to authenticate with the primary
+	pub password: Option<String>,
+	/// The interval at which to poll the primary for changes
+	pub interval: Duration,
+}
+
+/// The position of a replica in the change feed of the primary
+struct Position {
+	/// The versionstamp from which to request further changes
+	versionstamp: u64,
+	/// The digest of the definitions which have been applied
+	definitions: String,
+}
+
+/// Follows a primary server, replaying its snapshot and
+/// subsequent changes into the local datastore.
+struct Replica {
+	client: Client,
+	datastore: Arc<Datastore>,
+	session: Session,
+	options: ReplicaOptions,
+}
+
+impl Replica {
+	fn new(datastore: Arc<Datastore>, options: ReplicaOptions) -> Result<Self, Error> {
+		Ok(Replica {
+			client: Client::builder().build()?,
+			session: Session::owner().with_ns(&options.namespace).with_db(&options.database),
+			datastore,
+			options,
+		})
+	}
+
+	/// Fetches and applies a snapshot or a set of changes from
+	/// the primary, returning the position to continue from.
+	///
+	/// Returns `None` if the definitions on the primary have changed
+	/// since they were applied, or if the changes since the position have
+	/// been removed from the change feed, in which case a new snapshot is
+	/// needed.
+	async fn sync(&self, pos: Option<&Position>) -> Result<Option<Position>, Error> {
+		// Prepare the request to the primary
+		let url = format!("{}/sync", self.options.endpoint.trim_end_matches('/'));
+		let mut req = self
+			.client
+			.get(url)
+			.header(&NS, &self.options.namespace)
+			.header(&DB, &self.options.database);
+		if let Some(pos) = pos {
+			req = req.query(&[("since", pos.versionstamp)]);
+		}
+		if let Some(user) = &self.options.username {
+			req = req.basic_auth(user, self.options.password.as_ref());
+		}
+		// Send the request to the primary
+		let res = req.send().await?;
+		// The changes since this position are no longer retained
+		if pos.is_some() && res.status() == StatusCode::GONE {
+			return Ok(None);
+		}
+		let res = res.error_for_status()?;
+		// Fetch the position to continue from
+		let header = |name: &HeaderName| {
+			res.headers()
+				.get(name)
+				.and_then(|v| v.to_str().ok())
+				.map(ToOwned::to_owned)
+				.ok_or_else(|| Error::InvalidHeader(name.clone(), "missing".to_owned()))
+		};
+		let next = Position {
+			versionstamp: header(&VERSIONSTAMP)?.parse().map_err(|_| {
+				Error::InvalidHeader(VERSIONSTAMP.clone(), "invalid versionstamp".to_owned())
+			})?,
+			definitions: header(&DEFINITIONS)?,
+		};
+		// Changed definitions are not recorded in the change feed
+		if pos.is_some_and(|pos| pos.definitions != next.definitions) {
+			return Ok(None);
+		}
+		// Apply the snapshot or changes to the local datastore
+		let body =
+			res.bytes_stream().map_err(|e| surrealdb_core::err::Error::QueryStream(e.to_string()));
+		self.datastore.sync_apply(&self.session, pos.is_none(), body).await?;
+		// Return the position to continue from
+		Ok(Some(next))
+	}
+}
+
+/// Starts following the primary server in the background
+pub fn init(
+	ds: Arc<Datastore>,
+	ct: CancellationToken,
+	opts: ReplicaOptions,
+) -> Result<JoinHandle<()>, Error> {
+	// Get the delay interval from the config
+	let delay = opts.interval;
+	// Create the replication client
+	let replica = Replica::new(ds, opts)?;
+	// Spawn a future
+	Ok(tokio::spawn(async move {
+		// Log the replication details
+		info!(target: LOG, "Replicating from {} every {delay:?}", replica.options.endpoint);
+		// The position to continue from
+		let mut pos: Option<Position> = None;
+		// Loop continuously until the task is cancelled
+		loop {
+			let wait = tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = ct.cancelled() => break,
+				// Apply the next snapshot or set of changes
+				res = replica.sync(pos.as_ref()) => match res {
+					Ok(Some(next)) => {
+						if pos.is_none() {
+							info!(target: LOG, "Applied snapshot from {}", replica.options.endpoint);
+						}
+						// Continue immediately while there are further changes
+						let wait = pos.as_ref().is_some_and(|pos| pos.versionstamp == next.versionstamp);
+						pos = Some(next);
+						wait
+					}
+					Ok(None) => {
+						info!(target: LOG, "Unable to continue from the primary changes, applying a new snapshot");
+						pos = None;
+						false
+					}
+					Err(e) => {
+						error!(target: LOG, "Error replicating from the primary: {e}");
+						true
+					}
+				}
+			};
+			if wait {
+				tokio::select! {
+					biased;
+					// Check if this has shutdown
+					_ = ct.cancelled() => break,
+					// Wait before polling for further changes
+					_ = tokio::time::sleep(delay) => {}
+				}
+			}
+		}
+		trace!(target: LOG, "Background task exited: Replicating from the primary");
+	}))
+}
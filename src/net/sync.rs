@@ -1,20 +1,49 @@
+use super::AppState;
+use crate::cnf::HTTP_MAX_IMPORT_BODY_SIZE;
 use crate::err::Error;
-use axum::response::IntoResponse;
+use crate::net::output;
+use axum::body::Body;
+use axum::extract::{DefaultBodyLimit, Request};
+use axum::response::{IntoResponse, Response};
 use axum::routing::get;
 use axum::{Extension, Router};
+use axum_extra::extract::Query;
+use bytes::Bytes;
+use futures::{FutureExt, TryStreamExt};
+use http::StatusCode;
+use serde::Deserialize;
 use surrealdb::dbs::capabilities::RouteTarget;
+use surrealdb::dbs::Session;
+use surrealdb::headers::{DEFINITIONS, VERSIONSTAMP};
+use surrealdb::iam::check::check_ns_db;
+use surrealdb::iam::Action::{Edit, View};
+use surrealdb::iam::ResourceKind::Any;
+use tower_http::limit::RequestBodyLimitLayer;
 
-use super::AppState;
+#[derive(Default, Deserialize, Debug, Clone)]
+struct QueryOptions {
+	/// The versionstamp from which to read changes
+	pub since: Option<u64>,
+	/// Whether the applied changes are a full snapshot
+	#[serde(default)]
+	pub snapshot: bool,
+}
 
 pub(super) fn router<S>() -> Router<S>
 where
 	S: Clone + Send + Sync + 'static,
 {
-	Router::new().route("/sync", get(save).post(load))
+	Router::new()
+		.route("/sync", get(save).post(load))
+		.route_layer(DefaultBodyLimit::disable())
+		.layer(RequestBodyLimitLayer::new(*HTTP_MAX_IMPORT_BODY_SIZE))
 }
 
 async fn load(
 	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+	Query(query): Query<QueryOptions>,
+	request: Request,
 ) -> Result<impl IntoResponse, impl IntoResponse> {
 	// Get the datastore reference
 	let db = &state.datastore;
@@ -23,13 +52,28 @@ async fn load(
 		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Sync);
 		return Err(Error::ForbiddenRoute(RouteTarget::Sync.to_string()));
 	}
-
-	Ok("Load")
+	// Ensure a NS and DB are set
+	let (nsv, dbv) = check_ns_db(&session)?;
+	// Check the permissions level
+	db.check_with_roles(&session, Edit, Any.on_db(&nsv, &dbv)).await?;
+	// Convert the request body into a stream
+	let body_stream = request
+		.into_body()
+		.into_data_stream()
+		.map_err(|e| surrealdb_core::err::Error::QueryStream(e.to_string()));
+	// Apply the snapshot or changes to the database
+	match db.sync_apply(&session, query.snapshot, body_stream).await {
+		Ok(_) => Ok(output::none()),
+		// There was an error when applying the changes
+		Err(err) => Err(Error::from(err)),
+	}
 }
 
 async fn save(
 	Extension(state): Extension<AppState>,
-) -> Result<impl IntoResponse, impl IntoResponse> {
+	Extension(session): Extension<Session>,
+	Query(query): Query<QueryOptions>,
+) -> Result<impl IntoResponse, Error> {
 	// Get the datastore reference
 	let db = &state.datastore;
 	// Check if capabilities allow querying the requested HTTP route
@@ -37,6 +81,46 @@ async fn save(
 		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Sync);
 		return Err(Error::ForbiddenRoute(RouteTarget::Sync.to_string()));
 	}
-
-	Ok("Save")
+	// Create a chunked response
+	let (chn, body_stream) = surrealdb::channel::bounded::<Result<Bytes, Error>>(1);
+	let body = Body::from_stream(body_stream);
+	// Ensure a NS and DB are set
+	let (nsv, dbv) = check_ns_db(&session)?;
+	// Check the permissions level
+	db.check_with_roles(&session, View, Any.on_db(&nsv, &dbv)).await?;
+	// Create a new bounded channel
+	let (snd, rcv) = surrealdb::channel::bounded(1);
+	// Start a snapshot, or read the changes since a versionstamp
+	let (next, defs, task) = match query.since {
+		None => {
+			let (next, defs, task) = db.sync_snapshot(&session, snd).await?;
+			(next, defs, task.boxed())
+		}
+		Some(since) => {
+			let (next, defs, task) = db.sync_changes(&session, since, snd).await?;
+			(next, defs, task.boxed())
+		}
+	};
+	// Spawn a new database sync job
+	let err = chn.clone();
+	tokio::spawn(async move {
+		if let Err(e) = task.await {
+			error!("Error reading the database for replication: {e}");
+			// Abort the response, so that the body is never applied partially
+			let _ = err.send(Err(Error::from(e))).await;
+		}
+	});
+	// Process all chunk values
+	tokio::spawn(async move {
+		while let Ok(v) = rcv.recv().await {
+			let _ = chn.send(Ok(Bytes::from(v))).await;
+		}
+	});
+	// Return the chunked body
+	Ok(Response::builder()
+		.status(StatusCode::OK)
+		.header(&VERSIONSTAMP, next.to_string())
+		.header(&DEFINITIONS, defs)
+		.body(body)
+		.unwrap())
 }
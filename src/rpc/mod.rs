@@ -31,6 +31,9 @@ pub struct RpcState {
 	pub web_sockets: WebSockets,
 	/// Stores the currently initiated LIVE queries
 	pub live_queries: LiveQueries,
+	/// Routes LIVE query notifications to GraphQL subscriptions
+	#[cfg(surrealdb_unstable)]
+	pub gql_subscriptions: surrealdb::gql::Subscriptions,
 }
 
 impl RpcState {
@@ -38,6 +41,8 @@ impl RpcState {
 		RpcState {
 			web_sockets: WebSockets::default(),
 			live_queries: LiveQueries::default(),
+			#[cfg(surrealdb_unstable)]
+			gql_subscriptions: surrealdb::gql::Subscriptions::default(),
 		}
 	}
 }
@@ -93,6 +98,10 @@ pub(crate) async fn notifications(
 							// Pus the future to the pipeline
 							futures.push(future);
 						}
+					} else {
+						// Otherwise this may belong to a GraphQL subscription
+						#[cfg(surrealdb_unstable)]
+						state.gql_subscriptions.deliver(notification).await;
 					}
 				},
 			}
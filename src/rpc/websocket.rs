@@ -3,6 +3,7 @@ use crate::cnf::WEBSOCKET_PING_FREQUENCY;
 use crate::cnf::WEBSOCKET_RESPONSE_BUFFER_SIZE;
 use crate::cnf::WEBSOCKET_RESPONSE_CHANNEL_SIZE;
 use crate::cnf::WEBSOCKET_RESPONSE_FLUSH_PERIOD;
+use crate::cnf::WEBSOCKET_TRANSACTION_TIMEOUT;
 use crate::cnf::{PKG_NAME, PKG_VERSION};
 use crate::rpc::failure::Failure;
 use crate::rpc::format::WsFormat;
@@ -29,6 +30,7 @@ use surrealdb::rpc::format::Format;
 use surrealdb::rpc::Data;
 use surrealdb::rpc::Method;
 use surrealdb::rpc::RpcContext;
+use surrealdb::rpc::Transactions;
 use surrealdb::sql::Array;
 use surrealdb::sql::Value;
 use surrealdb_core::rpc::RpcProtocolV1;
@@ -66,6 +68,8 @@ pub struct Websocket {
 	pub(crate) canceller: CancellationToken,
 	/// The channels used to send and receive WebSocket messages
 	pub(crate) channel: Sender<Message>,
+	/// The interactive transactions opened on this WebSocket connection
+	pub(crate) transactions: Transactions,
 	/// The GraphQL schema cache stored in advance
 	#[cfg(surrealdb_unstable)]
 	pub(crate) gql_schema: SchemaCache<Pessimistic>,
@@ -95,6 +99,7 @@ impl Websocket {
 			canceller: CancellationToken::new(),
 			session: ArcSwap::from(Arc::new(session)),
 			channel: sender.clone(),
+			transactions: Transactions::new(Duration::from_secs(*WEBSOCKET_TRANSACTION_TIMEOUT)),
 			#[cfg(surrealdb_unstable)]
 			gql_schema: SchemaCache::new(datastore.clone()),
 			datastore,
@@ -140,6 +145,8 @@ impl Websocket {
 		trace!("WebSocket {id} disconnected");
 		// Cleanup the live queries for this WebSocket
 		rpc.cleanup_lqs().await;
+		// Cancel any open transactions for this WebSocket
+		rpc.transactions.cancel_all().await;
 		// Remove this WebSocket from the list
 		state.web_sockets.write().await.remove(&id);
 		// Stop telemetry metrics for this connection
@@ -163,6 +170,8 @@ impl Websocket {
 				_ = canceller.cancelled() => break,
 				// Send a regular ping message
 				_ = interval.tick() => {
+					// Cancel any transactions which have timed out
+					rpc.transactions.expire().await;
 					// Create a new ping message
 					let msg = Message::Ping(vec![]);
 					// Close the connection if the message fails
@@ -388,7 +397,7 @@ impl Websocket {
 							// Otherwise process the request message
 							else {
 								// Process the message
-								Self::process_message(rpc.clone(), req.version, req.method, req.params).await
+								Self::process_message(rpc.clone(), req.version, req.txn, req.method, req.params).await
 									.into_response(req.id)
 									.send(otel_cx.clone(), rpc.format, chn)
 									.with_context(otel_cx.as_ref().clone())
@@ -414,6 +423,7 @@ impl Websocket {
 	async fn process_message(
 		rpc: Arc<Websocket>,
 		version: Option<u8>,
+		txn: Option<Uuid>,
 		method: Method,
 		params: Array,
 	) -> Result<Data, Failure> {
@@ -423,7 +433,7 @@ impl Websocket {
 			return Err(Failure::METHOD_NOT_FOUND);
 		}
 		// Execute the specified method
-		RpcContext::execute(rpc.as_ref(), version, method, params).await.map_err(Into::into)
+		RpcContext::execute(rpc.as_ref(), version, txn, method, params).await.map_err(Into::into)
 	}
 
 	/// Reject a WebSocket message due to server overloading
@@ -507,6 +517,15 @@ impl RpcContext for Websocket {
 		}
 	}
 
+	// ------------------------------
+	// Transactions
+	// ------------------------------
+
+	/// Interactive transactions are enabled on WebSockets
+	fn transactions(&self) -> Option<&Transactions> {
+		Some(&self.transactions)
+	}
+
 	// ------------------------------
 	// GraphQL
 	// ------------------------------
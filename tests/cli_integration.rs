@@ -1003,6 +1003,98 @@ mod cli_integration {
 		}
 	}
 
+	#[test(tokio::test)]
+	async fn backup_and_restore() {
+		let src = format!("surrealkv://{}", common::tmp_file("backup-src.skv"));
+		let dst = format!("surrealkv://{}", common::tmp_file("backup-dst.skv"));
+		let full = common::tmp_file("backup-full.bak");
+		let incr = common::tmp_file("backup-incr.bak");
+
+		info!("* Create some data in the source datastore");
+		{
+			let args = format!("sql --conn {src} --ns test --db test --hide-welcome");
+			common::run(&args)
+				.input("DEFINE TABLE thing CHANGEFEED 1h; CREATE thing:one;\n")
+				.output()
+				.expect("success");
+		}
+
+		info!("* Take a full backup");
+		{
+			let args = format!("backup {src} {full}");
+			common::run(&args).output().unwrap_or_else(|_| panic!("failed to backup: {args}"));
+		}
+
+		info!("* Make some further changes");
+		{
+			let args = format!("sql --conn {src} --ns test --db test --hide-welcome");
+			common::run(&args)
+				.input("CREATE thing:two; DELETE thing:one;\n")
+				.output()
+				.expect("success");
+		}
+
+		info!("* Take an incremental backup");
+		{
+			let args = format!("backup {src} {incr} --since {full}");
+			common::run(&args).output().unwrap_or_else(|_| panic!("failed to backup: {args}"));
+		}
+
+		info!("* Restore the backups into a new datastore");
+		{
+			let args = format!("backup {full} {dst}");
+			common::run(&args).output().unwrap_or_else(|_| panic!("failed to restore: {args}"));
+			let args = format!("backup {incr} {dst}");
+			common::run(&args).output().unwrap_or_else(|_| panic!("failed to restore: {args}"));
+		}
+
+		info!("* Query the restored datastore");
+		{
+			let args = format!("sql --conn {dst} --ns test --db test --hide-welcome");
+			let output = common::run(&args).input("SELECT * FROM thing;\n").output().unwrap();
+			assert!(output.contains("[[{ id: thing:two }]]"), "unexpected output: {output}");
+		}
+
+		info!("* Backups require a datastore and a file");
+		{
+			let args = format!("backup {full} {incr}");
+			assert!(common::run(&args).output().is_err());
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn csv_import_and_export() {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let creds = "--user root --pass root";
+		let ns = Ulid::new();
+		let db = Ulid::new();
+
+		info!("* Import a CSV file with a column mapping");
+		{
+			let file = common::tmp_file("import.csv");
+			tokio::fs::write(&file, "id,full_name,age\none,Tobie,35\ntwo,Jaime,\n").await.unwrap();
+			let args = format!("import --conn http://{addr} {creds} --ns {ns} --db {db} --format csv --table person --columns id,full_name=name,age {file}");
+			common::run(&args).output().unwrap_or_else(|_| panic!("failed to run import: {args}"));
+		}
+
+		info!("* Export the table as NDJSON");
+		{
+			let args = format!("export --conn http://{addr} {creds} --ns {ns} --db {db} --format ndjson --tables person -");
+			let output = common::run(&args)
+				.output()
+				.unwrap_or_else(|_| panic!("failed to run export: {args}"));
+			assert!(output.contains("{\"age\":\"35\",\"id\":\"person:one\",\"name\":\"Tobie\"}\n"));
+			assert!(output.contains("{\"id\":\"person:two\",\"name\":\"Jaime\"}\n"));
+		}
+
+		info!("* Exporting as CSV requires a single table");
+		{
+			let args =
+				format!("export --conn http://{addr} {creds} --ns {ns} --db {db} --format csv -");
+			assert!(common::run(&args).output().is_err());
+		}
+	}
+
 	#[test(tokio::test)]
 	async fn node() {
 		// Commands without credentials when auth is disabled, should succeed
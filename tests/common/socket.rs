@@ -49,6 +49,7 @@ enum SocketMsg {
 	SendAwait {
 		method: String,
 		args: serde_json::Value,
+		txn: Option<String>,
 		channel: oneshot::Sender<serde_json::Value>,
 	},
 	Send {
@@ -236,12 +237,16 @@ impl Socket {
 		format: Format,
 		method: &str,
 		args: serde_json::Value,
+		txn: Option<String>,
 	) -> Result<()> {
-		let msg = json!({
+		let mut msg = json!({
 			"id": id,
 			"method": method,
 			"params": args,
 		});
+		if let Some(txn) = txn {
+			msg["txn"] = txn.into();
+		}
 
 		let msg = Self::to_msg(format, &msg)?;
 
@@ -269,16 +274,16 @@ impl Socket {
 						return Ok(());
 					};
 					match msg{
-						SocketMsg::SendAwait { method, args, channel } => {
+						SocketMsg::SendAwait { method, args, txn, channel } => {
 							let id = next_id;
 							next_id += 1;
 							awaiting.insert(id,channel);
-							Self::send_msg(&mut stream,id,format,&method, args).await?;
+							Self::send_msg(&mut stream,id,format,&method, args, txn).await?;
 						},
 						SocketMsg::Send { method, args } => {
 							let id = next_id;
 							next_id += 1;
-							Self::send_msg(&mut stream,id,format,&method, args).await?;
+							Self::send_msg(&mut stream,id,format,&method, args, None).await?;
 						},
 						SocketMsg::Close{ channel } => {
 							stream.close(None).await?;
@@ -312,6 +317,16 @@ impl Socket {
 		&self,
 		method: &str,
 		params: serde_json::Value,
+	) -> Result<serde_json::Value> {
+		self.send_request_with_txn(method, params, None).await
+	}
+
+	/// Send a message within an RPC transaction and receive a reponse from the WebSocket server
+	pub async fn send_request_with_txn(
+		&self,
+		method: &str,
+		params: serde_json::Value,
+		txn: Option<&str>,
 	) -> Result<serde_json::Value> {
 		let (send, recv) = oneshot::channel();
 		if (self
@@ -319,6 +334,7 @@ impl Socket {
 			.send(SocketMsg::SendAwait {
 				method: method.to_string(),
 				args: params,
+				txn: txn.map(str::to_string),
 				channel: send,
 			})
 			.await)
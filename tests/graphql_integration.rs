@@ -507,4 +507,224 @@ mod graphql_integration {
 
 		Ok(())
 	}
+
+	#[test(tokio::test)]
+	async fn mutations() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_gql_without_auth().await.unwrap();
+		let gql_url = &format!("http://{addr}/graphql");
+		let sql_url = &format!("http://{addr}/sql");
+
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// add schema
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+					DEFINE CONFIG GRAPHQL AUTO;
+					DEFINE TABLE foo SCHEMAFUL;
+					DEFINE FIELD val ON foo TYPE int;
+					DEFINE FIELD name ON foo TYPE option<string>;
+				"#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// create a record
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation{createFoo(id: "1", data: {val: 42}){id, val}}"#})
+						.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.text().await?;
+			let expected = json!({
+				"data": {
+					"createFoo": {
+						"id": "foo:1",
+						"val": 42
+					}
+				}
+			});
+			assert_eq!(expected.to_string(), body)
+		}
+
+		// input types are checked against the field kinds
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation{createFoo(data: {val: "text"}){id}}"#}).to_string(),
+				)
+				.send()
+				.await?;
+			let body = res.text().await?;
+			assert!(body.contains("errors"), "body: {body}")
+		}
+
+		// update and upsert records
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation{updateFoo(id: "foo:1", data: {name: "one"}){id, val, name}, upsertFoo(id: "2", data: {val: 43}){id, val}}"#})
+						.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.text().await?;
+			let expected = json!({
+				"data": {
+					"updateFoo": {
+						"id": "foo:1",
+						"val": 42,
+						"name": "one"
+					},
+					"upsertFoo": {
+						"id": "foo:2",
+						"val": 43
+					}
+				}
+			});
+			assert_eq!(expected.to_string(), body)
+		}
+
+		// delete records
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation{deleteFoo(id: "1"), again: deleteFoo(id: "1")}"#})
+						.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.text().await?;
+			let expected = json!({
+				"data": {
+					"deleteFoo": true,
+					"again": false
+				}
+			});
+			assert_eq!(expected.to_string(), body)
+		}
+
+		// check the remaining data
+		{
+			let res = client
+				.post(gql_url)
+				.body(json!({"query": r#"query{foo{id, val}}"#}).to_string())
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.text().await?;
+			let expected = json!({
+				"data": {
+					"foo": [
+						{
+							"id": "foo:2",
+							"val": 43
+						}
+					]
+				}
+			});
+			assert_eq!(expected.to_string(), body)
+		}
+
+		Ok(())
+	}
+
+	#[test(tokio::test)]
+	async fn subscriptions() -> Result<(), Box<dyn std::error::Error>> {
+		use futures::{SinkExt, StreamExt};
+		use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+		use tokio_tungstenite::tungstenite::Message;
+
+		let (addr, _server) = common::start_server_gql_without_auth().await.unwrap();
+		let gql_url = &format!("ws://{addr}/graphql");
+		let sql_url = &format!("http://{addr}/sql");
+
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// add schema
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+					DEFINE CONFIG GRAPHQL AUTO;
+					DEFINE TABLE foo SCHEMAFUL;
+					DEFINE FIELD val ON foo TYPE int;
+				"#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// connect and subscribe to changes
+		let mut req = gql_url.into_client_request()?;
+		req.headers_mut().insert("surreal-ns", ns.parse()?);
+		req.headers_mut().insert("surreal-db", db.parse()?);
+		req.headers_mut().insert(header::SEC_WEBSOCKET_PROTOCOL, "graphql-transport-ws".parse()?);
+		let (mut socket, _) = tokio_tungstenite::connect_async(req).await?;
+		socket.send(Message::text(json!({"type": "connection_init"}).to_string())).await?;
+		let msg = socket.next().await.unwrap()?;
+		assert_eq!(msg.into_text()?, json!({"type": "connection_ack"}).to_string());
+		let query = r#"subscription{foo(filter: {val: {ne: 1}}){action, id, record{val}}}"#;
+		let msg = json!({"id": "1", "type": "subscribe", "payload": {"query": query}});
+		socket.send(Message::text(msg.to_string())).await?;
+		// wait for the live query to start
+		tokio::time::sleep(Duration::from_millis(500)).await;
+
+		// check that changes are delivered
+		for (sql, expected) in [
+			(
+				"CREATE foo:1 SET val = 1; CREATE foo:2 SET val = 42;",
+				json!({"action": "CREATE", "id": "foo:2", "record": {"val": 42}}),
+			),
+			(
+				"UPDATE foo:2 SET val = 43;",
+				json!({"action": "UPDATE", "id": "foo:2", "record": {"val": 43}}),
+			),
+			("DELETE foo:2;", json!({"action": "DELETE", "id": "foo:2", "record": null})),
+		] {
+			let res = client.post(sql_url).body(sql).send().await?;
+			assert_eq!(res.status(), 200);
+			let msg =
+				tokio::time::timeout(Duration::from_secs(5), socket.next()).await?.unwrap()?;
+			let msg: serde_json::Value = serde_json::from_str(&msg.into_text()?)?;
+			assert_eq!(msg["type"], "next", "message: {msg}");
+			assert_eq!(msg["payload"]["data"]["foo"], expected, "message: {msg}");
+		}
+
+		Ok(())
+	}
 }
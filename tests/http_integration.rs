@@ -1021,24 +1021,151 @@ mod http_integration {
 			.default_headers(headers)
 			.build()?;
 
-		// GET
+		// Create some data
+		{
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body("DEFINE TABLE foo CHANGEFEED 1h; CREATE foo:one")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+		}
+
+		// When no auth is provided, the endpoint returns a 403
 		{
 			let res = client.get(url).send().await?;
+			assert_eq!(res.status(), 403, "body: {}", res.text().await?);
+		}
+
+		// When auth is provided, it returns a snapshot of the DB
+		let since = {
+			let res = client.get(url).basic_auth(USER, Some(PASS)).send().await?;
+			assert_eq!(res.status(), 200);
+			let since = res.headers().get("surreal-versionstamp").unwrap().to_str()?.to_owned();
+			let body = res.text().await?;
+			assert!(body.contains("DEFINE TABLE foo"), "body: {body}");
+			assert!(body.contains("foo:one"), "body: {body}");
+			since
+		};
+
+		// Make some further changes
+		{
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body("CREATE foo:two; DELETE foo:one")
+				.send()
+				.await?;
 			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+		}
+
+		// The changes since the snapshot are returned
+		{
+			let res = client
+				.get(url)
+				.query(&[("since", &since)])
+				.basic_auth(USER, Some(PASS))
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let next = res.headers().get("surreal-versionstamp").unwrap().to_str()?.to_owned();
+			assert_ne!(next, since);
 			let body = res.text().await?;
-			assert_eq!(body, r#"Save"#, "body: {body}");
+			assert!(body.contains("UPSERT foo:two"), "body: {body}");
+			assert!(body.contains("DELETE foo:one"), "body: {body}");
 		}
-		// POST
+
+		// Tables without a change feed can not be replicated
 		{
-			let res = client.post(url).body("").send().await?;
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body("DEFINE TABLE bar")
+				.send()
+				.await?;
 			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+			let res = client.get(url).basic_auth(USER, Some(PASS)).send().await?;
+			assert_eq!(res.status(), 400);
 			let body = res.text().await?;
-			assert_eq!(body, r#"Load"#, "body: {body}");
+			assert!(body.contains("does not have a change feed"), "body: {body}");
 		}
 
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn sync_replica() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+
+		// Prepare HTTP client
+		let mut headers = reqwest::header::HeaderMap::new();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+		let query = |addr: &str, sql: &'static str| {
+			client.post(format!("http://{addr}/sql")).basic_auth(USER, Some(PASS)).body(sql).send()
+		};
+
+		// Create some data on the primary
+		let res = query(&addr, "DEFINE TABLE foo CHANGEFEED 1h; CREATE foo:one").await?;
+		assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+
+		// Start a replica which follows the primary
+		let (replica, _replica_server) = common::start_server(StartServerArguments {
+			args: format!(
+				"--replica-of http://{addr} --replica-ns {ns} --replica-db {db} --replica-user {USER} --replica-pass {PASS} --replica-interval 100ms"
+			),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+
+		// Waits until a query on the replica returns the expected result
+		let wait = |sql: &'static str, expected: &'static str| {
+			let query = &query;
+			let replica = &replica;
+			async move {
+				for _ in 0..50 {
+					let res = query(replica, sql).await.unwrap();
+					let body = res.text().await.unwrap();
+					if body.contains(expected) {
+						return;
+					}
+					tokio::time::sleep(Duration::from_millis(100)).await;
+				}
+				panic!("The replica did not return {expected} for {sql}");
+			}
+		};
+
+		// The snapshot is applied to the replica
+		wait("SELECT * FROM foo", "foo:one").await;
+
+		// Subsequent changes are applied to the replica
+		let res = query(&addr, "CREATE foo:two; DELETE foo:one").await?;
+		assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+		wait("SELECT * FROM foo:two", "foo:two").await;
+		wait("SELECT count() FROM foo GROUP ALL", r#""count":1"#).await;
+
+		// Changed definitions are applied to the replica
+		let res = query(&addr, "DEFINE FIELD name ON foo TYPE option<string>").await?;
+		assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+		wait("INFO FOR TABLE foo", "DEFINE FIELD name ON foo").await;
+
+		// Data can not be written on the replica
+		let res = query(&replica, "CREATE foo:three").await?;
+		let body = res.text().await?;
+		assert!(body.contains("read only replica"), "body: {body}");
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn version_endpoint() -> Result<(), Box<dyn std::error::Error>> {
 		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
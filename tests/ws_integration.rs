@@ -631,6 +631,59 @@ pub async fn query(cfg_server: Option<Format>, cfg_format: Format) {
 	server.finish().unwrap();
 }
 
+pub async fn transaction(cfg_server: Option<Format>, cfg_format: Format) {
+	// Setup database server
+	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, cfg_server, cfg_format).await.unwrap();
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await.unwrap();
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await.unwrap();
+	// Start a transaction
+	let res = socket.send_request("begin", json!([])).await.unwrap();
+	assert!(res["result"].is_string(), "result: {res:?}");
+	let txn = res["result"].as_str().unwrap().to_owned();
+	// Create a record within the transaction
+	let res = socket
+		.send_request_with_txn("create", json!(["tester:one", {"value": 1}]), Some(&txn))
+		.await
+		.unwrap();
+	assert!(res["result"].is_object(), "result: {res:?}");
+	// Run a query within the transaction
+	let res = socket
+		.send_request_with_txn("query", json!(["SELECT * FROM tester"]), Some(&txn))
+		.await
+		.unwrap();
+	let res = res["result"].as_array().unwrap();
+	assert_eq!(res[0]["result"].as_array().unwrap().len(), 1, "result: {res:?}");
+	// The record is not visible outside of the transaction
+	let res = socket.send_request("select", json!(["tester"])).await.unwrap();
+	assert_eq!(res["result"].as_array().unwrap().len(), 0, "result: {res:?}");
+	// Commit the transaction
+	let res = socket.send_request("commit", json!([txn])).await.unwrap();
+	assert!(res.get("error").is_none(), "result: {res:?}");
+	// The record is now visible
+	let res = socket.send_request("select", json!(["tester"])).await.unwrap();
+	assert_eq!(res["result"].as_array().unwrap().len(), 1, "result: {res:?}");
+	// The transaction can no longer be used
+	let res = socket.send_request("commit", json!([txn])).await.unwrap();
+	assert!(res["error"].is_object(), "result: {res:?}");
+	// Start and cancel another transaction
+	let res = socket.send_request("begin", json!([])).await.unwrap();
+	let txn = res["result"].as_str().unwrap().to_owned();
+	let res =
+		socket.send_request_with_txn("create", json!(["tester:two"]), Some(&txn)).await.unwrap();
+	assert!(res["result"].is_object(), "result: {res:?}");
+	let res = socket.send_request("cancel", json!([txn])).await.unwrap();
+	assert!(res.get("error").is_none(), "result: {res:?}");
+	// The cancelled record was not stored
+	let res = socket.send_request("select", json!(["tester"])).await.unwrap();
+	assert_eq!(res["result"].as_array().unwrap().len(), 1, "result: {res:?}");
+	// Test passed
+	server.finish().unwrap();
+}
+
 pub async fn version(cfg_server: Option<Format>, cfg_format: Format) {
 	// Setup database server
 	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
@@ -2285,6 +2338,8 @@ define_include_tests! {
 	#[test_log::test(tokio::test)]
 	query,
 	#[test_log::test(tokio::test)]
+	transaction,
+	#[test_log::test(tokio::test)]
 	version,
 	#[test_log::test(tokio::test)]
 	concurrency,